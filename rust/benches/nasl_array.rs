@@ -0,0 +1,38 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use futures::StreamExt;
+use scannerlib::nasl::{
+    interpreter::CodeInterpreter, ContextFactory, ContextType, NaslValue, Register,
+};
+use scannerlib::storage::ContextKey;
+
+pub fn array_passing_benchmark(c: &mut Criterion) {
+    let list = NaslValue::from((0..10_000).map(NaslValue::from).collect::<Vec<_>>());
+    let code = r#"
+    function pass(x) {
+        return x;
+    }
+    for (i = 0; i < 1000; i++) list = pass(x: list);
+    list[0] = 1;
+    "#;
+    let factory = ContextFactory::default();
+    c.bench_function("pass 10k array 1000 times", |b| {
+        b.iter(|| {
+            let context = factory.build(ContextKey::default());
+            let register =
+                Register::root_initial(&[("list".to_string(), ContextType::Value(list.clone()))]);
+            let interpreter = CodeInterpreter::new(black_box(code), register, &context);
+            let results: Vec<_> =
+                futures::executor::block_on(interpreter.stream().collect::<Vec<_>>());
+            if let Some(err) = results.into_iter().find_map(|x| x.err()) {
+                panic!("Unexpected error: {err}");
+            }
+        })
+    });
+}
+
+criterion_group!(benches, array_passing_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,40 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use futures::StreamExt;
+use scannerlib::nasl::{interpreter::CodeInterpreter, ContextFactory, ContextType, Register};
+use scannerlib::storage::ContextKey;
+
+pub fn regex_loop_benchmark(c: &mut Criterion) {
+    let lines = (0..10_000)
+        .map(|i| format!("line {i}: value={}", i * 7))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let code = r#"
+    matches = 0;
+    foreach line (split(lines, keep: FALSE)) {
+        if (ereg(string: line, pattern: "value=[0-9]*7$")) matches++;
+    }
+    "#;
+    let factory = ContextFactory::default();
+    c.bench_function("ereg 10k lines", |b| {
+        b.iter(|| {
+            let context = factory.build(ContextKey::default());
+            let register = Register::root_initial(&[(
+                "lines".to_string(),
+                ContextType::Value(lines.clone().into()),
+            )]);
+            let interpreter = CodeInterpreter::new(black_box(code), register, &context);
+            let results: Vec<_> =
+                futures::executor::block_on(interpreter.stream().collect::<Vec<_>>());
+            if let Some(err) = results.into_iter().find_map(|x| x.err()) {
+                panic!("Unexpected error: {err}");
+            }
+        })
+    });
+}
+
+criterion_group!(benches, regex_loop_benchmark);
+criterion_main!(benches);
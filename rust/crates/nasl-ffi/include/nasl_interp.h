@@ -0,0 +1,93 @@
+/* SPDX-FileCopyrightText: 2025 Greenbone AG
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+ */
+
+/**
+ * @file nasl_interp.h
+ * @brief C interface to the Rust NASL interpreter.
+ *
+ * Memory ownership:
+ * - A handle returned by nasl_interp_new is owned by the caller and must be
+ *   released with nasl_interp_free exactly once.
+ * - Every string returned through a `char **` out parameter or as return
+ *   value of nasl_interp_last_error is newly allocated, owned by the caller
+ *   and must be released with nasl_interp_string_free. Do not use free(3)
+ *   on them, they are allocated by the Rust allocator.
+ * - Strings passed into the library are only borrowed for the duration of
+ *   the call.
+ *
+ * Every call to nasl_interp_eval evaluates the source independently, no
+ * variables are kept between calls. A handle must not be used by multiple
+ * threads at the same time.
+ */
+
+#ifndef NASL_INTERP_H
+#define NASL_INTERP_H
+
+#ifdef __cplusplus
+extern "C"
+{
+#endif
+
+  /** Opaque interpreter handle. */
+  typedef struct nasl_interp nasl_interp_t;
+
+  /** Error codes returned by nasl_interp_eval. */
+  enum nasl_interp_error
+  {
+    NASL_INTERP_OK = 0,
+    /** A NULL pointer or source that is not valid UTF-8 was passed. */
+    NASL_INTERP_INVALID_ARGUMENT = 1,
+    /** The source is syntactically invalid. */
+    NASL_INTERP_SYNTAX_ERROR = 2,
+    /** A built-in function returned an error. */
+    NASL_INTERP_FUNCTION_CALL_ERROR = 3,
+    /** A script could not be loaded, e.g. on include. */
+    NASL_INTERP_LOAD_ERROR = 4,
+    /** Any other error while interpreting, including internal panics. */
+    NASL_INTERP_ERROR = 5,
+  };
+
+  /**
+   * Creates a new interpreter handle. Returns NULL on allocation failure.
+   */
+  nasl_interp_t *
+  nasl_interp_new (void);
+
+  /**
+   * Releases a handle created by nasl_interp_new. Passing NULL is a no-op.
+   */
+  void
+  nasl_interp_free (nasl_interp_t *interp);
+
+  /**
+   * Evaluates the NUL terminated NASL source.
+   *
+   * On success NASL_INTERP_OK is returned and, if result is not NULL,
+   * *result is set to the string representation of the value of the last
+   * statement. On failure one of the nasl_interp_error codes is returned,
+   * *result is set to NULL and the message can be retrieved with
+   * nasl_interp_last_error.
+   */
+  int
+  nasl_interp_eval (nasl_interp_t *interp, const char *src, char **result);
+
+  /**
+   * Returns a copy of the message of the last failed nasl_interp_eval call
+   * or NULL if the last call succeeded.
+   */
+  char *
+  nasl_interp_last_error (const nasl_interp_t *interp);
+
+  /**
+   * Releases a string returned by this library. Passing NULL is a no-op.
+   */
+  void
+  nasl_interp_string_free (char *s);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* NASL_INTERP_H */
@@ -0,0 +1,179 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+//! C interface to the NASL interpreter.
+//!
+//! The functions are declared in `include/nasl_interp.h`, which also documents the ownership
+//! rules of the returned pointers.
+
+use std::any::Any;
+use std::ffi::{c_char, c_int, CStr, CString};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+use scannerlib::nasl::interpreter::{eval, InterpretError, InterpretErrorKind};
+use scannerlib::nasl::NaslValue;
+
+pub const NASL_INTERP_OK: c_int = 0;
+pub const NASL_INTERP_INVALID_ARGUMENT: c_int = 1;
+pub const NASL_INTERP_SYNTAX_ERROR: c_int = 2;
+pub const NASL_INTERP_FUNCTION_CALL_ERROR: c_int = 3;
+pub const NASL_INTERP_LOAD_ERROR: c_int = 4;
+pub const NASL_INTERP_ERROR: c_int = 5;
+
+/// Interpreter handle as seen by C callers.
+#[derive(Default)]
+pub struct NaslInterp {
+    last_error: Option<CString>,
+}
+
+/// Flattens an InterpretError into an error code.
+fn error_code(err: &InterpretError) -> c_int {
+    match err.kind {
+        InterpretErrorKind::SyntaxError(_) | InterpretErrorKind::IncludeSyntaxError { .. } => {
+            NASL_INTERP_SYNTAX_ERROR
+        }
+        InterpretErrorKind::FunctionCallError(_) => NASL_INTERP_FUNCTION_CALL_ERROR,
+        InterpretErrorKind::LoadError(_) => NASL_INTERP_LOAD_ERROR,
+        _ => NASL_INTERP_ERROR,
+    }
+}
+
+/// Evaluates `src` on a runtime of its own, as C callers are not running one.
+fn block_on_eval(src: &str) -> std::io::Result<Result<NaslValue, InterpretError>> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?;
+    Ok(runtime.block_on(eval(src)))
+}
+
+/// Converts a message into a C string. Interior NUL bytes are removed as C can't represent them.
+fn to_c_string(s: String) -> CString {
+    CString::new(s.replace('\0', "")).unwrap_or_default()
+}
+
+/// Returns the message a panic was started with.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown cause".to_string()
+    }
+}
+
+/// Creates a new interpreter handle.
+#[no_mangle]
+pub extern "C" fn nasl_interp_new() -> *mut NaslInterp {
+    panic::catch_unwind(|| Box::into_raw(Box::default())).unwrap_or(ptr::null_mut())
+}
+
+/// Releases a handle created by `nasl_interp_new`.
+///
+/// # Safety
+/// `interp` must be NULL or a pointer returned by `nasl_interp_new` that was not freed before.
+#[no_mangle]
+pub unsafe extern "C" fn nasl_interp_free(interp: *mut NaslInterp) {
+    if !interp.is_null() {
+        // A panicking drop can't be reported anymore, the handle is gone afterwards.
+        let _ = panic::catch_unwind(|| drop(Box::from_raw(interp)));
+    }
+}
+
+/// Evaluates the given source and stores the string representation of the last value in
+/// `result`. Panics are caught and reported like any other error.
+///
+/// # Safety
+/// `interp` must be a valid handle, `src` a NUL terminated string and `result` either NULL or
+/// a valid pointer to write to.
+#[no_mangle]
+pub unsafe extern "C" fn nasl_interp_eval(
+    interp: *mut NaslInterp,
+    src: *const c_char,
+    result: *mut *mut c_char,
+) -> c_int {
+    if !result.is_null() {
+        *result = ptr::null_mut();
+    }
+    let Some(interp) = interp.as_mut() else {
+        return NASL_INTERP_INVALID_ARGUMENT;
+    };
+    match panic::catch_unwind(AssertUnwindSafe(|| eval_into(interp, src, result))) {
+        Ok(code) => code,
+        Err(payload) => {
+            interp.last_error = Some(to_c_string(format!(
+                "interpreter panicked: {}",
+                panic_message(&*payload)
+            )));
+            NASL_INTERP_ERROR
+        }
+    }
+}
+
+/// Implements `nasl_interp_eval` for a valid handle.
+unsafe fn eval_into(
+    interp: &mut NaslInterp,
+    src: *const c_char,
+    result: *mut *mut c_char,
+) -> c_int {
+    if src.is_null() {
+        interp.last_error = Some(to_c_string("src must not be NULL".to_string()));
+        return NASL_INTERP_INVALID_ARGUMENT;
+    }
+    let src = match CStr::from_ptr(src).to_str() {
+        Ok(x) => x,
+        Err(e) => {
+            interp.last_error = Some(to_c_string(format!("src is not valid UTF-8: {e}")));
+            return NASL_INTERP_INVALID_ARGUMENT;
+        }
+    };
+    let outcome = match block_on_eval(src) {
+        Ok(outcome) => outcome,
+        Err(e) => {
+            interp.last_error = Some(to_c_string(format!("unable to start runtime: {e}")));
+            return NASL_INTERP_ERROR;
+        }
+    };
+    match outcome {
+        Ok(value) => {
+            interp.last_error = None;
+            if !result.is_null() {
+                *result = to_c_string(value.to_string()).into_raw();
+            }
+            NASL_INTERP_OK
+        }
+        Err(err) => {
+            let code = error_code(&err);
+            interp.last_error = Some(to_c_string(err.to_string()));
+            code
+        }
+    }
+}
+
+/// Returns a copy of the last error message or NULL.
+///
+/// # Safety
+/// `interp` must be NULL or a valid handle.
+#[no_mangle]
+pub unsafe extern "C" fn nasl_interp_last_error(interp: *const NaslInterp) -> *mut c_char {
+    panic::catch_unwind(
+        || match interp.as_ref().and_then(|x| x.last_error.clone()) {
+            Some(msg) => msg.into_raw(),
+            None => ptr::null_mut(),
+        },
+    )
+    .unwrap_or(ptr::null_mut())
+}
+
+/// Releases a string returned by this library.
+///
+/// # Safety
+/// `s` must be NULL or a string returned by this library that was not freed before.
+#[no_mangle]
+pub unsafe extern "C" fn nasl_interp_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        let _ = panic::catch_unwind(|| drop(CString::from_raw(s)));
+    }
+}
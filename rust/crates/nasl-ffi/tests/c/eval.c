@@ -0,0 +1,47 @@
+/* SPDX-FileCopyrightText: 2025 Greenbone AG
+ *
+ * SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+ */
+
+#include "nasl_interp.h"
+
+#include <stdio.h>
+#include <string.h>
+
+int
+main (void)
+{
+  nasl_interp_t *interp;
+  char *result = NULL;
+  char *error;
+  int rc;
+
+  interp = nasl_interp_new ();
+  if (interp == NULL)
+    return 1;
+
+  rc = nasl_interp_eval (interp, "a = 40; a + 2;", &result);
+  if (rc != NASL_INTERP_OK || result == NULL || strcmp (result, "42") != 0)
+    {
+      fprintf (stderr, "unexpected result %d: %s\n", rc, result);
+      return 2;
+    }
+  nasl_interp_string_free (result);
+  if (nasl_interp_last_error (interp) != NULL)
+    return 3;
+
+  rc = nasl_interp_eval (interp, "if (1 { a = 1; }", &result);
+  if (rc != NASL_INTERP_SYNTAX_ERROR || result != NULL)
+    {
+      fprintf (stderr, "expected a syntax error but got %d\n", rc);
+      return 4;
+    }
+  error = nasl_interp_last_error (interp);
+  if (error == NULL || strlen (error) == 0)
+    return 5;
+  nasl_interp_string_free (error);
+
+  nasl_interp_free (interp);
+  printf ("ok\n");
+  return 0;
+}
@@ -0,0 +1,43 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+//! Compiles the C program in `tests/c` against the built shared library and runs it.
+
+use std::path::PathBuf;
+use std::process::Command;
+
+#[test]
+fn evaluates_script_from_c() {
+    let manifest_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    // When building tests the shared library is placed next to the test binary in
+    // target/<profile>/deps.
+    let lib_dir = std::env::current_exe()
+        .unwrap()
+        .parent()
+        .unwrap()
+        .to_path_buf();
+    let binary = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("nasl_ffi_eval");
+    let status = Command::new(std::env::var("CC").unwrap_or_else(|_| "cc".to_string()))
+        .arg(manifest_dir.join("tests/c/eval.c"))
+        .arg("-I")
+        .arg(manifest_dir.join("include"))
+        .arg("-L")
+        .arg(&lib_dir)
+        .arg(format!("-Wl,-rpath,{}", lib_dir.display()))
+        .arg("-lnasl_ffi")
+        .arg("-o")
+        .arg(&binary)
+        .status()
+        .expect("a C compiler is required to run this test");
+    assert!(status.success(), "unable to compile tests/c/eval.c");
+
+    let output = Command::new(&binary).output().unwrap();
+    assert!(
+        output.status.success(),
+        "C test failed with {}: {}",
+        output.status,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "ok\n");
+}
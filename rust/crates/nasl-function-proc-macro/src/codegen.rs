@@ -1,4 +1,5 @@
 use crate::types::*;
+use crate::utils::type_name;
 use proc_macro2::TokenStream;
 use quote::quote;
 use syn::{token::Async, Ident, ItemFn, Signature};
@@ -169,6 +170,65 @@ impl<'a> ArgsStruct<'a> {
         }
     }
 
+    /// Returns an expression of the `FnSignature` of the function, if it is known. For functions
+    /// taking the register it is only known from the `NaslArgs` struct given via `args(...)` or
+    /// the list of arguments given via `named_args(...)`, whose elements convert into
+    /// `NamedArgSignature`s.
+    fn gen_signature(&self) -> Option<TokenStream> {
+        let returns = match &self.function.sig.output {
+            syn::ReturnType::Default => "()".to_string(),
+            syn::ReturnType::Type(_, ty) => type_name(ty),
+        };
+        if self.has_register_arg() {
+            let named = match self.attrs.args.as_ref()? {
+                ArgsDescription::Struct(path) => {
+                    quote! { <#path as crate::nasl::utils::function::NaslArgs>::named_args() }
+                }
+                ArgsDescription::List(path) => quote! {
+                    #path.iter().map(crate::nasl::utils::function::NamedArgSignature::from).collect()
+                },
+            };
+            return Some(quote! {
+                crate::nasl::utils::function::FnSignature {
+                    named: #named,
+                    min_positional: 0,
+                    max_positional: Some(0),
+                    returns: #returns,
+                }
+            });
+        }
+        let named = self.args.iter().filter_map(|arg| {
+            let name = match &arg.kind {
+                ArgKind::Named(named) | ArgKind::MaybeNamed(_, named) => &named.name,
+                _ => return None,
+            };
+            let ty = type_name(arg.inner_ty);
+            let required = !arg.optional;
+            Some(quote! {
+                crate::nasl::utils::function::NamedArgSignature {
+                    name: #name,
+                    ty: #ty,
+                    required: #required,
+                }
+            })
+        });
+        let min_positional = self.num_required_positional();
+        let max_positional = if self.has_positional_iterator_arg() {
+            quote! { None }
+        } else {
+            let num = self.max_num_allowed_positional();
+            quote! { Some(#num) }
+        };
+        Some(quote! {
+            crate::nasl::utils::function::FnSignature {
+                named: vec![#(#named),*],
+                min_positional: #min_positional,
+                max_positional: #max_positional,
+                returns: #returns,
+            }
+        })
+    }
+
     fn impl_add_to_set(
         &self,
         ident: &Ident,
@@ -199,9 +259,16 @@ impl<'a> ArgsStruct<'a> {
             ReceiverType::RefSelf | ReceiverType::RefMutSelf => (quote! {}, quote! { Self }),
         };
 
+        let add_signature = self.gen_signature().map(|signature| {
+            quote! {
+                set.add_signature(name, #signature);
+            }
+        });
+
         quote! {
             fn #ident #generics (set: &mut crate::nasl::utils::StoredFunctionSet<#state_type>, name: &str) {
                 set.add_nasl_function(name, crate::nasl::utils::NaslFunction::#nasl_function_expr);
+                #add_signature
             }
         }
     }
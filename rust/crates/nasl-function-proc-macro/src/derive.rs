@@ -0,0 +1,129 @@
+//! Implementation of `#[derive(NaslArgs)]`.
+//!
+//! Every named field of the struct becomes a named argument of the same
+//! name. Fields of type `Option<T>` (which can additionally be marked
+//! with `#[nasl(optional)]` for readability) are optional, all other
+//! fields are required.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    ext::IdentExt, parse_quote, spanned::Spanned, Data, DeriveInput, Error, Field, Fields,
+    GenericParam, Lifetime, LifetimeParam, Result,
+};
+
+use crate::utils::{get_subty_if_name_is, type_name};
+
+fn is_marked_optional(field: &Field) -> Result<bool> {
+    let mut optional = false;
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("nasl"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("optional") {
+                optional = true;
+                Ok(())
+            } else {
+                Err(meta.error("Unknown nasl attribute, expected `optional`."))
+            }
+        })?;
+    }
+    Ok(optional)
+}
+
+fn field_init(field: &Field) -> Result<TokenStream> {
+    let ident = field.ident.as_ref().unwrap();
+    let name = ident.unraw().to_string();
+    let inner_ty = get_subty_if_name_is(&field.ty, "Option");
+    let marked_optional = is_marked_optional(field)?;
+    match inner_ty {
+        Some(inner_ty) => Ok(quote! {
+            #ident: crate::nasl::utils::function::utils::get_optional_named_arg::<#inner_ty>(register, #name)?
+        }),
+        None if marked_optional => Err(Error::new(
+            field.ty.span(),
+            "Fields marked as `#[nasl(optional)]` need to be of type `Option<T>`.",
+        )),
+        None => {
+            let ty = &field.ty;
+            Ok(quote! {
+                #ident: crate::nasl::utils::function::utils::get_named_arg::<#ty>(register, #name)?
+            })
+        }
+    }
+}
+
+fn field_signature(field: &Field) -> TokenStream {
+    let name = field.ident.as_ref().unwrap().unraw().to_string();
+    let (ty, required) = match get_subty_if_name_is(&field.ty, "Option") {
+        Some(inner_ty) => (inner_ty, false),
+        None => (&field.ty, true),
+    };
+    let ty = type_name(ty);
+    quote! {
+        crate::nasl::utils::function::NamedArgSignature {
+            name: #name,
+            ty: #ty,
+            required: #required,
+        }
+    }
+}
+
+pub fn nasl_args(input: DeriveInput) -> Result<TokenStream> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new(
+            input.span(),
+            "NaslArgs can only be derived for structs.",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(Error::new(
+            input.span(),
+            "NaslArgs can only be derived for structs with named fields.",
+        ));
+    };
+    if let Some(param) = input.generics.type_params().next() {
+        return Err(Error::new(
+            param.span(),
+            "NaslArgs does not support type parameters.",
+        ));
+    }
+    let inits = fields
+        .named
+        .iter()
+        .map(field_init)
+        .collect::<Result<Vec<_>>>()?;
+    let signatures = fields.named.iter().map(field_signature);
+
+    // The arguments borrow from the register, so the lifetime of the struct (if any)
+    // is the lifetime of the register.
+    let ident = &input.ident;
+    let (_, ty_generics, where_clause) = input.generics.split_for_impl();
+    let mut impl_generics = input.generics.clone();
+    let lifetime = match input.generics.lifetimes().next() {
+        Some(param) => param.lifetime.clone(),
+        None => {
+            let lifetime: Lifetime = parse_quote!('__nasl_args);
+            impl_generics
+                .params
+                .push(GenericParam::Lifetime(LifetimeParam::new(lifetime.clone())));
+            lifetime
+        }
+    };
+    let (impl_generics, _, _) = impl_generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics crate::nasl::utils::function::NaslArgs<#lifetime> for #ident #ty_generics #where_clause {
+            fn from_register(register: &#lifetime crate::nasl::Register) -> std::result::Result<Self, crate::nasl::FnError> {
+                Ok(Self {
+                    #(#inits),*
+                })
+            }
+
+            fn named_args() -> Vec<crate::nasl::utils::function::NamedArgSignature> {
+                vec![#(#signatures),*]
+            }
+        }
+    })
+}
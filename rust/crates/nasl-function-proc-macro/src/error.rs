@@ -15,6 +15,7 @@ pub enum ErrorKind {
     WrongArgumentOrder,
     MovedReceiverType,
     TypedRefReceiverType,
+    ArgsStructWithoutRegister,
 }
 
 impl Error {
@@ -43,6 +44,9 @@ impl Error {
             ErrorKind::TypedRefReceiverType => {
                 "Specific type specified in receiver argument. Currently, only `&self` is supported."
             }
+            ErrorKind::ArgsStructWithoutRegister => {
+                "The args and named_args attributes are only supported for functions taking the `Register`."
+            }
             ErrorKind::WrongArgumentOrder => {
                 "Argument in wrong position. Order of arguments should be: Context/Register, Positionals, Named, *Positional list"
             }
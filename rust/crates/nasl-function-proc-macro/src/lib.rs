@@ -66,8 +66,23 @@
 //! signature of the annotated function is and can therefore derive
 //! which of the 6 variants of `NaslFunction` it should become,
 //! without requiring type-erasure via an intermediate trait.
+//!
+//! Additionally, the crate provides `#[derive(NaslArgs)]`, which
+//! implements `NaslArgs` for a struct so that builtins taking the
+//! `Register` directly can parse all of their named arguments at
+//! once, using the same conversions and errors as `nasl_function`.
+//!
+//! Both also describe the arguments they read, so that every function
+//! added to a set comes with its `FnSignature`. A function taking the
+//! `Register` can refer to its `NaslArgs` struct via
+//! `#[nasl_function(args(MyArgs))]` to get a signature as well. Functions
+//! that check their arguments against a constant list refer to it via
+//! `named_args(MY_ARGS)` instead. Its elements are converted with
+//! `NamedArgSignature::from`, so the list may keep whatever the check
+//! needs to know about an argument.
 
 mod codegen;
+mod derive;
 mod error;
 mod parse;
 mod types;
@@ -75,7 +90,7 @@ mod utils;
 
 use error::Result;
 use proc_macro2::TokenStream;
-use syn::{parse_macro_input, ItemFn};
+use syn::{parse_macro_input, DeriveInput, ItemFn};
 use types::{ArgsStruct, Attrs};
 
 #[proc_macro_attribute]
@@ -94,3 +109,11 @@ fn nasl_function_internal(function: ItemFn, attrs: Attrs) -> Result<TokenStream>
     let args = ArgsStruct::try_parse(&function, &attrs)?;
     Ok(args.impl_nasl_function_args())
 }
+
+#[proc_macro_derive(NaslArgs, attributes(nasl))]
+pub fn derive_nasl_args(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    derive::nasl_args(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
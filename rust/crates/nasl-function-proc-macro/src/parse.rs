@@ -3,13 +3,47 @@ use std::collections::HashSet;
 use crate::error::{Error, ErrorKind, Result};
 use crate::types::*;
 use crate::utils::{get_subty_if_name_is, ty_is_context, ty_is_register, ty_name_is};
+use syn::ext::IdentExt;
 use syn::punctuated::Punctuated;
 use syn::token::Comma;
-use syn::{parenthesized, parse::Parse, spanned::Spanned, FnArg, Ident, ItemFn, Token, Type};
+use syn::{parenthesized, parse::Parse, spanned::Spanned, FnArg, Ident, ItemFn, Path, Token, Type};
 
 mod attrs {
     syn::custom_keyword!(named);
     syn::custom_keyword!(maybe_named);
+    syn::custom_keyword!(args);
+    syn::custom_keyword!(named_args);
+}
+
+/// Either an attribute on the arguments or the `args(...)` or `named_args(...)` attribute.
+enum AttrItem {
+    Attr(Attr),
+    Args(ArgsDescription),
+}
+
+impl Parse for AttrItem {
+    fn parse(stream: syn::parse::ParseStream) -> syn::Result<Self> {
+        let content;
+        if stream.peek(attrs::args) {
+            let _: attrs::args = stream.parse()?;
+            let _ = parenthesized!(content in stream);
+            Ok(Self::Args(ArgsDescription::Struct(content.parse()?)))
+        } else if stream.peek(attrs::named_args) {
+            let _: attrs::named_args = stream.parse()?;
+            let _ = parenthesized!(content in stream);
+            Ok(Self::Args(ArgsDescription::List(content.parse()?)))
+        } else {
+            stream.parse().map(Self::Attr)
+        }
+    }
+}
+
+impl ArgsDescription {
+    fn path(&self) -> &Path {
+        match self {
+            ArgsDescription::Struct(path) | ArgsDescription::List(path) => path,
+        }
+    }
 }
 
 impl Parse for Attr {
@@ -54,8 +88,9 @@ impl Attrs {
             .iter()
             .find(|attr| attr.idents.contains(ident))
             .map(|attr| &attr.kind);
+        // Raw identifiers allow arguments named like keywords, e.g. `r#type`.
         let make_named = || NamedArg {
-            name: ident.to_string(),
+            name: ident.unraw().to_string(),
         };
         let make_positional = || PositionalArg { position };
         match attr_kind {
@@ -70,6 +105,8 @@ impl Attrs {
         self.check_no_arg_mentioned_twice()?;
         // Check that all arguments that are mentioned in the attributes actually exist.
         self.check_all_args_in_attrs_exist(args)?;
+        // Check that `args(...)` is only given for functions taking the register.
+        self.check_args_struct_has_register(args)?;
         Ok(())
     }
 
@@ -99,14 +136,39 @@ impl Attrs {
         }
         Ok(())
     }
+
+    fn check_args_struct_has_register(&self, args: &[Arg<'_>]) -> Result<()> {
+        match &self.args {
+            Some(desc) if !args.iter().any(|arg| matches!(arg.kind, ArgKind::Register)) => {
+                Err(Error {
+                    span: desc.path().span(),
+                    kind: ErrorKind::ArgsStructWithoutRegister,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
 }
 
 impl Parse for Attrs {
     fn parse(stream: syn::parse::ParseStream) -> syn::Result<Self> {
-        let attrs: Punctuated<Attr, Token![,]> = stream.parse_terminated(Attr::parse, Token![,])?;
-        Ok(Self {
-            attrs: attrs.into_iter().collect(),
-        })
+        let items: Punctuated<AttrItem, Token![,]> =
+            stream.parse_terminated(AttrItem::parse, Token![,])?;
+        let mut attrs = vec![];
+        let mut args = None;
+        for item in items {
+            match item {
+                AttrItem::Attr(attr) => attrs.push(attr),
+                AttrItem::Args(desc) if args.is_some() => {
+                    return Err(syn::Error::new(
+                        desc.path().span(),
+                        "args or named_args is given more than once.",
+                    ))
+                }
+                AttrItem::Args(desc) => args = Some(desc),
+            }
+        }
+        Ok(Self { attrs, args })
     }
 }
 
@@ -250,6 +312,7 @@ impl<'a> ArgsStruct<'a> {
         verify_args(&args)?;
         Ok(Self {
             function,
+            attrs,
             args,
             receiver_type,
         })
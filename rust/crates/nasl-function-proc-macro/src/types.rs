@@ -1,4 +1,4 @@
-use syn::{Ident, ItemFn, Type};
+use syn::{Ident, ItemFn, Path, Type};
 
 pub struct Attr {
     pub kind: AttrKind,
@@ -10,12 +10,23 @@ pub enum AttrKind {
     MaybeNamed,
 }
 
+/// Describes the named arguments of a function taking the `Register`.
+pub enum ArgsDescription {
+    /// A `NaslArgs` struct, given via `args(...)`.
+    Struct(Path),
+    /// A constant slice of arguments which convert into `NamedArgSignature`s, given via
+    /// `named_args(...)`.
+    List(Path),
+}
+
 pub struct Attrs {
     pub attrs: Vec<Attr>,
+    pub args: Option<ArgsDescription>,
 }
 
 pub struct ArgsStruct<'a> {
     pub function: &'a ItemFn,
+    pub attrs: &'a Attrs,
     pub args: Vec<Arg<'a>>,
     pub receiver_type: ReceiverType,
 }
@@ -1,3 +1,5 @@
+use proc_macro2::{Delimiter, TokenStream, TokenTree};
+use quote::ToTokens;
 use syn::*;
 
 pub fn ty_name_is(ty: &Type, name: &str) -> bool {
@@ -63,3 +65,50 @@ where
 {
     iter.next().filter(|_| iter.next().is_none())
 }
+
+/// Renders a type for the signature of a function, e.g. `Option<&[u8]>`. Lifetimes are left out,
+/// since they do not tell anything about the argument.
+pub fn type_name(ty: &Type) -> String {
+    let mut name = String::new();
+    write_tokens(&mut name, ty.to_token_stream());
+    name.replace("<>", "")
+}
+
+fn write_tokens(out: &mut String, tokens: TokenStream) {
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Punct(punct) if punct.as_char() == '\'' => {
+                // Skip the name of the lifetime and a separating comma.
+                tokens.next();
+                if matches!(tokens.peek(), Some(TokenTree::Punct(p)) if p.as_char() == ',') {
+                    tokens.next();
+                }
+            }
+            TokenTree::Punct(punct) => {
+                out.push(punct.as_char());
+                if punct.as_char() == ',' {
+                    out.push(' ');
+                }
+            }
+            TokenTree::Group(group) => {
+                let (open, close) = match group.delimiter() {
+                    Delimiter::Parenthesis => ("(", ")"),
+                    Delimiter::Bracket => ("[", "]"),
+                    Delimiter::Brace => ("{", "}"),
+                    Delimiter::None => ("", ""),
+                };
+                out.push_str(open);
+                write_tokens(out, group.stream());
+                out.push_str(close);
+            }
+            TokenTree::Ident(ident) => {
+                if out.ends_with(|c: char| c.is_alphanumeric() || c == '_') {
+                    out.push(' ');
+                }
+                out.push_str(&ident.to_string());
+            }
+            TokenTree::Literal(literal) => out.push_str(&literal.to_string()),
+        }
+    }
+}
@@ -0,0 +1,15 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+//! Python bindings for the NASL interpreter.
+//!
+//! The bindings are only built with the `python` feature enabled so that the workspace can be
+//! built without a Python installation:
+//!
+//! ```text
+//! cargo build -p nasl-python --features python --release
+//! ```
+
+#[cfg(feature = "python")]
+mod python;
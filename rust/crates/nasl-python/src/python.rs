@@ -0,0 +1,107 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict, PyList};
+use scannerlib::nasl::interpreter::{self, InterpretErrorKind};
+use scannerlib::nasl::NaslValue;
+
+create_exception!(
+    nasl,
+    InterpretError,
+    PyException,
+    "Raised when a script fails. Contains the `line` and `col` of the failing statement."
+);
+
+/// Converts a NaslValue into the matching Python type.
+///
+/// Values that only have a meaning within the interpreter, such as attack categories, are
+/// returned as their string representation.
+fn to_python(py: Python<'_>, value: NaslValue) -> PyResult<PyObject> {
+    Ok(match value {
+        NaslValue::Null => py.None(),
+        NaslValue::String(x) => x.into_pyobject(py)?.into_any().unbind(),
+        NaslValue::Data(x) => PyBytes::new(py, &x).into_any().unbind(),
+        NaslValue::Number(x) | NaslValue::Exit(x) => x.into_pyobject(py)?.into_any().unbind(),
+        NaslValue::Boolean(x) => x.into_pyobject(py)?.to_owned().into_any().unbind(),
+        NaslValue::Array(x) | NaslValue::Fork(x) => {
+            let list = PyList::empty(py);
+            for v in x {
+                list.append(to_python(py, v)?)?;
+            }
+            list.into_any().unbind()
+        }
+        NaslValue::Dict(x) => {
+            let dict = PyDict::new(py);
+            for (k, v) in x {
+                dict.set_item(k, to_python(py, v)?)?;
+            }
+            dict.into_any().unbind()
+        }
+        NaslValue::Return(x) => to_python(py, *x)?,
+        x => x.to_string().into_pyobject(py)?.into_any().unbind(),
+    })
+}
+
+/// Returns the position of the error.
+///
+/// Syntax errors are not bound to a statement, so their position is taken from the offending
+/// token instead.
+fn line_column(err: &interpreter::InterpretError) -> (usize, usize) {
+    match (&err.kind, err.line_column()) {
+        (InterpretErrorKind::SyntaxError(e), (0, 0)) => {
+            e.as_token().map(|t| t.line_column).unwrap_or_default()
+        }
+        (_, x) => x,
+    }
+}
+
+fn to_python_error(py: Python<'_>, err: interpreter::InterpretError) -> PyErr {
+    let (line, col) = line_column(&err);
+    let result = PyErr::new::<InterpretError, _>(err.to_string());
+    let value = result.value(py);
+    if let Err(e) = value
+        .setattr("line", line)
+        .and_then(|_| value.setattr("col", col))
+    {
+        return e;
+    }
+    result
+}
+
+/// Evaluates NASL scripts.
+///
+/// Each call of `eval` runs the given source on its own, variables are not kept between calls.
+#[pyclass]
+#[derive(Default)]
+struct Interpreter;
+
+#[pymethods]
+impl Interpreter {
+    #[new]
+    fn new() -> Self {
+        Self
+    }
+
+    /// Evaluates the source and returns the value of the last statement.
+    fn eval(&self, py: Python<'_>, source: &str) -> PyResult<PyObject> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        let result = py.allow_threads(|| runtime.block_on(interpreter::eval(source)));
+        match result {
+            Ok(value) => to_python(py, value),
+            Err(err) => Err(to_python_error(py, err)),
+        }
+    }
+}
+
+#[pymodule]
+fn nasl(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Interpreter>()?;
+    m.add("InterpretError", m.py().get_type::<InterpretError>())?;
+    Ok(())
+}
@@ -0,0 +1,38 @@
+# SPDX-FileCopyrightText: 2025 Greenbone AG
+#
+# SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+import pytest
+
+import nasl
+
+
+def test_eval_returns_native_values():
+    interp = nasl.Interpreter()
+    assert interp.eval("a = 40; a + 2;") == 42
+    assert interp.eval('"hello";') == "hello"
+    assert interp.eval("raw_string(0x00, 0xff);") == b"\x00\xff"
+    assert interp.eval('make_list(1, "a");') == [1, "a"]
+    assert interp.eval("TRUE;") is True
+
+
+def test_eval_does_not_keep_variables():
+    interp = nasl.Interpreter()
+    interp.eval("a = 1;")
+    assert interp.eval("a;") is None
+
+
+def test_eval_raises_interpret_error():
+    interp = nasl.Interpreter()
+    with pytest.raises(nasl.InterpretError) as info:
+        interp.eval("a = 1;\n  substr();")
+    assert "substr" in str(info.value)
+    assert info.value.line == 2
+    assert info.value.col == 3
+
+
+def test_eval_raises_on_syntax_error():
+    interp = nasl.Interpreter()
+    with pytest.raises(nasl.InterpretError) as info:
+        interp.eval("a = 1;\nif (1 { a = 1; }")
+    assert info.value.line == 2
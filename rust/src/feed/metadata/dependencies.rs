@@ -0,0 +1,195 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+//! Orders plugins by their `script_dependencies`.
+
+use std::collections::HashMap;
+
+use thiserror::Error;
+
+use super::ScriptMeta;
+
+/// Identifies a plugin by its path relative to the feed, e.g. `gb_ssh_login.nasl`.
+pub type ScriptId = String;
+
+/// Errors of [`resolve_dependencies`]
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum DependencyError {
+    /// A script cannot be referred to without filename.
+    #[error("The script at position {0} has no filename.")]
+    MissingFilename(usize),
+    /// The same filename is given more than once.
+    #[error("The script {0} is given more than once.")]
+    Duplicate(ScriptId),
+    /// A script depends on a script that is not given.
+    #[error("{script} depends on {dependency}, which is not given.")]
+    Missing {
+        script: ScriptId,
+        dependency: ScriptId,
+    },
+    /// The scripts depend on each other. Contains the path from the first script of the cycle
+    /// back to it.
+    #[error("Cyclic dependency {}.", .0.join(" -> "))]
+    Cycle(Vec<ScriptId>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Visiting,
+    Done,
+}
+
+struct Resolver<'a> {
+    scripts: HashMap<&'a str, &'a ScriptMeta>,
+    states: HashMap<&'a str, State>,
+    path: Vec<&'a str>,
+    order: Vec<ScriptId>,
+}
+
+impl<'a> Resolver<'a> {
+    /// Adds the dependencies of `id` to the order, followed by `id` itself.
+    fn visit(&mut self, id: &'a str) -> Result<(), DependencyError> {
+        match self.states.get(id) {
+            Some(State::Done) => return Ok(()),
+            Some(State::Visiting) => {
+                let start = self.path.iter().position(|x| *x == id).unwrap_or(0);
+                let mut cycle: Vec<_> = self.path[start..].iter().map(|x| x.to_string()).collect();
+                cycle.push(id.to_string());
+                return Err(DependencyError::Cycle(cycle));
+            }
+            None => {}
+        }
+        self.states.insert(id, State::Visiting);
+        self.path.push(id);
+        let script: &'a ScriptMeta = self.scripts[id];
+        for dependency in &script.dependencies {
+            if !self.scripts.contains_key(dependency.as_str()) {
+                return Err(DependencyError::Missing {
+                    script: id.to_string(),
+                    dependency: dependency.clone(),
+                });
+            }
+            self.visit(dependency)?;
+        }
+        self.path.pop();
+        self.states.insert(id, State::Done);
+        self.order.push(id.to_string());
+        Ok(())
+    }
+}
+
+/// Sorts the scripts so that each script comes after the scripts it depends on.
+///
+/// The scripts are identified by their [`ScriptMeta::filename`], which therefore must be set and
+/// unique. Independent scripts keep their given order. A dependency on a script that is not given
+/// is an error, as is a cycle, which is reported with its path.
+pub fn resolve_dependencies(scripts: &[ScriptMeta]) -> Result<Vec<ScriptId>, DependencyError> {
+    let mut ids = Vec::with_capacity(scripts.len());
+    let mut by_id = HashMap::with_capacity(scripts.len());
+    for (i, script) in scripts.iter().enumerate() {
+        let id = script
+            .filename
+            .as_deref()
+            .ok_or(DependencyError::MissingFilename(i))?;
+        if by_id.insert(id, script).is_some() {
+            return Err(DependencyError::Duplicate(id.to_string()));
+        }
+        ids.push(id);
+    }
+    let mut resolver = Resolver {
+        scripts: by_id,
+        states: HashMap::with_capacity(scripts.len()),
+        path: vec![],
+        order: Vec::with_capacity(scripts.len()),
+    };
+    for id in ids {
+        resolver.visit(id)?;
+    }
+    Ok(resolver.order)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn script(filename: &str, dependencies: &[&str]) -> ScriptMeta {
+        ScriptMeta {
+            filename: Some(filename.to_string()),
+            dependencies: dependencies.iter().map(|x| x.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn chain() {
+        let scripts = [
+            script("gb_apache_detect.nasl", &["http_version.nasl"]),
+            script("http_version.nasl", &["find_service.nasl"]),
+            script("ping_host.nasl", &[]),
+            script("find_service.nasl", &[]),
+            script(
+                "gb_apache_vuln.nasl",
+                &["gb_apache_detect.nasl", "find_service.nasl"],
+            ),
+        ];
+        assert_eq!(
+            resolve_dependencies(&scripts).unwrap(),
+            vec![
+                "find_service.nasl",
+                "http_version.nasl",
+                "gb_apache_detect.nasl",
+                "ping_host.nasl",
+                "gb_apache_vuln.nasl",
+            ]
+        );
+    }
+
+    #[test]
+    fn cycle() {
+        let scripts = [
+            script("a.nasl", &["b.nasl"]),
+            script("b.nasl", &["c.nasl"]),
+            script("c.nasl", &["b.nasl"]),
+        ];
+        let err = resolve_dependencies(&scripts).unwrap_err();
+        assert_eq!(
+            err,
+            DependencyError::Cycle(vec![
+                "b.nasl".to_string(),
+                "c.nasl".to_string(),
+                "b.nasl".to_string()
+            ])
+        );
+        assert_eq!(
+            err.to_string(),
+            "Cyclic dependency b.nasl -> c.nasl -> b.nasl."
+        );
+        assert_eq!(
+            resolve_dependencies(&[script("a.nasl", &["a.nasl"])]),
+            Err(DependencyError::Cycle(vec![
+                "a.nasl".to_string(),
+                "a.nasl".to_string()
+            ]))
+        );
+    }
+
+    #[test]
+    fn missing_dependency() {
+        let scripts = [
+            script("a.nasl", &[]),
+            script("b.nasl", &["a.nasl", "c.nasl"]),
+        ];
+        assert_eq!(
+            resolve_dependencies(&scripts),
+            Err(DependencyError::Missing {
+                script: "b.nasl".to_string(),
+                dependency: "c.nasl".to_string()
+            })
+        );
+        assert_eq!(
+            resolve_dependencies(&[ScriptMeta::default()]),
+            Err(DependencyError::MissingFilename(0))
+        );
+    }
+}
@@ -0,0 +1,272 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+//! Extracts the metadata of a plugin without executing it.
+
+mod dependencies;
+
+pub use dependencies::{resolve_dependencies, DependencyError, ScriptId};
+
+use crate::nasl::syntax::{IdentifierType, Statement, StatementKind, TokenCategory};
+use crate::nasl::utils::function::bytes_to_str;
+use crate::storage::item::ACT;
+
+/// The metadata registered within the description block of a plugin.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ScriptMeta {
+    /// The path of the plugin relative to the feed, which is how other plugins refer to it in
+    /// `script_dependencies`. Not set by [`extract_metadata`], as it only sees the source.
+    pub filename: Option<ScriptId>,
+    /// Set by `script_oid`
+    pub oid: Option<String>,
+    /// Set by `script_name`
+    pub name: Option<String>,
+    /// Set by `script_family`
+    pub family: Option<String>,
+    /// Set by `script_category`
+    pub category: Option<ACT>,
+    /// Set by `script_dependencies`
+    pub dependencies: Vec<String>,
+    /// The name and value of each `script_tag`
+    pub tags: Vec<(String, String)>,
+    /// Problems found while extracting the metadata, such as a missing OID
+    pub diagnostics: Vec<String>,
+}
+
+fn function_name(stmt: &Statement) -> Option<&str> {
+    match stmt.start().category() {
+        TokenCategory::Identifier(IdentifierType::Undefined(s)) => Some(s),
+        _ => None,
+    }
+}
+
+fn is_description_block(condition: &Statement) -> bool {
+    matches!(condition.kind(), StatementKind::Variable)
+        && function_name(condition) == Some("description")
+}
+
+/// Returns the value of a literal argument.
+fn literal(stmt: &Statement) -> Option<String> {
+    match (stmt.kind(), stmt.start().category()) {
+        (StatementKind::Primitive, TokenCategory::String(x)) => Some(x.clone()),
+        (StatementKind::Primitive, TokenCategory::Data(x)) => Some(bytes_to_str(x)),
+        (StatementKind::Primitive, TokenCategory::Number(x)) => Some(x.to_string()),
+        _ => None,
+    }
+}
+
+impl ScriptMeta {
+    fn positional<'a>(&mut self, function: &str, args: &'a [Statement]) -> Vec<&'a Statement> {
+        let positional: Vec<_> = args
+            .iter()
+            .filter(|x| !matches!(x.kind(), StatementKind::NamedParameter(_)))
+            .collect();
+        if positional.is_empty() {
+            self.diagnostics
+                .push(format!("{function} is called without an argument."));
+        }
+        positional
+    }
+
+    fn named(&mut self, function: &str, args: &[Statement], name: &str) -> Option<String> {
+        let value = args.iter().find_map(|x| match x.kind() {
+            StatementKind::NamedParameter(value) if function_name(x) == Some(name) => Some(value),
+            _ => None,
+        });
+        match value {
+            Some(value) => self.literal(function, value),
+            None => {
+                self.diagnostics
+                    .push(format!("{function} is missing the argument {name}."));
+                None
+            }
+        }
+    }
+
+    fn literal(&mut self, function: &str, stmt: &Statement) -> Option<String> {
+        let value = literal(stmt);
+        if value.is_none() {
+            self.diagnostics.push(format!(
+                "{function} uses the non literal argument {stmt} which is ignored."
+            ));
+        }
+        value
+    }
+
+    fn first_literal(&mut self, function: &str, args: &[Statement]) -> Option<String> {
+        self.positional(function, args)
+            .first()
+            .and_then(|x| self.literal(function, x))
+    }
+
+    fn register(&mut self, function: &str, args: &[Statement]) {
+        match function {
+            "script_oid" => self.oid = self.first_literal(function, args),
+            "script_name" => self.name = self.first_literal(function, args),
+            "script_family" => self.family = self.first_literal(function, args),
+            "script_category" => {
+                self.category = match self
+                    .positional(function, args)
+                    .first()
+                    .map(|x| x.start().category())
+                {
+                    Some(TokenCategory::Identifier(IdentifierType::ACT(act))) => Some(*act),
+                    Some(x) => {
+                        self.diagnostics
+                            .push(format!("{function} uses the unknown category {x}."));
+                        None
+                    }
+                    None => None,
+                }
+            }
+            "script_dependencies" => {
+                for arg in self.positional(function, args) {
+                    if let Some(dependency) = self.literal(function, arg) {
+                        self.dependencies.push(dependency);
+                    }
+                }
+            }
+            "script_tag" => {
+                let name = self.named(function, args, "name");
+                let value = self.named(function, args, "value");
+                if let (Some(name), Some(value)) = (name, value) {
+                    self.tags.push((name, value));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn check_mandatory(&mut self) {
+        let missing = [
+            ("script_oid", self.oid.is_none()),
+            ("script_name", self.name.is_none()),
+            ("script_family", self.family.is_none()),
+            ("script_category", self.category.is_none()),
+        ];
+        for (function, missing) in missing {
+            if missing {
+                self.diagnostics
+                    .push(format!("The mandatory {function} is missing."));
+            }
+        }
+    }
+}
+
+/// Extracts the metadata of a plugin by walking through its description block.
+///
+/// Only the calls of the `script_*` registration functions within `if (description) { ... }` are
+/// evaluated, nothing of the plugin is executed. Their arguments must be literals.
+/// Missing mandatory metadata, non literal arguments and syntax errors do not abort the
+/// extraction but are reported within [`ScriptMeta::diagnostics`].
+pub fn extract_metadata(source: &str) -> ScriptMeta {
+    let mut meta = ScriptMeta::default();
+    let mut found = false;
+    for stmt in crate::nasl::syntax::parse(source) {
+        let stmt = match stmt {
+            Ok(stmt) => stmt,
+            Err(e) => {
+                meta.diagnostics.push(e.to_string());
+                break;
+            }
+        };
+        if let StatementKind::If(condition, block, _, _) = stmt.kind() {
+            if is_description_block(condition) {
+                found = true;
+                for stmt in block.children() {
+                    match stmt.kind() {
+                        StatementKind::Exit(_) => break,
+                        StatementKind::Call(_) => {
+                            if let Some(function) = function_name(stmt) {
+                                meta.register(function, stmt.children());
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                break;
+            }
+        }
+    }
+    if !found {
+        meta.diagnostics
+            .push("No description block found.".to_string());
+    }
+    meta.check_mandatory();
+    meta
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plugin_header() {
+        let code = r#"
+if(description)
+{
+  script_oid("1.3.6.1.4.1.25623.1.0.10330");
+  script_version("2023-08-01T13:29:10+0000");
+  script_name("Services");
+  script_category(ACT_GATHER_INFO);
+  script_family("Service detection");
+  script_tag(name:"cvss_base", value:"0.0");
+  script_tag(name:"summary", value:'Detects services.');
+  script_dependencies("find_service_spontaneous.nasl", "cpe_inventory.nasl");
+  exit(0);
+}
+
+soc = open_sock_tcp(80);
+"#;
+        let meta = extract_metadata(code);
+        assert_eq!(
+            meta,
+            ScriptMeta {
+                filename: None,
+                oid: Some("1.3.6.1.4.1.25623.1.0.10330".to_string()),
+                name: Some("Services".to_string()),
+                family: Some("Service detection".to_string()),
+                category: Some(ACT::GatherInfo),
+                dependencies: vec![
+                    "find_service_spontaneous.nasl".to_string(),
+                    "cpe_inventory.nasl".to_string()
+                ],
+                tags: vec![
+                    ("cvss_base".to_string(), "0.0".to_string()),
+                    ("summary".to_string(), "Detects services.".to_string())
+                ],
+                diagnostics: vec![],
+            }
+        );
+    }
+
+    #[test]
+    fn diagnostics() {
+        let code = r#"
+if(description)
+{
+  script_name(name);
+  script_category(ACT_SCANNER);
+  script_family("Port scanners");
+  exit(0);
+  script_oid("1.2.3");
+}
+"#;
+        let meta = extract_metadata(code);
+        assert_eq!(meta.name, None);
+        assert_eq!(meta.oid, None);
+        assert_eq!(meta.category, Some(ACT::Scanner));
+        assert_eq!(
+            meta.diagnostics,
+            vec![
+                "script_name uses the non literal argument name which is ignored.",
+                "The mandatory script_oid is missing.",
+                "The mandatory script_name is missing.",
+            ]
+        );
+        let meta = extract_metadata("display('hi');");
+        assert_eq!(meta.diagnostics.len(), 5);
+        assert_eq!(meta.diagnostics[0], "No description block found.");
+    }
+}
@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
 
 #![doc = include_str!("README.md")]
+mod metadata;
 mod oid;
 mod transpile;
 mod update;
@@ -11,6 +12,7 @@ mod verify;
 #[cfg(test)]
 mod update_tests;
 
+pub use metadata::{extract_metadata, resolve_dependencies, DependencyError, ScriptId, ScriptMeta};
 pub use oid::Oid;
 pub use update::feed_version as version;
 pub use update::Error as UpdateError;
@@ -17,17 +17,24 @@ use crate::nasl::prelude::*;
 /// NASL function to create a dictionary out of an even number of arguments
 ///
 /// Each uneven arguments out of positional arguments are used as keys while each even even argument is used a value.
-/// When there is an uneven number of elements the last key will be dropped, as there is no corresponding value.
-/// So `make_array(1, 0, 1)` will return the same response as `make_array(1, 0)`.
+/// An uneven number of arguments is an error, as the last key has no corresponding value.
 #[nasl_function]
-fn make_array(positionals: CheckedPositionals<NaslValue>) -> HashMap<String, NaslValue> {
+fn make_array(
+    positionals: CheckedPositionals<NaslValue>,
+) -> Result<HashMap<String, NaslValue>, ArgumentError> {
+    if positionals.len() % 2 == 1 {
+        return Err(ArgumentError::WrongArgument(format!(
+            "Expected an even number of arguments for make_array, but got {}.",
+            positionals.len()
+        )));
+    }
     let mut values = HashMap::new();
     for (idx, val) in positionals.iter().enumerate() {
         if idx % 2 == 1 {
             values.insert(positionals[idx - 1].to_string(), val.clone());
         }
     }
-    values
+    Ok(values)
 }
 
 fn create_list(positionals: CheckedPositionals<NaslValue>) -> Vec<NaslValue> {
@@ -35,7 +42,7 @@ fn create_list(positionals: CheckedPositionals<NaslValue>) -> Vec<NaslValue> {
     for val in positionals.iter() {
         match val {
             NaslValue::Dict(x) => values.extend(x.values().cloned().collect::<Vec<NaslValue>>()),
-            NaslValue::Array(x) => values.extend(x.clone()),
+            NaslValue::Array(x) => values.extend(x.iter().cloned()),
             NaslValue::Null => {}
             x => values.push(x.clone()),
         }
@@ -49,6 +56,9 @@ fn make_list(positionals: CheckedPositionals<NaslValue>) -> Vec<NaslValue> {
 }
 
 /// NASL function to sorts the values of a dict/array. WARNING: drops the keys of a dict and returns an array.
+///
+/// Numbers are sorted numerically before strings, which are sorted by their bytes. See the `Ord`
+/// implementation of `NaslValue` for the complete order.
 #[nasl_function]
 fn nasl_sort(positionals: CheckedPositionals<NaslValue>) -> Vec<NaslValue> {
     let mut values = create_list(positionals);
@@ -27,12 +27,12 @@ mod tests {
     #[test]
     fn make_array() {
         check_code_result("make_array(1, 0, 2, 1);", make_dict!(1 => 0i64, 2 => 1i64));
-        check_code_result(
+        check_code_result("make_array();", make_dict!());
+        check_err_matches!(
             "make_array(1, 0, 2, 1, 1);",
-            make_dict!(1 => 0i64, 2 => 1i64),
+            ArgumentError::WrongArgument(_)
         );
-        check_code_result("make_array(1);", make_dict!());
-        check_code_result("make_array();", make_dict!());
+        check_err_matches!("make_array(1);", ArgumentError::WrongArgument(_));
     }
 
     #[test]
@@ -54,7 +54,7 @@ mod tests {
         t.run(r#"l = make_list("abbb", 1, "aaaa", 0, a);"#);
         t.ok(
             r#"s = sort(l);"#,
-            NaslValue::Array(vec![
+            NaslValue::from(vec![
                 NaslValue::Number(0),
                 NaslValue::Number(1),
                 NaslValue::Number(6),
@@ -65,6 +65,30 @@ mod tests {
         );
     }
 
+    #[test]
+    fn sort_mixed() {
+        let mut t = TestBuilder::default();
+        t.run(r#"l = make_list("b", 300, "a", -5, 'a', 2, "10", TRUE);"#);
+        t.ok(
+            r#"sort(l);"#,
+            NaslValue::from(vec![
+                NaslValue::Number(-5),
+                NaslValue::Boolean(true),
+                NaslValue::Number(2),
+                NaslValue::Number(300),
+                NaslValue::String("10".to_string()),
+                NaslValue::String("a".to_string()),
+                NaslValue::Data(b"a".to_vec()),
+                NaslValue::String("b".to_string()),
+            ]),
+        );
+        // The result does not depend on the order of the input.
+        t.ok(
+            r#"sort(l) == sort(make_list(TRUE, "10", 2, 'a', -5, "a", 300, "b"));"#,
+            true,
+        );
+    }
+
     #[test]
     fn keys() {
         let mut t = TestBuilder::default();
@@ -72,7 +96,7 @@ mod tests {
         t.run(r#"l = make_list("foo", "bar");"#);
         t.ok(
             r#"keys(a,l);"#,
-            NaslValue::Array(vec![
+            NaslValue::from(vec![
                 NaslValue::String("a".to_string()),
                 NaslValue::Number(0),
                 NaslValue::Number(1),
@@ -85,8 +109,51 @@ mod tests {
         let mut t = TestBuilder::default();
         t.run(r#"l = [1,2,3,4,5];"#);
         t.ok(r#"max_index(l);"#, 5);
-        t.ok(r#"max_index(make_array(1,2,3,4,5,6,7));"#, 3);
+        t.ok(r#"max_index(make_array(1,2,3,4,5,6));"#, 3);
         t.ok(r#"max_index(make_list(1, 0));"#, 2);
         t.ok(r#"max_index(make_list());"#, 0);
     }
+
+    #[test]
+    fn list_and_map() {
+        let mut t = TestBuilder::default();
+        t.run(r#"l = make_list("a", make_list(1, 2), NULL, "b");"#);
+        t.ok(r#"max_index(l);"#, 4);
+        t.ok(r#"keys(l);"#, vec![0, 1, 2, 3u32]);
+        t.run(r#"m = make_array("x", 1, "y", l);"#);
+        t.ok(r#"max_index(m);"#, 2);
+        t.ok(
+            r#"m["y"];"#,
+            NaslValue::from(vec![
+                NaslValue::String("a".to_string()),
+                NaslValue::Number(1),
+                NaslValue::Number(2),
+                NaslValue::String("b".to_string()),
+            ]),
+        );
+        t.ok(r#"sort(keys(m));"#, vec!["x", "y"]);
+    }
+
+    #[test]
+    fn dict_order() {
+        let code = r#"
+        a["zeta"] = 1;
+        a["alpha"] = 2;
+        a["mu"] = 3;
+        a;
+        "#;
+        let results = TestBuilder::from_code(code).results();
+        let dict = results.last().unwrap().as_ref().unwrap();
+        assert_eq!(dict.to_string(), "alpha: 2,mu: 3,zeta: 1");
+        assert_eq!(
+            dict.clone().as_primitive().to_string(),
+            "alpha: 2,mu: 3,zeta: 1"
+        );
+        let mut t = TestBuilder::default();
+        t.run(r#"a["zeta"] = 1;"#);
+        t.run(r#"a["alpha"] = 2;"#);
+        t.run(r#"a["mu"] = 3;"#);
+        t.ok(r#"keys(a);"#, vec!["alpha", "mu", "zeta"]);
+        t.ok(r#"make_list(a);"#, vec![2, 3, 1u32]);
+    }
 }
@@ -402,7 +402,7 @@ impl NaslCerts {
                 .map(|fpr| NaslValue::String(encode_hex(fpr.as_ref())))
                 .map_err(|_| CertError::UnableToCalculateSHA1Fingerprint)?,
             CertCommands::All => return Err(CertError::QueryParamAllNotImplemented.into()),
-            CertCommands::Hostnames => NaslValue::Array(
+            CertCommands::Hostnames => NaslValue::from(
                 Self::hostnames(cert)
                     .into_iter()
                     .map(NaslValue::String)
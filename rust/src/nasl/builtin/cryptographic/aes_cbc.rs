@@ -13,8 +13,18 @@ use aes::{
 use cbc::{Decryptor, Encryptor};
 
 use crate::nasl::prelude::*;
+use crate::nasl::utils::function::Bytes;
 
-use super::{get_data, get_iv, get_key, get_len, Crypt};
+use super::{Crypt, CryptographicError};
+
+#[derive(NaslArgs)]
+struct CbcArgs<'a> {
+    key: Bytes<'a>,
+    data: Bytes<'a>,
+    iv: Bytes<'a>,
+    #[nasl(optional)]
+    len: Option<usize>,
+}
 
 /// Base function for en- and decrypting Cipher Block Chaining (CBC) mode
 fn cbc<D>(register: &Register, crypt: Crypt) -> Result<NaslValue, FnError>
@@ -22,25 +32,27 @@ where
     D: BlockCipher + BlockEncrypt + BlockDecrypt + KeyInit,
 {
     // Get Arguments
-    let key = get_key(register)?;
-    let data = get_data(register)?;
-    let iv = get_iv(register)?;
+    let CbcArgs {
+        key: Bytes(key),
+        data: Bytes(data),
+        iv: Bytes(iv),
+        len,
+    } = CbcArgs::from_register(register)?;
 
     // Mode Encrypt or Decrypt
     match crypt {
         Crypt::Encrypt => {
-            let res = Encryptor::<D>::new_from_slices(key, iv);
+            let res = Encryptor::<D>::new_from_slices(&key, &iv);
             match res {
-                Ok(encryptor) => Ok(encryptor.encrypt_padded_vec_mut::<ZeroPadding>(data).into()),
-                Err(e) => Err(ArgumentError::WrongArgument(e.to_string()).into()),
+                Ok(encryptor) => Ok(encryptor
+                    .encrypt_padded_vec_mut::<ZeroPadding>(&data)
+                    .into()),
+                Err(_) => Err(CryptographicError::cbc_init::<D>("AesCbc", &key, &iv)),
             }
         }
         Crypt::Decrypt => {
             // length for encrypted data
-            let len = match get_len(register)? {
-                Some(x) => x,
-                None => data.len(),
-            };
+            let len = len.unwrap_or(data.len());
 
             // len should not be more than the length of the data
             if len > data.len() {
@@ -51,14 +63,14 @@ where
                 )
                 .into());
             }
-            let res = Decryptor::<D>::new_from_slices(key, iv);
+            let res = Decryptor::<D>::new_from_slices(&key, &iv);
             match res {
                 Ok(decryptor) => Ok(decryptor
-                    .decrypt_padded_vec_mut::<NoPadding>(data)
-                    .map_err(|e| ArgumentError::WrongArgument(e.to_string()))?[..len]
+                    .decrypt_padded_vec_mut::<NoPadding>(&data)
+                    .map_err(|_| CryptographicError::PaddingError("AesCbc".to_string()))?[..len]
                     .to_vec()
                     .into()),
-                Err(e) => Err(ArgumentError::WrongArgument(e.to_string()).into()),
+                Err(_) => Err(CryptographicError::cbc_init::<D>("AesCbc", &key, &iv)),
             }
         }
     }
@@ -71,7 +83,7 @@ where
 ///   Currently the data is filled with zeroes. Therefore the length of the encrypted data must be
 ///   known for decryption. If no length is given, the last block is decrypted as a whole.
 /// - The iv must have a length of 16 bytes
-#[nasl_function]
+#[nasl_function(args(CbcArgs))]
 fn aes128_cbc_encrypt(register: &Register) -> Result<NaslValue, FnError> {
     cbc::<Aes128>(register, Crypt::Encrypt)
 }
@@ -84,7 +96,7 @@ fn aes128_cbc_encrypt(register: &Register) -> Result<NaslValue, FnError> {
 ///   Currently the data is filled with zeroes. Therefore the length of the encrypted data must be
 ///   known for decryption. If no length is given, the last block is decrypted as a whole.
 /// - The iv must have a length of 16 bytes
-#[nasl_function]
+#[nasl_function(args(CbcArgs))]
 fn aes128_cbc_decrypt(register: &Register) -> Result<NaslValue, FnError> {
     cbc::<Aes128>(register, Crypt::Decrypt)
 }
@@ -96,7 +108,7 @@ fn aes128_cbc_decrypt(register: &Register) -> Result<NaslValue, FnError> {
 ///   Currently the data is filled with zeroes. Therefore the length of the encrypted data must be
 ///   known for decryption. If no length is given, the last block is decrypted as a whole.
 /// - The iv must have a length of 16 bytes
-#[nasl_function]
+#[nasl_function(args(CbcArgs))]
 fn aes192_cbc_encrypt(register: &Register) -> Result<NaslValue, FnError> {
     cbc::<Aes192>(register, Crypt::Encrypt)
 }
@@ -109,7 +121,7 @@ fn aes192_cbc_encrypt(register: &Register) -> Result<NaslValue, FnError> {
 ///   Currently the data is filled with zeroes. Therefore the length of the encrypted data must be
 ///   known for decryption. If no length is given, the last block is decrypted as a whole.
 /// - The iv must have a length of 16 bytes
-#[nasl_function]
+#[nasl_function(args(CbcArgs))]
 fn aes192_cbc_decrypt(register: &Register) -> Result<NaslValue, FnError> {
     cbc::<Aes192>(register, Crypt::Decrypt)
 }
@@ -121,7 +133,7 @@ fn aes192_cbc_decrypt(register: &Register) -> Result<NaslValue, FnError> {
 ///   Currently the data is filled with zeroes. Therefore the length of the encrypted data must be
 ///   known for decryption. If no length is given, the last block is decrypted as a whole.
 /// - The iv must have a length of 16 bytes
-#[nasl_function]
+#[nasl_function(args(CbcArgs))]
 fn aes256_cbc_encrypt(register: &Register) -> Result<NaslValue, FnError> {
     cbc::<Aes256>(register, Crypt::Encrypt)
 }
@@ -134,11 +146,58 @@ fn aes256_cbc_encrypt(register: &Register) -> Result<NaslValue, FnError> {
 ///   Currently the data is filled with zeroes. Therefore the length of the encrypted data must be
 ///   known for decryption. If no length is given, the last block is decrypted as a whole.
 /// - The iv must have a length of 16 bytes
-#[nasl_function]
+#[nasl_function(args(CbcArgs))]
 fn aes256_cbc_decrypt(register: &Register) -> Result<NaslValue, FnError> {
     cbc::<Aes256>(register, Crypt::Decrypt)
 }
 
+fn cbc_mac<D>(key: &[u8], data: &[u8]) -> Result<Vec<u8>, FnError>
+where
+    D: BlockCipher + BlockEncrypt + KeyInit,
+{
+    let encryptor = Encryptor::<D>::new_from_slices(key, &[0; 16])
+        .map_err(|e| ArgumentError::WrongArgument(e.to_string()))?;
+    let encrypted = encryptor.encrypt_padded_vec_mut::<NoPadding>(data);
+    Ok(encrypted[encrypted.len() - 16..].to_vec())
+}
+
+/// NASL function to calculate a raw CBC-MAC with AES.
+///
+/// This function expects 2 named arguments key and data either in a string or data type.
+/// - The key must have a length of 16, 24 or 32 bytes and selects aes128, aes192 or aes256.
+/// - The data must be a non empty multiple of 16 bytes, it is not padded.
+///
+/// The MAC is the last block of the data encrypted in CBC mode with a zero iv.
+///
+/// Raw CBC-MAC is only secure for messages of a fixed length, or at least of lengths where no
+/// message is the prefix of another one. Otherwise a valid MAC for a new message can be forged out
+/// of two known message and MAC pairs. It only exists for legacy protocols relying on it, anything
+/// else should use CMAC (`aes_cmac`), which fixes that weakness.
+#[nasl_function(named(key, data))]
+fn aes_cbc_mac(key: Bytes, data: Bytes) -> Result<NaslValue, FnError> {
+    let (Bytes(key), Bytes(data)) = (key, data);
+    if data.is_empty() || data.len() % 16 != 0 {
+        return Err(ArgumentError::wrong_argument(
+            "data",
+            "a non empty multiple of 16 bytes long",
+            &format!("{} bytes long", data.len()),
+        )
+        .into());
+    }
+    let mac = match key.len() {
+        16 => cbc_mac::<Aes128>(&key, &data),
+        24 => cbc_mac::<Aes192>(&key, &data),
+        32 => cbc_mac::<Aes256>(&key, &data),
+        len => Err(ArgumentError::wrong_argument(
+            "key",
+            "16, 24 or 32 bytes long",
+            &format!("{len} bytes long"),
+        )
+        .into()),
+    }?;
+    Ok(NaslValue::Data(mac))
+}
+
 pub struct AesCbc;
 
 function_set! {
@@ -150,5 +209,6 @@ function_set! {
         aes192_cbc_decrypt,
         aes256_cbc_encrypt,
         aes256_cbc_decrypt,
+        aes_cbc_mac,
     )
 }
@@ -13,7 +13,49 @@ use digest::generic_array::ArrayLength;
 
 use crate::nasl::prelude::*;
 
-use super::{get_aad, get_data, get_iv, get_key, get_len, Crypt, CryptographicError};
+use super::{
+    data_arg, get_aad, get_data, get_iv, get_key, get_len, get_optional_named_data, number_arg,
+    validate_named_args, Crypt, CryptographicError, NamedArg,
+};
+
+const ARGS: &[NamedArg] = &[
+    data_arg("key", true),
+    data_arg("data", true),
+    data_arg("iv", true),
+    number_arg("len", false),
+    data_arg("aad", false),
+];
+const AUTH_ARGS: &[NamedArg] = &[
+    data_arg("key", true),
+    data_arg("data", true),
+    data_arg("iv", true),
+    number_arg("len", false),
+    data_arg("aad", true),
+];
+
+/// Checks the CCM constraints of RFC 3610. The nonce must be 7 to 13 bytes long and the length
+/// field size L = 15 - nonce length must be large enough to encode the message length.
+fn validate_ccm_parameters(nonce: &[u8], message_len: usize) -> Result<(), ArgumentError> {
+    if !(7..=13).contains(&nonce.len()) {
+        return Err(ArgumentError::wrong_argument(
+            "iv",
+            "between 7 and 13 bytes long",
+            &format!("{} bytes long", nonce.len()),
+        ));
+    }
+    let l = 15 - nonce.len();
+    let max_len = 1u64
+        .checked_shl(8 * l as u32)
+        .map(|x| x - 1)
+        .unwrap_or(u64::MAX);
+    if message_len as u64 > max_len {
+        return Err(ArgumentError::WrongArgument(format!(
+            "Expected data to be at most {max_len} bytes long for a {} byte iv but it is {message_len} bytes long",
+            nonce.len()
+        )));
+    }
+    Ok(())
+}
 
 /// Core function to en- and decrypt data. Throws error in case of failure.
 fn ccm_crypt<D, M, N>(
@@ -41,6 +83,7 @@ fn ccm<D>(register: &Register, crypt: Crypt, auth: bool) -> Result<NaslValue, Fn
 where
     D: BlockCipher + BlockSizeUser<BlockSize = U16> + BlockEncrypt + BlockDecrypt + KeyInit,
 {
+    validate_named_args(register, if auth { AUTH_ARGS } else { ARGS })?;
     // Get parameters
     let key = get_key(register)?;
     let data = get_data(register)?;
@@ -48,14 +91,21 @@ where
     let tag_size = get_len(register)?.unwrap_or(16);
     let aad = match auth {
         true => get_aad(register)?,
-        false => b"",
+        false => get_optional_named_data(register, "aad")?.unwrap_or_default(),
+    };
+    let message_len = match crypt {
+        Crypt::Encrypt => data.len(),
+        Crypt::Decrypt => data.len().saturating_sub(tag_size),
     };
+    validate_ccm_parameters(&nonce, message_len)?;
+    let decrypt = matches!(crypt, Crypt::Decrypt);
     // Switch mode dependent on iv length
-    let res = ccm_typed::<D>(tag_size, nonce.len(), crypt, key, nonce, data, aad)?;
+    let res = ccm_typed::<D>(tag_size, nonce.len(), crypt, &key, &nonce, &data, &aad)?;
 
     // Error handling
     match res {
         Ok(x) => Ok(NaslValue::Data(x)),
+        Err(_) if decrypt => Err(CryptographicError::AuthenticationFailed.recoverable()),
         Err(_) => Err(CryptographicError::AesCcmUnableToEncrypt.into()),
     }
 }
@@ -66,8 +116,10 @@ where
 /// Additionally the tag_size can be given as int.
 /// - The length of the key should be 16 bytes long
 /// - The iv must have a length of 7-13 bytes
+/// - The length of the data is limited by the iv length, e.g. to 65535 bytes for a 13 byte iv
+/// - Additional authenticated data can optionally be given as aad
 /// - The tag_size default is 16, it can be set to either 4, 6, 8, 10, 12, 14 or 16
-#[nasl_function]
+#[nasl_function(named_args(ARGS))]
 fn aes128_ccm_encrypt(register: &Register) -> Result<NaslValue, FnError> {
     ccm::<Aes128>(register, Crypt::Encrypt, false)
 }
@@ -78,8 +130,9 @@ fn aes128_ccm_encrypt(register: &Register) -> Result<NaslValue, FnError> {
 /// Additionally the tag_size can be given as int.
 /// - The length of the key should be 16 bytes long
 /// - The iv must have a length of 7-13 bytes
+/// - The length of the data is limited by the iv length, e.g. to 65535 bytes for a 13 byte iv
 /// - The tag_size default is 16, it can be set to either 4, 6, 8, 10, 12, 14 or 16
-#[nasl_function]
+#[nasl_function(named_args(AUTH_ARGS))]
 fn aes128_ccm_encrypt_auth(register: &Register) -> Result<NaslValue, FnError> {
     ccm::<Aes128>(register, Crypt::Encrypt, true)
 }
@@ -90,8 +143,10 @@ fn aes128_ccm_encrypt_auth(register: &Register) -> Result<NaslValue, FnError> {
 /// Additionally the tag_size can be given as int.
 /// - The length of the key should be 16 bytes long
 /// - The iv must have a length of 7-13 bytes
+/// - The length of the data is limited by the iv length, e.g. to 65535 bytes for a 13 byte iv
+/// - Additional authenticated data can optionally be given as aad
 /// - The tag_size default is 16, it can be set to either 4, 6, 8, 10, 12, 14 or 16
-#[nasl_function]
+#[nasl_function(named_args(ARGS))]
 fn aes128_ccm_decrypt(register: &Register) -> Result<NaslValue, FnError> {
     ccm::<Aes128>(register, Crypt::Decrypt, false)
 }
@@ -102,8 +157,9 @@ fn aes128_ccm_decrypt(register: &Register) -> Result<NaslValue, FnError> {
 /// Additionally the tag_size can be given as int.
 /// - The length of the key should be 16 bytes long
 /// - The iv must have a length of 7-13 bytes
+/// - The length of the data is limited by the iv length, e.g. to 65535 bytes for a 13 byte iv
 /// - The tag_size default is 16, it can be set to either 4, 6, 8, 10, 12, 14 or 16
-#[nasl_function]
+#[nasl_function(named_args(AUTH_ARGS))]
 fn aes128_ccm_decrypt_auth(register: &Register) -> Result<NaslValue, FnError> {
     ccm::<Aes128>(register, Crypt::Decrypt, true)
 }
@@ -114,8 +170,10 @@ fn aes128_ccm_decrypt_auth(register: &Register) -> Result<NaslValue, FnError> {
 /// Additionally the tag_size can be given as int.
 /// - The length of the key should be 16 bytes long
 /// - The iv must have a length of 7-13 bytes
+/// - The length of the data is limited by the iv length, e.g. to 65535 bytes for a 13 byte iv
+/// - Additional authenticated data can optionally be given as aad
 /// - The tag_size default is 16, it can be set to either 4, 6, 8, 10, 12, 14 or 16
-#[nasl_function]
+#[nasl_function(named_args(ARGS))]
 fn aes192_ccm_encrypt(register: &Register) -> Result<NaslValue, FnError> {
     ccm::<Aes192>(register, Crypt::Encrypt, false)
 }
@@ -126,8 +184,9 @@ fn aes192_ccm_encrypt(register: &Register) -> Result<NaslValue, FnError> {
 /// Additionally the tag_size can be given as int.
 /// - The length of the key should be 16 bytes long
 /// - The iv must have a length of 7-13 bytes
+/// - The length of the data is limited by the iv length, e.g. to 65535 bytes for a 13 byte iv
 /// - The tag_size default is 16, it can be set to either 4, 6, 8, 10, 12, 14 or 16
-#[nasl_function]
+#[nasl_function(named_args(AUTH_ARGS))]
 fn aes192_ccm_encrypt_auth(register: &Register) -> Result<NaslValue, FnError> {
     ccm::<Aes192>(register, Crypt::Encrypt, true)
 }
@@ -138,8 +197,10 @@ fn aes192_ccm_encrypt_auth(register: &Register) -> Result<NaslValue, FnError> {
 /// Additionally the tag_size can be given as int.
 /// - The length of the key should be 16 bytes long
 /// - The iv must have a length of 7-13 bytes
+/// - The length of the data is limited by the iv length, e.g. to 65535 bytes for a 13 byte iv
+/// - Additional authenticated data can optionally be given as aad
 /// - The tag_size default is 16, it can be set to either 4, 6, 8, 10, 12, 14 or 16
-#[nasl_function]
+#[nasl_function(named_args(ARGS))]
 fn aes192_ccm_decrypt(register: &Register) -> Result<NaslValue, FnError> {
     ccm::<Aes192>(register, Crypt::Decrypt, false)
 }
@@ -150,8 +211,9 @@ fn aes192_ccm_decrypt(register: &Register) -> Result<NaslValue, FnError> {
 /// Additionally the tag_size can be given as int.
 /// - The length of the key should be 16 bytes long
 /// - The iv must have a length of 7-13 bytes
+/// - The length of the data is limited by the iv length, e.g. to 65535 bytes for a 13 byte iv
 /// - The tag_size default is 16, it can be set to either 4, 6, 8, 10, 12, 14 or 16
-#[nasl_function]
+#[nasl_function(named_args(AUTH_ARGS))]
 fn aes192_ccm_decrypt_auth(register: &Register) -> Result<NaslValue, FnError> {
     ccm::<Aes192>(register, Crypt::Decrypt, true)
 }
@@ -162,8 +224,10 @@ fn aes192_ccm_decrypt_auth(register: &Register) -> Result<NaslValue, FnError> {
 /// Additionally the tag_size can be given as int.
 /// - The length of the key should be 16 bytes long
 /// - The iv must have a length of 7-13 bytes
+/// - The length of the data is limited by the iv length, e.g. to 65535 bytes for a 13 byte iv
+/// - Additional authenticated data can optionally be given as aad
 /// - The tag_size default is 16, it can be set to either 4, 6, 8, 10, 12, 14 or 16
-#[nasl_function]
+#[nasl_function(named_args(ARGS))]
 fn aes256_ccm_encrypt(register: &Register) -> Result<NaslValue, FnError> {
     ccm::<Aes256>(register, Crypt::Encrypt, false)
 }
@@ -174,8 +238,9 @@ fn aes256_ccm_encrypt(register: &Register) -> Result<NaslValue, FnError> {
 /// Additionally the tag_size can be given as int.
 /// - The length of the key should be 16 bytes long
 /// - The iv must have a length of 7-13 bytes
+/// - The length of the data is limited by the iv length, e.g. to 65535 bytes for a 13 byte iv
 /// - The tag_size default is 16, it can be set to either 4, 6, 8, 10, 12, 14 or 16
-#[nasl_function]
+#[nasl_function(named_args(AUTH_ARGS))]
 fn aes256_ccm_encrypt_auth(register: &Register) -> Result<NaslValue, FnError> {
     ccm::<Aes256>(register, Crypt::Encrypt, true)
 }
@@ -186,8 +251,10 @@ fn aes256_ccm_encrypt_auth(register: &Register) -> Result<NaslValue, FnError> {
 /// Additionally the tag_size can be given as int.
 /// - The length of the key should be 16 bytes long
 /// - The iv must have a length of 7-13 bytes
+/// - The length of the data is limited by the iv length, e.g. to 65535 bytes for a 13 byte iv
+/// - Additional authenticated data can optionally be given as aad
 /// - The tag_size default is 16, it can be set to either 4, 6, 8, 10, 12, 14 or 16
-#[nasl_function]
+#[nasl_function(named_args(ARGS))]
 fn aes256_ccm_decrypt(register: &Register) -> Result<NaslValue, FnError> {
     ccm::<Aes256>(register, Crypt::Decrypt, false)
 }
@@ -198,8 +265,9 @@ fn aes256_ccm_decrypt(register: &Register) -> Result<NaslValue, FnError> {
 /// Additionally the tag_size can be given as int.
 /// - The length of the key should be 16 bytes long
 /// - The iv must have a length of 7-13 bytes
+/// - The length of the data is limited by the iv length, e.g. to 65535 bytes for a 13 byte iv
 /// - The tag_size default is 16, it can be set to either 4, 6, 8, 10, 12, 14 or 16
-#[nasl_function]
+#[nasl_function(named_args(AUTH_ARGS))]
 fn aes256_ccm_decrypt_auth(register: &Register) -> Result<NaslValue, FnError> {
     ccm::<Aes256>(register, Crypt::Decrypt, true)
 }
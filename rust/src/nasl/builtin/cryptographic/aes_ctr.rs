@@ -3,19 +3,23 @@
 // SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
 
 use aes::{
-    cipher::{
-        BlockCipher, BlockDecrypt, BlockEncrypt, BlockSizeUser, KeyIvInit, StreamCipher,
-        StreamCipherSeek,
-    },
+    cipher::{BlockCipher, BlockDecrypt, BlockEncrypt, BlockSizeUser, KeyIvInit, StreamCipher},
     Aes128, Aes192, Aes256,
 };
 use digest::typenum::U16;
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicI64, Ordering},
+        Mutex,
+    },
+};
 
 use crate::nasl::prelude::*;
 
-use super::{get_data, get_iv, get_key, get_len, Crypt};
+use super::{get_data, get_iv, get_key, get_len_or_rest, CryptographicError};
 
-fn ctr<D>(register: &Register, crypt: Crypt) -> Result<NaslValue, FnError>
+fn ctr<D>(register: &Register) -> Result<NaslValue, FnError>
 where
     D: BlockSizeUser<BlockSize = U16>
         + aes::cipher::KeyInit
@@ -26,102 +30,203 @@ where
     // Get data
     let key = get_key(register)?;
     let data = get_data(register)?;
-    let data_len = data.len();
     let iv = get_iv(register)?;
-    let len = match get_len(register)? {
-        Some(x) => x,
-        None => data_len,
-    };
-
-    let mut cipher = ctr::Ctr64BE::<D>::new(key.into(), iv.into());
-    let mut buf = data.to_vec();
-    // Mode Encrypt or Decrypt
-    match crypt {
-        Crypt::Encrypt => {
-            cipher.apply_keystream(&mut buf);
-            Ok(buf.to_vec().into())
-        }
-        Crypt::Decrypt => {
-            cipher.seek(0u32);
-            cipher.apply_keystream(&mut buf);
-            Ok(buf[..len].to_vec().into())
-        }
-    }
+    // Omitting len or a negative len processes the whole data, a len larger than the data is an
+    // error.
+    let len = get_len_or_rest(register)?.resolve(data.len())?;
+
+    let mut cipher = ctr::Ctr64BE::<D>::new(key.as_ref().into(), iv.as_ref().into());
+    let mut buf = data[..len].to_vec();
+    // CTR only XORs the keystream, so en- and decryption are the same operation
+    cipher.apply_keystream(&mut buf);
+    Ok(buf.into())
 }
 
 /// NASL function to encrypt data with aes128 ctr.
 ///
 /// This function expects 3 named arguments key, data and iv either in a string or data type.
-/// - The data is divided into blocks of 16 bytes. The last block is filled so it also has 16 bytes.
-///   Currently the data is filled with zeroes. Therefore the length of the encrypted data must be
-///   known for decryption. If no length is given, the last block is decrypted as a whole.
+/// - The optional len argument is a number. Only the first len bytes of the data are processed.
+///   If it is omitted or negative the whole data is used, if it is larger than the data an error
+///   is returned.
 /// - The iv must have a length of 16 bytes. It is used as the initial counter.
 #[nasl_function]
 fn aes128_ctr_encrypt(register: &Register) -> Result<NaslValue, FnError> {
-    ctr::<Aes128>(register, Crypt::Encrypt)
+    ctr::<Aes128>(register)
 }
 
 /// NASL function to decrypt data with aes128 ctr.
 ///
 /// This function expects 4 named arguments key, data and iv either in a string or data type. The
 /// len argument is a number.
-/// - The data is divided into blocks of 16 bytes. The last block is filled so it also has 16 bytes.
-///   Currently the data is filled with zeroes. Therefore the length of the encrypted data must be
-///   known for decryption. If no length is given, the last block is decrypted as a whole.
+/// - The optional len argument is a number. Only the first len bytes of the data are processed.
+///   If it is omitted or negative the whole data is used, if it is larger than the data an error
+///   is returned.
 /// - The iv must have a length of 16 bytes. It is used as the initial counter.
 #[nasl_function]
 fn aes128_ctr_decrypt(register: &Register) -> Result<NaslValue, FnError> {
-    ctr::<Aes128>(register, Crypt::Decrypt)
+    ctr::<Aes128>(register)
 }
 
 /// NASL function to encrypt data with aes192 ctr.
 ///
 /// This function expects 3 named arguments key, data and iv either in a string or data type.
-/// - The data is divided into blocks of 16 bytes. The last block is filled so it also has 16 bytes.
-///   Currently the data is filled with zeroes. Therefore the length of the encrypted data must be
-///   known for decryption. If no length is given, the last block is decrypted as a whole.
+/// - The optional len argument is a number. Only the first len bytes of the data are processed.
+///   If it is omitted or negative the whole data is used, if it is larger than the data an error
+///   is returned.
 /// - The iv must have a length of 16 bytes. It is used as the initial counter.
 #[nasl_function]
 fn aes192_ctr_encrypt(register: &Register) -> Result<NaslValue, FnError> {
-    ctr::<Aes192>(register, Crypt::Encrypt)
+    ctr::<Aes192>(register)
 }
 
 /// NASL function to decrypt data with aes192 ctr.
 ///
 /// This function expects 4 named arguments key, data and iv either in a string or data type. The
 /// len argument is a number.
-/// - The data is divided into blocks of 16 bytes. The last block is filled so it also has 16 bytes.
-///   Currently the data is filled with zeroes. Therefore the length of the encrypted data must be
-///   known for decryption. If no length is given, the last block is decrypted as a whole.
+/// - The optional len argument is a number. Only the first len bytes of the data are processed.
+///   If it is omitted or negative the whole data is used, if it is larger than the data an error
+///   is returned.
 /// - The iv must have a length of 16 bytes. It is used as the initial counter.
 #[nasl_function]
 fn aes192_ctr_decrypt(register: &Register) -> Result<NaslValue, FnError> {
-    ctr::<Aes192>(register, Crypt::Decrypt)
+    ctr::<Aes192>(register)
 }
 
 /// NASL function to encrypt data with aes256 ctr.
 ///
 /// This function expects 3 named arguments key, data and iv either in a string or data type.
-/// - The data is divided into blocks of 16 bytes. The last block is filled so it also has 16 bytes.
-///   Currently the data is filled with zeroes. Therefore the length of the encrypted data must be
-///   known for decryption. If no length is given, the last block is decrypted as a whole.
+/// - The optional len argument is a number. Only the first len bytes of the data are processed.
+///   If it is omitted or negative the whole data is used, if it is larger than the data an error
+///   is returned.
 /// - The iv must have a length of 16 bytes. It is used as the initial counter.
 #[nasl_function]
 fn aes256_ctr_encrypt(register: &Register) -> Result<NaslValue, FnError> {
-    ctr::<Aes256>(register, Crypt::Encrypt)
+    ctr::<Aes256>(register)
 }
 
 /// NASL function to decrypt data with aes256 ctr.
 ///
 /// This function expects 4 named arguments key, data and iv either in a string or data type. The
 /// len argument is a number.
-/// - The data is divided into blocks of 16 bytes. The last block is filled so it also has 16 bytes.
-///   Currently the data is filled with zeroes. Therefore the length of the encrypted data must be
-///   known for decryption. If no length is given, the last block is decrypted as a whole.
+/// - The optional len argument is a number. Only the first len bytes of the data are processed.
+///   If it is omitted or negative the whole data is used, if it is larger than the data an error
+///   is returned.
 /// - The iv must have a length of 16 bytes. It is used as the initial counter.
 #[nasl_function]
 fn aes256_ctr_decrypt(register: &Register) -> Result<NaslValue, FnError> {
-    ctr::<Aes256>(register, Crypt::Decrypt)
+    ctr::<Aes256>(register)
+}
+
+/// Keystream state of an open streaming AES-CTR cipher.
+enum AesCtrStream {
+    Aes128(ctr::Ctr64BE<Aes128>),
+    Aes192(ctr::Ctr64BE<Aes192>),
+    Aes256(ctr::Ctr64BE<Aes256>),
+}
+
+impl AesCtrStream {
+    fn new(key: &[u8], iv: &[u8]) -> Result<Self, ArgumentError> {
+        if iv.len() != 16 {
+            return Err(ArgumentError::wrong_argument(
+                "iv",
+                "16 bytes long",
+                &format!("{} bytes long", iv.len()),
+            ));
+        }
+        Ok(match key.len() {
+            16 => Self::Aes128(ctr::Ctr64BE::new(key.into(), iv.into())),
+            24 => Self::Aes192(ctr::Ctr64BE::new(key.into(), iv.into())),
+            32 => Self::Aes256(ctr::Ctr64BE::new(key.into(), iv.into())),
+            x => {
+                return Err(ArgumentError::wrong_argument(
+                    "key",
+                    "16, 24 or 32 bytes long",
+                    &format!("{x} bytes long"),
+                ))
+            }
+        })
+    }
+
+    fn apply_keystream(&mut self, buf: &mut [u8]) {
+        match self {
+            Self::Aes128(c) => c.apply_keystream(buf),
+            Self::Aes192(c) => c.apply_keystream(buf),
+            Self::Aes256(c) => c.apply_keystream(buf),
+        }
+    }
+}
+
+/// Handles of streaming AES-CTR ciphers. The keystream position is kept between calls of
+/// `aes_ctr_update`, so data can be processed in chunks.
+pub struct AesCtrHandles {
+    next_id: AtomicI64,
+    streams: Mutex<HashMap<i64, AesCtrStream>>,
+}
+
+impl Default for AesCtrHandles {
+    fn default() -> Self {
+        Self {
+            next_id: AtomicI64::new(1),
+            streams: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl AesCtrHandles {
+    /// Opens a streaming AES-CTR cipher.
+    ///
+    /// Expects the named arguments key and iv either in a string or data type.
+    /// - The key must have a length of 16, 24 or 32 bytes, selecting aes128, aes192 or aes256.
+    /// - The iv must have a length of 16 bytes. It is used as the initial counter.
+    ///
+    /// Returns the handle to be used with `aes_ctr_update` and `aes_ctr_free`.
+    #[nasl_function]
+    pub fn aes_ctr_init(&self, register: &Register) -> Result<NaslValue, FnError> {
+        let stream = AesCtrStream::new(&get_key(register)?, &get_iv(register)?)?;
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        self.streams
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(id, stream);
+        Ok(NaslValue::Number(id))
+    }
+
+    /// En- or decrypts the next chunk of data of an open stream.
+    ///
+    /// Expects the named arguments handle, as returned by `aes_ctr_init`, and data. Since CTR
+    /// mode only XORs the keystream, the same function is used for en- and decryption.
+    #[nasl_function(named(handle, data))]
+    pub fn aes_ctr_update(&self, handle: i64, data: &NaslValue) -> Result<Vec<u8>, FnError> {
+        let mut streams = self.streams.lock().unwrap_or_else(|e| e.into_inner());
+        let stream = streams
+            .get_mut(&handle)
+            .ok_or_else(|| CryptographicError::AesCtr(format!("unknown handle {handle}")))?;
+        let mut buf = Vec::<u8>::from(data);
+        stream.apply_keystream(&mut buf);
+        Ok(buf)
+    }
+
+    /// Closes an open stream and releases its state.
+    ///
+    /// Expects the named argument handle as returned by `aes_ctr_init`.
+    #[nasl_function(named(handle))]
+    pub fn aes_ctr_free(&self, handle: i64) -> Result<(), FnError> {
+        self.streams
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&handle)
+            .map(|_| ())
+            .ok_or_else(|| CryptographicError::AesCtr(format!("unknown handle {handle}")).into())
+    }
+}
+
+function_set! {
+    AesCtrHandles,
+    (
+        (AesCtrHandles::aes_ctr_init, "aes_ctr_init"),
+        (AesCtrHandles::aes_ctr_update, "aes_ctr_update"),
+        (AesCtrHandles::aes_ctr_free, "aes_ctr_free"),
+    )
 }
 
 pub struct AesCtr;
@@ -3,7 +3,12 @@
 // SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
 
 // FnError::GeneralError
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
 use crate::nasl::prelude::*;
+use crate::nasl::utils::random;
+use crate::nasl::utils::ContextType;
 use aes::{
     cipher::{BlockCipher, BlockDecrypt, BlockEncrypt, BlockSizeUser, KeyInit},
     Aes128, Aes192, Aes256,
@@ -14,7 +19,84 @@ use aes_gcm::{
 };
 use digest::typenum::{U12, U16};
 
-use super::{get_aad, get_data, get_iv, get_key, get_len, Crypt, CryptographicError};
+use super::{
+    data_arg, get_aad, get_data, get_key, get_len, get_optional_named_data, number_arg, string_arg,
+    validate_named_args, Crypt, CryptographicError, NamedArg,
+};
+
+/// Length of the iv generated if none is given on encryption.
+const GENERATED_IV_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+
+const ENCRYPT_ARGS: &[NamedArg] = &[
+    data_arg("key", true),
+    data_arg("data", true),
+    number_arg("len", false),
+    data_arg("iv", false),
+    string_arg("tag_mode", false),
+];
+const ENCRYPT_AUTH_ARGS: &[NamedArg] = &[
+    data_arg("key", true),
+    data_arg("data", true),
+    number_arg("len", false),
+    data_arg("iv", false),
+    string_arg("tag_mode", false),
+    data_arg("aad", true),
+];
+const DECRYPT_ARGS: &[NamedArg] = &[
+    data_arg("key", true),
+    data_arg("data", true),
+    number_arg("len", false),
+    data_arg("iv", true),
+    data_arg("tag", false),
+];
+const DECRYPT_AUTH_ARGS: &[NamedArg] = &[
+    data_arg("key", true),
+    data_arg("data", true),
+    number_arg("len", false),
+    data_arg("iv", true),
+    data_arg("tag", false),
+    data_arg("aad", true),
+];
+
+/// How the tag is returned by an encryption.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum TagMode {
+    /// The tag is appended to the ciphertext.
+    Appended,
+    /// The ciphertext and the tag are returned in a dictionary.
+    Separate,
+}
+
+/// Get the optional tag_mode argument, which defaults to [`TagMode::Appended`].
+fn get_tag_mode(register: &Register) -> Result<TagMode, ArgumentError> {
+    match register.named("tag_mode") {
+        None => Ok(TagMode::Appended),
+        Some(ContextType::Value(NaslValue::String(x))) if x == "appended" => Ok(TagMode::Appended),
+        Some(ContextType::Value(NaslValue::String(x))) if x == "separate" => Ok(TagMode::Separate),
+        Some(x) => Err(ArgumentError::wrong_argument(
+            "tag_mode",
+            "appended or separate",
+            &format!("{x:?}"),
+        )),
+    }
+}
+
+/// Returns the result of an encryption as dictionary of the ciphertext, the tag and the iv, if it
+/// was generated.
+fn separate(iv: Option<&[u8]>, mut ciphertext: Vec<u8>) -> NaslValue {
+    let tag = ciphertext.split_off(ciphertext.len() - TAG_LEN);
+    let mut result: BTreeMap<_, _> = [
+        ("ciphertext".to_string(), NaslValue::Data(ciphertext)),
+        ("tag".to_string(), NaslValue::Data(tag)),
+    ]
+    .into_iter()
+    .collect();
+    if let Some(iv) = iv {
+        result.insert("iv".to_string(), NaslValue::Data(iv.to_vec()));
+    }
+    NaslValue::Dict(result)
+}
 
 fn gcm<D>(register: &Register, crypt: Crypt, auth: bool) -> Result<NaslValue, FnError>
 where
@@ -24,19 +106,43 @@ where
         + BlockEncrypt
         + BlockDecrypt,
 {
+    let args = match (&crypt, auth) {
+        (Crypt::Encrypt, false) => ENCRYPT_ARGS,
+        (Crypt::Encrypt, true) => ENCRYPT_AUTH_ARGS,
+        (Crypt::Decrypt, false) => DECRYPT_ARGS,
+        (Crypt::Decrypt, true) => DECRYPT_AUTH_ARGS,
+    };
+    validate_named_args(register, args)?;
     // Get data
     let key = get_key(register)?;
     let data = get_data(register)?;
-    let iv = get_iv(register)?;
+    // The tag may be given separately instead of being appended to the data.
+    let data = match (&crypt, get_optional_named_data(register, "tag")?) {
+        (Crypt::Decrypt, Some(tag)) => Cow::Owned([data.as_ref(), tag.as_ref()].concat()),
+        _ => data,
+    };
+    let tag_mode = get_tag_mode(register)?;
+    // A missing iv is generated on encryption and returned along with the result.
+    let (iv, generated_iv) = match (&crypt, get_optional_named_data(register, "iv")?) {
+        (_, Some(iv)) => (iv, false),
+        (Crypt::Encrypt, None) => {
+            let iv: [u8; GENERATED_IV_LEN] = random::random_bytes()?;
+            (Cow::Owned(iv.to_vec()), true)
+        }
+        (Crypt::Decrypt, None) => return Err(ArgumentError::MissingNamed(vec!["iv".into()]).into()),
+    };
     let len = get_len(register)?;
     let aad = match auth {
         true => get_aad(register)?,
-        false => b"",
+        false => Cow::Borrowed(&[][..]),
     };
 
-    let cipher = AesGcm::<D, U12>::new(key.into());
+    let cipher = AesGcm::<D, U12>::new(key.as_ref().into());
 
-    let mut payload = Payload { msg: data, aad };
+    let mut payload = Payload {
+        msg: &data,
+        aad: &aad,
+    };
 
     let res = match crypt {
         Crypt::Encrypt => {
@@ -48,22 +154,33 @@ where
                     vec.push(0);
                 }
                 payload.msg = vec.as_slice();
-                cipher.encrypt(iv.into(), payload)
+                cipher.encrypt(iv.as_ref().into(), payload)
             } else {
-                cipher.encrypt(iv.into(), payload)
+                cipher.encrypt(iv.as_ref().into(), payload)
             }
         }
-        Crypt::Decrypt => cipher.decrypt(iv.into(), payload),
+        Crypt::Decrypt => cipher.decrypt(iv.as_ref().into(), payload),
     };
     match res {
         Ok(x) => match crypt {
             Crypt::Decrypt => match len {
+                Some(y) if y > x.len() => Err(ArgumentError::wrong_argument(
+                    "len",
+                    &format!("at most the data length of {}", x.len()),
+                    &y.to_string(),
+                )
+                .into()),
                 Some(y) => Ok(x[..y].to_vec().into()),
                 None => Ok(x.into()),
             },
+            Crypt::Encrypt if generated_iv => Ok(separate(Some(&iv), x)),
+            Crypt::Encrypt if tag_mode == TagMode::Separate => Ok(separate(None, x)),
             Crypt::Encrypt => Ok(x.into()),
         },
-        Err(_) => Err(CryptographicError::InsufficientBufferSize.into()),
+        Err(_) => match crypt {
+            Crypt::Decrypt => Err(CryptographicError::AuthenticationFailed.recoverable()),
+            Crypt::Encrypt => Err(CryptographicError::InsufficientBufferSize.into()),
+        },
     }
 }
 
@@ -76,7 +193,11 @@ where
 /// - The iv must have a length of 16 bytes. It is used as the initial counter.
 /// - The result contains the ciphertext and the calculated tag in a single data type.
 /// - The tag has a size of 16 Bytes.
-#[nasl_function]
+/// - With tag_mode "separate" the result is a dictionary with the ciphertext and the tag instead.
+///   The default tag_mode is "appended".
+/// - If no iv is given, a random 12 byte iv is generated. The result is then a dictionary with the
+///   iv, the ciphertext and the tag.
+#[nasl_function(named_args(ENCRYPT_ARGS))]
 fn aes128_gcm_encrypt(register: &Register) -> Result<NaslValue, FnError> {
     gcm::<Aes128>(register, Crypt::Encrypt, false)
 }
@@ -90,7 +211,11 @@ fn aes128_gcm_encrypt(register: &Register) -> Result<NaslValue, FnError> {
 /// - The iv must have a length of 16 bytes. It is used as the initial counter.
 /// - The result contains the ciphertext and the calculated tag in a single data type.
 /// - The tag has a size of 16 Bytes.
-#[nasl_function]
+/// - With tag_mode "separate" the result is a dictionary with the ciphertext and the tag instead.
+///   The default tag_mode is "appended".
+/// - If no iv is given, a random 12 byte iv is generated. The result is then a dictionary with the
+///   iv, the ciphertext and the tag.
+#[nasl_function(named_args(ENCRYPT_AUTH_ARGS))]
 fn aes128_gcm_encrypt_auth(register: &Register) -> Result<NaslValue, FnError> {
     gcm::<Aes128>(register, Crypt::Encrypt, true)
 }
@@ -103,8 +228,9 @@ fn aes128_gcm_encrypt_auth(register: &Register) -> Result<NaslValue, FnError> {
 ///   Currently the data is filled with zeroes. Therefore the length of the encrypted data must be
 ///   known for decryption. If no length is given, the last block is decrypted as a whole.
 /// - The iv must have a length of 16 bytes. It is used as the initial counter.
-/// - The tag is needed as a postfix in the given data in order to decrypt successfully.
-#[nasl_function]
+/// - The tag is needed as a postfix in the given data or separately as tag in order to decrypt
+///   successfully.
+#[nasl_function(named_args(DECRYPT_ARGS))]
 fn aes128_gcm_decrypt(register: &Register) -> Result<NaslValue, FnError> {
     gcm::<Aes128>(register, Crypt::Decrypt, false)
 }
@@ -117,8 +243,9 @@ fn aes128_gcm_decrypt(register: &Register) -> Result<NaslValue, FnError> {
 ///   Currently the data is filled with zeroes. Therefore the length of the encrypted data must be
 ///   known for decryption. If no length is given, the last block is decrypted as a whole.
 /// - The iv must have a length of 16 bytes. It is used as the initial counter.
-/// - The tag is needed as a postfix in the given data in order to decrypt successfully.
-#[nasl_function]
+/// - The tag is needed as a postfix in the given data or separately as tag in order to decrypt
+///   successfully.
+#[nasl_function(named_args(DECRYPT_AUTH_ARGS))]
 fn aes128_gcm_decrypt_auth(register: &Register) -> Result<NaslValue, FnError> {
     gcm::<Aes128>(register, Crypt::Decrypt, true)
 }
@@ -132,7 +259,11 @@ fn aes128_gcm_decrypt_auth(register: &Register) -> Result<NaslValue, FnError> {
 /// - The iv must have a length of 16 bytes. It is used as the initial counter.
 /// - The result contains the ciphertext and the calculated tag in a single data type.
 /// - The tag has a size of 16 Bytes.
-#[nasl_function]
+/// - With tag_mode "separate" the result is a dictionary with the ciphertext and the tag instead.
+///   The default tag_mode is "appended".
+/// - If no iv is given, a random 12 byte iv is generated. The result is then a dictionary with the
+///   iv, the ciphertext and the tag.
+#[nasl_function(named_args(ENCRYPT_ARGS))]
 fn aes192_gcm_encrypt(register: &Register) -> Result<NaslValue, FnError> {
     gcm::<Aes192>(register, Crypt::Encrypt, false)
 }
@@ -146,7 +277,11 @@ fn aes192_gcm_encrypt(register: &Register) -> Result<NaslValue, FnError> {
 /// - The iv must have a length of 16 bytes. It is used as the initial counter.
 /// - The result contains the ciphertext and the calculated tag in a single data type.
 /// - The tag has a size of 16 Bytes.
-#[nasl_function]
+/// - With tag_mode "separate" the result is a dictionary with the ciphertext and the tag instead.
+///   The default tag_mode is "appended".
+/// - If no iv is given, a random 12 byte iv is generated. The result is then a dictionary with the
+///   iv, the ciphertext and the tag.
+#[nasl_function(named_args(ENCRYPT_AUTH_ARGS))]
 fn aes192_gcm_encrypt_auth(register: &Register) -> Result<NaslValue, FnError> {
     gcm::<Aes192>(register, Crypt::Encrypt, true)
 }
@@ -159,8 +294,9 @@ fn aes192_gcm_encrypt_auth(register: &Register) -> Result<NaslValue, FnError> {
 ///   Currently the data is filled with zeroes. Therefore the length of the encrypted data must be
 ///   known for decryption. If no length is given, the last block is decrypted as a whole.
 /// - The iv must have a length of 16 bytes. It is used as the initial counter.
-/// - The tag is needed as a postfix in the given data in order to decrypt successfully.
-#[nasl_function]
+/// - The tag is needed as a postfix in the given data or separately as tag in order to decrypt
+///   successfully.
+#[nasl_function(named_args(DECRYPT_ARGS))]
 fn aes192_gcm_decrypt(register: &Register) -> Result<NaslValue, FnError> {
     gcm::<Aes192>(register, Crypt::Decrypt, false)
 }
@@ -173,8 +309,9 @@ fn aes192_gcm_decrypt(register: &Register) -> Result<NaslValue, FnError> {
 ///   Currently the data is filled with zeroes. Therefore the length of the encrypted data must be
 ///   known for decryption. If no length is given, the last block is decrypted as a whole.
 /// - The iv must have a length of 16 bytes. It is used as the initial counter.
-/// - The tag is needed as a postfix in the given data in order to decrypt successfully.
-#[nasl_function]
+/// - The tag is needed as a postfix in the given data or separately as tag in order to decrypt
+///   successfully.
+#[nasl_function(named_args(DECRYPT_AUTH_ARGS))]
 fn aes192_gcm_decrypt_auth(register: &Register) -> Result<NaslValue, FnError> {
     gcm::<Aes192>(register, Crypt::Decrypt, true)
 }
@@ -188,7 +325,11 @@ fn aes192_gcm_decrypt_auth(register: &Register) -> Result<NaslValue, FnError> {
 /// - The iv must have a length of 16 bytes. It is used as the initial counter.
 /// - The result contains the ciphertext and the calculated tag in a single data type.
 /// - The tag has a size of 16 Bytes.
-#[nasl_function]
+/// - With tag_mode "separate" the result is a dictionary with the ciphertext and the tag instead.
+///   The default tag_mode is "appended".
+/// - If no iv is given, a random 12 byte iv is generated. The result is then a dictionary with the
+///   iv, the ciphertext and the tag.
+#[nasl_function(named_args(ENCRYPT_ARGS))]
 fn aes256_gcm_encrypt(register: &Register) -> Result<NaslValue, FnError> {
     gcm::<Aes256>(register, Crypt::Encrypt, false)
 }
@@ -202,7 +343,11 @@ fn aes256_gcm_encrypt(register: &Register) -> Result<NaslValue, FnError> {
 /// - The iv must have a length of 16 bytes. It is used as the initial counter.
 /// - The result contains the ciphertext and the calculated tag in a single data type.
 /// - The tag has a size of 16 Bytes.
-#[nasl_function]
+/// - With tag_mode "separate" the result is a dictionary with the ciphertext and the tag instead.
+///   The default tag_mode is "appended".
+/// - If no iv is given, a random 12 byte iv is generated. The result is then a dictionary with the
+///   iv, the ciphertext and the tag.
+#[nasl_function(named_args(ENCRYPT_AUTH_ARGS))]
 fn aes256_gcm_encrypt_auth(register: &Register) -> Result<NaslValue, FnError> {
     gcm::<Aes256>(register, Crypt::Encrypt, true)
 }
@@ -215,8 +360,9 @@ fn aes256_gcm_encrypt_auth(register: &Register) -> Result<NaslValue, FnError> {
 ///   Currently the data is filled with zeroes. Therefore the length of the encrypted data must be
 ///   known for decryption. If no length is given, the last block is decrypted as a whole.
 /// - The iv must have a length of 16 bytes. It is used as the initial counter.
-/// - The tag is needed as a postfix in the given data in order to decrypt successfully.
-#[nasl_function]
+/// - The tag is needed as a postfix in the given data or separately as tag in order to decrypt
+///   successfully.
+#[nasl_function(named_args(DECRYPT_ARGS))]
 fn aes256_gcm_decrypt(register: &Register) -> Result<NaslValue, FnError> {
     gcm::<Aes256>(register, Crypt::Decrypt, false)
 }
@@ -229,8 +375,9 @@ fn aes256_gcm_decrypt(register: &Register) -> Result<NaslValue, FnError> {
 ///   Currently the data is filled with zeroes. Therefore the length of the encrypted data must be
 ///   known for decryption. If no length is given, the last block is decrypted as a whole.
 /// - The iv must have a length of 16 bytes. It is used as the initial counter.
-/// - The tag is needed as a postfix in the given data in order to decrypt successfully.
-#[nasl_function]
+/// - The tag is needed as a postfix in the given data or separately as tag in order to decrypt
+///   successfully.
+#[nasl_function(named_args(DECRYPT_AUTH_ARGS))]
 fn aes256_gcm_decrypt_auth(register: &Register) -> Result<NaslValue, FnError> {
     gcm::<Aes256>(register, Crypt::Decrypt, true)
 }
@@ -0,0 +1,125 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+use aes_gcm_siv::{
+    aead::{Aead, KeyInit, Payload},
+    Aes128GcmSiv, Aes256GcmSiv,
+};
+
+use crate::nasl::prelude::*;
+
+use super::{
+    get_data, get_iv, get_key, get_optional_named_data, validate_named_args, Crypt,
+    CryptographicError, AEAD_ARGS,
+};
+
+/// Size of the nonce in bytes as defined in RFC 8452.
+const NONCE_SIZE: usize = 12;
+
+fn gcm_siv<C>(register: &Register, crypt: Crypt) -> Result<NaslValue, FnError>
+where
+    C: KeyInit + Aead,
+{
+    validate_named_args(register, AEAD_ARGS)?;
+    let key = get_key(register)?;
+    let data = get_data(register)?;
+    let iv = get_iv(register)?;
+    let aad = get_optional_named_data(register, "aad")?.unwrap_or_default();
+
+    let cipher = C::new_from_slice(&key).map_err(|_| {
+        ArgumentError::wrong_argument(
+            "key",
+            &format!("{} bytes long", C::key_size()),
+            &format!("{} bytes long", key.len()),
+        )
+    })?;
+    if iv.len() != NONCE_SIZE {
+        return Err(ArgumentError::wrong_argument(
+            "iv",
+            &format!("{NONCE_SIZE} bytes long"),
+            &format!("{} bytes long", iv.len()),
+        )
+        .into());
+    }
+
+    let payload = Payload {
+        msg: &data,
+        aad: &aad,
+    };
+    let res = match crypt {
+        Crypt::Encrypt => cipher
+            .encrypt(iv.as_ref().into(), payload)
+            .map_err(|_| CryptographicError::AesGcmSiv("unable to encrypt".to_string()))?,
+        Crypt::Decrypt => cipher
+            .decrypt(iv.as_ref().into(), payload)
+            .map_err(|_| CryptographicError::AuthenticationFailed.recoverable())?,
+    };
+    Ok(res.into())
+}
+
+/// NASL function to encrypt data with aes128 gcm-siv (RFC 8452).
+///
+/// This function expects 3 named arguments key, data and iv either in a string or data type and
+/// an optional aad argument.
+/// - The key must have a length of 16 bytes.
+/// - The iv must have a length of 12 bytes. Unlike in GCM, reusing it only reveals whether the
+///   same plaintext was encrypted twice.
+/// - The result contains the ciphertext and the calculated tag in a single data type.
+/// - The tag has a size of 16 Bytes.
+#[nasl_function(named_args(AEAD_ARGS))]
+fn aes128_gcm_siv_encrypt(register: &Register) -> Result<NaslValue, FnError> {
+    gcm_siv::<Aes128GcmSiv>(register, Crypt::Encrypt)
+}
+
+/// NASL function to decrypt data with aes128 gcm-siv (RFC 8452).
+///
+/// This function expects 3 named arguments key, data and iv either in a string or data type and
+/// an optional aad argument.
+/// - The key must have a length of 16 bytes.
+/// - The iv must have a length of 12 bytes.
+/// - The tag is needed as a postfix in the given data. If the tag does not match, an error is
+///   returned.
+#[nasl_function(named_args(AEAD_ARGS))]
+fn aes128_gcm_siv_decrypt(register: &Register) -> Result<NaslValue, FnError> {
+    gcm_siv::<Aes128GcmSiv>(register, Crypt::Decrypt)
+}
+
+/// NASL function to encrypt data with aes256 gcm-siv (RFC 8452).
+///
+/// This function expects 3 named arguments key, data and iv either in a string or data type and
+/// an optional aad argument.
+/// - The key must have a length of 32 bytes.
+/// - The iv must have a length of 12 bytes. Unlike in GCM, reusing it only reveals whether the
+///   same plaintext was encrypted twice.
+/// - The result contains the ciphertext and the calculated tag in a single data type.
+/// - The tag has a size of 16 Bytes.
+#[nasl_function(named_args(AEAD_ARGS))]
+fn aes256_gcm_siv_encrypt(register: &Register) -> Result<NaslValue, FnError> {
+    gcm_siv::<Aes256GcmSiv>(register, Crypt::Encrypt)
+}
+
+/// NASL function to decrypt data with aes256 gcm-siv (RFC 8452).
+///
+/// This function expects 3 named arguments key, data and iv either in a string or data type and
+/// an optional aad argument.
+/// - The key must have a length of 32 bytes.
+/// - The iv must have a length of 12 bytes.
+/// - The tag is needed as a postfix in the given data. If the tag does not match, an error is
+///   returned.
+#[nasl_function(named_args(AEAD_ARGS))]
+fn aes256_gcm_siv_decrypt(register: &Register) -> Result<NaslValue, FnError> {
+    gcm_siv::<Aes256GcmSiv>(register, Crypt::Decrypt)
+}
+
+pub struct AesGcmSivFns;
+
+function_set! {
+    AesGcmSivFns,
+    (
+        aes128_gcm_siv_encrypt,
+        aes128_gcm_siv_decrypt,
+        aes256_gcm_siv_encrypt,
+        aes256_gcm_siv_decrypt,
+    )
+}
@@ -19,7 +19,7 @@ fn aes_gmac(register: &Register) -> Result<NaslValue, FnError> {
     let data = get_data(register)?;
     let iv = get_iv(register)?;
 
-    match aes_gmac(data, key, iv) {
+    match aes_gmac(&data, &key, &iv) {
         Ok(val) => Ok(val.into()),
         Err(msg) => Err(CryptographicError::AesGmacError(msg.into()).into()),
     }
@@ -0,0 +1,126 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+use aes::{
+    cipher::{BlockCipher, BlockEncryptMut, KeyInit, KeySizeUser},
+    Aes128, Aes256,
+};
+use aes_siv::siv::{CmacSiv, MAX_HEADERS};
+use cmac::Cmac;
+use digest::{consts::U16, FixedOutputReset, Mac};
+
+use crate::nasl::prelude::*;
+
+use super::{
+    data_arg, data_array_arg, get_data, get_key, get_optional_named_array, validate_named_args,
+    Crypt, CryptographicError, NamedArg,
+};
+
+const ARGS: &[NamedArg] = &[
+    data_arg("key", true),
+    data_arg("data", true),
+    data_array_arg("aad", false),
+];
+
+fn siv<C>(register: &Register, crypt: Crypt) -> Result<NaslValue, FnError>
+where
+    C: BlockCipher<BlockSize = U16> + BlockEncryptMut + KeyInit + KeySizeUser + Clone,
+    Cmac<C>: Mac<OutputSize = U16> + FixedOutputReset + KeyInit,
+    CmacSiv<C>: KeyInit,
+{
+    validate_named_args(register, ARGS)?;
+    let key = get_key(register)?;
+    let data = get_data(register)?;
+    let aad = get_optional_named_array(register, "aad")?.unwrap_or_default();
+
+    let mut cipher = CmacSiv::<C>::new_from_slice(&key).map_err(|_| {
+        ArgumentError::wrong_argument(
+            "key",
+            &format!("{} bytes long", CmacSiv::<C>::key_size()),
+            &format!("{} bytes long", key.len()),
+        )
+    })?;
+    if aad.len() > MAX_HEADERS {
+        return Err(ArgumentError::wrong_argument(
+            "aad",
+            &format!("at most {MAX_HEADERS} components"),
+            &format!("{} components", aad.len()),
+        )
+        .into());
+    }
+
+    let res = match crypt {
+        Crypt::Encrypt => cipher
+            .encrypt(&aad, &data)
+            .map_err(|_| CryptographicError::AesSiv("unable to encrypt".to_string()))?,
+        Crypt::Decrypt => cipher
+            .decrypt(&aad, &data)
+            .map_err(|_| CryptographicError::AuthenticationFailed.recoverable())?,
+    };
+    Ok(res.into())
+}
+
+/// NASL function to encrypt data deterministically with aes128 siv (RFC 5297).
+///
+/// This function expects 2 named arguments key and data either in a string or data type and an
+/// optional aad argument.
+/// - The key must have a length of 32 bytes, the first half is used for S2V, the second for CTR.
+/// - The aad is an array of up to 126 components, which are authenticated separately. A single
+///   string or data value is treated as one component. A nonce, if used, is the last component.
+/// - The result contains the synthetic iv of 16 bytes followed by the ciphertext.
+#[nasl_function(named_args(ARGS))]
+fn aes128_siv_encrypt(register: &Register) -> Result<NaslValue, FnError> {
+    siv::<Aes128>(register, Crypt::Encrypt)
+}
+
+/// NASL function to decrypt data with aes128 siv (RFC 5297).
+///
+/// This function expects 2 named arguments key and data either in a string or data type and an
+/// optional aad argument.
+/// - The key must have a length of 32 bytes.
+/// - The aad must contain the same components as on encryption.
+/// - The synthetic iv is needed as a prefix in the given data. If it does not match, an error is
+///   returned.
+#[nasl_function(named_args(ARGS))]
+fn aes128_siv_decrypt(register: &Register) -> Result<NaslValue, FnError> {
+    siv::<Aes128>(register, Crypt::Decrypt)
+}
+
+/// NASL function to encrypt data deterministically with aes256 siv (RFC 5297).
+///
+/// This function expects 2 named arguments key and data either in a string or data type and an
+/// optional aad argument.
+/// - The key must have a length of 64 bytes, the first half is used for S2V, the second for CTR.
+/// - The aad is an array of up to 126 components, which are authenticated separately. A single
+///   string or data value is treated as one component. A nonce, if used, is the last component.
+/// - The result contains the synthetic iv of 16 bytes followed by the ciphertext.
+#[nasl_function(named_args(ARGS))]
+fn aes256_siv_encrypt(register: &Register) -> Result<NaslValue, FnError> {
+    siv::<Aes256>(register, Crypt::Encrypt)
+}
+
+/// NASL function to decrypt data with aes256 siv (RFC 5297).
+///
+/// This function expects 2 named arguments key and data either in a string or data type and an
+/// optional aad argument.
+/// - The key must have a length of 64 bytes.
+/// - The aad must contain the same components as on encryption.
+/// - The synthetic iv is needed as a prefix in the given data. If it does not match, an error is
+///   returned.
+#[nasl_function(named_args(ARGS))]
+fn aes256_siv_decrypt(register: &Register) -> Result<NaslValue, FnError> {
+    siv::<Aes256>(register, Crypt::Decrypt)
+}
+
+pub struct AesSivFns;
+
+function_set! {
+    AesSivFns,
+    (
+        aes128_siv_encrypt,
+        aes128_siv_decrypt,
+        aes256_siv_encrypt,
+        aes256_siv_decrypt,
+    )
+}
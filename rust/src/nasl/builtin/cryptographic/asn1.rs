@@ -0,0 +1,199 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+//! Minimal DER parser and encoder.
+//!
+//! An element is represented as a NASL array of the form `[tag, length, value]`. The tag is the
+//! identifier octet, e.g. 0x30 for a SEQUENCE. If the constructed bit (0x20) of the tag is set,
+//! the value is an array of the contained elements, otherwise it is the raw content as data.
+
+use crate::nasl::prelude::*;
+use crate::nasl::utils::function::str_to_bytes;
+
+use super::CryptographicError;
+
+const CONSTRUCTED: u8 = 0x20;
+const HIGH_TAG_NUMBER: u8 = 0x1f;
+/// Constructed elements nested deeper than this are rejected instead of recursing further.
+const MAX_DEPTH: usize = 64;
+
+fn invalid(offset: usize, reason: &str) -> CryptographicError {
+    CryptographicError::Asn1 {
+        offset,
+        reason: reason.to_string(),
+    }
+}
+
+/// Parses all elements in `data`. `base` is the offset of `data` within the complete input and is
+/// only used for error reporting, `depth` is the number of constructed elements around `data`.
+fn parse_elements(
+    data: &[u8],
+    base: usize,
+    depth: usize,
+) -> Result<Vec<NaslValue>, CryptographicError> {
+    let mut elements = vec![];
+    let mut pos = 0;
+    while pos < data.len() {
+        let (element, consumed) = parse_element(&data[pos..], base + pos, depth)?;
+        elements.push(element);
+        pos += consumed;
+    }
+    Ok(elements)
+}
+
+/// Parses a single element at the start of `data` and returns it together with the number of
+/// consumed bytes.
+fn parse_element(
+    data: &[u8],
+    base: usize,
+    depth: usize,
+) -> Result<(NaslValue, usize), CryptographicError> {
+    let tag = data[0];
+    if tag & HIGH_TAG_NUMBER == HIGH_TAG_NUMBER {
+        return Err(invalid(base, "high tag numbers are not supported"));
+    }
+    let first = *data
+        .get(1)
+        .ok_or_else(|| invalid(base + 1, "missing length"))?;
+    let (length, header) = if first & 0x80 == 0 {
+        (first as usize, 2)
+    } else {
+        let count = (first & 0x7f) as usize;
+        if count == 0 {
+            return Err(invalid(base + 1, "indefinite length is not allowed in DER"));
+        }
+        if count > std::mem::size_of::<usize>() {
+            return Err(invalid(base + 1, "length is too large"));
+        }
+        let bytes = data
+            .get(2..2 + count)
+            .ok_or_else(|| invalid(base + 1, "truncated length"))?;
+        if bytes[0] == 0 {
+            return Err(invalid(base + 2, "length is not minimally encoded"));
+        }
+        let length = bytes.iter().fold(0usize, |acc, b| (acc << 8) | *b as usize);
+        if length < 0x80 {
+            return Err(invalid(base + 1, "length is not minimally encoded"));
+        }
+        (length, 2 + count)
+    };
+    let end = header
+        .checked_add(length)
+        .ok_or_else(|| invalid(base + 1, "length is too large"))?;
+    let content = data
+        .get(header..end)
+        .ok_or_else(|| invalid(base + header, "truncated value"))?;
+    let value = if tag & CONSTRUCTED != 0 {
+        if depth >= MAX_DEPTH {
+            return Err(invalid(base, "elements are nested too deeply"));
+        }
+        NaslValue::Array(parse_elements(content, base + header, depth + 1)?.into())
+    } else {
+        NaslValue::Data(content.to_vec())
+    };
+    let element = NaslValue::from(vec![
+        NaslValue::Number(tag as i64),
+        NaslValue::Number(length as i64),
+        value,
+    ]);
+    Ok((element, end))
+}
+
+fn encode_length(length: usize, out: &mut Vec<u8>) {
+    if length < 0x80 {
+        out.push(length as u8);
+    } else {
+        let bytes = length.to_be_bytes();
+        let skip = bytes.iter().take_while(|b| **b == 0).count();
+        out.push(0x80 | (bytes.len() - skip) as u8);
+        out.extend_from_slice(&bytes[skip..]);
+    }
+}
+
+fn wrong_structure(got: &NaslValue) -> ArgumentError {
+    ArgumentError::wrong_argument(
+        "structure",
+        "an array of [tag, length, value] elements",
+        &format!("{got:?}"),
+    )
+}
+
+fn encode_element(element: &NaslValue, out: &mut Vec<u8>) -> Result<(), ArgumentError> {
+    let NaslValue::Array(items) = element else {
+        return Err(wrong_structure(element));
+    };
+    let tag = match items.first() {
+        Some(NaslValue::Number(x)) => {
+            u8::try_from(*x).map_err(|_| wrong_structure(&NaslValue::Number(*x)))?
+        }
+        _ => return Err(wrong_structure(element)),
+    };
+    if tag & HIGH_TAG_NUMBER == HIGH_TAG_NUMBER {
+        return Err(ArgumentError::wrong_argument(
+            "structure",
+            "a low tag number",
+            &tag.to_string(),
+        ));
+    }
+    let content = match items.get(2) {
+        Some(NaslValue::Array(children)) => encode_elements(children)?,
+        Some(NaslValue::Data(x)) => x.clone(),
+        Some(NaslValue::String(x)) => str_to_bytes(x).into_owned(),
+        Some(NaslValue::Null) | None => vec![],
+        Some(x) => return Err(wrong_structure(x)),
+    };
+    out.push(tag);
+    encode_length(content.len(), out);
+    out.extend(content);
+    Ok(())
+}
+
+fn encode_elements(elements: &[NaslValue]) -> Result<Vec<u8>, ArgumentError> {
+    let mut out = vec![];
+    for element in elements {
+        encode_element(element, &mut out)?;
+    }
+    Ok(out)
+}
+
+/// NASL function to parse DER encoded data.
+///
+/// Expects the data as the first positional or the named argument `data`. Returns an array of all
+/// top level elements, each represented as `[tag, length, value]`. Constructed elements contain
+/// an array of their children as value.
+///
+/// Truncated or invalid data results in an error containing the offending byte offset.
+#[nasl_function(maybe_named(data))]
+fn asn1_parse(data: &NaslValue) -> Result<NaslValue, FnError> {
+    let data = Vec::<u8>::from(data);
+    Ok(NaslValue::from(parse_elements(&data, 0, 0)?))
+}
+
+/// NASL function to encode a structure as returned by `asn1_parse` to DER.
+///
+/// Expects the structure as the first positional or the named argument `structure`. It can either
+/// be a single `[tag, length, value]` element or an array of them which are concatenated. The
+/// given length is ignored and calculated from the value.
+#[nasl_function(maybe_named(structure))]
+fn asn1_encode(structure: &NaslValue) -> Result<Vec<u8>, FnError> {
+    match structure {
+        NaslValue::Array(items) if matches!(items.first(), Some(NaslValue::Number(_))) => {
+            let mut out = vec![];
+            encode_element(structure, &mut out)?;
+            Ok(out)
+        }
+        NaslValue::Array(items) => Ok(encode_elements(items)?),
+        x => Err(wrong_structure(x).into()),
+    }
+}
+
+pub struct Asn1;
+
+function_set! {
+    Asn1,
+    (
+        asn1_parse,
+        asn1_encode,
+    )
+}
@@ -14,7 +14,7 @@ use cbc::{Decryptor, Encryptor};
 
 use crate::nasl::prelude::*;
 
-use super::{get_data, get_iv, get_key, get_len, Crypt};
+use super::{get_data, get_iv, get_key, get_len, Crypt, CryptographicError};
 
 /// Base function for en- and decrypting Cipher Block Chaining (CBC) mode
 fn cbc<D>(register: &Register, crypt: Crypt) -> Result<NaslValue, FnError>
@@ -29,10 +29,12 @@ where
     // Mode Encrypt or Decrypt
     match crypt {
         Crypt::Encrypt => {
-            let res = Encryptor::<D>::new_from_slices(key, iv);
+            let res = Encryptor::<D>::new_from_slices(&key, &iv);
             match res {
-                Ok(encryptor) => Ok(encryptor.encrypt_padded_vec_mut::<ZeroPadding>(data).into()),
-                Err(e) => Err(ArgumentError::WrongArgument(e.to_string()).into()),
+                Ok(encryptor) => Ok(encryptor
+                    .encrypt_padded_vec_mut::<ZeroPadding>(&data)
+                    .into()),
+                Err(_) => Err(CryptographicError::cbc_init::<D>("BfCbc", &key, &iv)),
             }
         }
         Crypt::Decrypt => {
@@ -51,14 +53,14 @@ where
                 )
                 .into());
             }
-            let res = Decryptor::<D>::new_from_slices(key, iv);
+            let res = Decryptor::<D>::new_from_slices(&key, &iv);
             match res {
                 Ok(decryptor) => Ok(decryptor
-                    .decrypt_padded_vec_mut::<NoPadding>(data)
-                    .map_err(|e| ArgumentError::WrongArgument(e.to_string()))?[..len]
+                    .decrypt_padded_vec_mut::<NoPadding>(&data)
+                    .map_err(|_| CryptographicError::PaddingError("BfCbc".to_string()))?[..len]
                     .to_vec()
                     .into()),
-                Err(e) => Err(ArgumentError::WrongArgument(e.to_string()).into()),
+                Err(_) => Err(CryptographicError::cbc_init::<D>("BfCbc", &key, &iv)),
             }
         }
     }
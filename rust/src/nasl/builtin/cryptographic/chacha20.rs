@@ -0,0 +1,76 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+use chacha20::{
+    cipher::{KeyIvInit, StreamCipher, StreamCipherSeek},
+    ChaCha20,
+};
+
+use crate::nasl::prelude::*;
+
+use super::{
+    get_data, get_key, get_optional_named_number, get_required_named_data, CryptographicError,
+};
+
+/// Size of a ChaCha20 block in bytes. The block counter advances once per block.
+const BLOCK_SIZE: u64 = 64;
+
+/// NASL function to apply the raw ChaCha20 (RFC 8439) keystream to data.
+///
+/// This function expects the named arguments key, nonce and data either in a string or data type
+/// and the optional named argument counter as a number.
+/// - The key must have a length of 32 bytes.
+/// - The nonce must have a length of 12 bytes.
+/// - The counter is the initial 32 bit block counter. It defaults to 0.
+///
+/// The result is the XOR of the data with the keystream. No MAC is calculated, since encryption
+/// and decryption are the same operation this function is used for both.
+#[nasl_function]
+fn chacha20(register: &Register) -> Result<NaslValue, FnError> {
+    let key = get_key(register)?;
+    let nonce = get_required_named_data(register, "nonce")?;
+    let data = get_data(register)?;
+    let counter = get_optional_named_number(register, "counter")?.unwrap_or(0);
+
+    if key.len() != 32 {
+        return Err(ArgumentError::wrong_argument(
+            "key",
+            "32 bytes long",
+            &format!("{} bytes long", key.len()),
+        )
+        .into());
+    }
+    if nonce.len() != 12 {
+        return Err(ArgumentError::wrong_argument(
+            "nonce",
+            "12 bytes long",
+            &format!("{} bytes long", nonce.len()),
+        )
+        .into());
+    }
+    let counter = u32::try_from(counter).map_err(|_| {
+        ArgumentError::wrong_argument(
+            "counter",
+            &format!("a number between 0 and {}", u32::MAX),
+            &counter.to_string(),
+        )
+    })?;
+
+    let mut cipher = ChaCha20::new(key.as_ref().into(), nonce.as_ref().into());
+    cipher.seek(counter as u64 * BLOCK_SIZE);
+    let mut buf = data.to_vec();
+    cipher
+        .try_apply_keystream(&mut buf)
+        .map_err(|e| CryptographicError::ChaCha20(e.to_string()))?;
+    Ok(buf.into())
+}
+
+pub struct ChaCha20Fns;
+
+function_set! {
+    ChaCha20Fns,
+    (
+        chacha20,
+    )
+}
@@ -2,26 +2,44 @@
 //
 // SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
 
-use digest::Digest;
+use std::borrow::Cow;
+
+use blake2::{Blake2bVar, Blake2sVar};
+use digest::{Digest, Update, VariableOutput};
 use md2::Md2;
 use md4::Md4;
 use md5::Md5;
 use ripemd::Ripemd160;
 use sha1::Sha1;
-use sha2::{Sha256, Sha512};
+use sha2::{Sha256, Sha384, Sha512};
+use sha3::{Sha3_224, Sha3_256, Sha3_384, Sha3_512};
+use streebog::{Streebog256, Streebog512};
 
 use crate::nasl::prelude::*;
-use crate::nasl::utils::function::StringOrData;
+use crate::nasl::utils::function::{str_to_bytes, StringOrData};
+
+use super::{get_len, maybe_blocking};
 
-fn nasl_hash<D: Digest>(data: Option<StringOrData>) -> Result<NaslValue, FnError>
+/// Upper bound for the output length of BLAKE3. As an extendable output function BLAKE3 could
+/// produce far more, this only protects against scripts requesting absurd amounts of output.
+const BLAKE3_MAX_LEN: usize = 1024;
+
+async fn nasl_hash<D: Digest>(
+    context: &Context<'_>,
+    data: Option<StringOrData>,
+) -> Result<NaslValue, FnError>
 where
     D::OutputSize: std::ops::Add,
     <D::OutputSize as std::ops::Add>::Output: digest::generic_array::ArrayLength<u8>,
 {
     if let Some(data) = data {
-        let mut hash = D::new();
-        hash.update(data.0.as_bytes());
-        Ok(NaslValue::Data(hash.finalize().as_slice().to_vec()))
+        let digest = maybe_blocking(
+            context.blocking_threshold(),
+            str_to_bytes(&data.0),
+            |data| D::digest(data).to_vec(),
+        )
+        .await;
+        Ok(NaslValue::Data(digest))
     } else {
         Ok(NaslValue::Null)
     }
@@ -29,44 +47,217 @@ where
 
 /// NASL function to get MD2 hash
 #[nasl_function]
-pub fn hash_md2(data: Option<StringOrData>) -> Result<NaslValue, FnError> {
-    nasl_hash::<Md2>(data)
+pub async fn hash_md2(
+    context: &Context<'_>,
+    data: Option<StringOrData>,
+) -> Result<NaslValue, FnError> {
+    nasl_hash::<Md2>(context, data).await
 }
 
 /// NASL function to get MD4 hash
 #[nasl_function]
-pub fn hash_md4(data: Option<StringOrData>) -> Result<NaslValue, FnError> {
-    nasl_hash::<Md4>(data)
+pub async fn hash_md4(
+    context: &Context<'_>,
+    data: Option<StringOrData>,
+) -> Result<NaslValue, FnError> {
+    nasl_hash::<Md4>(context, data).await
 }
 
 /// NASL function to get MD5 hash
 #[nasl_function]
-pub fn hash_md5(data: Option<StringOrData>) -> Result<NaslValue, FnError> {
-    nasl_hash::<Md5>(data)
+pub async fn hash_md5(
+    context: &Context<'_>,
+    data: Option<StringOrData>,
+) -> Result<NaslValue, FnError> {
+    nasl_hash::<Md5>(context, data).await
 }
 
 /// NASL function to get SHA1 hash
 #[nasl_function]
-pub fn hash_sha1(data: Option<StringOrData>) -> Result<NaslValue, FnError> {
-    nasl_hash::<Sha1>(data)
+pub async fn hash_sha1(
+    context: &Context<'_>,
+    data: Option<StringOrData>,
+) -> Result<NaslValue, FnError> {
+    nasl_hash::<Sha1>(context, data).await
 }
 
 /// NASL function to get SHA256 hash
 #[nasl_function]
-pub fn hash_sha256(data: Option<StringOrData>) -> Result<NaslValue, FnError> {
-    nasl_hash::<Sha256>(data)
+pub async fn hash_sha256(
+    context: &Context<'_>,
+    data: Option<StringOrData>,
+) -> Result<NaslValue, FnError> {
+    nasl_hash::<Sha256>(context, data).await
 }
 
 /// NASL function to get SHA512 hash
 #[nasl_function]
-pub fn hash_sha512(data: Option<StringOrData>) -> Result<NaslValue, FnError> {
-    nasl_hash::<Sha512>(data)
+pub async fn hash_sha512(
+    context: &Context<'_>,
+    data: Option<StringOrData>,
+) -> Result<NaslValue, FnError> {
+    nasl_hash::<Sha512>(context, data).await
+}
+
+/// NASL function to get the Streebog-256 (GOST R 34.11-2012) hash
+#[nasl_function]
+pub async fn hash_streebog256(
+    context: &Context<'_>,
+    data: Option<StringOrData>,
+) -> Result<NaslValue, FnError> {
+    nasl_hash::<Streebog256>(context, data).await
+}
+
+/// NASL function to get the Streebog-512 (GOST R 34.11-2012) hash
+#[nasl_function]
+pub async fn hash_streebog512(
+    context: &Context<'_>,
+    data: Option<StringOrData>,
+) -> Result<NaslValue, FnError> {
+    nasl_hash::<Streebog512>(context, data).await
 }
 
 /// NASL function to get RIPemd160 hash
 #[nasl_function]
-pub fn hash_ripemd160(data: Option<StringOrData>) -> Result<NaslValue, FnError> {
-    nasl_hash::<Ripemd160>(data)
+pub async fn hash_ripemd160(
+    context: &Context<'_>,
+    data: Option<StringOrData>,
+) -> Result<NaslValue, FnError> {
+    nasl_hash::<Ripemd160>(context, data).await
+}
+
+/// Returns the requested output length of a hash function with variable output, which has to be
+/// between 1 and `max` bytes.
+fn output_len(register: &Register, default: usize, max: usize) -> Result<usize, FnError> {
+    match get_len(register)? {
+        None => Ok(default),
+        Some(len) if (1..=max).contains(&len) => Ok(len),
+        Some(len) => Err(ArgumentError::wrong_argument(
+            "len",
+            &format!("between 1 and {max}"),
+            &len.to_string(),
+        )
+        .into()),
+    }
+}
+
+fn nasl_hash_var<D: VariableOutput + Update>(
+    register: &Register,
+    data: Option<StringOrData>,
+) -> Result<NaslValue, FnError> {
+    let len = output_len(register, D::MAX_OUTPUT_SIZE, D::MAX_OUTPUT_SIZE)?;
+    let Some(data) = data else {
+        return Ok(NaslValue::Null);
+    };
+    // The length is within the bounds checked above, creating the hasher cannot fail.
+    let mut hash = D::new(len).expect("valid output length");
+    hash.update(&str_to_bytes(&data.0));
+    let mut out = vec![0; len];
+    hash.finalize_variable(&mut out)
+        .expect("output buffer has the requested length");
+    Ok(NaslValue::Data(out))
+}
+
+/// NASL function to get the BLAKE2b hash. The optional named argument len selects the output
+/// length between 1 and 64 bytes, it defaults to 64.
+#[nasl_function]
+pub fn hash_blake2b(register: &Register, data: Option<StringOrData>) -> Result<NaslValue, FnError> {
+    nasl_hash_var::<Blake2bVar>(register, data)
+}
+
+/// NASL function to get the BLAKE2s hash. The optional named argument len selects the output
+/// length between 1 and 32 bytes, it defaults to 32.
+#[nasl_function]
+pub fn hash_blake2s(register: &Register, data: Option<StringOrData>) -> Result<NaslValue, FnError> {
+    nasl_hash_var::<Blake2sVar>(register, data)
+}
+
+/// NASL function to get the BLAKE3 hash. The optional named argument len selects the output
+/// length between 1 and 1024 bytes, it defaults to 32. Shorter outputs are prefixes of longer
+/// ones.
+#[nasl_function]
+pub fn hash_blake3(register: &Register, data: Option<StringOrData>) -> Result<NaslValue, FnError> {
+    let len = output_len(register, blake3::OUT_LEN, BLAKE3_MAX_LEN)?;
+    let Some(data) = data else {
+        return Ok(NaslValue::Null);
+    };
+    let mut out = vec![0; len];
+    blake3::Hasher::new()
+        .update(&str_to_bytes(&data.0))
+        .finalize_xof()
+        .fill(&mut out);
+    Ok(NaslValue::Data(out))
+}
+
+/// Names of the algorithms supported by the `hash` function.
+const HASH_ALGORITHMS: &[&str] = &[
+    "md2",
+    "md4",
+    "md5",
+    "ripemd160",
+    "sha1",
+    "sha256",
+    "sha384",
+    "sha512",
+    "sha3-224",
+    "sha3-256",
+    "sha3-384",
+    "sha3-512",
+    "streebog256",
+    "streebog512",
+];
+
+/// Calculates the digest of data with the algorithm of the given name. The name is case
+/// insensitive. Returns None for unknown algorithms.
+pub(super) fn digest_by_name(algo: &str, data: &[u8]) -> Option<Vec<u8>> {
+    let digest = match algo.to_lowercase().as_str() {
+        "md2" => Md2::digest(data).to_vec(),
+        "md4" => Md4::digest(data).to_vec(),
+        "md5" => Md5::digest(data).to_vec(),
+        "ripemd160" => Ripemd160::digest(data).to_vec(),
+        "sha1" => Sha1::digest(data).to_vec(),
+        "sha256" => Sha256::digest(data).to_vec(),
+        "sha384" => Sha384::digest(data).to_vec(),
+        "sha512" => Sha512::digest(data).to_vec(),
+        "sha3-224" => Sha3_224::digest(data).to_vec(),
+        "sha3-256" => Sha3_256::digest(data).to_vec(),
+        "sha3-384" => Sha3_384::digest(data).to_vec(),
+        "sha3-512" => Sha3_512::digest(data).to_vec(),
+        "streebog256" => Streebog256::digest(data).to_vec(),
+        "streebog512" => Streebog512::digest(data).to_vec(),
+        _ => return None,
+    };
+    Some(digest)
+}
+
+/// NASL function to hash data with an algorithm selected at runtime.
+///
+/// Expects the named arguments algo, the case insensitive name of the algorithm, and data, either
+/// a string or data. Supported algorithms are md2, md4, md5, ripemd160, sha1, sha256, sha384,
+/// sha512, sha3-224, sha3-256, sha3-384, sha3-512, streebog256 and streebog512.
+///
+/// Large data is hashed on a blocking thread, see [`Context::blocking_threshold`].
+#[nasl_function(named(algo, data))]
+async fn hash(context: &Context<'_>, algo: &str, data: &NaslValue) -> Result<NaslValue, FnError> {
+    let bytes = match data {
+        NaslValue::String(x) => str_to_bytes(x),
+        NaslValue::Data(x) => Cow::Borrowed(x.as_slice()),
+        x => {
+            return Err(ArgumentError::wrong_argument(
+                "data",
+                "a String or Data Value",
+                &format!("{x:?}"),
+            )
+            .into())
+        }
+    };
+    let name = algo.to_string();
+    maybe_blocking(context.blocking_threshold(), bytes, move |data| {
+        digest_by_name(&name, data)
+    })
+    .await
+    .map(NaslValue::Data)
+    .ok_or_else(|| ArgumentError::wrong_argument("algo", &HASH_ALGORITHMS.join(", "), algo).into())
 }
 
 pub struct Hash;
@@ -74,6 +265,7 @@ pub struct Hash;
 function_set! {
     Hash,
     (
+        hash,
         (hash_md2, "MD2"),
         (hash_md4, "MD4"),
         (hash_md5, "MD5"),
@@ -81,5 +273,10 @@ function_set! {
         (hash_sha1, "SHA1"),
         (hash_sha256, "SHA256"),
         (hash_sha512, "SHA512"),
+        (hash_streebog256, "STREEBOG256"),
+        (hash_streebog512, "STREEBOG512"),
+        (hash_blake2b, "blake2b"),
+        (hash_blake2s, "blake2s"),
+        (hash_blake3, "blake3"),
     )
 }
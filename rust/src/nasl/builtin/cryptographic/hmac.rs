@@ -2,6 +2,8 @@
 //
 // SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
 
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use blake2::{Blake2bMac512, Blake2sMac256};
 use digest::{
     block_buffer::Eager,
     core_api::{BufferKindUser, CoreProxy, FixedOutputCore, UpdateCore},
@@ -16,10 +18,16 @@ use md5::Md5;
 use ripemd::Ripemd160;
 use sha1::Sha1;
 use sha2::{Sha256, Sha384, Sha512};
+use sha3::{Sha3_256, Sha3_512};
+use subtle::ConstantTimeEq;
 
 use crate::nasl::prelude::*;
+use crate::nasl::utils::function::{str_to_bytes, Bytes};
 
-fn hmac<D>(key: &str, data: &str) -> Result<NaslValue, FnError>
+use super::{maybe_blocking, CryptographicError};
+
+/// Calculates the raw HMAC of data with the given key.
+pub(super) fn hmac_bytes<D>(key: &[u8], data: &[u8]) -> Result<Vec<u8>, FnError>
 where
     D: CoreProxy,
     D::Core: HashMarker
@@ -31,7 +39,7 @@ where
     <D::Core as BlockSizeUser>::BlockSize: IsLess<U256>,
     Le<<D::Core as BlockSizeUser>::BlockSize, U256>: NonZero,
 {
-    let mut hmac = match Hmac::<D>::new_from_slice(key.as_bytes()) {
+    let mut hmac = match Hmac::<D>::new_from_slice(key) {
         Ok(x) => x,
         Err(InvalidLength) => {
             return Err(FnError::wrong_unnamed_argument(
@@ -40,52 +48,258 @@ where
             ))
         }
     };
-    hmac.update(data.as_bytes());
-    Ok(NaslValue::String(encode(
-        hmac.finalize().into_bytes().as_slice(),
-    )))
+    hmac.update(data);
+    Ok(hmac.finalize().into_bytes().to_vec())
+}
+
+/// Calculates the HMAC of data with the given key as hex string, truncated to `out_len` bytes if
+/// given.
+///
+/// Keys longer than the block size of the hash function are hashed first, as defined by RFC 2104.
+fn hmac<D>(key: &str, data: &str, out_len: Option<usize>) -> Result<NaslValue, FnError>
+where
+    D: CoreProxy,
+    D::Core: HashMarker
+        + UpdateCore
+        + FixedOutputCore
+        + BufferKindUser<BufferKind = Eager>
+        + Default
+        + Clone,
+    <D::Core as BlockSizeUser>::BlockSize: IsLess<U256>,
+    Le<<D::Core as BlockSizeUser>::BlockSize, U256>: NonZero,
+{
+    truncate_tag(
+        hmac_bytes::<D>(&str_to_bytes(key), &str_to_bytes(data))?,
+        out_len,
+    )
+}
+
+/// Truncates the tag to `out_len` bytes if given and returns it as hex string.
+fn truncate_tag(mut tag: Vec<u8>, out_len: Option<usize>) -> Result<NaslValue, FnError> {
+    if let Some(out_len) = out_len {
+        if out_len == 0 || out_len > tag.len() {
+            return Err(ArgumentError::wrong_argument(
+                "out_len",
+                &format!("between 1 and {}", tag.len()),
+                &out_len.to_string(),
+            )
+            .into());
+        }
+        tag.truncate(out_len);
+    }
+    Ok(NaslValue::String(encode(tag)))
 }
 
 /// NASL function to get HMAC MD2 string
-#[nasl_function(named(key, data))]
-pub fn hmac_md2(key: &str, data: &str) -> Result<NaslValue, FnError> {
-    hmac::<Md2>(key, data)
+///
+/// The optional out_len truncates the tag to the given number of bytes.
+#[nasl_function(named(key, data, out_len))]
+pub fn hmac_md2(key: &str, data: &str, out_len: Option<usize>) -> Result<NaslValue, FnError> {
+    hmac::<Md2>(key, data, out_len)
 }
 
 /// NASL function to get HMAC MD5 string
-#[nasl_function(named(key, data))]
-pub fn hmac_md5(key: &str, data: &str) -> Result<NaslValue, FnError> {
-    hmac::<Md5>(key, data)
+///
+/// The optional out_len truncates the tag to the given number of bytes.
+#[nasl_function(named(key, data, out_len))]
+pub fn hmac_md5(key: &str, data: &str, out_len: Option<usize>) -> Result<NaslValue, FnError> {
+    hmac::<Md5>(key, data, out_len)
 }
 
 /// NASL function to get HMAC RIPEMD160 string
-#[nasl_function(named(key, data))]
-pub fn hmac_ripemd160(key: &str, data: &str) -> Result<NaslValue, FnError> {
-    hmac::<Ripemd160>(key, data)
+///
+/// The optional out_len truncates the tag to the given number of bytes.
+#[nasl_function(named(key, data, out_len))]
+pub fn hmac_ripemd160(key: &str, data: &str, out_len: Option<usize>) -> Result<NaslValue, FnError> {
+    hmac::<Ripemd160>(key, data, out_len)
 }
 
 /// NASL function to get HMAC SHA1 string
-#[nasl_function(named(key, data))]
-pub fn hmac_sha1(key: &str, data: &str) -> Result<NaslValue, FnError> {
-    hmac::<Sha1>(key, data)
+///
+/// The optional out_len truncates the tag to the given number of bytes.
+#[nasl_function(named(key, data, out_len))]
+pub fn hmac_sha1(key: &str, data: &str, out_len: Option<usize>) -> Result<NaslValue, FnError> {
+    hmac::<Sha1>(key, data, out_len)
 }
 
 /// NASL function to get HMAC SHA256 string
-#[nasl_function(named(key, data))]
-pub fn hmac_sha256(key: &str, data: &str) -> Result<NaslValue, FnError> {
-    hmac::<Sha256>(key, data)
+///
+/// The optional out_len truncates the tag to the given number of bytes.
+#[nasl_function(named(key, data, out_len))]
+pub fn hmac_sha256(key: &str, data: &str, out_len: Option<usize>) -> Result<NaslValue, FnError> {
+    hmac::<Sha256>(key, data, out_len)
 }
 
 /// NASL function to get HMAC SHA384 string
-#[nasl_function(named(key, data))]
-pub fn hmac_sha384(key: &str, data: &str) -> Result<NaslValue, FnError> {
-    hmac::<Sha384>(key, data)
+///
+/// The optional out_len truncates the tag to the given number of bytes.
+#[nasl_function(named(key, data, out_len))]
+pub fn hmac_sha384(key: &str, data: &str, out_len: Option<usize>) -> Result<NaslValue, FnError> {
+    hmac::<Sha384>(key, data, out_len)
 }
 
 /// NASL function to get HMAC SHA512 string
+///
+/// The optional out_len truncates the tag to the given number of bytes.
+#[nasl_function(named(key, data, out_len))]
+pub fn hmac_sha512(key: &str, data: &str, out_len: Option<usize>) -> Result<NaslValue, FnError> {
+    hmac::<Sha512>(key, data, out_len)
+}
+
+/// NASL function to get HMAC SHA3-256 string
+///
+/// The optional out_len truncates the tag to the given number of bytes.
+#[nasl_function(named(key, data, out_len))]
+pub fn hmac_sha3_256(key: &str, data: &str, out_len: Option<usize>) -> Result<NaslValue, FnError> {
+    hmac::<Sha3_256>(key, data, out_len)
+}
+
+/// NASL function to get HMAC SHA3-512 string
+///
+/// The optional out_len truncates the tag to the given number of bytes.
+#[nasl_function(named(key, data, out_len))]
+pub fn hmac_sha3_512(key: &str, data: &str, out_len: Option<usize>) -> Result<NaslValue, FnError> {
+    hmac::<Sha3_512>(key, data, out_len)
+}
+
+/// Calculates the keyed BLAKE2 hash of data, which BLAKE2 supports natively instead of HMAC.
+/// The key must not be empty nor longer than `max_key_len` bytes.
+fn blake2_mac<M: Mac + digest::KeyInit>(
+    key: &str,
+    data: &str,
+    max_key_len: usize,
+) -> Result<NaslValue, FnError> {
+    let key = str_to_bytes(key);
+    if key.is_empty() || key.len() > max_key_len {
+        return Err(ArgumentError::wrong_argument(
+            "key",
+            &format!("between 1 and {max_key_len} bytes"),
+            &format!("{} bytes", key.len()),
+        )
+        .into());
+    }
+    let mut mac = <M as Mac>::new_from_slice(&key)
+        .map_err(|_| FnError::wrong_unnamed_argument("valid size key", "invalid size key"))?;
+    mac.update(&str_to_bytes(data));
+    Ok(NaslValue::String(encode(mac.finalize().into_bytes())))
+}
+
+/// NASL function to get the keyed BLAKE2b-512 hash as string. The key has at most 64 bytes.
+#[nasl_function(named(key, data))]
+pub fn blake2b_mac(key: &str, data: &str) -> Result<NaslValue, FnError> {
+    blake2_mac::<Blake2bMac512>(key, data, 64)
+}
+
+/// NASL function to get the keyed BLAKE2s-256 hash as string. The key has at most 32 bytes.
 #[nasl_function(named(key, data))]
-pub fn hmac_sha512(key: &str, data: &str) -> Result<NaslValue, FnError> {
-    hmac::<Sha512>(key, data)
+pub fn blake2s_mac(key: &str, data: &str) -> Result<NaslValue, FnError> {
+    blake2_mac::<Blake2sMac256>(key, data, 32)
+}
+
+/// Names of the hash functions supported by the `hmac` function.
+const HMAC_ALGORITHMS: &[&str] = &[
+    "md2",
+    "md5",
+    "ripemd160",
+    "sha1",
+    "sha256",
+    "sha384",
+    "sha512",
+    "sha3-256",
+    "sha3-512",
+];
+
+/// The hash function used by `hmac` if no algo is given.
+const DEFAULT_HMAC_ALGORITHM: &str = "sha256";
+
+/// Calculates the raw HMAC with the hash function of the given name. The name is case
+/// insensitive. Returns None for unknown algorithms.
+fn hmac_by_name(algo: &str, key: &[u8], data: &[u8]) -> Option<Result<Vec<u8>, FnError>> {
+    let tag = match algo.to_lowercase().as_str() {
+        "md2" => hmac_bytes::<Md2>(key, data),
+        "md5" => hmac_bytes::<Md5>(key, data),
+        "ripemd160" => hmac_bytes::<Ripemd160>(key, data),
+        "sha1" => hmac_bytes::<Sha1>(key, data),
+        "sha256" => hmac_bytes::<Sha256>(key, data),
+        "sha384" => hmac_bytes::<Sha384>(key, data),
+        "sha512" => hmac_bytes::<Sha512>(key, data),
+        "sha3-256" => hmac_bytes::<Sha3_256>(key, data),
+        "sha3-512" => hmac_bytes::<Sha3_512>(key, data),
+        _ => return None,
+    };
+    Some(tag)
+}
+
+/// Calculates the raw HMAC like [`hmac_by_name`] but reports unknown algorithms as error.
+fn hmac_by_name_or_err(algo: Option<&str>, key: &[u8], data: &[u8]) -> Result<Vec<u8>, FnError> {
+    let algo = algo.unwrap_or(DEFAULT_HMAC_ALGORITHM);
+    hmac_by_name(algo, key, data)
+        .ok_or_else(|| ArgumentError::wrong_argument("algo", &HMAC_ALGORITHMS.join(", "), algo))?
+}
+
+/// NASL function to get a HMAC string with a hash function selected at runtime.
+///
+/// Expects the named arguments key and data, either a string or data, and optionally algo, the
+/// case insensitive name of the hash function, which defaults to sha256. Supported algorithms are
+/// md2, md5, ripemd160, sha1, sha256, sha384, sha512, sha3-256 and sha3-512. The optional out_len
+/// truncates the tag to the given number of bytes. Large data is processed on a blocking thread,
+/// see [`Context::blocking_threshold`].
+#[nasl_function(named(algo, key, data, out_len))]
+async fn hmac_by_algo(
+    context: &Context<'_>,
+    algo: Option<&str>,
+    key: Bytes<'_>,
+    data: Bytes<'_>,
+    out_len: Option<usize>,
+) -> Result<NaslValue, FnError> {
+    let algo = algo.map(str::to_string);
+    let key = key.0.into_owned();
+    let tag = maybe_blocking(context.blocking_threshold(), data.0, move |data| {
+        hmac_by_name_or_err(algo.as_deref(), &key, data)
+    })
+    .await?;
+    truncate_tag(tag, out_len)
+}
+
+/// NASL function to create a signed token, e.g. for a cookie or a state parameter.
+///
+/// Expects the named arguments data and key, either a string or data, and optionally algo, the
+/// hash function of the HMAC as for `hmac`, which defaults to sha256.
+///
+/// Returns the token as `base64url(data).base64url(hmac)` without padding.
+#[nasl_function(named(data, key, algo))]
+fn sign_token(data: Bytes<'_>, key: Bytes<'_>, algo: Option<&str>) -> Result<String, FnError> {
+    let tag = hmac_by_name_or_err(algo, &key.0, &data.0)?;
+    Ok(format!(
+        "{}.{}",
+        URL_SAFE_NO_PAD.encode(&data.0),
+        URL_SAFE_NO_PAD.encode(tag)
+    ))
+}
+
+/// NASL function to verify a token created by `sign_token`.
+///
+/// Expects the named arguments token, key and optionally algo as for `sign_token`.
+///
+/// Returns the decoded payload as data. A malformed token or a signature which does not match
+/// results in an error. The signature is compared in constant time.
+#[nasl_function(named(token, key, algo))]
+fn verify_token(token: &str, key: Bytes<'_>, algo: Option<&str>) -> Result<Vec<u8>, FnError> {
+    let invalid = |reason: &str| CryptographicError::Token(reason.to_string());
+    let (payload, signature) = token
+        .split_once('.')
+        .ok_or_else(|| invalid("expected the payload and the signature separated by '.'"))?;
+    let payload = URL_SAFE_NO_PAD
+        .decode(payload)
+        .map_err(|_| invalid("the payload is not base64url encoded"))?;
+    let signature = URL_SAFE_NO_PAD
+        .decode(signature)
+        .map_err(|_| invalid("the signature is not base64url encoded"))?;
+    let tag = hmac_by_name_or_err(algo, &key.0, &payload)?;
+    if !bool::from(tag.ct_eq(&signature)) {
+        return Err(invalid("the signature does not match").into());
+    }
+    Ok(payload)
 }
 
 pub struct HmacFns;
@@ -93,6 +307,7 @@ pub struct HmacFns;
 function_set! {
     HmacFns,
     (
+        (hmac_by_algo, "hmac"),
         (hmac_md2, "HMAC_MD2"),
         (hmac_md5, "HMAC_MD5"),
         (hmac_ripemd160, "HMAC_RIPEMD160"),
@@ -100,5 +315,11 @@ function_set! {
         (hmac_sha256, "HMAC_SHA256"),
         (hmac_sha384, "HMAC_SHA384"),
         (hmac_sha512, "HMAC_SHA512"),
+        (hmac_sha3_256, "HMAC_SHA3_256"),
+        (hmac_sha3_512, "HMAC_SHA3_512"),
+        (blake2b_mac, "BLAKE2B_MAC"),
+        (blake2s_mac, "BLAKE2S_MAC"),
+        sign_token,
+        verify_token,
     )
 }
@@ -0,0 +1,174 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+use std::collections::BTreeMap;
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use p256::ecdsa::{signature::Verifier, Signature, VerifyingKey};
+use rsa::pkcs8::DecodePublicKey;
+use rsa::{Pkcs1v15Sign, RsaPublicKey};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+
+use crate::nasl::prelude::*;
+
+use super::hmac::hmac_bytes;
+use super::CryptographicError;
+
+/// The segments of a JWS compact serialization (RFC 7515, section 7.1).
+struct Token<'a> {
+    header: &'a str,
+    claims: &'a str,
+    signature: &'a str,
+}
+
+impl<'a> Token<'a> {
+    fn parse(token: &'a str) -> Result<Self, CryptographicError> {
+        let segments: Vec<_> = token.split('.').collect();
+        match segments[..] {
+            [header, claims, signature] => Ok(Self {
+                header,
+                claims,
+                signature,
+            }),
+            _ => Err(CryptographicError::Jwt(format!(
+                "expected 3 segments separated by '.' but found {}",
+                segments.len()
+            ))),
+        }
+    }
+
+    /// The part the signature is calculated over.
+    fn signing_input(&self) -> String {
+        format!("{}.{}", self.header, self.claims)
+    }
+}
+
+fn decode_segment(name: &str, segment: &str) -> Result<Vec<u8>, CryptographicError> {
+    URL_SAFE_NO_PAD
+        .decode(segment)
+        .map_err(|e| CryptographicError::Jwt(format!("{name} is not base64url encoded: {e}")))
+}
+
+fn decode_json(name: &str, segment: &str) -> Result<Value, CryptographicError> {
+    let json = decode_segment(name, segment)?;
+    match serde_json::from_slice(&json) {
+        Ok(x @ Value::Object(_)) => Ok(x),
+        Ok(_) => Err(CryptographicError::Jwt(format!(
+            "{name} is not a JSON object"
+        ))),
+        Err(e) => Err(CryptographicError::Jwt(format!(
+            "{name} is not valid JSON: {e}"
+        ))),
+    }
+}
+
+/// Converts a JSON value. Numbers which do not fit into a NASL number are kept as string.
+fn json_to_nasl(value: Value) -> NaslValue {
+    match value {
+        Value::Null => NaslValue::Null,
+        Value::Bool(x) => NaslValue::Boolean(x),
+        Value::Number(x) => match x.as_i64() {
+            Some(x) => NaslValue::Number(x),
+            None => NaslValue::String(x.to_string()),
+        },
+        Value::String(x) => NaslValue::String(x),
+        Value::Array(x) => NaslValue::from(x.into_iter().map(json_to_nasl).collect::<Vec<_>>()),
+        Value::Object(x) => {
+            NaslValue::Dict(x.into_iter().map(|(k, v)| (k, json_to_nasl(v))).collect())
+        }
+    }
+}
+
+fn verify_hs256(key: &[u8], input: &[u8], signature: &[u8]) -> Result<bool, FnError> {
+    let mac = hmac_bytes::<Sha256>(key, input)?;
+    Ok(mac.ct_eq(signature).into())
+}
+
+fn verify_rs256(key: &str, input: &[u8], signature: &[u8]) -> Result<bool, FnError> {
+    let key = RsaPublicKey::from_public_key_pem(key).map_err(|e| {
+        ArgumentError::WrongArgument(format!(
+            "Expected key to be a PEM encoded RSA public key, {e}"
+        ))
+    })?;
+    let hashed = Sha256::digest(input);
+    Ok(key
+        .verify(Pkcs1v15Sign::new::<Sha256>(), &hashed, signature)
+        .is_ok())
+}
+
+fn verify_es256(key: &str, input: &[u8], signature: &[u8]) -> Result<bool, FnError> {
+    let key = VerifyingKey::from_public_key_pem(key).map_err(|e| {
+        ArgumentError::WrongArgument(format!(
+            "Expected key to be a PEM encoded P-256 public key, {e}"
+        ))
+    })?;
+    // JWS uses the raw concatenation of r and s instead of a DER encoding.
+    let Ok(signature) = Signature::from_slice(signature) else {
+        return Ok(false);
+    };
+    Ok(key.verify(input, &signature).is_ok())
+}
+
+/// NASL function to decode a JSON Web Token (RFC 7519) without verifying its signature.
+///
+/// Expects the token in the compact serialization as the first positional or the named argument
+/// token.
+///
+/// Returns a dict with the decoded `header` and `claims`. Numbers which exceed the range of a
+/// NASL number are returned as string.
+/// A malformed token results in an error telling which segment is broken.
+#[nasl_function(maybe_named(token))]
+fn jwt_decode(token: &str) -> Result<NaslValue, FnError> {
+    let token = Token::parse(token)?;
+    let header = decode_json("header", token.header)?;
+    let claims = decode_json("claims", token.claims)?;
+    decode_segment("signature", token.signature)?;
+    Ok(NaslValue::Dict(BTreeMap::from([
+        ("header".to_string(), json_to_nasl(header)),
+        ("claims".to_string(), json_to_nasl(claims)),
+    ])))
+}
+
+/// NASL function to verify the signature of a JSON Web Token (RFC 7519).
+///
+/// Expects the token as the first positional or the named argument token and the named
+/// arguments:
+/// - key: the shared secret for HS256, a PEM encoded public key for RS256 and ES256.
+/// - algo: the expected algorithm, one of HS256, RS256 or ES256.
+///
+/// Returns TRUE if the signature is valid for the given key. A token whose header names a
+/// different algorithm than `algo` is rejected with FALSE. The claims, e.g. the expiration time,
+/// are not checked. A malformed token results in an error telling which segment is broken.
+#[nasl_function(maybe_named(token), named(key, algo))]
+fn jwt_verify(token: &str, key: &NaslValue, algo: &str) -> Result<bool, FnError> {
+    let algo = algo.to_uppercase();
+    if !matches!(algo.as_str(), "HS256" | "RS256" | "ES256") {
+        return Err(ArgumentError::wrong_argument("algo", "HS256, RS256 or ES256", &algo).into());
+    }
+    let token = Token::parse(token)?;
+    let header = decode_json("header", token.header)?;
+    decode_json("claims", token.claims)?;
+    let signature = decode_segment("signature", token.signature)?;
+    let input = token.signing_input();
+    if header.get("alg").and_then(Value::as_str) != Some(algo.as_str()) {
+        return Ok(false);
+    }
+    match algo.as_str() {
+        "HS256" => verify_hs256(&Vec::<u8>::from(key), input.as_bytes(), &signature),
+        "RS256" => verify_rs256(&key.to_string(), input.as_bytes(), &signature),
+        _ => verify_es256(&key.to_string(), input.as_bytes(), &signature),
+    }
+}
+
+pub struct Jwt;
+
+function_set! {
+    Jwt,
+    (
+        jwt_decode,
+        jwt_verify,
+    )
+}
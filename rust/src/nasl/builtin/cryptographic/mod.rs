@@ -2,11 +2,15 @@
 //
 // SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
 
+use std::borrow::Cow;
+
 use thiserror::Error;
 
 // use crate::nasl::utils::combine_function_sets;
 use crate::nasl::prelude::*;
 
+use crate::nasl::utils::function::{str_to_bytes, NamedArgSignature};
+use crate::nasl::utils::lookup_keys::FC_ANON_ARGS;
 use crate::nasl::utils::{ContextType, IntoFunctionSet, Register, StoredFunctionSet};
 
 pub mod aes_cbc;
@@ -14,13 +18,24 @@ pub mod aes_ccm;
 pub mod aes_cmac;
 pub mod aes_ctr;
 pub mod aes_gcm;
+pub mod aes_gcm_siv;
 pub mod aes_gmac;
+pub mod aes_ocb;
+pub mod aes_siv;
+pub mod asn1;
 pub mod bf_cbc;
+pub mod chacha20;
 pub mod des;
 pub mod hash;
 pub mod hmac;
+pub mod jwt;
+pub mod ntlm;
+pub mod otp;
+pub mod password;
+pub mod poly1305;
 pub mod rc4;
 pub mod rsa;
+pub mod tls_prf;
 
 #[cfg(test)]
 mod tests;
@@ -35,10 +50,82 @@ pub enum CryptographicError {
     AesGmacError(String),
     #[error("Invalid length of key in AesCmac {0}.")]
     AesCmacInvalidLength(digest::InvalidLength),
+    /// The cipher, given by its name, does not accept the length of the key.
+    #[error("Invalid length of key in {0}.")]
+    InvalidKeyLength(String),
+    /// The data decrypted by the cipher, given by its name, is not padded to its block size.
+    #[error("Invalid padding of the data in {0}.")]
+    PaddingError(String),
     #[error("Error in RSA: {0}.")]
     Rsa(String),
     #[error("Error in RC4: {0}.")]
     Rc4(String),
+    #[error("Error in AesCtr: {0}.")]
+    AesCtr(String),
+    #[error("Error in AesGcmSiv: {0}.")]
+    AesGcmSiv(String),
+    #[error("Error in AesOcb: {0}.")]
+    AesOcb(String),
+    #[error("Error in AesSiv: {0}.")]
+    AesSiv(String),
+    #[error("Invalid ASN.1 at byte offset {offset}: {reason}.")]
+    Asn1 { offset: usize, reason: String },
+    #[error("Error in ChaCha20: {0}.")]
+    ChaCha20(String),
+    #[error("Malformed JWT: {0}.")]
+    Jwt(String),
+    #[error("Error in NTLM: {0}.")]
+    Ntlm(String),
+    #[error("Invalid token: {0}.")]
+    Token(String),
+    /// The tag of an AEAD ciphertext does not match. It is the same error for any tampered byte,
+    /// so it does not tell which part of the ciphertext or tag was altered.
+    #[error("Authentication of the ciphertext failed.")]
+    AuthenticationFailed,
+}
+
+impl CryptographicError {
+    /// Returns the error of a block cipher in CBC mode which cannot be created from the key and
+    /// iv, which is either the length of the key or, if the key is fine, the length of the iv.
+    fn cbc_init<D: aes::cipher::KeyInit>(cipher: &str, key: &[u8], iv: &[u8]) -> FnError {
+        if D::new_from_slice(key).is_err() {
+            CryptographicError::InvalidKeyLength(cipher.to_string()).into()
+        } else {
+            ArgumentError::wrong_argument(
+                "iv",
+                "as long as the block size",
+                &format!("{} bytes long", iv.len()),
+            )
+            .into()
+        }
+    }
+
+    /// Marks the error as [`Recoverable`] with empty data as the value of the failed call, so that
+    /// a script can go on after an error which depends on the processed data rather than on the
+    /// script, e.g. a tampered ciphertext.
+    fn recoverable(self) -> FnError {
+        self.with(ReturnValue(NaslValue::Data(vec![])))
+            .with(Recoverable)
+    }
+}
+
+/// Applies `f` to `data`. Data of at least `threshold` bytes, see [`Context::blocking_threshold`],
+/// is processed via [`tokio::task::spawn_blocking`], smaller data directly to avoid the overhead.
+/// Without a tokio runtime the data is always processed directly.
+async fn maybe_blocking<T, F>(threshold: usize, data: Cow<'_, [u8]>, f: F) -> T
+where
+    T: Send + 'static,
+    F: FnOnce(&[u8]) -> T + Send + 'static,
+{
+    if data.len() < threshold || tokio::runtime::Handle::try_current().is_err() {
+        return f(&data);
+    }
+    let data = data.into_owned();
+    match tokio::task::spawn_blocking(move || f(&data)).await {
+        Ok(x) => x,
+        Err(e) if e.is_panic() => std::panic::resume_unwind(e.into_panic()),
+        Err(e) => panic!("Blocking task was cancelled: {e}"),
+    }
 }
 
 enum Crypt {
@@ -46,6 +133,205 @@ enum Crypt {
     Decrypt,
 }
 
+/// Alternative names of named arguments used by classic NASL scripts.
+const NAMED_ALIASES: &[(&str, &[&str])] = &[("key", &["cryptkey"])];
+
+/// Looks up the named argument `key` or, if it is not given, one of its aliases. Returns the name
+/// which was found along with the value, so errors refer to the name used in the script.
+fn named_or_alias<'a>(register: &'a Register, key: &'a str) -> Option<(&'a str, &'a ContextType)> {
+    if let Some(x) = register.named(key) {
+        return Some((key, x));
+    }
+    let (_, aliases) = NAMED_ALIASES.iter().find(|(name, _)| *name == key)?;
+    aliases
+        .iter()
+        .find_map(|alias| Some((*alias, register.named(alias)?)))
+}
+
+/// Converts the value of a named argument found by [`named_or_alias`] into bytes. This is where
+/// the value is actually used, so it also logs the warning about a deprecated alias. Validating
+/// the arguments beforehand does not warn, so that each call warns once.
+fn named_data<'a>(
+    key: &str,
+    (name, value): (&'a str, &'a ContextType),
+) -> Result<Cow<'a, [u8]>, ArgumentError> {
+    if name != key {
+        tracing::warn!("The argument {name} is deprecated, use {key} instead.");
+    }
+    match value {
+        ContextType::Value(NaslValue::Data(x)) => Ok(Cow::Borrowed(x)),
+        ContextType::Value(NaslValue::String(x)) => Ok(str_to_bytes(x)),
+        x => Err(ArgumentError::wrong_argument(
+            name,
+            "a String or Data Value",
+            format!("{:?}", x).as_str(),
+        )),
+    }
+}
+
+/// The type expected for a named argument checked by [`validate_named_args`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Expected {
+    /// A String or Data value.
+    Data,
+    /// A String value.
+    String,
+    /// A Number value.
+    Number,
+    /// An array of String or Data values or a single one of them.
+    DataArray,
+}
+
+impl Expected {
+    /// The Rust type the argument is converted into, as given in its [`NamedArgSignature`].
+    const fn ty(self) -> &'static str {
+        match self {
+            Expected::Data => "Bytes",
+            Expected::String => "&str",
+            Expected::Number => "i64",
+            Expected::DataArray => "Vec<Bytes>",
+        }
+    }
+
+    fn matches(self, value: &ContextType) -> bool {
+        matches!(
+            (self, value),
+            (
+                Expected::Data,
+                ContextType::Value(NaslValue::Data(_) | NaslValue::String(_))
+            ) | (Expected::String, ContextType::Value(NaslValue::String(_)))
+                | (Expected::Number, ContextType::Value(NaslValue::Number(_)))
+                | (
+                    Expected::DataArray,
+                    ContextType::Value(
+                        NaslValue::Array(_) | NaslValue::Data(_) | NaslValue::String(_)
+                    )
+                )
+        )
+    }
+
+    fn description(self) -> &'static str {
+        match self {
+            Expected::Data => "a String or Data Value",
+            Expected::String => "a String Value",
+            Expected::Number => "a Number Value",
+            Expected::DataArray => "an array of String or Data Values",
+        }
+    }
+}
+
+/// A named argument checked by [`validate_named_args`].
+#[derive(Debug, Clone, Copy)]
+struct NamedArg {
+    name: &'static str,
+    expected: Expected,
+    required: bool,
+}
+
+impl From<&NamedArg> for NamedArgSignature {
+    fn from(arg: &NamedArg) -> Self {
+        NamedArgSignature {
+            name: arg.name,
+            ty: arg.expected.ty(),
+            required: arg.required,
+        }
+    }
+}
+
+/// A named argument of type String or Data.
+const fn data_arg(name: &'static str, required: bool) -> NamedArg {
+    NamedArg {
+        name,
+        expected: Expected::Data,
+        required,
+    }
+}
+
+/// A named argument of type String.
+const fn string_arg(name: &'static str, required: bool) -> NamedArg {
+    NamedArg {
+        name,
+        expected: Expected::String,
+        required,
+    }
+}
+
+/// A named argument of type Number.
+const fn number_arg(name: &'static str, required: bool) -> NamedArg {
+    NamedArg {
+        name,
+        expected: Expected::Number,
+        required,
+    }
+}
+
+/// A named argument of type Array whose elements are String or Data values, see
+/// [`get_optional_named_array`].
+const fn data_array_arg(name: &'static str, required: bool) -> NamedArg {
+    NamedArg {
+        name,
+        expected: Expected::DataArray,
+        required,
+    }
+}
+
+/// The named arguments of the AEAD ciphers which take a key, data, an iv and an optional aad.
+const AEAD_ARGS: &[NamedArg] = &[
+    data_arg("key", true),
+    data_arg("data", true),
+    data_arg("iv", true),
+    data_arg("aad", false),
+];
+
+/// Checks all named arguments of a function before any of them is used, so that a script gets
+/// to know about every problem at once instead of one after another. Reports missing required
+/// arguments, arguments of the wrong type and, with the `enforce-no-trailing-arguments` feature,
+/// arguments which are not expected. A single problem is returned as is, more of them as
+/// [`ArgumentError::Multiple`].
+///
+/// The same list is given to `#[nasl_function(named_args(...))]`, so that the signature of the
+/// function is generated from it.
+fn validate_named_args(register: &Register, args: &[NamedArg]) -> Result<(), ArgumentError> {
+    let mut errors = vec![];
+    let mut missing = vec![];
+    for arg in args {
+        match named_or_alias(register, arg.name) {
+            None if arg.required => missing.push(arg.name.to_string()),
+            None => {}
+            Some((name, x)) if !arg.expected.matches(x) => errors.push(
+                ArgumentError::wrong_argument(name, arg.expected.description(), &format!("{x:?}")),
+            ),
+            Some(_) => {}
+        }
+    }
+    if !missing.is_empty() {
+        errors.insert(0, ArgumentError::MissingNamed(missing));
+    }
+    let known = |name: &str| {
+        name == FC_ANON_ARGS
+            || args.iter().any(|arg| {
+                arg.name == name
+                    || NAMED_ALIASES
+                        .iter()
+                        .any(|(key, aliases)| *key == arg.name && aliases.contains(&name))
+            })
+    };
+    for name in register.iter_named_args().into_iter().flatten() {
+        if known(name) {
+            continue;
+        }
+        #[cfg(feature = "enforce-no-trailing-arguments")]
+        errors.push(ArgumentError::UnexpectedArgument(name.into()));
+        #[cfg(not(feature = "enforce-no-trailing-arguments"))]
+        tracing::debug!("Unexpected named argument '{name}'.");
+    }
+    match errors.len() {
+        0 => Ok(()),
+        1 => Err(errors.remove(0)),
+        _ => Err(ArgumentError::Multiple(errors)),
+    }
+}
+
 /// Get named argument of Type Data or String from the register with appropriate error handling.
 /// In case the argument is required, the returned value is either an Error or the Option is always
 /// set to Some value. If it is false, no error will be returned but the Option can be either Some
@@ -53,16 +339,56 @@ enum Crypt {
 fn get_required_named_data<'a>(
     register: &'a Register,
     key: &'a str,
-) -> Result<&'a [u8], ArgumentError> {
+) -> Result<Cow<'a, [u8]>, ArgumentError> {
+    match named_or_alias(register, key) {
+        Some(found) => named_data(key, found),
+        None => Err(ArgumentError::MissingNamed(vec![key.into()])),
+    }
+}
+
+/// Get named argument of Type Data or String from the register with appropriate error handling.
+///
+/// This distinguishes an absent from an empty argument: `None` means the argument was not given,
+/// while `Some` of an empty slice means it was given as an empty data or string value. Functions
+/// for which an empty value has a meaning of its own, like the AAD of the AEAD ciphers, can rely
+/// on that instead of treating both the same. The value is borrowed for data, strings are
+/// converted with [`str_to_bytes`].
+fn get_optional_named_data<'a>(
+    register: &'a Register,
+    key: &'a str,
+) -> Result<Option<Cow<'a, [u8]>>, ArgumentError> {
+    named_or_alias(register, key)
+        .map(|found| named_data(key, found))
+        .transpose()
+}
+
+/// Get named argument of Type Array, whose elements are Data or String values, from the register
+/// with appropriate error handling. A single Data or String value is treated as an array with just
+/// that element. In case the argument is not given, None is returned.
+fn get_optional_named_array<'a>(
+    register: &'a Register,
+    key: &'a str,
+) -> Result<Option<Vec<Cow<'a, [u8]>>>, ArgumentError> {
+    let element = |x: &'a NaslValue| match x {
+        NaslValue::Data(x) => Ok(Cow::Borrowed(x.as_slice())),
+        NaslValue::String(x) => Ok(str_to_bytes(x)),
+        x => Err(ArgumentError::wrong_argument(
+            key,
+            "an array of String or Data Values",
+            format!("{:?}", x).as_str(),
+        )),
+    };
     match register.named(key) {
-        Some(ContextType::Value(NaslValue::Data(x))) => Ok(x.as_slice()),
-        Some(ContextType::Value(NaslValue::String(x))) => Ok(x.as_bytes()),
+        None => Ok(None),
+        Some(ContextType::Value(NaslValue::Array(x))) => {
+            x.iter().map(element).collect::<Result<_, _>>().map(Some)
+        }
+        Some(ContextType::Value(x)) => element(x).map(|x| Some(vec![x])),
         Some(x) => Err(ArgumentError::wrong_argument(
             key,
-            "a String or Data Value",
+            "an array of String or Data Values",
             format!("{:?}", x).as_str(),
         )),
-        _ => Err(ArgumentError::MissingNamed(vec![key.into()])),
     }
 }
 
@@ -83,22 +409,22 @@ fn get_optional_named_number(register: &Register, key: &str) -> Result<Option<i6
 }
 
 /// Get the required key argument or error.
-fn get_key(register: &Register) -> Result<&[u8], ArgumentError> {
+fn get_key(register: &Register) -> Result<Cow<'_, [u8]>, ArgumentError> {
     get_required_named_data(register, "key")
 }
 
 /// Get the required data argument or error.
-fn get_data(register: &Register) -> Result<&[u8], ArgumentError> {
+fn get_data(register: &Register) -> Result<Cow<'_, [u8]>, ArgumentError> {
     get_required_named_data(register, "data")
 }
 
 /// Get the required iv argument or error.
-fn get_iv(register: &Register) -> Result<&[u8], ArgumentError> {
+fn get_iv(register: &Register) -> Result<Cow<'_, [u8]>, ArgumentError> {
     get_required_named_data(register, "iv")
 }
 
 /// Get the required iv argument or error.
-fn get_aad(register: &Register) -> Result<&[u8], ArgumentError> {
+fn get_aad(register: &Register) -> Result<Cow<'_, [u8]>, ArgumentError> {
     get_required_named_data(register, "aad")
 }
 
@@ -119,6 +445,41 @@ fn get_len(register: &Register) -> Result<Option<usize>, ArgumentError> {
     }
 }
 
+/// A len argument that may refer to the rest of a buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LenArg {
+    /// Use everything that is remaining.
+    Rest,
+    /// Use exactly the given number of bytes.
+    Exact(usize),
+}
+
+impl LenArg {
+    /// Resolves the length against the number of `available` bytes. An exact length larger than
+    /// the available bytes is an error.
+    fn resolve(self, available: usize) -> Result<usize, ArgumentError> {
+        match self {
+            LenArg::Rest => Ok(available),
+            LenArg::Exact(x) if x > available => Err(ArgumentError::wrong_argument(
+                "len",
+                &format!("at most the data length of {available}"),
+                &x.to_string(),
+            )),
+            LenArg::Exact(x) => Ok(x),
+        }
+    }
+}
+
+/// Get the optional len argument. Ported scripts pass a negative number, usually -1, to mean "to
+/// the end of the buffer", so a negative or omitted len results in [`LenArg::Rest`].
+fn get_len_or_rest(register: &Register) -> Result<LenArg, ArgumentError> {
+    match get_optional_named_number(register, "len")? {
+        None => Ok(LenArg::Rest),
+        Some(x) if x < 0 => Ok(LenArg::Rest),
+        Some(x) => Ok(LenArg::Exact(x as usize)),
+    }
+}
+
 pub struct Cryptographic;
 
 impl IntoFunctionSet for Cryptographic {
@@ -131,12 +492,22 @@ impl IntoFunctionSet for Cryptographic {
         set.add_set(aes_cbc::AesCbc);
         set.add_set(aes_ctr::AesCtr);
         set.add_set(aes_gcm::AesGcmFns);
+        set.add_set(aes_gcm_siv::AesGcmSivFns);
         set.add_set(aes_cmac::AesCmac);
         set.add_set(aes_gmac::AesGmac);
+        set.add_set(aes_ocb::AesOcbFns);
+        set.add_set(aes_siv::AesSivFns);
         set.add_set(hash::Hash);
         set.add_set(des::Des);
         set.add_set(rsa::Rsa);
         set.add_set(bf_cbc::BfCbc);
+        set.add_set(chacha20::ChaCha20Fns);
+        set.add_set(poly1305::Poly1305Fns);
+        set.add_set(otp::Otp);
+        set.add_set(jwt::Jwt);
+        set.add_set(ntlm::Ntlm);
+        set.add_set(asn1::Asn1);
+        set.add_set(tls_prf::TlsPrf);
         set
     }
 }
@@ -0,0 +1,332 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+//! Construction of the NTLM messages as defined in MS-NLMP.
+//!
+//! Only NTLMv2 is supported. The messages neither contain a version nor a MIC and no session key
+//! is exchanged.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use digest::Digest;
+use md4::Md4;
+use md5::Md5;
+
+use crate::nasl::prelude::*;
+use crate::nasl::utils::random;
+
+use super::hmac::hmac_bytes;
+use super::CryptographicError;
+
+const SIGNATURE: &[u8; 8] = b"NTLMSSP\0";
+
+const NEGOTIATE_UNICODE: u32 = 0x0000_0001;
+const REQUEST_TARGET: u32 = 0x0000_0004;
+const NEGOTIATE_NTLM: u32 = 0x0000_0200;
+const OEM_DOMAIN_SUPPLIED: u32 = 0x0000_1000;
+const OEM_WORKSTATION_SUPPLIED: u32 = 0x0000_2000;
+const NEGOTIATE_ALWAYS_SIGN: u32 = 0x0000_8000;
+const NEGOTIATE_EXTENDED_SESSIONSECURITY: u32 = 0x0008_0000;
+const NEGOTIATE_TARGET_INFO: u32 = 0x0080_0000;
+const NEGOTIATE_128: u32 = 0x2000_0000;
+const NEGOTIATE_56: u32 = 0x8000_0000;
+
+/// The flags sent in the negotiate message. The authenticate message uses those of them which
+/// the server accepted.
+const NEGOTIATE_FLAGS: u32 = NEGOTIATE_UNICODE
+    | REQUEST_TARGET
+    | NEGOTIATE_NTLM
+    | NEGOTIATE_ALWAYS_SIGN
+    | NEGOTIATE_EXTENDED_SESSIONSECURITY
+    | NEGOTIATE_TARGET_INFO
+    | NEGOTIATE_128
+    | NEGOTIATE_56;
+
+/// AvIds of the pairs within the target info.
+const MSV_AV_EOL: u16 = 0;
+const MSV_AV_TIMESTAMP: u16 = 7;
+
+/// Difference between the FILETIME epoch (1601-01-01) and the unix epoch in seconds.
+const FILETIME_UNIX_OFFSET: u64 = 11_644_473_600;
+
+fn ntlm_error(reason: &str) -> FnError {
+    CryptographicError::Ntlm(reason.into()).into()
+}
+
+fn utf16le(s: &str) -> Vec<u8> {
+    s.encode_utf16().flat_map(u16::to_le_bytes).collect()
+}
+
+/// Builds the messages by appending the payload after the fixed fields and pointing the security
+/// buffers at it.
+struct MessageBuilder {
+    header: Vec<u8>,
+    payload: Vec<u8>,
+    header_len: usize,
+}
+
+impl MessageBuilder {
+    fn new(message_type: u32, header_len: usize) -> Self {
+        let mut header = Vec::with_capacity(header_len);
+        header.extend_from_slice(SIGNATURE);
+        header.extend_from_slice(&message_type.to_le_bytes());
+        Self {
+            header,
+            payload: vec![],
+            header_len,
+        }
+    }
+
+    /// Appends `data` to the payload. The length of a security buffer is a u16, so longer data
+    /// is an error instead of being truncated.
+    fn field(&mut self, data: &[u8]) -> Result<(), FnError> {
+        let len = u16::try_from(data.len())
+            .map_err(|_| ntlm_error(&format!("field of {} bytes is too long", data.len())))?;
+        let offset = u32::try_from(self.header_len + self.payload.len())
+            .map_err(|_| ntlm_error("message is too long"))?;
+        self.header.extend_from_slice(&len.to_le_bytes());
+        self.header.extend_from_slice(&len.to_le_bytes());
+        self.header.extend_from_slice(&offset.to_le_bytes());
+        self.payload.extend_from_slice(data);
+        Ok(())
+    }
+
+    fn flags(&mut self, flags: u32) {
+        self.header.extend_from_slice(&flags.to_le_bytes());
+    }
+
+    fn build(mut self) -> Vec<u8> {
+        debug_assert_eq!(self.header.len(), self.header_len);
+        self.header.append(&mut self.payload);
+        self.header
+    }
+}
+
+/// The parts of a challenge message needed for the authenticate message.
+struct Challenge {
+    flags: u32,
+    server_challenge: [u8; 8],
+    target_info: Vec<u8>,
+}
+
+impl Challenge {
+    /// Parses a challenge message given either as raw bytes or base64 encoded, as it is sent in
+    /// the HTTP `WWW-Authenticate` header.
+    fn parse(message: &[u8]) -> Result<Self, FnError> {
+        let decoded;
+        let message = if message.starts_with(SIGNATURE) {
+            message
+        } else {
+            decoded = STANDARD
+                .decode(message.trim_ascii())
+                .map_err(|_| ntlm_error("the challenge is neither a message nor base64 encoded"))?;
+            &decoded
+        };
+        if message.len() < 32 || !message.starts_with(SIGNATURE) {
+            return Err(ntlm_error("the challenge is too short"));
+        }
+        let u16_at = |i: usize| u16::from_le_bytes([message[i], message[i + 1]]);
+        let u32_at = |i: usize| u32::from_le_bytes(message[i..i + 4].try_into().unwrap());
+        if u32_at(8) != 2 {
+            return Err(ntlm_error("the message is not a challenge message"));
+        }
+        let flags = u32_at(20);
+        let server_challenge = message[24..32].try_into().unwrap();
+        let target_info = if message.len() >= 48 {
+            let len = u16_at(40) as usize;
+            let offset = u32_at(44) as usize;
+            message
+                .get(offset..offset + len)
+                .ok_or_else(|| ntlm_error("the target info exceeds the challenge"))?
+                .to_vec()
+        } else {
+            vec![]
+        };
+        Ok(Self {
+            flags,
+            server_challenge,
+            target_info,
+        })
+    }
+
+    /// Returns the value of MsvAvTimestamp if the target info contains it.
+    fn timestamp(&self) -> Option<u64> {
+        let mut rest = self.target_info.as_slice();
+        while rest.len() >= 4 {
+            let id = u16::from_le_bytes([rest[0], rest[1]]);
+            let len = u16::from_le_bytes([rest[2], rest[3]]) as usize;
+            let value = rest.get(4..4 + len)?;
+            match id {
+                MSV_AV_EOL => return None,
+                MSV_AV_TIMESTAMP if len == 8 => {
+                    return Some(u64::from_le_bytes(value.try_into().unwrap()))
+                }
+                _ => rest = &rest[4 + len..],
+            }
+        }
+        None
+    }
+}
+
+/// NTOWFv2 as defined in MS-NLMP, section 3.3.2.
+fn ntowf_v2(user: &str, domain: &str, password: &str) -> Result<Vec<u8>, FnError> {
+    let nt_hash = Md4::digest(utf16le(password));
+    let identity = utf16le(&format!("{}{domain}", user.to_uppercase()));
+    hmac_bytes::<Md5>(&nt_hash, &identity)
+}
+
+/// The LMv2 and NTLMv2 responses to a challenge, as defined in MS-NLMP, section 3.3.2.
+fn ntlm_v2_responses(
+    key: &[u8],
+    challenge: &Challenge,
+    client_challenge: &[u8; 8],
+    timestamp: u64,
+) -> Result<(Vec<u8>, Vec<u8>), FnError> {
+    let mut temp = vec![1, 1, 0, 0, 0, 0, 0, 0];
+    temp.extend_from_slice(&timestamp.to_le_bytes());
+    temp.extend_from_slice(client_challenge);
+    temp.extend_from_slice(&[0; 4]);
+    temp.extend_from_slice(&challenge.target_info);
+    temp.extend_from_slice(&[0; 4]);
+
+    let mut input = challenge.server_challenge.to_vec();
+    input.extend_from_slice(&temp);
+    let mut nt_response = hmac_bytes::<Md5>(key, &input)?;
+    nt_response.extend(temp);
+
+    // A server sending a timestamp expects no LMv2 response.
+    let lm_response = if challenge.timestamp().is_some() {
+        vec![0; 24]
+    } else {
+        let mut input = challenge.server_challenge.to_vec();
+        input.extend_from_slice(client_challenge);
+        let mut lm_response = hmac_bytes::<Md5>(key, &input)?;
+        lm_response.extend_from_slice(client_challenge);
+        lm_response
+    };
+    Ok((lm_response, nt_response))
+}
+
+fn as_filetime(time: SystemTime) -> u64 {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    (since_epoch.as_secs() + FILETIME_UNIX_OFFSET) * 10_000_000
+        + since_epoch.subsec_nanos() as u64 / 100
+}
+
+fn encode(message: Vec<u8>, base64: Option<bool>) -> NaslValue {
+    if base64.unwrap_or(false) {
+        NaslValue::String(STANDARD.encode(message))
+    } else {
+        NaslValue::Data(message)
+    }
+}
+
+/// NASL function to build an NTLM negotiate (type 1) message.
+///
+/// Named arguments:
+/// - domain: the optional domain of the client.
+/// - workstation: the optional name of the client.
+/// - base64: if TRUE, the message is returned base64 encoded as string, e.g. for the HTTP
+///   `Authorization` header. Defaults to FALSE, which returns the raw message as data.
+#[nasl_function(named(domain, workstation, base64))]
+fn ntlm_type1(
+    domain: Option<&str>,
+    workstation: Option<&str>,
+    base64: Option<bool>,
+) -> Result<NaslValue, FnError> {
+    let domain = domain.unwrap_or_default().to_uppercase();
+    let workstation = workstation.unwrap_or_default().to_uppercase();
+    let mut flags = NEGOTIATE_FLAGS;
+    if !domain.is_empty() {
+        flags |= OEM_DOMAIN_SUPPLIED;
+    }
+    if !workstation.is_empty() {
+        flags |= OEM_WORKSTATION_SUPPLIED;
+    }
+    let mut message = MessageBuilder::new(1, 32);
+    message.flags(flags);
+    message.field(domain.as_bytes())?;
+    message.field(workstation.as_bytes())?;
+    Ok(encode(message.build(), base64))
+}
+
+/// NASL function to build an NTLMv2 authenticate (type 3) message in response to a challenge.
+///
+/// Named arguments:
+/// - challenge: the challenge (type 2) message of the server, either raw or base64 encoded.
+/// - user: the name of the user.
+/// - domain: the domain of the user.
+/// - password: the password of the user.
+/// - workstation: the optional name of the client.
+/// - client_challenge: 8 bytes of client challenge. Random if omitted.
+/// - timestamp: the time as FILETIME, i.e. in 100 nanoseconds since 1601-01-01. Defaults to the
+///   timestamp within the target info of the challenge or, if there is none, the current time.
+/// - base64: if TRUE, the message is returned base64 encoded as string. Defaults to FALSE.
+#[nasl_function(named(
+    challenge,
+    user,
+    domain,
+    password,
+    workstation,
+    client_challenge,
+    timestamp,
+    base64
+))]
+fn ntlm_type3(
+    context: &Context,
+    challenge: &NaslValue,
+    user: &str,
+    domain: &str,
+    password: &str,
+    workstation: Option<&str>,
+    client_challenge: Option<&NaslValue>,
+    timestamp: Option<i64>,
+    base64: Option<bool>,
+) -> Result<NaslValue, FnError> {
+    let challenge = Challenge::parse(&Vec::<u8>::from(challenge))?;
+    let client_challenge: [u8; 8] = match client_challenge {
+        Some(x) => Vec::<u8>::from(x).try_into().map_err(|x: Vec<u8>| {
+            ArgumentError::wrong_argument(
+                "client_challenge",
+                "8 bytes",
+                &format!("{} bytes", x.len()),
+            )
+        })?,
+        None => random::random_bytes()?,
+    };
+    let timestamp = match timestamp {
+        Some(x) => u64::try_from(x).map_err(|_| {
+            ArgumentError::wrong_argument("timestamp", "a positive number", &x.to_string())
+        })?,
+        None => challenge
+            .timestamp()
+            .unwrap_or_else(|| as_filetime(context.clock().now())),
+    };
+
+    let key = ntowf_v2(user, domain, password)?;
+    let (lm_response, nt_response) =
+        ntlm_v2_responses(&key, &challenge, &client_challenge, timestamp)?;
+
+    let flags = (challenge.flags & NEGOTIATE_FLAGS) | NEGOTIATE_UNICODE | NEGOTIATE_NTLM;
+    let mut message = MessageBuilder::new(3, 64);
+    message.field(&lm_response)?;
+    message.field(&nt_response)?;
+    message.field(&utf16le(domain))?;
+    message.field(&utf16le(user))?;
+    message.field(&utf16le(workstation.unwrap_or_default()))?;
+    message.field(&[])?;
+    message.flags(flags);
+    Ok(encode(message.build(), base64))
+}
+
+pub struct Ntlm;
+
+function_set! {
+    Ntlm,
+    (
+        ntlm_type1,
+        ntlm_type3,
+    )
+}
@@ -0,0 +1,148 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+use std::time::UNIX_EPOCH;
+
+use sha1::Sha1;
+use sha2::{Sha256, Sha512};
+
+use crate::nasl::builtin::string::decode_base32;
+use crate::nasl::prelude::*;
+
+use super::hmac::hmac_bytes;
+
+const DEFAULT_DIGITS: i64 = 6;
+const DEFAULT_STEP: i64 = 30;
+
+/// Returns the secret as bytes. If `base32` is set, the secret is decoded first.
+fn get_secret(secret: &NaslValue, base32: Option<bool>) -> Result<Vec<u8>, ArgumentError> {
+    if base32.unwrap_or(false) {
+        decode_base32(&secret.to_string()).map_err(|e| {
+            ArgumentError::WrongArgument(format!("Expected secret to be base32 encoded, {e}"))
+        })
+    } else {
+        Ok(Vec::<u8>::from(secret))
+    }
+}
+
+fn get_digits(digits: Option<i64>) -> Result<u32, ArgumentError> {
+    match digits.unwrap_or(DEFAULT_DIGITS) {
+        x @ 6..=8 => Ok(x as u32),
+        x => Err(ArgumentError::wrong_argument(
+            "digits",
+            "a number between 6 and 8",
+            &x.to_string(),
+        )),
+    }
+}
+
+/// Calculates the HOTP value as described in RFC 4226.
+fn compute_hotp(secret: &[u8], counter: u64, digits: u32, algo: &str) -> Result<String, FnError> {
+    let msg = counter.to_be_bytes();
+    let mac = match algo.to_uppercase().as_str() {
+        "SHA1" => hmac_bytes::<Sha1>(secret, &msg)?,
+        "SHA256" => hmac_bytes::<Sha256>(secret, &msg)?,
+        "SHA512" => hmac_bytes::<Sha512>(secret, &msg)?,
+        _ => {
+            return Err(
+                ArgumentError::wrong_argument("algo", "SHA1, SHA256 or SHA512", algo).into(),
+            )
+        }
+    };
+    // Dynamic truncation, RFC 4226 section 5.3
+    let offset = (mac[mac.len() - 1] & 0x0f) as usize;
+    let code = u32::from_be_bytes([
+        mac[offset] & 0x7f,
+        mac[offset + 1],
+        mac[offset + 2],
+        mac[offset + 3],
+    ]);
+    Ok(format!(
+        "{:0width$}",
+        code % 10u32.pow(digits),
+        width = digits as usize
+    ))
+}
+
+/// NASL function to calculate a HMAC-based one-time password (RFC 4226).
+///
+/// Named arguments:
+/// - secret: the shared secret as string or data.
+/// - counter: the moving factor as a non negative number.
+/// - digits: number of digits of the result between 6 and 8, defaults to 6.
+/// - algo: HMAC algorithm to use, one of SHA1, SHA256 or SHA512, defaults to SHA1.
+/// - base32: if TRUE the secret is base32 encoded, defaults to FALSE.
+///
+/// Returns the zero padded code as string.
+#[nasl_function(named(secret, counter, digits, algo, base32))]
+fn hotp(
+    secret: &NaslValue,
+    counter: i64,
+    digits: Option<i64>,
+    algo: Option<&str>,
+    base32: Option<bool>,
+) -> Result<NaslValue, FnError> {
+    let secret = get_secret(secret, base32)?;
+    let digits = get_digits(digits)?;
+    let counter = u64::try_from(counter).map_err(|_| {
+        ArgumentError::wrong_argument("counter", "a non negative number", &counter.to_string())
+    })?;
+    let code = compute_hotp(&secret, counter, digits, algo.unwrap_or("SHA1"))?;
+    Ok(NaslValue::String(code))
+}
+
+/// NASL function to calculate a time-based one-time password (RFC 6238).
+///
+/// Named arguments:
+/// - secret: the shared secret as string or data.
+/// - time: unix timestamp to calculate the code for, defaults to the current time of the clock of
+///   the context.
+/// - step: time step in seconds, defaults to 30.
+/// - digits: number of digits of the result between 6 and 8, defaults to 6.
+/// - algo: HMAC algorithm to use, one of SHA1, SHA256 or SHA512, defaults to SHA1.
+/// - base32: if TRUE the secret is base32 encoded, defaults to FALSE.
+///
+/// Returns the zero padded code as string.
+#[nasl_function(named(secret, time, step, digits, algo, base32))]
+fn totp(
+    context: &Context,
+    secret: &NaslValue,
+    time: Option<i64>,
+    step: Option<i64>,
+    digits: Option<i64>,
+    algo: Option<&str>,
+    base32: Option<bool>,
+) -> Result<NaslValue, FnError> {
+    let secret = get_secret(secret, base32)?;
+    let digits = get_digits(digits)?;
+    let time = match time {
+        Some(x) => u64::try_from(x).map_err(|_| {
+            ArgumentError::wrong_argument("time", "a non negative number", &x.to_string())
+        })?,
+        None => context
+            .clock()
+            .now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default(),
+    };
+    let step = step.unwrap_or(DEFAULT_STEP);
+    if step <= 0 {
+        return Err(
+            ArgumentError::wrong_argument("step", "a positive number", &step.to_string()).into(),
+        );
+    }
+    let code = compute_hotp(&secret, time / step as u64, digits, algo.unwrap_or("SHA1"))?;
+    Ok(NaslValue::String(code))
+}
+
+pub struct Otp;
+
+function_set! {
+    Otp,
+    (
+        hotp,
+        totp,
+    )
+}
@@ -0,0 +1,109 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::nasl::prelude::*;
+
+/// The bundled list of common passwords, one password per line.
+pub const DEFAULT_COMMON_PASSWORDS: &str = include_str!("common_passwords.txt");
+
+fn to_set<I, S>(passwords: I) -> HashSet<String>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    passwords
+        .into_iter()
+        .map(|x| x.as_ref().trim().to_lowercase())
+        .filter(|x| !x.is_empty())
+        .collect()
+}
+
+/// The password functions along with the list of common passwords used by `is_common_password`.
+pub struct Passwords {
+    common: HashSet<String>,
+}
+
+impl Default for Passwords {
+    /// Uses the bundled list of common passwords, see [`DEFAULT_COMMON_PASSWORDS`].
+    fn default() -> Self {
+        Self::new(DEFAULT_COMMON_PASSWORDS.lines())
+    }
+}
+
+impl Passwords {
+    /// Uses the given list of common passwords instead of the bundled one.
+    ///
+    /// The comparison is case insensitive and ignores surrounding whitespace.
+    pub fn new<I, S>(common_passwords: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self {
+            common: to_set(common_passwords),
+        }
+    }
+
+    /// Reads the list of common passwords from a file containing one password per line.
+    pub fn from_file(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        Ok(Self::new(content.lines()))
+    }
+
+    /// NASL function to check whether a password is contained in the list of common passwords.
+    ///
+    /// Expects the password as the first positional or the named argument password. The
+    /// comparison is case insensitive.
+    ///
+    /// Returns TRUE if the password is common. The bundled list of common passwords is used
+    /// unless another one was given when the functions were created.
+    #[nasl_function(maybe_named(password))]
+    fn is_common_password(&self, password: &str) -> bool {
+        self.common.contains(&password.trim().to_lowercase())
+    }
+}
+
+/// Returns the number of possible characters of all character classes used in `password`.
+fn pool_size(password: &str) -> u32 {
+    let used = |is_class: fn(&char) -> bool| password.chars().any(|c| is_class(&c));
+    [
+        (used(char::is_ascii_lowercase), 26),
+        (used(char::is_ascii_uppercase), 26),
+        (used(char::is_ascii_digit), 10),
+        (used(|c| c.is_ascii_punctuation() || *c == ' '), 33),
+        (used(|c| !c.is_ascii()), 100),
+    ]
+    .iter()
+    .filter(|(used, _)| *used)
+    .map(|(_, size)| size)
+    .sum()
+}
+
+/// NASL function to estimate the strength of a password.
+///
+/// Expects the password as the first positional or the named argument password.
+///
+/// Returns the estimated entropy in bits, rounded down, as length multiplied by the binary
+/// logarithm of the size of all used character classes: lower case letters (26), upper case
+/// letters (26), digits (10), ASCII symbols including space (33) and non ASCII characters (100).
+/// The estimation does not detect patterns or dictionary words, see `is_common_password`.
+#[nasl_function(maybe_named(password))]
+fn password_entropy(password: &str) -> i64 {
+    let pool = pool_size(password);
+    if pool == 0 {
+        return 0;
+    }
+    (password.chars().count() as f64 * f64::from(pool).log2()) as i64
+}
+
+function_set! {
+    Passwords,
+    (
+        password_entropy,
+        (Passwords::is_common_password, "is_common_password"),
+    )
+}
@@ -0,0 +1,61 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+use poly1305::{universal_hash::KeyInit, Poly1305};
+use subtle::ConstantTimeEq;
+
+use crate::nasl::prelude::*;
+
+use super::{get_data, get_key, get_required_named_data};
+
+/// Calculates the Poly1305 tag of the data with the given one-time key.
+fn compute_tag(register: &Register) -> Result<Vec<u8>, FnError> {
+    let key = get_key(register)?;
+    let data = get_data(register)?;
+    if key.len() != 32 {
+        return Err(ArgumentError::wrong_argument(
+            "key",
+            "32 bytes long",
+            &format!("{} bytes long", key.len()),
+        )
+        .into());
+    }
+    let mac = Poly1305::new(key.as_ref().into());
+    Ok(mac.compute_unpadded(&data).to_vec())
+}
+
+/// NASL function to calculate a Poly1305 (RFC 8439) message authentication code.
+///
+/// This function expects the named arguments key and data either in a string or data type.
+/// - The key is a 32 byte one-time key. It must never be used for more than one message.
+///
+/// The result is the 16 byte tag.
+#[nasl_function]
+fn poly1305(register: &Register) -> Result<NaslValue, FnError> {
+    Ok(compute_tag(register)?.into())
+}
+
+/// NASL function to verify a Poly1305 (RFC 8439) message authentication code.
+///
+/// This function expects the named arguments key, data and tag either in a string or data type.
+/// - The key is a 32 byte one-time key.
+/// - The tag is the expected 16 byte tag.
+///
+/// Returns TRUE if the tag matches, FALSE otherwise. The comparison is done in constant time.
+#[nasl_function]
+fn poly1305_verify(register: &Register) -> Result<NaslValue, FnError> {
+    let tag = get_required_named_data(register, "tag")?;
+    let computed = compute_tag(register)?;
+    Ok(NaslValue::Boolean(computed.ct_eq(&tag).into()))
+}
+
+pub struct Poly1305Fns;
+
+function_set! {
+    Poly1305Fns,
+    (
+        poly1305,
+        poly1305_verify,
+    )
+}
@@ -4,7 +4,7 @@
 // SPDX-License-Identifier: GPL-2.0-or-later
 use crate::function_set;
 use crate::nasl::prelude::*;
-use ccm::aead::OsRng;
+use crate::nasl::utils::random;
 use nasl_function_proc_macro::nasl_function;
 use rsa::pkcs8::DecodePrivateKey;
 use rsa::signature::digest::Digest;
@@ -19,9 +19,8 @@ fn rsa_public_encrypt(
     n: &[u8],
     e: &[u8],
     pad: Option<bool>,
-) -> Result<NaslValue, CryptographicError> {
+) -> Result<NaslValue, FnError> {
     let pad = pad.unwrap_or_default();
-    let mut rng = rand::thread_rng();
     let pub_key = RsaPublicKey::new(
         rsa::BigUint::from_bytes_be(n),
         rsa::BigUint::from_bytes_be(e),
@@ -30,7 +29,7 @@ fn rsa_public_encrypt(
     let biguint_data = BigUint::from_bytes_be(data);
     let enc_data = if pad {
         pub_key
-            .encrypt(&mut rng, Pkcs1v15Encrypt, data)
+            .encrypt(&mut random::rng()?, Pkcs1v15Encrypt, data)
             .map_err(|e| CryptographicError::Rsa(e.to_string()))?
     } else {
         rsa::hazmat::rsa_encrypt(&pub_key, &biguint_data)
@@ -49,39 +48,22 @@ fn rsa_private_decrypt(
     pad: Option<bool>,
 ) -> Result<NaslValue, FnError> {
     let pad = pad.unwrap_or_default();
-    let priv_key = match RsaPrivateKey::from_components(
+    let priv_key = RsaPrivateKey::from_components(
         rsa::BigUint::from_bytes_be(n),
         rsa::BigUint::from_bytes_be(e),
         rsa::BigUint::from_bytes_be(d),
         vec![],
-    ) {
-        Ok(val) => Ok(val),
-        Err(code) => Err(
-            FnError::from(CryptographicError::Rsa(format!("Error code {}", code))).with(
-                ReturnValue(NaslValue::Array(vec![
-                    NaslValue::Data(n.to_vec()),
-                    NaslValue::Data(e.to_vec()),
-                    NaslValue::Data(d.to_vec()),
-                ])),
-            ),
-        ),
-    }
-    .map_err(|e| CryptographicError::Rsa(e.to_string()))?;
-    let mut rng = OsRng;
+    )
+    .map_err(|err| CryptographicError::Rsa(err.to_string()).recoverable())?;
+    let mut rng = random::rng()?;
     let biguint_data = BigUint::from_bytes_be(data);
     let dec_data = if pad {
-        match priv_key.decrypt(Pkcs1v15Encrypt, data) {
-            Ok(val) => Ok(val),
-            Err(code) => Err(FnError::from(CryptographicError::Rsa(format!(
-                "Error code {}",
-                code
-            )))
-            .with(ReturnValue(NaslValue::Data(data.to_vec())))),
-        }
-        .map_err(|e| CryptographicError::Rsa(e.to_string()))?
+        priv_key
+            .decrypt(Pkcs1v15Encrypt, data)
+            .map_err(|err| CryptographicError::Rsa(err.to_string()).recoverable())?
     } else {
         rsa::hazmat::rsa_decrypt_and_check(&priv_key, Some(&mut rng), &biguint_data)
-            .map_err(|e| CryptographicError::Rsa(e.to_string()))?
+            .map_err(|err| CryptographicError::Rsa(err.to_string()).recoverable())?
             .to_bytes_be()
     };
 
@@ -113,9 +95,8 @@ fn rsa_public_decrypt(sign: &[u8], n: &[u8], e: &[u8]) -> Result<NaslValue, FnEr
     let n_b = rsa::BigUint::from_bytes_be(n);
     let public_key =
         RsaPublicKey::new(n_b, e_b).map_err(|e| CryptographicError::Rsa(e.to_string()))?;
-    let mut rng = rand::thread_rng();
     let enc_data = public_key
-        .encrypt(&mut rng, Pkcs1v15Encrypt, sign)
+        .encrypt(&mut random::rng()?, Pkcs1v15Encrypt, sign)
         .map_err(|e| CryptographicError::Rsa(e.to_string()))?;
     Ok(enc_data.to_vec().into())
 }
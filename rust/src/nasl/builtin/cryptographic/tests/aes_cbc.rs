@@ -2,6 +2,7 @@
 //
 // SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
 
+use crate::nasl::builtin::cryptographic::CryptographicError;
 use crate::nasl::test_prelude::*;
 
 use super::helper::decode_hex;
@@ -73,3 +74,63 @@ fn padding() {
         results[results.len() - 1].as_ref().unwrap()
     );
 }
+
+#[test]
+fn aes_cbc_mac() {
+    let mut t = TestBuilder::default();
+    // FIPS-197 C.1, a single block is just encrypted
+    t.ok(
+        r#"aes_cbc_mac(key: hexstr_to_data("000102030405060708090a0b0c0d0e0f"), data: hexstr_to_data("00112233445566778899aabbccddeeff"));"#,
+        decode_hex("69c4e0d86a7b0430d8cdb78070b4c55a").unwrap(),
+    );
+    t.run(r#"data = hexstr_to_data("6bc1bee22e409f96e93d7e117393172aae2d8a571e03ac9c9eb76fac45af8e51");"#);
+    t.ok(
+        r#"aes_cbc_mac(key: hexstr_to_data("2b7e151628aed2a6abf7158809cf4f3c"), data: data);"#,
+        decode_hex("b148c17f309ee692287ae57cf12add49").unwrap(),
+    );
+    t.ok(
+        r#"aes_cbc_mac(key: hexstr_to_data("8e73b0f7da0e6452c810f32b809079e562f8ead2522c6b7b"), data: data);"#,
+        decode_hex("0f771e2a4f0fbe323cb4146a0d1b86d9").unwrap(),
+    );
+    t.ok(
+        r#"aes_cbc_mac(key: hexstr_to_data("603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4"), data: data);"#,
+        decode_hex("e3c48b48365cfb14dc9aaa37b1abc15c").unwrap(),
+    );
+    check_err_matches!(
+        t,
+        r#"aes_cbc_mac(key: hexstr_to_data("2b7e151628aed2a6abf7158809cf4f3c"), data: "not a block");"#,
+        ArgumentError::WrongArgument(_),
+    );
+    check_err_matches!(
+        t,
+        r#"aes_cbc_mac(key: hexstr_to_data("2b7e151628aed2a6abf7158809cf4f3c"), data: "");"#,
+        ArgumentError::WrongArgument(_),
+    );
+    check_err_matches!(
+        t,
+        r#"aes_cbc_mac(key: "short", data: data);"#,
+        ArgumentError::WrongArgument(_),
+    );
+}
+
+#[test]
+fn aes_cbc_errors() {
+    let mut t = TestBuilder::default();
+    t.run(r#"key = hexstr_to_data("00000000000000000000000000000000");"#);
+    t.run(r#"iv = hexstr_to_data("00000000000000000000000000000000");"#);
+    check_err_matches!(
+        t,
+        r#"aes128_cbc_encrypt(key: "short", data: "data", iv: iv);"#,
+        CryptographicError::InvalidKeyLength(_),
+    );
+    check_err_matches!(
+        t,
+        r#"aes128_cbc_encrypt(key: key, data: "data", iv: "short");"#,
+        ArgumentError::WrongArgument(_),
+    );
+    check_err_matches!(
+        t,
+        r#"aes128_cbc_decrypt(key: key, data: "not a block", iv: iv);"#,
+        CryptographicError::PaddingError(_),
+    );
+}
@@ -2,6 +2,7 @@
 //
 // SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
 
+use super::aes_gcm::tamper;
 use super::helper::decode_hex;
 use crate::nasl::test_prelude::*;
 
@@ -121,3 +122,122 @@ fn aes256_ccm_crypt_auth() {
         decode_hex("771a7baa9cf83aa253349f6475d5e74dba4525307b022ba7").unwrap(),
     );
 }
+
+#[test]
+fn aes_ccm_nonce_length() {
+    let mut t = TestBuilder::default();
+    t.run(r#"key = hexstr_to_data("d24a3d3dde8c84830280cb87abad0bb3");"#);
+    t.run(r#"data = hexstr_to_data("7c86135ed9c2a515aaae0e9a208133897269220f30870006");"#);
+    // 13 byte iv, the maximum allowed
+    t.ok(
+        r#"aes128_ccm_encrypt(key: key, data: data, iv: hexstr_to_data("f1100035bb24a8d26004e0e24b"));"#,
+        decode_hex(
+            "1faeb0ee2ca2cd52f0aa3966578344f24e69b742c4ab37ab1123301219c70599b7c373ad4b3ad67b",
+        )
+        .unwrap(),
+    );
+    check_err_matches!(
+        t,
+        r#"aes128_ccm_encrypt(key: key, data: data, iv: hexstr_to_data("f1100035bb24"));"#,
+        ArgumentError::WrongArgument(_),
+    );
+    check_err_matches!(
+        t,
+        r#"aes128_ccm_decrypt(key: key, data: data, iv: hexstr_to_data("f1100035bb24"));"#,
+        ArgumentError::WrongArgument(_),
+    );
+    check_err_matches!(
+        t,
+        r#"aes128_ccm_encrypt(key: key, data: data, iv: hexstr_to_data("f1100035bb24a8d26004e0e24b00"));"#,
+        ArgumentError::WrongArgument(_),
+    );
+}
+
+#[test]
+fn aes_ccm_message_too_long() {
+    let mut t = TestBuilder::default();
+    t.run(r#"key = hexstr_to_data("d24a3d3dde8c84830280cb87abad0bb3");"#);
+    t.run(r#"iv = hexstr_to_data("f1100035bb24a8d26004e0e24b");"#);
+    // A 13 byte iv leaves 2 bytes to encode the length of the message.
+    t.check(
+        r#"aes128_ccm_encrypt(key: key, data: crap(length: 65535), iv: iv);"#,
+        |r| matches!(r, Ok(NaslValue::Data(x)) if x.len() == 65535 + 16),
+        Some("65535 bytes of ciphertext and a 16 byte tag"),
+    );
+    check_err_matches!(
+        t,
+        r#"aes128_ccm_encrypt(key: key, data: crap(length: 65536), iv: iv);"#,
+        ArgumentError::WrongArgument(_),
+    );
+}
+
+#[test]
+fn aes_ccm_optional_aad() {
+    let mut t = TestBuilder::default();
+    t.run(r#"key = hexstr_to_data("5a33980e71e7d67fd6cf171454dc96e5");"#);
+    t.run(r#"data = hexstr_to_data("a34dfa24847c365291ce1b54bcf8d9a75d861e5133cc3a74");"#);
+    t.run(r#"iv = hexstr_to_data("33ae68ebb8010c6b3da6b9cb29");"#);
+    t.run(r#"ad = hexstr_to_data("eca622a37570df619e10ebb18bebadb2f2b49c4d2b2ff715873bb672e30fc0ff");"#);
+    t.ok(
+        r#"crypt = aes128_ccm_encrypt(key: key, data: data, iv: iv, aad: ad);"#,
+        decode_hex(
+            "7a60fa7ee8859e283cce378fb6b95522ab8b70efcdb0265f7c4b4fa597666b86dd1353e400f28864",
+        )
+        .unwrap(),
+    );
+    t.ok(
+        r#"aes128_ccm_decrypt(key: key, data: crypt, iv: iv, aad: ad);"#,
+        decode_hex("a34dfa24847c365291ce1b54bcf8d9a75d861e5133cc3a74").unwrap(),
+    );
+}
+
+#[test]
+fn aes_ccm_tampered_tag() {
+    let crypt = "1faeb0ee2ca2cd52f0aa3966578344f24e69b742c4ab37ab1123301219c70599b7c373ad4b3ad67b";
+    let mut t = TestBuilder::default();
+    t.run(r#"key = hexstr_to_data("d24a3d3dde8c84830280cb87abad0bb3");"#);
+    t.run(r#"iv = hexstr_to_data("f1100035bb24a8d26004e0e24b");"#);
+    // Altering any byte of the ciphertext or the tag results in empty data.
+    for i in 0..40 {
+        t.ok(
+            format!(
+                r#"aes128_ccm_decrypt(key: key, data: hexstr_to_data("{}"), iv: iv);"#,
+                tamper(crypt, i)
+            ),
+            NaslValue::Data(vec![]),
+        );
+    }
+}
+
+#[test]
+fn aes_ccm_empty_data() {
+    let mut t = TestBuilder::default();
+    t.run(r#"key = hexstr_to_data("d24a3d3dde8c84830280cb87abad0bb3");"#);
+    t.run(r#"iv = hexstr_to_data("f1100035bb24a8d26004e0e24b");"#);
+    // Authentication only: the result is just the tag.
+    t.ok(
+        r#"tag = aes128_ccm_encrypt_auth(key: key, data: "", iv: iv, aad: "header");"#,
+        decode_hex("eacadde15df297275972f9737f97a549").unwrap(),
+    );
+    t.ok(
+        r#"aes128_ccm_decrypt_auth(key: key, data: tag, iv: iv, aad: "header");"#,
+        NaslValue::Data(vec![]),
+    );
+    t.ok(
+        r#"aes128_ccm_decrypt_auth(key: key, data: tag, iv: iv, aad: "other");"#,
+        NaslValue::Data(vec![]),
+    );
+    // Both empty
+    t.ok(
+        r#"tag = aes128_ccm_encrypt_auth(key: key, data: "", iv: iv, aad: "");"#,
+        decode_hex("12c84b2c87d135aceb2b695245d9ccbf").unwrap(),
+    );
+    t.ok(
+        r#"aes128_ccm_encrypt(key: key, data: "", iv: iv) == tag;"#,
+        true,
+    );
+    t.ok(
+        r#"aes128_ccm_decrypt(key: key, data: tag, iv: iv);"#,
+        NaslValue::Data(vec![]),
+    );
+}
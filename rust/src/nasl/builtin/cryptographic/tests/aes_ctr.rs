@@ -3,7 +3,8 @@
 // SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
 
 use super::helper::decode_hex;
-use crate::nasl::test_utils::TestBuilder;
+use crate::nasl::builtin::cryptographic::CryptographicError;
+use crate::nasl::test_prelude::*;
 
 #[test]
 fn aes128_ctr_crypt() {
@@ -52,3 +53,121 @@ fn aes256_ctr_crypt() {
         decode_hex("6bc1bee22e409f96e93d7e117393172a").unwrap(),
     );
 }
+
+#[test]
+fn aes_ctr_streaming() {
+    let data = b"The quick brown fox jumps over the lazy dog";
+    let chunks = [&data[..5], &data[5..25], &data[25..]];
+    let mut t = TestBuilder::default();
+    t.run_all(format!(
+        r#"
+        key = hexstr_to_data("2b7e151628aed2a6abf7158809cf4f3c");
+        iv = hexstr_to_data("f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff");
+        aes128_ctr_encrypt(key: key, data: "{}", iv: iv);
+        hd = aes_ctr_init(key: key, iv: iv);
+        aes_ctr_update(handle: hd, data: "{}");
+        aes_ctr_update(handle: hd, data: "{}");
+        aes_ctr_update(handle: hd, data: "{}");
+        aes_ctr_free(handle: hd);
+        aes_ctr_update(handle: hd, data: "a");
+        "#,
+        std::str::from_utf8(data).unwrap(),
+        std::str::from_utf8(chunks[0]).unwrap(),
+        std::str::from_utf8(chunks[1]).unwrap(),
+        std::str::from_utf8(chunks[2]).unwrap(),
+    ));
+    let results = t.results();
+    let data = |i: usize| match &results[i] {
+        Ok(NaslValue::Data(x)) => x.clone(),
+        x => panic!("unexpected result {x:?}"),
+    };
+    let chunked = [data(4), data(5), data(6)].concat();
+    assert_eq!(chunked, data(2));
+    assert!(matches!(results[7], Ok(NaslValue::Null)));
+    assert!(results[8].is_err());
+}
+
+#[test]
+fn aes_ctr_streaming_decrypt() {
+    let mut t = TestBuilder::default();
+    t.run(r#"key = hexstr_to_data("603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4");"#);
+    t.run(r#"iv = hexstr_to_data("f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff");"#);
+    t.run(r#"hd = aes_ctr_init(key: key, iv: iv);"#);
+    t.run(r#"crypt = aes_ctr_update(handle: hd, data: hexstr_to_data("6bc1bee22e409f96e93d7e117393172a"));"#);
+    t.ok(
+        r#"crypt;"#,
+        decode_hex("601ec313775789a5b7a7f504bbf3d228").unwrap(),
+    );
+    t.run(r#"aes_ctr_free(handle: hd);"#);
+    t.run(r#"hd = aes_ctr_init(key: key, iv: iv);"#);
+    t.ok(
+        r#"aes_ctr_update(handle: hd, data: crypt);"#,
+        decode_hex("6bc1bee22e409f96e93d7e117393172a").unwrap(),
+    );
+    t.run(r#"aes_ctr_free(handle: hd);"#);
+    check_err_matches!(
+        t,
+        r#"aes_ctr_update(handle: hd, data: crypt);"#,
+        CryptographicError::AesCtr(_),
+    );
+    check_err_matches!(
+        t,
+        r#"aes_ctr_init(key: "short", iv: iv);"#,
+        ArgumentError::WrongArgument(_),
+    );
+}
+
+#[test]
+fn aes_ctr_len() {
+    let mut t = TestBuilder::default();
+    t.run(r#"key = hexstr_to_data("2b7e151628aed2a6abf7158809cf4f3c");"#);
+    t.run(r#"data = hexstr_to_data("6bc1bee22e409f96e93d7e117393172a");"#);
+    t.run(r#"iv = hexstr_to_data("f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff");"#);
+    // omitted len processes the whole data
+    t.ok(
+        r#"aes128_ctr_encrypt(key: key, data: data, iv: iv);"#,
+        decode_hex("874d6191b620e3261bef6864990db6ce").unwrap(),
+    );
+    t.ok(
+        r#"aes128_ctr_encrypt(key: key, data: data, iv: iv, len: 16);"#,
+        decode_hex("874d6191b620e3261bef6864990db6ce").unwrap(),
+    );
+    t.ok(
+        r#"aes128_ctr_encrypt(key: key, data: data, iv: iv, len: 4);"#,
+        decode_hex("874d6191").unwrap(),
+    );
+    t.ok(
+        r#"aes128_ctr_decrypt(key: key, data: hexstr_to_data("874d6191b620e3261bef6864990db6ce"), iv: iv, len: 16);"#,
+        decode_hex("6bc1bee22e409f96e93d7e117393172a").unwrap(),
+    );
+    check_err_matches!(
+        t,
+        r#"aes128_ctr_encrypt(key: key, data: data, iv: iv, len: 17);"#,
+        ArgumentError::WrongArgument(_),
+    );
+    check_err_matches!(
+        t,
+        r#"aes128_ctr_decrypt(key: key, data: data, iv: iv, len: 17);"#,
+        ArgumentError::WrongArgument(_),
+    );
+}
+
+#[test]
+fn aes_ctr_len_rest() {
+    let mut t = TestBuilder::default();
+    t.run(r#"key = hexstr_to_data("2b7e151628aed2a6abf7158809cf4f3c");"#);
+    t.run(r#"data = hexstr_to_data("6bc1bee22e409f96e93d7e117393172a");"#);
+    t.run(r#"iv = hexstr_to_data("f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff");"#);
+    t.ok(
+        r#"aes128_ctr_encrypt(key: key, data: data, iv: iv, len: -1);"#,
+        decode_hex("874d6191b620e3261bef6864990db6ce").unwrap(),
+    );
+    t.ok(
+        r#"aes128_ctr_encrypt(key: key, data: data, iv: iv, len: 0);"#,
+        Vec::<u8>::new(),
+    );
+    t.ok(
+        r#"aes128_ctr_encrypt(key: key, data: data, iv: iv, len: 8);"#,
+        decode_hex("874d6191b620e326").unwrap(),
+    );
+}
@@ -4,7 +4,17 @@
 
 // FnError::GeneralError
 use super::helper::decode_hex;
+use crate::nasl::builtin::cryptographic::CryptographicError;
+use crate::nasl::interpreter::CodeInterpreter;
 use crate::nasl::test_prelude::*;
+use crate::storage::ContextKey;
+
+/// Returns the hex encoded `data` with the byte at `index` altered.
+pub(super) fn tamper(data: &str, index: usize) -> String {
+    let mut bytes = decode_hex(data).unwrap();
+    bytes[index] ^= 0x01;
+    hex::encode(bytes)
+}
 
 #[test]
 fn aes128_gcm_crypt() {
@@ -124,3 +134,200 @@ fn padding() {
         results[results.len() - 1].as_ref().unwrap()
     );
 }
+
+#[test]
+fn aes_gcm_tampered_tag() {
+    let crypt = "2ccda4a5415cb91e135c2a0f78c9b2fdb36d1df9b9d5e596f83e8b7f52971cb3";
+    let mut t = TestBuilder::default();
+    t.run(r#"key = hexstr_to_data("7fddb57453c241d03efbed3ac44e371c");"#);
+    t.run(r#"iv = hexstr_to_data("ee283a3fc75575e33efd4887");"#);
+    // Altering any byte of the ciphertext or the tag results in empty data.
+    for i in 0..32 {
+        t.ok(
+            format!(
+                r#"aes128_gcm_decrypt(key: key, data: hexstr_to_data("{}"), iv: iv);"#,
+                tamper(crypt, i)
+            ),
+            NaslValue::Data(vec![]),
+        );
+    }
+    t.ok(
+        format!(
+            r#"aes128_gcm_decrypt_auth(key: key, data: hexstr_to_data("{crypt}"), iv: iv, aad: "x");"#
+        ),
+        NaslValue::Data(vec![]),
+    );
+}
+
+#[tokio::test]
+async fn aes_gcm_tampered_tag_warns_about_failed_authentication() {
+    let crypt = "2ccda4a5415cb91e135c2a0f78c9b2fdb36d1df9b9d5e596f83e8b7f52971cb3";
+    let mut code = String::from(
+        r#"
+key = hexstr_to_data("7fddb57453c241d03efbed3ac44e371c");
+iv = hexstr_to_data("ee283a3fc75575e33efd4887");
+"#,
+    );
+    for i in 0..32 {
+        code.push_str(&format!(
+            "aes128_gcm_decrypt(key: key, data: hexstr_to_data(\"{}\"), iv: iv);\n",
+            tamper(crypt, i)
+        ));
+    }
+    let factory = ContextFactory::default();
+    let context = factory.build(ContextKey::default());
+    let outcome = CodeInterpreter::new(&code, Register::root_initial(&[]), &context)
+        .run()
+        .await
+        .unwrap();
+    assert_eq!(outcome.exit_value, NaslValue::Data(vec![]));
+    // Altering any byte of the ciphertext or the tag results in the same warning.
+    assert_eq!(outcome.warnings.len(), 32);
+    for warning in &outcome.warnings {
+        let error: &CryptographicError = warning.try_into().unwrap();
+        assert!(matches!(error, CryptographicError::AuthenticationFailed));
+    }
+}
+
+#[test]
+fn aes_gcm_generated_iv() {
+    let mut t = TestBuilder::default();
+    t.run(r#"key = hexstr_to_data("7fddb57453c241d03efbed3ac44e371c");"#);
+    t.run(r#"data = hexstr_to_data("d5de42b461646c255c87bd2962d3b9a2");"#);
+    t.run(r#"a = aes128_gcm_encrypt(key: key, data: data);"#);
+    t.run(r#"b = aes128_gcm_encrypt(key: key, data: data);"#);
+    t.run(r#"crypt = hexstr_to_data(hexstr(a["ciphertext"]) + hexstr(a["tag"]));"#);
+    t.ok(r#"length(a["iv"]);"#, 12);
+    t.ok(r#"length(a["tag"]);"#, 16);
+    t.ok(r#"a["iv"] == b["iv"];"#, false);
+    t.ok(
+        r#"aes128_gcm_decrypt(key: key, data: crypt, iv: a["iv"]);"#,
+        decode_hex("d5de42b461646c255c87bd2962d3b9a2").unwrap(),
+    );
+    t.ok(
+        r#"aes128_gcm_encrypt(key: key, data: data, iv: a["iv"]) == crypt;"#,
+        true,
+    );
+    check_err_matches!(
+        t,
+        r#"aes128_gcm_decrypt(key: key, data: crypt);"#,
+        ArgumentError::MissingNamed(_),
+    );
+}
+
+/// Test vector from the NIST GCM test vectors (gcmEncryptExtIV128.rsp)
+#[test]
+fn aes_gcm_tag_mode() {
+    let mut t = TestBuilder::default();
+    t.run(r#"key = hexstr_to_data("7fddb57453c241d03efbed3ac44e371c");"#);
+    t.run(r#"data = hexstr_to_data("d5de42b461646c255c87bd2962d3b9a2");"#);
+    t.run(r#"iv = hexstr_to_data("ee283a3fc75575e33efd4887");"#);
+    t.run(r#"r = aes128_gcm_encrypt(key: key, data: data, iv: iv, tag_mode: "separate");"#);
+    t.ok(
+        r#"r["ciphertext"];"#,
+        decode_hex("2ccda4a5415cb91e135c2a0f78c9b2fd").unwrap(),
+    );
+    t.ok(
+        r#"r["tag"];"#,
+        decode_hex("b36d1df9b9d5e596f83e8b7f52971cb3").unwrap(),
+    );
+    t.ok(
+        r#"aes128_gcm_decrypt(key: key, data: r["ciphertext"], tag: r["tag"], iv: iv);"#,
+        decode_hex("d5de42b461646c255c87bd2962d3b9a2").unwrap(),
+    );
+    t.ok(
+        r#"crypt = aes128_gcm_encrypt(key: key, data: data, iv: iv, tag_mode: "appended");"#,
+        decode_hex("2ccda4a5415cb91e135c2a0f78c9b2fdb36d1df9b9d5e596f83e8b7f52971cb3").unwrap(),
+    );
+    t.ok(
+        r#"aes128_gcm_decrypt(key: key, data: crypt, iv: iv);"#,
+        decode_hex("d5de42b461646c255c87bd2962d3b9a2").unwrap(),
+    );
+    check_err_matches!(
+        t,
+        r#"aes128_gcm_encrypt(key: key, data: data, iv: iv, tag_mode: "prepended");"#,
+        ArgumentError::WrongArgument(_),
+    );
+}
+
+/// Test vectors from the NIST GCM test vectors (gcmEncryptExtIV128.rsp)
+#[test]
+fn aes_gcm_empty_data() {
+    let mut t = TestBuilder::default();
+    // Authentication only: the result is just the tag.
+    t.run(r#"key = hexstr_to_data("77be63708971c4e240d1cb79e8d77feb");"#);
+    t.run(r#"iv = hexstr_to_data("e0e00f19fed7ba0136a797f3");"#);
+    t.run(r#"aad = hexstr_to_data("7a43ec1d9c0a5a78a0b16533a6213cab");"#);
+    t.ok(
+        r#"auth_tag = aes128_gcm_encrypt_auth(key: key, data: "", iv: iv, aad: aad);"#,
+        decode_hex("209fcc8d3675ed938e9c7166709dd946").unwrap(),
+    );
+    t.ok(
+        r#"aes128_gcm_decrypt_auth(key: key, data: auth_tag, iv: iv, aad: aad);"#,
+        NaslValue::Data(vec![]),
+    );
+    t.ok(
+        r#"aes128_gcm_decrypt_auth(key: key, data: auth_tag, iv: iv, aad: aad, len: 0);"#,
+        NaslValue::Data(vec![]),
+    );
+    check_err_matches!(
+        t,
+        r#"aes128_gcm_decrypt_auth(key: key, data: auth_tag, iv: iv, aad: aad, len: 1);"#,
+        ArgumentError::WrongArgument(_),
+    );
+    t.ok(
+        r#"aes128_gcm_decrypt_auth(key: key, data: auth_tag, iv: iv, aad: "other");"#,
+        NaslValue::Data(vec![]),
+    );
+    t.ok(
+        r#"aes128_gcm_decrypt(key: key, data: auth_tag, iv: iv);"#,
+        NaslValue::Data(vec![]),
+    );
+    // Both empty
+    t.run(r#"key = hexstr_to_data("00000000000000000000000000000000");"#);
+    t.run(r#"iv = hexstr_to_data("000000000000000000000000");"#);
+    t.ok(
+        r#"auth_tag = aes128_gcm_encrypt_auth(key: key, data: "", iv: iv, aad: "");"#,
+        decode_hex("58e2fccefa7e3061367f1d57a4e7455a").unwrap(),
+    );
+    t.ok(
+        r#"aes128_gcm_encrypt(key: key, data: "", iv: iv) == auth_tag;"#,
+        true,
+    );
+    t.ok(
+        r#"aes128_gcm_decrypt_auth(key: key, data: auth_tag, iv: iv, aad: "");"#,
+        NaslValue::Data(vec![]),
+    );
+    // An empty aad is the same as no aad.
+    t.run(r#"data = hexstr_to_data("d5de42b461646c255c87bd2962d3b9a2");"#);
+    t.ok(
+        r#"aes128_gcm_encrypt_auth(key: key, data: data, iv: iv, aad: "") == aes128_gcm_encrypt(key: key, data: data, iv: iv);"#,
+        true,
+    );
+}
+
+#[test]
+fn aes_gcm_reports_all_argument_errors() {
+    let mut t = TestBuilder::default();
+    t.check(
+        r#"aes128_gcm_decrypt(key: 5, data: "data");"#,
+        |r| {
+            let Err(e) = r else { return false };
+            let Ok(ArgumentError::Multiple(errors)) = <&ArgumentError>::try_from(e) else {
+                return false;
+            };
+            matches!(
+                errors.as_slice(),
+                [ArgumentError::MissingNamed(missing), ArgumentError::WrongArgument(wrong)]
+                    if missing == &["iv"] && wrong.contains("key")
+            )
+        },
+        Some("missing iv and wrong key"),
+    );
+    // A single problem is not wrapped.
+    check_err_matches!(
+        t,
+        r#"aes128_gcm_decrypt(key: "key", data: "data");"#,
+        ArgumentError::MissingNamed(_),
+    );
+}
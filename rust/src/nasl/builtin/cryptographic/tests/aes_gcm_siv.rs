@@ -0,0 +1,168 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+use super::aes_gcm::tamper;
+use super::helper::decode_hex;
+use crate::nasl::builtin::cryptographic::CryptographicError;
+use crate::nasl::interpreter::CodeInterpreter;
+use crate::nasl::test_prelude::*;
+use crate::storage::ContextKey;
+
+/// Test vectors from RFC 8452, appendix C.1
+#[test]
+fn aes128_gcm_siv_crypt() {
+    let mut t = TestBuilder::default();
+    t.run(r#"key = hexstr_to_data("01000000000000000000000000000000");"#);
+    t.run(r#"iv = hexstr_to_data("030000000000000000000000");"#);
+    t.ok(
+        r#"aes128_gcm_siv_encrypt(key: key, data: "", iv: iv);"#,
+        decode_hex("dc20e2d83f25705bb49e439eca56de25").unwrap(),
+    );
+    t.ok(
+        r#"crypt = aes128_gcm_siv_encrypt(key: key, data: hexstr_to_data("0100000000000000"), iv: iv);"#,
+        decode_hex("b5d839330ac7b786578782fff6013b815b287c22493a364c").unwrap(),
+    );
+    t.ok(
+        r#"aes128_gcm_siv_decrypt(key: key, data: crypt, iv: iv);"#,
+        decode_hex("0100000000000000").unwrap(),
+    );
+}
+
+/// Test vector from RFC 8452, appendix C.1
+#[test]
+fn aes128_gcm_siv_crypt_aad() {
+    let mut t = TestBuilder::default();
+    t.run(r#"key = hexstr_to_data("01000000000000000000000000000000");"#);
+    t.run(r#"iv = hexstr_to_data("030000000000000000000000");"#);
+    t.run(r#"ad = hexstr_to_data("01");"#);
+    t.ok(
+        r#"crypt = aes128_gcm_siv_encrypt(key: key, data: hexstr_to_data("0200000000000000"), iv: iv, aad: ad);"#,
+        decode_hex("1e6daba35669f4273b0a1a2560969cdf790d99759abd1508").unwrap(),
+    );
+    t.ok(
+        r#"aes128_gcm_siv_decrypt(key: key, data: crypt, iv: iv, aad: ad);"#,
+        decode_hex("0200000000000000").unwrap(),
+    );
+    t.ok(
+        r#"aes128_gcm_siv_decrypt(key: key, data: crypt, iv: iv);"#,
+        NaslValue::Data(vec![]),
+    );
+}
+
+/// Test vector from RFC 8452, appendix C.2
+#[test]
+fn aes256_gcm_siv_crypt() {
+    let mut t = TestBuilder::default();
+    t.run(
+        r#"key = hexstr_to_data("0100000000000000000000000000000000000000000000000000000000000000");"#,
+    );
+    t.run(r#"iv = hexstr_to_data("030000000000000000000000");"#);
+    t.ok(
+        r#"crypt = aes256_gcm_siv_encrypt(key: key, data: hexstr_to_data("0100000000000000"), iv: iv);"#,
+        decode_hex("c2ef328e5c71c83b843122130f7364b761e0b97427e3df28").unwrap(),
+    );
+    t.ok(
+        r#"aes256_gcm_siv_decrypt(key: key, data: crypt, iv: iv);"#,
+        decode_hex("0100000000000000").unwrap(),
+    );
+}
+
+#[test]
+fn aes_gcm_siv_tampered_tag() {
+    let crypt = "b5d839330ac7b786578782fff6013b815b287c22493a364c";
+    let mut t = TestBuilder::default();
+    t.run(r#"key = hexstr_to_data("01000000000000000000000000000000");"#);
+    t.run(r#"iv = hexstr_to_data("030000000000000000000000");"#);
+    // Altering any byte of the ciphertext or the tag results in empty data.
+    for i in 0..24 {
+        t.ok(
+            format!(
+                r#"aes128_gcm_siv_decrypt(key: key, data: hexstr_to_data("{}"), iv: iv);"#,
+                tamper(crypt, i)
+            ),
+            NaslValue::Data(vec![]),
+        );
+    }
+}
+
+#[tokio::test]
+async fn aes_gcm_siv_tampered_tag_warns_about_failed_authentication() {
+    let crypt = "b5d839330ac7b786578782fff6013b815b287c22493a364c";
+    let mut code = String::from(
+        r#"
+key = hexstr_to_data("01000000000000000000000000000000");
+iv = hexstr_to_data("030000000000000000000000");
+"#,
+    );
+    for i in 0..24 {
+        code.push_str(&format!(
+            "aes128_gcm_siv_decrypt(key: key, data: hexstr_to_data(\"{}\"), iv: iv);\n",
+            tamper(crypt, i)
+        ));
+    }
+    let factory = ContextFactory::default();
+    let context = factory.build(ContextKey::default());
+    let outcome = CodeInterpreter::new(&code, Register::root_initial(&[]), &context)
+        .run()
+        .await
+        .unwrap();
+    assert_eq!(outcome.exit_value, NaslValue::Data(vec![]));
+    assert_eq!(outcome.warnings.len(), 24);
+    for warning in &outcome.warnings {
+        let error: &CryptographicError = warning.try_into().unwrap();
+        assert!(matches!(error, CryptographicError::AuthenticationFailed));
+    }
+}
+
+#[test]
+fn aes_gcm_siv_wrong_lengths() {
+    let mut t = TestBuilder::default();
+    check_err_matches!(
+        t,
+        r#"aes128_gcm_siv_encrypt(key: "short", data: "a", iv: "030000000000");"#,
+        ArgumentError::WrongArgument(_),
+    );
+    check_err_matches!(
+        t,
+        r#"aes128_gcm_siv_encrypt(key: "0123456789abcdef", data: "a", iv: "short");"#,
+        ArgumentError::WrongArgument(_),
+    );
+}
+
+fn xor(a: &[u8], b: &[u8]) -> Vec<u8> {
+    a.iter().zip(b).map(|(x, y)| x ^ y).collect()
+}
+
+/// With plain GCM, reusing a nonce leaks the XOR of both plaintexts through the XOR of the
+/// ciphertexts. GCM-SIV derives the keystream from the plaintext, so this does not happen.
+#[test]
+fn aes_gcm_siv_nonce_reuse() {
+    let p1 = b"attack at dawn!!";
+    let p2 = b"retreat at noon!";
+    let mut t = TestBuilder::default();
+    t.run_all(format!(
+        r#"
+        key = hexstr_to_data("01000000000000000000000000000000");
+        iv = hexstr_to_data("030000000000000000000000");
+        aes128_gcm_encrypt(key: key, data: "{p1}", iv: iv);
+        aes128_gcm_encrypt(key: key, data: "{p2}", iv: iv);
+        aes128_gcm_siv_encrypt(key: key, data: "{p1}", iv: iv);
+        aes128_gcm_siv_encrypt(key: key, data: "{p2}", iv: iv);
+        "#,
+        p1 = std::str::from_utf8(p1).unwrap(),
+        p2 = std::str::from_utf8(p2).unwrap(),
+    ));
+    let results: Vec<Vec<u8>> = t
+        .results()
+        .into_iter()
+        .skip(2)
+        .map(|r| match r {
+            Ok(NaslValue::Data(x)) => x[..16].to_vec(),
+            x => panic!("unexpected result {x:?}"),
+        })
+        .collect();
+    let plain_xor = xor(p1, p2);
+    assert_eq!(xor(&results[0], &results[1]), plain_xor);
+    assert_ne!(xor(&results[2], &results[3]), plain_xor);
+}
@@ -0,0 +1,100 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+use super::helper::decode_hex;
+use crate::nasl::test_prelude::*;
+
+/// Test vectors from RFC 7253, appendix A
+#[test]
+fn aes128_ocb_crypt() {
+    let mut t = TestBuilder::default();
+    t.run(r#"key = hexstr_to_data("000102030405060708090a0b0c0d0e0f");"#);
+    t.run(r#"data = hexstr_to_data("0001020304050607");"#);
+    t.ok(
+        r#"aes128_ocb_encrypt(key: key, data: "", iv: hexstr_to_data("bbaa99887766554433221100"));"#,
+        decode_hex("785407bfffc8ad9edcc5520ac9111ee6").unwrap(),
+    );
+    t.ok(
+        r#"crypt = aes128_ocb_encrypt(key: key, data: data, iv: hexstr_to_data("bbaa99887766554433221101"), aad: data);"#,
+        decode_hex("6820b3657b6f615a5725bda0d3b4eb3a257c9af1f8f03009").unwrap(),
+    );
+    t.ok(
+        r#"aes128_ocb_decrypt(key: key, data: crypt, iv: hexstr_to_data("bbaa99887766554433221101"), aad: data);"#,
+        decode_hex("0001020304050607").unwrap(),
+    );
+    t.ok(
+        r#"aes128_ocb_encrypt(key: key, data: "", iv: hexstr_to_data("bbaa99887766554433221102"), aad: data);"#,
+        decode_hex("81017f8203f081277152fade694a0a00").unwrap(),
+    );
+    t.ok(
+        r#"crypt = aes128_ocb_encrypt(key: key, data: data, iv: hexstr_to_data("bbaa99887766554433221103"));"#,
+        decode_hex("45dd69f8f5aae72414054cd1f35d82760b2cd00d2f99bfa9").unwrap(),
+    );
+    t.ok(
+        r#"aes128_ocb_decrypt(key: key, data: crypt, iv: hexstr_to_data("bbaa99887766554433221103"));"#,
+        decode_hex("0001020304050607").unwrap(),
+    );
+}
+
+#[test]
+fn aes192_and_aes256_ocb_crypt() {
+    let mut t = TestBuilder::default();
+    t.run(r#"data = hexstr_to_data("0001020304050607");"#);
+    t.run(r#"iv = hexstr_to_data("bbaa99887766554433221101");"#);
+    t.run(r#"key = hexstr_to_data("000102030405060708090a0b0c0d0e0f1011121314151617");"#);
+    t.ok(
+        r#"crypt = aes192_ocb_encrypt(key: key, data: data, iv: iv, aad: data);"#,
+        decode_hex("d8c7f12fb7484c6f5aed3b188585fc346528e93e2e73a876").unwrap(),
+    );
+    t.ok(
+        r#"aes192_ocb_decrypt(key: key, data: crypt, iv: iv, aad: data);"#,
+        decode_hex("0001020304050607").unwrap(),
+    );
+    t.run(
+        r#"key = hexstr_to_data("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f");"#,
+    );
+    t.ok(
+        r#"crypt = aes256_ocb_encrypt(key: key, data: data, iv: iv, aad: data);"#,
+        decode_hex("9dbfab6806ec035c4b08ce1f17ba56109f6f00a34edde831").unwrap(),
+    );
+    t.ok(
+        r#"aes256_ocb_decrypt(key: key, data: crypt, iv: iv, aad: data);"#,
+        decode_hex("0001020304050607").unwrap(),
+    );
+}
+
+#[test]
+fn aes_ocb_tampered_tag() {
+    let mut t = TestBuilder::default();
+    t.run(r#"key = hexstr_to_data("000102030405060708090a0b0c0d0e0f");"#);
+    t.run(r#"iv = hexstr_to_data("bbaa99887766554433221103");"#);
+    t.ok(
+        r#"aes128_ocb_decrypt(key: key, data: hexstr_to_data("45dd69f8f5aae72414054cd1f35d82760b2cd00d2f99bfa8"), iv: iv);"#,
+        NaslValue::Data(vec![]),
+    );
+    t.ok(
+        r#"aes128_ocb_decrypt(key: key, data: hexstr_to_data("45dd69f8f5aae72414054cd1f35d82760b2cd00d2f99bfa9"), iv: iv, aad: "x");"#,
+        NaslValue::Data(vec![]),
+    );
+}
+
+#[test]
+fn aes_ocb_wrong_lengths() {
+    let mut t = TestBuilder::default();
+    check_err_matches!(
+        t,
+        r#"aes128_ocb_encrypt(key: "short", data: "abc", iv: "123456789012");"#,
+        ArgumentError::WrongArgument(_),
+    );
+    check_err_matches!(
+        t,
+        r#"aes128_ocb_encrypt(key: "1234567890123456", data: "abc", iv: "1234567890123");"#,
+        ArgumentError::WrongArgument(_),
+    );
+    check_err_matches!(
+        t,
+        r#"aes256_ocb_encrypt(key: "1234567890123456", data: "abc", iv: "123456789012");"#,
+        ArgumentError::WrongArgument(_),
+    );
+}
@@ -0,0 +1,103 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+use super::helper::decode_hex;
+use crate::nasl::test_prelude::*;
+
+/// Test vector from RFC 5297, appendix A.1
+#[test]
+fn aes128_siv_deterministic() {
+    let mut t = TestBuilder::default();
+    t.run(
+        r#"key = hexstr_to_data("fffefdfcfbfaf9f8f7f6f5f4f3f2f1f0f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff");"#,
+    );
+    t.run(r#"ad = hexstr_to_data("101112131415161718191a1b1c1d1e1f2021222324252627");"#);
+    t.run(r#"data = hexstr_to_data("112233445566778899aabbccddee");"#);
+    t.ok(
+        r#"crypt = aes128_siv_encrypt(key: key, data: data, aad: make_list(ad));"#,
+        decode_hex("85632d07c6e8f37f950acd320a2ecc9340c02b9690c4dc04daef7f6afe5c").unwrap(),
+    );
+    t.ok(
+        r#"aes128_siv_encrypt(key: key, data: data, aad: ad) == crypt;"#,
+        true,
+    );
+    t.ok(
+        r#"aes128_siv_decrypt(key: key, data: crypt, aad: make_list(ad));"#,
+        decode_hex("112233445566778899aabbccddee").unwrap(),
+    );
+}
+
+/// Test vector from RFC 5297, appendix A.2
+#[test]
+fn aes128_siv_multiple_aad() {
+    let mut t = TestBuilder::default();
+    t.run(
+        r#"key = hexstr_to_data("7f7e7d7c7b7a797877767574737271704041424344454647" + "48494a4b4c4d4e4f");"#,
+    );
+    t.run(
+        r#"ad1 = hexstr_to_data("00112233445566778899aabbccddeeffdeaddadadeaddadaffeeddccbbaa99887766554433221100");"#,
+    );
+    t.run(r#"ad2 = hexstr_to_data("102030405060708090a0");"#);
+    t.run(r#"nonce = hexstr_to_data("09f911029d74e35bd84156c5635688c0");"#);
+    t.run(r#"data = "this is some plaintext to encrypt using SIV-AES";"#);
+    t.ok(
+        r#"crypt = aes128_siv_encrypt(key: key, data: data, aad: make_list(ad1, ad2, nonce));"#,
+        decode_hex("7bdb6e3b432667eb06f4d14bff2fbd0fcb900f2fddbe404326601965c889bf17dba77ceb094fa663b7a3f748ba8af829ea64ad544a272e9c485b62a3fd5c0d").unwrap(),
+    );
+    t.ok(
+        r#"aes128_siv_decrypt(key: key, data: crypt, aad: make_list(ad1, ad2, nonce));"#,
+        NaslValue::Data(b"this is some plaintext to encrypt using SIV-AES".to_vec()),
+    );
+    // The order of the components matters.
+    t.ok(
+        r#"aes128_siv_decrypt(key: key, data: crypt, aad: make_list(ad2, ad1, nonce));"#,
+        NaslValue::Data(vec![]),
+    );
+    t.ok(
+        r#"aes128_siv_decrypt(key: key, data: crypt, aad: make_list(ad1, ad2));"#,
+        NaslValue::Data(vec![]),
+    );
+}
+
+#[test]
+fn aes256_siv_crypt() {
+    let mut t = TestBuilder::default();
+    t.run(
+        r#"key = hexstr_to_data("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f" + "202122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d3e3f");"#,
+    );
+    t.ok(
+        r#"crypt = aes256_siv_encrypt(key: key, data: "hello", aad: make_list("header"));"#,
+        decode_hex("42f9009f5bb8016def713612842518304489859a83").unwrap(),
+    );
+    t.ok(
+        r#"aes256_siv_decrypt(key: key, data: crypt, aad: make_list("header"));"#,
+        NaslValue::Data(b"hello".to_vec()),
+    );
+}
+
+#[test]
+fn aes_siv_errors() {
+    let mut t = TestBuilder::default();
+    t.run(
+        r#"key = hexstr_to_data("fffefdfcfbfaf9f8f7f6f5f4f3f2f1f0f0f1f2f3f4f5f6f7f8f9fafbfcfdfeff");"#,
+    );
+    t.ok(
+        r#"aes128_siv_decrypt(key: key, data: hexstr_to_data("85632d07c6e8f37f950acd320a2ecc9340c02b9690c4dc04daef7f6afe5d"));"#,
+        NaslValue::Data(vec![]),
+    );
+    t.ok(
+        r#"aes128_siv_decrypt(key: key, data: "short");"#,
+        NaslValue::Data(vec![]),
+    );
+    check_err_matches!(
+        t,
+        r#"aes256_siv_encrypt(key: key, data: "abc");"#,
+        ArgumentError::WrongArgument(_),
+    );
+    check_err_matches!(
+        t,
+        r#"aes128_siv_encrypt(key: key, data: "abc", aad: make_list(1));"#,
+        ArgumentError::WrongArgument(_),
+    );
+}
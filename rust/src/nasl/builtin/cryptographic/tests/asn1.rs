@@ -0,0 +1,122 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+use super::helper::decode_hex;
+use crate::nasl::builtin::cryptographic::CryptographicError;
+use crate::nasl::test_prelude::*;
+
+const SIGNATURE: &str = "304502210092e996e7ac4df058d095b9ca188989631a29cc8adfb333f647c5b09814a10849022055c73a359e13bb647607155fc1a3c35b7dfb29d4852c49ad81780a56836c32c4";
+
+#[test]
+fn asn1_parse_ecdsa_signature() {
+    let mut t = TestBuilder::default();
+    t.run(format!(r#"sig = hexstr_to_data("{SIGNATURE}");"#));
+    t.run("parsed = asn1_parse(sig);");
+    t.ok("max_index(parsed);", 1);
+    t.run("seq = parsed[0];");
+    t.ok("seq[0];", 0x30);
+    t.ok("seq[1];", 0x45);
+    t.run("ints = seq[2];");
+    t.ok("max_index(ints);", 2);
+    t.run("r = ints[0];");
+    t.ok("r[0];", 0x02);
+    t.ok("r[1];", 33);
+    t.ok(
+        "r[2];",
+        decode_hex("0092e996e7ac4df058d095b9ca188989631a29cc8adfb333f647c5b09814a10849").unwrap(),
+    );
+    t.run("s = ints[1];");
+    t.ok("s[1];", 32);
+    t.ok(
+        "s[2];",
+        decode_hex("55c73a359e13bb647607155fc1a3c35b7dfb29d4852c49ad81780a56836c32c4").unwrap(),
+    );
+    t.ok("asn1_encode(parsed) == sig;", true);
+    t.ok("asn1_encode(seq) == sig;", true);
+}
+
+#[test]
+fn asn1_encode_long_length() {
+    let mut t = TestBuilder::default();
+    t.run("value = crap(length: 200, data: raw_string(0x41));");
+    let mut expected = decode_hex("0481c8").unwrap();
+    expected.extend([0x41; 200]);
+    t.ok("asn1_encode(make_list(0x04, 0, value));", expected);
+    t.run("parsed = asn1_parse(asn1_encode(make_list(0x04, 0, value)));");
+    t.run("e = parsed[0];");
+    t.ok("e[1];", 200);
+}
+
+#[test]
+fn asn1_parse_invalid() {
+    let mut t = TestBuilder::default();
+    // truncated value of the sequence
+    check_err_matches!(
+        t,
+        r#"asn1_parse(hexstr_to_data("3007020100020105"));"#,
+        CryptographicError::Asn1 { offset: 2, .. },
+    );
+    // truncated value of the integer within the sequence
+    check_err_matches!(
+        t,
+        r#"asn1_parse(hexstr_to_data("3003020205"));"#,
+        CryptographicError::Asn1 { offset: 4, .. },
+    );
+    // truncated value of the second top level element
+    check_err_matches!(
+        t,
+        r#"asn1_parse(hexstr_to_data("30030201050201"));"#,
+        CryptographicError::Asn1 { offset: 7, .. },
+    );
+    // indefinite length
+    check_err_matches!(
+        t,
+        r#"asn1_parse(hexstr_to_data("30800201050000"));"#,
+        CryptographicError::Asn1 { offset: 1, .. },
+    );
+    // length in long form although it is below 128
+    check_err_matches!(
+        t,
+        r#"asn1_parse(hexstr_to_data("308103020105"));"#,
+        CryptographicError::Asn1 { offset: 1, .. },
+    );
+    // length that overflows when added to the header
+    check_err_matches!(
+        t,
+        r#"asn1_parse(hexstr_to_data("0488ffffffffffffffff00"));"#,
+        CryptographicError::Asn1 { offset: 1, .. },
+    );
+    // sequences nested deeper than the limit
+    check_err_matches!(
+        t,
+        format!(r#"asn1_parse(hexstr_to_data("{}"));"#, nested_sequences(65)),
+        CryptographicError::Asn1 { .. },
+    );
+    t.ok(
+        format!(
+            r#"max_index(asn1_parse(hexstr_to_data("{}")));"#,
+            nested_sequences(64)
+        ),
+        1,
+    );
+}
+
+/// Returns the hex encoding of `depth` nested sequences, the innermost one being empty.
+fn nested_sequences(depth: usize) -> String {
+    let mut der = vec![];
+    for _ in 0..depth {
+        let mut outer = vec![0x30];
+        match der.len() {
+            len @ 0..0x80 => outer.push(len as u8),
+            len @ 0x80..0x100 => outer.extend([0x81, len as u8]),
+            len => {
+                outer.push(0x82);
+                outer.extend((len as u16).to_be_bytes());
+            }
+        }
+        outer.extend(der);
+        der = outer;
+    }
+    der.iter().map(|b| format!("{b:02x}")).collect()
+}
@@ -0,0 +1,63 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+use super::helper::decode_hex;
+use crate::nasl::test_prelude::*;
+
+/// Test vector from RFC 8439, section 2.4.2
+#[test]
+fn chacha20_rfc8439() {
+    let mut t = TestBuilder::default();
+    t.run(
+        r#"key = hexstr_to_data("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f");"#,
+    );
+    t.run(r#"nonce = hexstr_to_data("000000000000004a00000000");"#);
+    t.run(r#"data = "Ladies and Gentlemen of the class of '99: If I could offer you only one tip for the future, sunscreen would be it.";"#);
+    t.ok(
+        r#"crypt = chacha20(key: key, nonce: nonce, counter: 1, data: data);"#,
+        decode_hex("6e2e359a2568f98041ba0728dd0d6981e97e7aec1d4360c20a27afccfd9fae0bf91b65c5524733ab8f593dabcd62b3571639d624e65152ab8f530c359f0861d807ca0dbf500d6a6156a38e088a22b65e52bc514d16ccf806818ce91ab77937365af90bbf74a35be6b40b8eedf2785e42874d").unwrap(),
+    );
+    t.ok(
+        r#"chacha20(key: key, nonce: nonce, counter: 1, data: crypt);"#,
+        "Ladies and Gentlemen of the class of '99: If I could offer you only one tip for the future, sunscreen would be it.".as_bytes().to_vec(),
+    );
+}
+
+/// Keystream for block counter 1, RFC 8439 section 2.3.2
+#[test]
+fn chacha20_keystream_block() {
+    let mut t = TestBuilder::default();
+    t.run(
+        r#"key = hexstr_to_data("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f");"#,
+    );
+    t.run(r#"nonce = hexstr_to_data("000000090000004a00000000");"#);
+    t.ok(
+        r#"chacha20(key: key, nonce: nonce, counter: 1, data: crap(length: 64, data: raw_string(0x00)));"#,
+        decode_hex("10f1e7e4d13b5915500fdd1fa32071c4c7d1f4c733c068030422aa9ac3d46c4ed2826446079faa0914c2d705d98b02a2b5129cd1de164eb9cbd083e8a2503c4e").unwrap(),
+    );
+}
+
+#[test]
+fn chacha20_wrong_key_length() {
+    let mut t = TestBuilder::default();
+    t.run(r#"nonce = hexstr_to_data("000000000000004a00000000");"#);
+    check_err_matches!(
+        t,
+        r#"chacha20(key: "too short", nonce: nonce, data: "a");"#,
+        ArgumentError::WrongArgument(_),
+    );
+}
+
+#[test]
+fn chacha20_wrong_nonce_length() {
+    let mut t = TestBuilder::default();
+    t.run(
+        r#"key = hexstr_to_data("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f");"#,
+    );
+    check_err_matches!(
+        t,
+        r#"chacha20(key: key, nonce: "short", data: "a");"#,
+        ArgumentError::WrongArgument(_),
+    );
+}
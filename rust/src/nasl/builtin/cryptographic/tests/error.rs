@@ -0,0 +1,103 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+use crate::nasl::builtin::cryptographic::CryptographicError;
+use crate::nasl::test_prelude::*;
+use crate::nasl::utils::error::ReturnBehavior;
+
+/// Converts `error` into an `FnError` and back, checking the message on the way.
+fn round_trip(error: CryptographicError, message: &str) -> FnError {
+    let error = FnError::from(error);
+    assert_eq!(error.to_string(), message);
+    assert!(matches!(
+        <&BuiltinError>::try_from(&error),
+        Ok(BuiltinError::Cryptographic(_))
+    ));
+    error
+}
+
+#[test]
+fn authentication_failed_round_trip() {
+    let error = round_trip(
+        CryptographicError::AuthenticationFailed,
+        "Authentication of the ciphertext failed.",
+    );
+    assert!(matches!(
+        <&CryptographicError>::try_from(&error),
+        Ok(CryptographicError::AuthenticationFailed)
+    ));
+}
+
+#[test]
+fn invalid_key_length_round_trip() {
+    let error = round_trip(
+        CryptographicError::InvalidKeyLength("AesCbc".to_string()),
+        "Invalid length of key in AesCbc.",
+    );
+    assert!(matches!(
+        <&CryptographicError>::try_from(&error),
+        Ok(CryptographicError::InvalidKeyLength(cipher)) if cipher == "AesCbc"
+    ));
+}
+
+#[test]
+fn padding_error_round_trip() {
+    let error = round_trip(
+        CryptographicError::PaddingError("BfCbc".to_string()),
+        "Invalid padding of the data in BfCbc.",
+    );
+    assert!(matches!(
+        <&CryptographicError>::try_from(&error),
+        Ok(CryptographicError::PaddingError(cipher)) if cipher == "BfCbc"
+    ));
+    assert!(<&ArgumentError>::try_from(&error).is_err());
+}
+
+#[test]
+fn aes_cmac_invalid_length_round_trip() {
+    let error = round_trip(
+        CryptographicError::AesCmacInvalidLength(digest::InvalidLength),
+        "Invalid length of key in AesCmac Invalid Length.",
+    );
+    assert!(matches!(
+        <&CryptographicError>::try_from(&error),
+        Ok(CryptographicError::AesCmacInvalidLength(_))
+    ));
+    let mut t = TestBuilder::default();
+    check_err_matches!(
+        t,
+        r#"aes_cmac(key: "short", data: "data");"#,
+        CryptographicError::AesCmacInvalidLength(_),
+    );
+}
+
+#[test]
+fn cbc_errors_abort_the_script() {
+    for error in [
+        CryptographicError::InvalidKeyLength("AesCbc".to_string()),
+        CryptographicError::PaddingError("AesCbc".to_string()),
+    ] {
+        // Like the ArgumentError these errors replace, they exit the script ...
+        let error = FnError::from(error);
+        assert!(matches!(
+            error.return_behavior(),
+            ReturnBehavior::ExitScript
+        ));
+        // ... and are not Recoverable, so they are not reported as warnings.
+        assert!(error.recovered_value().is_none());
+    }
+    let error = FnError::from(ArgumentError::WrongArgument("key".to_string()));
+    assert!(matches!(
+        error.return_behavior(),
+        ReturnBehavior::ExitScript
+    ));
+    assert!(error.recovered_value().is_none());
+    let mut t = TestBuilder::default();
+    t.run(r#"key = hexstr_to_data("00000000000000000000000000000000");"#);
+    check_err_matches!(
+        t,
+        r#"aes128_cbc_decrypt(key: key, data: "not a block", iv: key);"#,
+        CryptographicError::PaddingError(_),
+    );
+}
@@ -2,7 +2,18 @@
 //
 // SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
 
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use digest::Digest;
+use sha2::Sha256;
+
+use super::helper::decode_hex;
+use crate::nasl::builtin::cryptographic::maybe_blocking;
+use crate::nasl::interpreter::CodeInterpreter;
 use crate::nasl::test_prelude::*;
+use crate::nasl::utils::context::DEFAULT_BLOCKING_THRESHOLD;
+use crate::storage::ContextKey;
 
 #[test]
 fn hash_md5() {
@@ -86,3 +97,229 @@ fn hash_ripemd160() {
         ],
     );
 }
+
+/// Test vectors from RFC 6986, section 10. The RFC prints the messages and digests in reversed
+/// byte order, below they are given in the order they are processed.
+const STREEBOG_M1: &str = "012345678901234567890123456789012345678901234567890123456789012";
+const STREEBOG_M2: &str = "d1e520e2e5f2f0e82c20d1f2f0e8e1eee6e820e2edf3f6e82c20e2e5fef2fa20f120eceef0ff20f1f2f0e5ebe0ece820ede020f5f0e0e1f0fbff20efebfaeafb20c8e3eef0e5e2fb";
+
+#[test]
+fn hash_streebog256() {
+    let mut t = TestBuilder::default();
+    t.ok(
+        format!(r#"STREEBOG256("{STREEBOG_M1}");"#),
+        decode_hex("9d151eefd8590b89daa6ba6cb74af9275dd051026bb149a452fd84e5e57b5500").unwrap(),
+    );
+    t.ok(
+        format!(r#"STREEBOG256(hexstr_to_data("{STREEBOG_M2}"));"#),
+        decode_hex("9dd2fe4e90409e5da87f53976d7405b0c0cac628fc669a741d50063c557e8f50").unwrap(),
+    );
+    t.ok(
+        r#"STREEBOG256("");"#,
+        decode_hex("3f539a213e97c802cc229d474c6aa32a825a360b2a933a949fd925208d9ce1bb").unwrap(),
+    );
+    t.ok(r#"STREEBOG256();"#, NaslValue::Null);
+}
+
+#[test]
+fn hash_streebog512() {
+    let mut t = TestBuilder::default();
+    t.ok(
+        format!(r#"STREEBOG512("{STREEBOG_M1}");"#),
+        decode_hex("1b54d01a4af5b9d5cc3d86d68d285462b19abc2475222f35c085122be4ba1ffa00ad30f8767b3a82384c6574f024c311e2a481332b08ef7f41797891c1646f48").unwrap(),
+    );
+    t.ok(
+        format!(r#"STREEBOG512(hexstr_to_data("{STREEBOG_M2}"));"#),
+        decode_hex("1e88e62226bfca6f9994f1f2d51569e0daf8475a3b0fe61a5300eee46d961376035fe83549ada2b8620fcd7c496ce5b33f0cb9dddc2b6460143b03dabac9fb28").unwrap(),
+    );
+    t.ok(
+        r#"STREEBOG512("");"#,
+        decode_hex("8e945da209aa869f0455928529bcae4679e9873ab707b55315f56ceb98bef0a7362f715528356ee83cda5f2aac4c6ad2ba3a715c1bcd81cb8e9f90bf4c1c1a8a").unwrap(),
+    );
+}
+
+#[test]
+fn hash_by_name() {
+    let mut t = TestBuilder::default();
+    t.ok(
+        r#"hash(algo: "md5", data: "hola mundo");"#,
+        decode_hex("0ad066a5d29f3f2a2a1c7c17dd082a79").unwrap(),
+    );
+    t.ok(
+        r#"hash(algo: "SHA1", data: "abc");"#,
+        decode_hex("a9993e364706816aba3e25717850c26c9cd0d89d").unwrap(),
+    );
+    t.ok(
+        r#"hash(algo: "sha256", data: "abc");"#,
+        decode_hex("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad").unwrap(),
+    );
+    t.ok(
+        r#"hash(algo: "sha512", data: "abc");"#,
+        decode_hex("ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f").unwrap(),
+    );
+    t.ok(
+        r#"hash(algo: "sha3-256", data: "abc");"#,
+        decode_hex("3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532").unwrap(),
+    );
+    t.ok(
+        r#"hash(algo: "sha3-256", data: "");"#,
+        decode_hex("a7ffc6f8bf1ed76651c14756a061d662f580ff4de43b49fa82d80a4b80f8434a").unwrap(),
+    );
+    t.ok(
+        r#"hash(algo: "sha256", data: "hola mundo") == SHA256("hola mundo");"#,
+        true,
+    );
+}
+
+#[test]
+fn hash_string_bytes() {
+    let mut t = TestBuilder::default();
+    t.ok(
+        r#"MD5(string(raw_string(0xff))) == MD5(raw_string(0xff));"#,
+        true,
+    );
+    t.ok(
+        r#"hash(algo: "sha256", data: string(raw_string(0x80, 0xff))) == SHA256(raw_string(0x80, 0xff));"#,
+        true,
+    );
+}
+
+#[test]
+fn hash_unknown_algorithm() {
+    let mut t = TestBuilder::default();
+    check_err_matches!(
+        t,
+        r#"hash(algo: "sha4", data: "abc");"#,
+        ArgumentError::WrongArgument(_),
+    );
+    t.check(
+        r#"hash(algo: "sha4", data: "abc");"#,
+        |r| matches!(r, Err(e) if e.to_string().contains("sha3-256, sha3-384")),
+        Some("an error listing the supported algorithms"),
+    );
+    check_err_matches!(t, r#"hash(data: "abc");"#, ArgumentError::MissingNamed(_));
+}
+
+#[test]
+fn hash_blake2() {
+    let mut t = TestBuilder::default();
+    // RFC 7693, Appendix A and B
+    t.ok(
+        r#"blake2b("abc");"#,
+        decode_hex("ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d17d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923").unwrap(),
+    );
+    t.ok(
+        r#"blake2s("abc");"#,
+        decode_hex("508c5e8c327c14e2e1a72ba34eeb452f37458b209ed63a294d999b4c86675982").unwrap(),
+    );
+    t.ok(
+        r#"blake2b("abc", len: 32);"#,
+        decode_hex("bddd813c634239723171ef3fee98579b94964e3bb1cb3e427262c8c068d52319").unwrap(),
+    );
+    t.ok(
+        r#"blake2s("abc", len: 16);"#,
+        decode_hex("aa4938119b1dc7b87cbad0ffd200d0ae").unwrap(),
+    );
+    t.ok(
+        r#"blake2b("");"#,
+        decode_hex("786a02f742015903c6c6fd852552d272912f4740e15847618a86e217f71f5419d25e1031afee585313896444934eb04b903a685b1448b755d56f701afe9be2ce").unwrap(),
+    );
+    t.ok(
+        r#"blake2s("");"#,
+        decode_hex("69217a3079908094e11121d042354a7c1f55b6482ca1a51e1b250dfd1ed0eef9").unwrap(),
+    );
+    t.ok(r#"blake2b();"#, NaslValue::Null);
+    check_err_matches!(
+        t,
+        r#"blake2b("abc", len: 65);"#,
+        ArgumentError::WrongArgument(_)
+    );
+    check_err_matches!(
+        t,
+        r#"blake2s("abc", len: 33);"#,
+        ArgumentError::WrongArgument(_)
+    );
+    check_err_matches!(
+        t,
+        r#"blake2s("abc", len: 0);"#,
+        ArgumentError::WrongArgument(_)
+    );
+}
+
+#[test]
+fn hash_blake3() {
+    let mut t = TestBuilder::default();
+    t.ok(
+        r#"blake3("abc");"#,
+        decode_hex("6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85").unwrap(),
+    );
+    t.ok(
+        r#"blake3("");"#,
+        decode_hex("af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262").unwrap(),
+    );
+    t.ok(
+        r#"blake3("abc", len: 8);"#,
+        decode_hex("6437b3ac38465133").unwrap(),
+    );
+    t.ok(r#"length(blake3("abc", len: 1024));"#, 1024);
+    t.ok(
+        r#"substr(blake3("abc", len: 64), 0, 32) == blake3("abc");"#,
+        true,
+    );
+    check_err_matches!(
+        t,
+        r#"blake3("abc", len: 1025);"#,
+        ArgumentError::WrongArgument(_)
+    );
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn large_hash_does_not_block() {
+    let data = vec![b'a'; 2 * DEFAULT_BLOCKING_THRESHOLD];
+    let expected = Sha256::digest(&data).to_vec();
+    let done = AtomicBool::new(false);
+    let hash = async {
+        let digest = maybe_blocking(DEFAULT_BLOCKING_THRESHOLD, Cow::Owned(data), |data| {
+            Sha256::digest(data).to_vec()
+        })
+        .await;
+        done.store(true, Ordering::SeqCst);
+        digest
+    };
+    // Runs while the hash is calculated, unless it blocks the only thread of the runtime.
+    let other = async { !done.load(Ordering::SeqCst) };
+    let (digest, ran_concurrently) = tokio::join!(hash, other);
+    assert_eq!(digest, expected);
+    assert!(ran_concurrently);
+}
+
+#[tokio::test]
+async fn hash_large_data() {
+    let mut t = TestBuilder::default();
+    t.run(r#"data = crap(data: "a", length: 2097152);"#);
+    t.ok(r#"hash(algo: "sha256", data: data) == SHA256(data);"#, true);
+    t.ok(
+        r#"hmac(key: "key", data: data) == HMAC_SHA256(key: "key", data: data);"#,
+        true,
+    );
+    t.async_verify().await;
+}
+
+#[tokio::test]
+async fn blocking_threshold_of_the_context() {
+    // Even the smallest data is hashed on a blocking thread.
+    let factory = ContextFactory::default().blocking_threshold(0);
+    let context = factory.build(ContextKey::default());
+    assert_eq!(context.blocking_threshold(), 0);
+    let code = r#"hexstr(SHA256("abc"));"#;
+    let outcome = CodeInterpreter::new(code, Register::root_initial(&[]), &context)
+        .run()
+        .await
+        .unwrap();
+    assert_eq!(
+        outcome.exit_value,
+        NaslValue::String(
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad".into()
+        )
+    );
+}
@@ -2,7 +2,8 @@
 //
 // SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
 
-use crate::nasl::test_utils::check_code_result;
+use crate::nasl::builtin::cryptographic::CryptographicError;
+use crate::nasl::test_prelude::*;
 
 #[test]
 fn hmac_md2() {
@@ -53,3 +54,188 @@ fn hmac_sha384() {
 fn hmac_sha512() {
     check_code_result(r#"HMAC_SHA512(key: "my_shared?key", data: "so much wow");"#, "7e251167d67f7f29fc978048d338f6ebe0d8bb5213f5ccacca50359b3435df19e60fa709241b98b0ed9e1aeb994df6f900c5fa87201c3fc971b0120968c96cb3");
 }
+
+#[test]
+fn hmac_truncated() {
+    let mut t = TestBuilder::default();
+    // RFC 2202, test case 5
+    t.run(r#"key = string(crap(data: raw_string(0x0c), length: 20));"#);
+    t.ok(
+        r#"HMAC_SHA1(key: key, data: "Test With Truncation");"#,
+        "4c1a03424b55e07fe7f27be1d58bb9324a9a5a04",
+    );
+    t.ok(
+        r#"HMAC_SHA1(key: key, data: "Test With Truncation", out_len: 12);"#,
+        "4c1a03424b55e07fe7f27be1",
+    );
+    t.ok(
+        r#"HMAC_SHA1(key: key, data: "Test With Truncation", out_len: 20);"#,
+        "4c1a03424b55e07fe7f27be1d58bb9324a9a5a04",
+    );
+    check_err_matches!(
+        t,
+        r#"HMAC_SHA1(key: key, data: "Test With Truncation", out_len: 21);"#,
+        ArgumentError::WrongArgument(_),
+    );
+    check_err_matches!(
+        t,
+        r#"HMAC_SHA256(key: key, data: "Test With Truncation", out_len: 0);"#,
+        ArgumentError::WrongArgument(_),
+    );
+}
+
+#[test]
+fn hmac_long_key() {
+    let mut t = TestBuilder::default();
+    // RFC 2202, test case 6, the key is longer than the block size and hashed first
+    t.run(r#"key = string(crap(data: raw_string(0xaa), length: 80));"#);
+    t.ok(
+        r#"HMAC_SHA1(key: key, data: "Test Using Larger Than Block-Size Key - Hash Key First");"#,
+        "aa4ae5e15272d00e95705637ce8a3b55ed402112",
+    );
+    t.ok(
+        r#"HMAC_MD5(key: key, data: "Test Using Larger Than Block-Size Key - Hash Key First");"#,
+        "6b1ab7fe4bd7bf8f0b62e6ce61b9d0cd",
+    );
+}
+
+#[test]
+fn hmac_sha3() {
+    check_code_result(
+        r#"HMAC_SHA3_256(key: "key", data: "The quick brown fox jumps over the lazy dog");"#,
+        "8c6e0683409427f8931711b10ca92a506eb1fafa48fadd66d76126f47ac2c333",
+    );
+    check_code_result(
+        r#"HMAC_SHA3_512(key: "key", data: "The quick brown fox jumps over the lazy dog");"#,
+        "237a35049c40b3ef5ddd960b3dc893d8284953b9a4756611b1b61bffcf53edd979f93547db714b06ef0a692062c609b70208ab8d4a280ceee40ed8100f293063",
+    );
+}
+
+#[test]
+fn blake2_keyed() {
+    let mut t = TestBuilder::default();
+    t.ok(
+        r#"BLAKE2B_MAC(key: "key", data: "The quick brown fox jumps over the lazy dog");"#,
+        "66f642208454bf2e066dac9eab68fae0146bb544c1d46e1f427008f068a45d872cd0c1fc23e7ba82a95d084aadf5e4af9edaf761fb6ced9e485a28c59a3f714c",
+    );
+    t.ok(
+        r#"BLAKE2S_MAC(key: "key", data: "The quick brown fox jumps over the lazy dog");"#,
+        "eec94d00b8c9d214636adfad587bc9c75f271d7a64d9639ef2e959f94da468e6",
+    );
+    t.ok(
+        r#"BLAKE2B_MAC(key: crap(data: "k", length: 64), data: "The quick brown fox jumps over the lazy dog");"#,
+        "6dca56ec4e6f98d0376f919b9c68f8e1f1ec80f9a8cead2682edaf03d826a7de12c3612c03eadcbc52fe34783fc5ea0789e2c06d099358fbc89e46e166f493eb",
+    );
+    t.ok(
+        r#"BLAKE2S_MAC(key: crap(data: "k", length: 32), data: "The quick brown fox jumps over the lazy dog");"#,
+        "0932ebcefe7417022c7faed93819eeeb947ba9afc3a5cfff1a22c07f80032602",
+    );
+    check_err_matches!(
+        t,
+        r#"BLAKE2B_MAC(key: crap(data: "k", length: 65), data: "abc");"#,
+        ArgumentError::WrongArgument(_),
+    );
+    check_err_matches!(
+        t,
+        r#"BLAKE2S_MAC(key: crap(data: "k", length: 33), data: "abc");"#,
+        ArgumentError::WrongArgument(_),
+    );
+    check_err_matches!(
+        t,
+        r#"BLAKE2S_MAC(key: "", data: "abc");"#,
+        ArgumentError::WrongArgument(_),
+    );
+}
+
+#[test]
+fn hmac_by_algo() {
+    let mut t = TestBuilder::default();
+    t.ok(
+        r#"hmac(algo: "sha1", key: "my_shared?key", data: "so much wow");"#,
+        "3815da2d914cdddd3fe2ca620dd1f1a2ba5f17bc",
+    );
+    t.ok(
+        r#"hmac(algo: "SHA256", key: "my_shared?key", data: "so much wow") == HMAC_SHA256(key: "my_shared?key", data: "so much wow");"#,
+        true,
+    );
+    t.ok(
+        r#"hmac(algo: "sha512", key: "my_shared?key", data: "so much wow") == HMAC_SHA512(key: "my_shared?key", data: "so much wow");"#,
+        true,
+    );
+    t.ok(
+        r#"hmac(key: "my_shared?key", data: "so much wow") == HMAC_SHA256(key: "my_shared?key", data: "so much wow");"#,
+        true,
+    );
+    t.ok(
+        r#"hmac(algo: "sha1", key: "key", data: "The quick brown fox jumps over the lazy dog", out_len: 4);"#,
+        "de7c9b85",
+    );
+    // RFC 2202, test case 1, the key is given as data
+    t.ok(
+        r#"hmac(algo: "sha1", key: crap(data: raw_string(0x0b), length: 20), data: "Hi There");"#,
+        "b617318655057264e28bc0b6fb378c8ef146be00",
+    );
+}
+
+#[test]
+fn hmac_unknown_algorithm() {
+    let mut t = TestBuilder::default();
+    check_err_matches!(
+        t,
+        r#"hmac(algo: "sha4", key: "key", data: "abc");"#,
+        ArgumentError::WrongArgument(_),
+    );
+    t.check(
+        r#"hmac(algo: "sha4", key: "key", data: "abc");"#,
+        |r| matches!(r, Err(e) if e.to_string().contains("sha1, sha256, sha384, sha512")),
+        Some("an error listing the supported algorithms"),
+    );
+}
+
+#[test]
+fn signed_token() {
+    let mut t = TestBuilder::default();
+    // HMAC-SHA256 of "my payload" with the key "secret"
+    t.ok(
+        r#"token = sign_token(data: "my payload", key: "secret");"#,
+        "bXkgcGF5bG9hZA.boC4KXWsY47LaMJna5XtP7UJMUXwXsRyAVkkTvgkDUo",
+    );
+    t.ok(
+        r#"verify_token(token: token, key: "secret");"#,
+        "my payload".as_bytes().to_vec(),
+    );
+    t.run(
+        r#"token = sign_token(data: raw_string(0x00, 0xfb, 0xff), key: "secret", algo: "sha1");"#,
+    );
+    t.ok(
+        r#"verify_token(token: token, key: "secret", algo: "SHA1");"#,
+        vec![0x00u8, 0xfb, 0xff],
+    );
+    check_err_matches!(
+        t,
+        r#"verify_token(token: token, key: "secret");"#,
+        CryptographicError::Token(_),
+    );
+    check_err_matches!(
+        t,
+        r#"sign_token(data: "my payload", key: "secret", algo: "sha4");"#,
+        ArgumentError::WrongArgument(_),
+    );
+}
+
+#[test]
+fn signed_token_tampered() {
+    let mut t = TestBuilder::default();
+    t.run(r#"token = sign_token(data: "user=guest", key: "secret");"#);
+    t.run(r#"signature = split(token, sep: ".", keep: FALSE);"#);
+    // "user=admin"
+    t.run(r#"tampered = "dXNlcj1hZG1pbg." + signature[1];"#);
+    for code in [
+        r#"verify_token(token: tampered, key: "secret");"#,
+        r#"verify_token(token: token, key: "another secret");"#,
+        r#"verify_token(token: "dXNlcj1hZG1pbg", key: "secret");"#,
+        r#"verify_token(token: "dXNlcj1hZG1pbg.!", key: "secret");"#,
+    ] {
+        check_err_matches!(t, code, CryptographicError::Token(_));
+    }
+}
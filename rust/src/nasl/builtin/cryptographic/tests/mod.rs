@@ -7,10 +7,23 @@ mod aes_ccm;
 mod aes_cmac;
 mod aes_ctr;
 mod aes_gcm;
+mod aes_gcm_siv;
+mod aes_ocb;
+mod aes_siv;
+mod asn1;
 mod bf_cbc;
+mod chacha20;
 mod des;
+mod error;
 mod hash;
 mod helper;
 mod hmac;
+mod jwt;
+mod named_data;
+mod ntlm;
+mod otp;
+mod password;
+mod poly1305;
 mod rc4;
 mod rsa;
+mod tls_prf;
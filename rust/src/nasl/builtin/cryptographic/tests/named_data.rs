@@ -0,0 +1,102 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+use crate::nasl::builtin::cryptographic::{get_optional_named_data, get_required_named_data};
+use crate::nasl::test_prelude::*;
+
+fn with_aad(value: NaslValue) -> Register {
+    Register::root_initial(&[("aad".to_string(), ContextType::Value(value))])
+}
+
+#[test]
+fn absent() {
+    let register = Register::default();
+    assert_eq!(get_optional_named_data(&register, "aad").unwrap(), None);
+    assert!(matches!(
+        get_required_named_data(&register, "aad"),
+        Err(ArgumentError::MissingNamed(_))
+    ));
+}
+
+#[test]
+fn empty_data() {
+    let register = with_aad(NaslValue::Data(vec![]));
+    let aad = get_optional_named_data(&register, "aad").unwrap();
+    assert_eq!(aad.as_deref(), Some(&[][..]));
+    assert!(get_required_named_data(&register, "aad")
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn empty_string() {
+    let register = with_aad(NaslValue::String(String::new()));
+    let aad = get_optional_named_data(&register, "aad").unwrap();
+    assert_eq!(aad.as_deref(), Some(&[][..]));
+}
+
+#[test]
+fn non_empty() {
+    let register = with_aad(NaslValue::String("\u{fc}".to_string()));
+    let aad = get_optional_named_data(&register, "aad").unwrap();
+    assert_eq!(aad.as_deref(), Some(&[0xfc][..]));
+    let register = with_aad(NaslValue::Number(1));
+    assert!(matches!(
+        get_optional_named_data(&register, "aad"),
+        Err(ArgumentError::WrongArgument(_))
+    ));
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn alias() {
+    let mut t = TestBuilder::default();
+    t.run(r#"k = "0123456789abcdef";"#);
+    t.run(r#"d = "some message";"#);
+    t.run(r#"iv = "fedcba9876543210";"#);
+    t.ok(
+        r#"aes128_ctr_encrypt(cryptkey: k, data: d, iv: iv) == aes128_ctr_encrypt(key: k, data: d, iv: iv);"#,
+        true,
+    );
+    // The real name takes precedence.
+    t.ok(
+        r#"aes128_ctr_encrypt(key: k, cryptkey: 1, data: d, iv: iv) == aes128_ctr_encrypt(key: k, data: d, iv: iv);"#,
+        true,
+    );
+    check_err_matches!(
+        t,
+        r#"aes128_ctr_encrypt(cryptkey: 5, data: d, iv: iv);"#,
+        ArgumentError::WrongArgument(_),
+    );
+    t.check(
+        r#"aes128_ctr_encrypt(cryptkey: 5, data: d, iv: iv);"#,
+        |r| matches!(r, Err(e) if e.to_string().contains("cryptkey")),
+        Some("an error naming the aliased argument"),
+    );
+    drop(t);
+    assert!(logs_contain(
+        "The argument cryptkey is deprecated, use key instead."
+    ));
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn alias_warns_once_per_call() {
+    let mut t = TestBuilder::default();
+    // The AEAD functions validate their arguments before using them.
+    t.run(
+        r#"aes128_gcm_encrypt(cryptkey: "0123456789abcdef", data: "some message", iv: "fedcba987654");"#,
+    );
+    drop(t);
+    logs_assert(|lines: &[&str]| {
+        match lines
+            .iter()
+            .filter(|line| line.contains("The argument cryptkey is deprecated"))
+            .count()
+        {
+            1 => Ok(()),
+            n => Err(format!("expected one warning, got {n}")),
+        }
+    });
+}
@@ -0,0 +1,105 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+use std::sync::Arc;
+use std::time::{Duration, UNIX_EPOCH};
+
+use super::helper::decode_hex;
+use crate::nasl::builtin::cryptographic::CryptographicError;
+use crate::nasl::test_prelude::*;
+use crate::nasl::utils::MockClock;
+
+/// The challenge message of MS-NLMP, section 4.2.4.3, with the target name "Server" and the
+/// target info containing the NetBIOS domain name "Domain" and computer name "Server".
+const CHALLENGE: &str = "4e544c4d53535000020000000c000c003800000033828ae20123456789abcdef00000000000000002400240044000000060070170000000f53006500720076006500720002000c0044006f006d00610069006e0001000c0053006500720076006500720000000000";
+
+/// The authenticate message to [`CHALLENGE`] for the user "User" of the domain "Domain" with the
+/// password "Password", the client challenge 0xaa.. and the time 0 as used in MS-NLMP, section
+/// 4.2.4. It contains the LMv2 response 86c35097ac9cec102554764a57cccc19 and the NTProofStr
+/// 68cd0ab851e51c96aabc927bebef6a1c of the specification.
+const AUTHENTICATE: &str = "4e544c4d5353500003000000180018004000000054005400580000000c000c00ac00000008000800b800000010001000c000000000000000d0000000018288a086c35097ac9cec102554764a57cccc19aaaaaaaaaaaaaaaa68cd0ab851e51c96aabc927bebef6a1c01010000000000000000000000000000aaaaaaaaaaaaaaaa0000000002000c0044006f006d00610069006e0001000c00530065007200760065007200000000000000000044006f006d00610069006e00550073006500720043004f004d0050005500540045005200";
+
+#[test]
+fn ntlm_type1() {
+    let mut t = TestBuilder::default();
+    t.ok(
+        "ntlm_type1();",
+        decode_hex("4e544c4d5353500001000000058288a000000000200000000000000020000000").unwrap(),
+    );
+    t.ok(
+        r#"ntlm_type1(domain: "dom", workstation: "ws");"#,
+        decode_hex("4e544c4d535350000100000005b288a003000300200000000200020023000000444f4d5753")
+            .unwrap(),
+    );
+    t.ok(
+        "ntlm_type1(base64: TRUE);",
+        "TlRMTVNTUAABAAAABYKIoAAAAAAgAAAAAAAAACAAAAA=",
+    );
+}
+
+#[test]
+fn ntlm_type3_ms_nlmp_test_vector() {
+    let mut t = TestBuilder::default();
+    t.run(format!(r#"challenge = hexstr_to_data("{CHALLENGE}");"#));
+    t.run(r#"client_challenge = hexstr_to_data("aaaaaaaaaaaaaaaa");"#);
+    t.ok(
+        r#"ntlm_type3(challenge: challenge, user: "User", domain: "Domain", password: "Password", workstation: "COMPUTER", client_challenge: client_challenge, timestamp: 0);"#,
+        decode_hex(AUTHENTICATE).unwrap(),
+    );
+}
+
+#[test]
+fn ntlm_type3_uses_the_clock_of_the_context() {
+    // One second after the epoch, given in 100 ns intervals since 1601-01-01.
+    let clock = Arc::new(MockClock::new(UNIX_EPOCH + Duration::from_secs(1)));
+    let mut t = TestBuilder::default().with_clock(clock);
+    t.run(format!(r#"challenge = hexstr_to_data("{CHALLENGE}");"#));
+    t.run(r#"client_challenge = hexstr_to_data("aaaaaaaaaaaaaaaa");"#);
+    t.run(r#"a = ntlm_type3(challenge: challenge, user: "User", domain: "Domain", password: "Password", client_challenge: client_challenge);"#);
+    t.run(r#"b = ntlm_type3(challenge: challenge, user: "User", domain: "Domain", password: "Password", client_challenge: client_challenge, timestamp: 116444736010000000);"#);
+    t.ok("a == b;", true);
+}
+
+#[test]
+fn ntlm_type3_base64() {
+    let mut t = TestBuilder::default();
+    t.run(r#"client_challenge = hexstr_to_data("aaaaaaaaaaaaaaaa");"#);
+    t.ok(
+        r#"ntlm_type3(challenge: "TlRMTVNTUAACAAAADAAMADgAAAAzgoriASNFZ4mrze8AAAAAAAAAACQAJABEAAAABgBwFwAAAA9TAGUAcgB2AGUAcgACAAwARABvAG0AYQBpAG4AAQAMAFMAZQByAHYAZQByAAAAAAA=", user: "User", domain: "Domain", password: "Password", workstation: "COMPUTER", client_challenge: client_challenge, timestamp: 0, base64: TRUE);"#,
+        "TlRMTVNTUAADAAAAGAAYAEAAAABUAFQAWAAAAAwADACsAAAACAAIALgAAAAQABAAwAAAAAAAAADQAAAAAYKIoIbDUJesnOwQJVR2SlfMzBmqqqqqqqqqqmjNCrhR5RyWqrySe+vvahwBAQAAAAAAAAAAAAAAAAAAqqqqqqqqqqoAAAAAAgAMAEQAbwBtAGEAaQBuAAEADABTAGUAcgB2AGUAcgAAAAAAAAAAAEQAbwBtAGEAaQBuAFUAcwBlAHIAQwBPAE0AUABVAFQARQBSAA==",
+    );
+}
+
+#[test]
+fn ntlm_type3_invalid_arguments() {
+    let mut t = TestBuilder::default();
+    check_err_matches!(
+        t,
+        r#"ntlm_type3(challenge: "no challenge", user: "u", domain: "d", password: "p");"#,
+        CryptographicError::Ntlm(_),
+    );
+    check_err_matches!(
+        t,
+        r#"ntlm_type3(challenge: "TlRMTVNTUAABAAAABYKIoAAAAAAgAAAAAAAAACAAAAA=", user: "u", domain: "d", password: "p");"#,
+        CryptographicError::Ntlm(_),
+    );
+    t.run(format!(r#"challenge = hexstr_to_data("{CHALLENGE}");"#));
+    check_err_matches!(
+        t,
+        r#"ntlm_type3(challenge: challenge, user: "u", domain: "d", password: "p", client_challenge: "abc");"#,
+        ArgumentError::WrongArgument(_),
+    );
+    // The length of the user in UTF-16 does not fit into the security buffer.
+    check_err_matches!(
+        t,
+        r#"ntlm_type3(challenge: challenge, user: crap(40000), domain: "d", password: "p");"#,
+        CryptographicError::Ntlm(_),
+    );
+    check_err_matches!(
+        t,
+        "ntlm_type1(domain: crap(65536));",
+        CryptographicError::Ntlm(_),
+    );
+    t.ok("typeof(ntlm_type1(domain: crap(65535)));", "data");
+}
@@ -0,0 +1,122 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+use std::sync::Arc;
+use std::time::{Duration, UNIX_EPOCH};
+
+use crate::nasl::test_prelude::*;
+use crate::nasl::utils::MockClock;
+
+/// Test vectors from RFC 4226, appendix D
+#[test]
+fn hotp_rfc4226() {
+    let mut t = TestBuilder::default();
+    t.run(r#"secret = "12345678901234567890";"#);
+    let expected = [
+        "755224", "287082", "359152", "969429", "338314", "254676", "287922", "162583", "399871",
+        "520489",
+    ];
+    for (counter, code) in expected.iter().enumerate() {
+        t.ok(
+            format!("hotp(secret: secret, counter: {counter});"),
+            code.to_string(),
+        );
+    }
+}
+
+/// Test vectors from RFC 6238, appendix B
+#[test]
+fn totp_rfc6238() {
+    let mut t = TestBuilder::default();
+    t.run(r#"sha1 = "12345678901234567890";"#);
+    t.run(r#"sha256 = "12345678901234567890123456789012";"#);
+    t.run(r#"sha512 = "1234567890123456789012345678901234567890123456789012345678901234";"#);
+    let expected = [
+        (59i64, "94287082", "46119246", "90693936"),
+        (1111111109, "07081804", "68084774", "25091201"),
+        (1111111111, "14050471", "67062674", "99943326"),
+        (1234567890, "89005924", "91819424", "93441116"),
+        (2000000000, "69279037", "90698825", "38618901"),
+        (20000000000, "65353130", "77737706", "47863826"),
+    ];
+    for (time, sha1, sha256, sha512) in expected {
+        t.ok(
+            format!("totp(secret: sha1, time: {time}, digits: 8);"),
+            sha1.to_string(),
+        );
+        t.ok(
+            format!(r#"totp(secret: sha256, time: {time}, digits: 8, algo: "SHA256");"#),
+            sha256.to_string(),
+        );
+        t.ok(
+            format!(r#"totp(secret: sha512, time: {time}, digits: 8, algo: "SHA512");"#),
+            sha512.to_string(),
+        );
+    }
+}
+
+#[test]
+fn totp_base32_secret() {
+    let mut t = TestBuilder::default();
+    t.ok(
+        r#"totp(secret: "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ", base32: TRUE, time: 59, digits: 8);"#,
+        "94287082",
+    );
+    t.ok(
+        r#"totp(secret: "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ", base32: TRUE, time: 59);"#,
+        "287082",
+    );
+    check_err_matches!(
+        t,
+        r#"totp(secret: "not base32!", base32: TRUE);"#,
+        ArgumentError::WrongArgument(_),
+    );
+}
+
+#[test]
+fn otp_invalid_arguments() {
+    let mut t = TestBuilder::default();
+    t.run(r#"secret = "12345678901234567890";"#);
+    check_err_matches!(
+        t,
+        r#"hotp(secret: secret, counter: 0, digits: 5);"#,
+        ArgumentError::WrongArgument(_),
+    );
+    check_err_matches!(
+        t,
+        r#"totp(secret: secret, digits: 9);"#,
+        ArgumentError::WrongArgument(_),
+    );
+    check_err_matches!(
+        t,
+        r#"hotp(secret: secret, counter: 0, algo: "MD5");"#,
+        ArgumentError::WrongArgument(_),
+    );
+    check_err_matches!(
+        t,
+        r#"totp(secret: secret, step: 0);"#,
+        ArgumentError::WrongArgument(_),
+    );
+}
+
+/// Test vector from RFC 6238, appendix B, with the time taken from the clock of the context
+#[test]
+fn totp_mock_clock() {
+    let clock = Arc::new(MockClock::new(UNIX_EPOCH + Duration::from_secs(1111111109)));
+    let mut t = TestBuilder::default().with_clock(clock.clone());
+    t.ok(
+        r#"totp(secret: "12345678901234567890", digits: 8);"#,
+        "07081804",
+    );
+    t.ok(r#"unixtime();"#, 1111111109);
+    t.ok(r#"gettimeofday();"#, "1111111109.000000");
+    t.ok(r#"isotime_now();"#, "20050318T015829");
+    drop(t);
+    clock.set(UNIX_EPOCH + Duration::from_secs(59));
+    let mut t = TestBuilder::default().with_clock(clock);
+    t.ok(
+        r#"totp(secret: "12345678901234567890", digits: 8);"#,
+        "94287082",
+    );
+}
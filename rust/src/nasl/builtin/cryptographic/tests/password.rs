@@ -0,0 +1,61 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+use crate::nasl::builtin::cryptographic::password::Passwords;
+use crate::nasl::test_prelude::*;
+use crate::nasl::NoOpLoader;
+use crate::storage::DefaultDispatcher;
+
+#[test]
+fn password_entropy() {
+    let mut t = TestBuilder::default();
+    t.ok(r#"password_entropy("");"#, 0);
+    t.ok(r#"password_entropy("abc");"#, 14);
+    t.ok(r#"password_entropy("123456");"#, 19);
+    t.ok(r#"password_entropy(password: "Tr0ub4dor&3");"#, 72);
+    t.ok(r#"password_entropy("correct horse battery staple");"#, 164);
+    t.ok(
+        r#"password_entropy("abc") < password_entropy("aB3$");"#,
+        true,
+    );
+}
+
+#[test]
+fn common_passwords() {
+    let mut t = TestBuilder::default();
+    t.ok(r#"is_common_password("123456");"#, true);
+    t.ok(r#"is_common_password(password: "Password");"#, true);
+    t.ok(r#"is_common_password("Tr0ub4dor&3");"#, false);
+}
+
+fn with_common_passwords(passwords: Passwords) -> TestBuilder<NoOpLoader, DefaultDispatcher> {
+    let functions = ContextFactory::default()
+        .common_passwords(passwords)
+        .functions;
+    TestBuilder::default().with_executor(functions)
+}
+
+#[test]
+fn common_passwords_override() {
+    let mut t = with_common_passwords(Passwords::new(["Tr0ub4dor&3"]));
+    t.ok(r#"is_common_password("tr0ub4dor&3");"#, true);
+    t.ok(r#"is_common_password("123456");"#, false);
+    // The other functions of the set are still available.
+    t.ok(r#"password_entropy("abc");"#, 14);
+    // Other sets keep the bundled list.
+    TestBuilder::default().ok(r#"is_common_password("123456");"#, true);
+}
+
+#[test]
+fn common_passwords_from_file() {
+    let path = std::env::temp_dir().join(format!("common_passwords_{}.txt", std::process::id()));
+    std::fs::write(&path, "first\n\n  second \n").unwrap();
+    let passwords = Passwords::from_file(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert!(Passwords::from_file(&path).is_err());
+    let mut t = with_common_passwords(passwords);
+    t.ok(r#"is_common_password("second");"#, true);
+    t.ok(r#"is_common_password("");"#, false);
+    t.ok(r#"is_common_password("123456");"#, false);
+}
@@ -0,0 +1,44 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+use super::helper::decode_hex;
+use crate::nasl::test_prelude::*;
+
+/// Test vector from RFC 8439, section 2.5.2
+#[test]
+fn poly1305_rfc8439() {
+    let mut t = TestBuilder::default();
+    t.run(
+        r#"key = hexstr_to_data("85d6be7857556d337f4452fe42d506a80103808afb0db2fd4abff6af4149f51b");"#,
+    );
+    t.run(r#"data = "Cryptographic Forum Research Group";"#);
+    t.ok(
+        r#"tag = poly1305(key: key, data: data);"#,
+        decode_hex("a8061dc1305136c6c22b8baf0c0127a9").unwrap(),
+    );
+    t.ok(r#"poly1305_verify(key: key, data: data, tag: tag);"#, true);
+    t.ok(
+        r#"poly1305_verify(key: key, data: "Cryptographic Forum Research Grouq", tag: tag);"#,
+        false,
+    );
+    t.ok(
+        r#"poly1305_verify(key: key, data: data, tag: substr(tag, 0, 14));"#,
+        false,
+    );
+}
+
+#[test]
+fn poly1305_wrong_key_length() {
+    let mut t = TestBuilder::default();
+    check_err_matches!(
+        t,
+        r#"poly1305(key: "too short", data: "a");"#,
+        ArgumentError::WrongArgument(_),
+    );
+    check_err_matches!(
+        t,
+        r#"poly1305_verify(key: "too short", data: "a", tag: "a");"#,
+        ArgumentError::WrongArgument(_),
+    );
+}
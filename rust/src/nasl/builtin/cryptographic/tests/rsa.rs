@@ -6,8 +6,12 @@
 mod tests {
 
     use crate::nasl::builtin::cryptographic::tests::helper::decode_hex;
+    use crate::nasl::builtin::cryptographic::CryptographicError;
+    use crate::nasl::interpreter::CodeInterpreter;
     use crate::nasl::test_prelude::*;
     use crate::nasl::test_utils::TestBuilder;
+    use crate::nasl::utils::error::ReturnBehavior;
+    use crate::storage::ContextKey;
 
     #[test]
     fn rsa_public_encrypt() {
@@ -30,4 +34,25 @@ mod tests {
         t.ok(r#"sign = rsa_sign(data:data,pem:priv_pem,passphrase:"");"#,decode_hex("802D2364DC1B9A99B62AFC6E5344B5682FD7742767C42EEB90E49C60281B0475984FFFA40C68CFB61D1EFAC490D4B3282F09BE84DA781D90BB356954264107D3").unwrap());
         t.run(r#"rsa_public_decrypt(sign:data,e:e,n:n);"#);
     }
+
+    #[tokio::test]
+    async fn rsa_private_decrypt_invalid_key() {
+        // The key material must not end up in the error.
+        let code = r#"rsa_private_decrypt(data: raw_string(0x01), n: raw_string(0x00), e: raw_string(0x03), d: raw_string(0x05));"#;
+        let factory = ContextFactory::default();
+        let context = factory.build(ContextKey::default());
+        let outcome = CodeInterpreter::new(code, Register::root_initial(&[]), &context)
+            .run()
+            .await
+            .unwrap();
+        assert_eq!(outcome.exit_value, NaslValue::Data(vec![]));
+        assert_eq!(outcome.warnings.len(), 1);
+        let warning = &outcome.warnings[0];
+        assert!(matches!(
+            warning.return_behavior(),
+            ReturnBehavior::ReturnValue(NaslValue::Data(data)) if data.is_empty()
+        ));
+        let error: &CryptographicError = warning.try_into().unwrap();
+        assert!(matches!(error, CryptographicError::Rsa(_)));
+    }
 }
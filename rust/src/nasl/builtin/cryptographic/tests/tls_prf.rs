@@ -0,0 +1,52 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+use super::helper::decode_hex;
+use crate::nasl::test_prelude::*;
+
+#[test]
+fn tls_prf_sha256() {
+    let mut t = TestBuilder::default();
+    t.run(r#"secret = hexstr_to_data("9bbe436ba940f017b17652849a71db35");"#);
+    t.run(r#"seed = hexstr_to_data("a0ba9f936cda311827a6f796ffd5198c");"#);
+    t.ok(
+        r#"tls_prf(secret: secret, label: "test label", seed: seed, len: 100, hash: "SHA256");"#,
+        decode_hex("e3f229ba727be17b8d122620557cd453c2aab21d07c3d495329b52d4e61edb5a6b301791e90d35c9c9a46b4e14baf9af0fa022f7077def17abfd3797c0564bab4fbc91666e9def9b97fce34f796789baa48082d122ee42c5a72e5a5110fff70187347b66").unwrap(),
+    );
+    t.ok(
+        r#"tls_prf(secret: secret, label: "test label", seed: seed, len: 16);"#,
+        decode_hex("e3f229ba727be17b8d122620557cd453").unwrap(),
+    );
+}
+
+#[test]
+fn tls_prf_sha384() {
+    let mut t = TestBuilder::default();
+    t.run(r#"secret = hexstr_to_data("b80b733d6ceefcdc71566ea48e5567df");"#);
+    t.run(r#"seed = hexstr_to_data("cd665cf6a8447dd6ff8b27555edb7465");"#);
+    t.ok(
+        r#"tls_prf(secret: secret, label: "test label", seed: seed, len: 148, hash: "SHA384");"#,
+        decode_hex("7b0c18e9ced410ed1804f2cfa34a336a1c14dffb4900bb5fd7942107e81c83cde9ca0faa60be9fe34f82b1233c9146a0e534cb400fed2700884f9dc236f80edd8bfa961144c9e8d792eca722a7b32fc3d416d473ebc2c5fd4abfdad05d9184259b5bf8cd4d90fa0d31e2dec479e4f1a26066f2eea9a69236a3e52655c9e9aee691c8f3a26854308d5eaa3be85e0990703d73e56f").unwrap(),
+    );
+}
+
+#[test]
+fn tls_prf_invalid_arguments() {
+    let mut t = TestBuilder::default();
+    check_err_matches!(
+        t,
+        r#"tls_prf(secret: "a", label: "b", seed: "c", len: 0);"#,
+        ArgumentError::WrongArgument(_),
+    );
+    check_err_matches!(
+        t,
+        r#"tls_prf(secret: "a", label: "b", seed: "c", len: 1099511627776);"#,
+        ArgumentError::WrongArgument(_),
+    );
+    check_err_matches!(
+        t,
+        r#"tls_prf(secret: "a", label: "b", seed: "c", len: 1, hash: "MD5");"#,
+        ArgumentError::WrongArgument(_),
+    );
+}
@@ -0,0 +1,85 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+use sha1::Sha1;
+use sha2::{Sha256, Sha384, Sha512};
+
+use crate::nasl::prelude::*;
+
+use super::hmac::hmac_bytes;
+
+/// Upper bound for `len`, far above the key material any TLS version derives.
+const MAX_LEN: i64 = 1 << 16;
+
+/// P_hash data expansion function as defined in RFC 5246, section 5.
+///
+/// `mac` calculates the HMAC of the given data with the secret.
+fn p_hash<F>(mac: F, seed: &[u8], len: usize) -> Result<Vec<u8>, FnError>
+where
+    F: Fn(&[u8]) -> Result<Vec<u8>, FnError>,
+{
+    let mut result = Vec::with_capacity(len);
+    // A(0) = seed, A(i) = HMAC_hash(secret, A(i-1))
+    let mut a = seed.to_vec();
+    while result.len() < len {
+        a = mac(&a)?;
+        let mut input = a.clone();
+        input.extend_from_slice(seed);
+        result.extend(mac(&input)?);
+    }
+    result.truncate(len);
+    Ok(result)
+}
+
+/// NASL function to calculate the TLS 1.2 pseudo random function (RFC 5246).
+///
+/// Named arguments:
+/// - secret: the secret as string or data.
+/// - label: the ASCII label, e.g. "master secret".
+/// - seed: the seed as string or data.
+/// - len: the number of bytes to generate. Must be greater than 0 and at most 65536.
+/// - hash: the hash algorithm used by P_hash, one of SHA1, SHA256, SHA384 or SHA512. Defaults to
+///   SHA256.
+///
+/// Returns `len` bytes of data.
+#[nasl_function(named(secret, label, seed, len, hash))]
+fn tls_prf(
+    secret: &NaslValue,
+    label: &NaslValue,
+    seed: &NaslValue,
+    len: i64,
+    hash: Option<&str>,
+) -> Result<Vec<u8>, FnError> {
+    if !(1..=MAX_LEN).contains(&len) {
+        return Err(ArgumentError::wrong_argument(
+            "len",
+            &format!("a number between 1 and {MAX_LEN}"),
+            &len.to_string(),
+        )
+        .into());
+    }
+    let len = len as usize;
+    let secret = Vec::<u8>::from(secret);
+    let mut label_seed = Vec::<u8>::from(label);
+    label_seed.extend(Vec::<u8>::from(seed));
+    let hash = hash.unwrap_or("SHA256");
+    match hash.to_uppercase().as_str() {
+        "SHA1" => p_hash(|d| hmac_bytes::<Sha1>(&secret, d), &label_seed, len),
+        "SHA256" => p_hash(|d| hmac_bytes::<Sha256>(&secret, d), &label_seed, len),
+        "SHA384" => p_hash(|d| hmac_bytes::<Sha384>(&secret, d), &label_seed, len),
+        "SHA512" => p_hash(|d| hmac_bytes::<Sha512>(&secret, d), &label_seed, len),
+        _ => Err(
+            ArgumentError::wrong_argument("hash", "SHA1, SHA256, SHA384 or SHA512", hash).into(),
+        ),
+    }
+}
+
+pub struct TlsPrf;
+
+function_set! {
+    TlsPrf,
+    (
+        tls_prf,
+    )
+}
@@ -0,0 +1,235 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+#![doc = include_str!("README.md")]
+
+#[cfg(test)]
+mod tests;
+
+use std::net::{IpAddr, SocketAddr};
+use std::str::FromStr;
+use std::time::Duration;
+
+use hickory_resolver::config::{NameServerConfigGroup, ResolverConfig, ResolverOpts};
+use hickory_resolver::error::{ResolveError, ResolveErrorKind};
+use hickory_resolver::proto::op::ResponseCode;
+use hickory_resolver::proto::rr::{RData, RecordType};
+use hickory_resolver::TokioAsyncResolver;
+use thiserror::Error;
+
+use crate::nasl::prelude::*;
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+const DNS_PORT: u16 = 53;
+
+#[derive(Debug, Error)]
+pub enum DnsError {
+    #[error("Domain {0} does not exist.")]
+    NxDomain(String),
+    #[error("Server failure while resolving {0}.")]
+    ServFail(String),
+    #[error("Timeout while resolving {0}.")]
+    Timeout(String),
+    #[error("No records found for {0}.")]
+    NoRecords(String),
+    #[error("Resolving {0} failed: {1}")]
+    Failed(String, String),
+}
+
+impl DnsError {
+    fn new(name: &str, e: ResolveError) -> Self {
+        let name = name.to_string();
+        match e.kind() {
+            ResolveErrorKind::NoRecordsFound { response_code, .. } => match *response_code {
+                ResponseCode::NoError => DnsError::NoRecords(name),
+                ResponseCode::NXDomain => DnsError::NxDomain(name),
+                ResponseCode::ServFail => DnsError::ServFail(name),
+                code => DnsError::Failed(name, code.to_string()),
+            },
+            ResolveErrorKind::Timeout => DnsError::Timeout(name),
+            _ => DnsError::Failed(name, e.to_string()),
+        }
+    }
+}
+
+/// The DNS server to query, given as an IP address with an optional port.
+struct Server(SocketAddr);
+
+impl FromNaslValue<'_> for Server {
+    fn from_nasl_value(value: &NaslValue) -> Result<Self, FnError> {
+        let s = String::from_nasl_value(value)?;
+        SocketAddr::from_str(&s)
+            .or_else(|_| IpAddr::from_str(&s).map(|ip| SocketAddr::new(ip, DNS_PORT)))
+            .map(Server)
+            .map_err(|_| ArgumentError::wrong_argument("server", "an IP address", &s).into())
+    }
+}
+
+/// A supported record type.
+struct Type(RecordType);
+
+impl FromNaslValue<'_> for Type {
+    fn from_nasl_value(value: &NaslValue) -> Result<Self, FnError> {
+        let s = String::from_nasl_value(value)?;
+        match s.to_ascii_uppercase().as_str() {
+            "A" => Ok(Type(RecordType::A)),
+            "AAAA" => Ok(Type(RecordType::AAAA)),
+            "MX" => Ok(Type(RecordType::MX)),
+            "TXT" => Ok(Type(RecordType::TXT)),
+            "CNAME" => Ok(Type(RecordType::CNAME)),
+            _ => Err(ArgumentError::wrong_argument("type", "A, AAAA, MX, TXT or CNAME", &s).into()),
+        }
+    }
+}
+
+/// Creates a resolver querying `server`, or the system's name servers if not given.
+pub fn resolver(
+    server: Option<SocketAddr>,
+    timeout: Option<i64>,
+) -> Result<TokioAsyncResolver, DnsError> {
+    let (config, mut opts) = match server {
+        Some(addr) => (
+            ResolverConfig::from_parts(
+                None,
+                vec![],
+                NameServerConfigGroup::from_ips_clear(&[addr.ip()], addr.port(), true),
+            ),
+            ResolverOpts::default(),
+        ),
+        None => hickory_resolver::system_conf::read_system_conf()
+            .map_err(|e| DnsError::Failed(String::new(), e.to_string()))?,
+    };
+    opts.timeout = timeout
+        .filter(|x| *x > 0)
+        .map(|x| Duration::from_secs(x as u64))
+        .unwrap_or(DEFAULT_TIMEOUT);
+    opts.attempts = 1;
+    opts.cache_size = 0;
+    Ok(TokioAsyncResolver::tokio(config, opts))
+}
+
+fn without_root(name: &impl ToString) -> String {
+    let name = name.to_string();
+    name.strip_suffix('.').map(str::to_string).unwrap_or(name)
+}
+
+fn to_nasl_value(data: &RData) -> Option<NaslValue> {
+    let s = match data {
+        RData::A(x) => x.to_string(),
+        RData::AAAA(x) => x.to_string(),
+        RData::MX(x) => format!("{} {}", x.preference(), without_root(x.exchange())),
+        RData::TXT(x) => x
+            .txt_data()
+            .iter()
+            .map(|x| String::from_utf8_lossy(x))
+            .collect(),
+        RData::CNAME(x) => without_root(&x.0),
+        _ => return None,
+    };
+    Some(NaslValue::String(s))
+}
+
+/// Looks up the records of type `ty` of `name`.
+///
+/// A name which exists but has no such records results in an empty list.
+pub async fn lookup(
+    resolver: &TokioAsyncResolver,
+    name: &str,
+    ty: RecordType,
+) -> Result<Vec<NaslValue>, DnsError> {
+    match resolver.lookup(name, ty).await {
+        Ok(lookup) => Ok(lookup
+            .record_iter()
+            .filter(|record| record.record_type() == ty)
+            .filter_map(|record| record.data().and_then(to_nasl_value))
+            .collect()),
+        Err(e) => match DnsError::new(name, e) {
+            DnsError::NoRecords(_) => Ok(vec![]),
+            e => Err(e),
+        },
+    }
+}
+
+/// Resolves records of `hostname`.
+///
+/// `type` is one of `A` (the default), `AAAA`, `MX`, `TXT` or `CNAME`. The records are returned
+/// as strings, MX records as the preference followed by the exchange.
+/// `server` is the IP address, optionally with port, of the DNS server to query instead of the
+/// system's name servers. `timeout` is given in seconds.
+///
+/// A non existing domain, a server failure and a timeout result in distinct errors, while an
+/// existing name without records of that type returns an empty array.
+#[nasl_function(maybe_named(hostname, r#type), named(server, timeout))]
+async fn resolve(
+    hostname: &str,
+    r#type: Option<Type>,
+    server: Option<Server>,
+    timeout: Option<i64>,
+) -> Result<NaslValue, FnError> {
+    let ty = r#type.map(|x| x.0).unwrap_or(RecordType::A);
+    let resolver = resolver(server.map(|x| x.0), timeout)?;
+    Ok(NaslValue::from(lookup(&resolver, hostname, ty).await?))
+}
+
+/// Returns the first IPv4, or if there is none, IPv6 address of `name`.
+///
+/// Returns NULL if the name does not resolve. Accepts the same `server` and `timeout` arguments
+/// as `resolve`.
+#[nasl_function(maybe_named(name), named(server, timeout))]
+async fn gethostbyname(
+    name: &str,
+    server: Option<Server>,
+    timeout: Option<i64>,
+) -> Result<NaslValue, FnError> {
+    let resolver = resolver(server.map(|x| x.0), timeout)?;
+    for ty in [RecordType::A, RecordType::AAAA] {
+        match lookup(&resolver, name, ty).await {
+            Ok(addrs) => {
+                if let Some(addr) = addrs.into_iter().next() {
+                    return Ok(addr);
+                }
+            }
+            Err(DnsError::NxDomain(_)) => break,
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(NaslValue::Null)
+}
+
+/// Returns the host name an IPv4 or IPv6 address points to.
+///
+/// Returns NULL if there is no such name. Accepts the same `server` and `timeout` arguments as
+/// `resolve`.
+#[nasl_function(maybe_named(ip), named(server, timeout))]
+async fn gethostbyaddr(
+    ip: &str,
+    server: Option<Server>,
+    timeout: Option<i64>,
+) -> Result<NaslValue, FnError> {
+    let addr = IpAddr::from_str(ip)
+        .map_err(|_| ArgumentError::wrong_argument("ip", "an IP address", ip))?;
+    let resolver = resolver(server.map(|x| x.0), timeout)?;
+    match resolver.reverse_lookup(addr).await {
+        Ok(names) => Ok(names
+            .iter()
+            .next()
+            .map(|x| NaslValue::String(without_root(&x.0)))
+            .unwrap_or(NaslValue::Null)),
+        Err(e) => match DnsError::new(ip, e) {
+            DnsError::NxDomain(_) | DnsError::NoRecords(_) => Ok(NaslValue::Null),
+            e => Err(e.into()),
+        },
+    }
+}
+
+pub struct Dns;
+
+function_set! {
+    Dns,
+    (
+        resolve,
+        gethostbyname,
+        gethostbyaddr,
+    )
+}
@@ -0,0 +1,197 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+use std::net::{Ipv4Addr, SocketAddr};
+use std::str::FromStr;
+
+use hickory_resolver::proto::op::{Message, MessageType, Query, ResponseCode};
+use hickory_resolver::proto::rr::rdata::{A, AAAA, MX, PTR, TXT};
+use hickory_resolver::proto::rr::{Name, RData, Record, RecordType};
+use tokio::net::UdpSocket;
+
+use super::DnsError;
+use crate::nasl::test_prelude::*;
+
+/// Answers the queries of the stub resolver.
+fn zone(query: &Query) -> (ResponseCode, Vec<RData>) {
+    let name = query.name().to_string();
+    match (name.as_str(), query.query_type()) {
+        ("example.test.", RecordType::A) => (
+            ResponseCode::NoError,
+            vec![
+                RData::A(A::new(192, 0, 2, 1)),
+                RData::A(A::new(192, 0, 2, 2)),
+            ],
+        ),
+        ("example.test.", RecordType::MX) => (
+            ResponseCode::NoError,
+            vec![RData::MX(MX::new(
+                10,
+                Name::from_str("mail.example.test.").unwrap(),
+            ))],
+        ),
+        ("example.test.", RecordType::TXT) => (
+            ResponseCode::NoError,
+            vec![RData::TXT(TXT::new(vec!["v=spf1 ".into(), "-all".into()]))],
+        ),
+        ("example.test.", _) => (ResponseCode::NoError, vec![]),
+        ("v6.test.", RecordType::AAAA) => (
+            ResponseCode::NoError,
+            vec![RData::AAAA(AAAA::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1))],
+        ),
+        ("v6.test.", _) => (ResponseCode::NoError, vec![]),
+        ("1.2.0.192.in-addr.arpa.", RecordType::PTR) => (
+            ResponseCode::NoError,
+            vec![RData::PTR(PTR(Name::from_str("example.test.").unwrap()))],
+        ),
+        (
+            "1.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.8.b.d.0.1.0.0.2.ip6.arpa.",
+            RecordType::PTR,
+        ) => (
+            ResponseCode::NoError,
+            vec![RData::PTR(PTR(Name::from_str("v6.test.").unwrap()))],
+        ),
+        ("broken.test.", _) => (ResponseCode::ServFail, vec![]),
+        _ => (ResponseCode::NXDomain, vec![]),
+    }
+}
+
+/// Starts a DNS server on localhost answering according to `zone`.
+async fn stub_resolver() -> SocketAddr {
+    let socket = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).await.unwrap();
+    let addr = socket.local_addr().unwrap();
+    tokio::spawn(async move {
+        let mut buf = [0; 512];
+        while let Ok((n, peer)) = socket.recv_from(&mut buf).await {
+            let request = Message::from_vec(&buf[..n]).unwrap();
+            let query = request.queries()[0].clone();
+            let (code, data) = zone(&query);
+            let mut response = Message::new();
+            response
+                .set_id(request.id())
+                .set_message_type(MessageType::Response)
+                .set_op_code(request.op_code())
+                .set_recursion_desired(request.recursion_desired())
+                .set_recursion_available(true)
+                .set_response_code(code)
+                .add_query(query.clone());
+            for data in data {
+                response.add_answer(Record::from_rdata(query.name().clone(), 60, data));
+            }
+            let _ = socket.send_to(&response.to_vec().unwrap(), peer).await;
+        }
+    });
+    addr
+}
+
+#[tokio::test]
+async fn resolve_a() {
+    let server = stub_resolver().await;
+    let mut t = TestBuilder::default();
+    t.ok(
+        format!(r#"resolve("example.test", server: "{server}", timeout: 1);"#),
+        NaslValue::from(vec![
+            NaslValue::String("192.0.2.1".into()),
+            NaslValue::String("192.0.2.2".into()),
+        ]),
+    );
+    t.ok(
+        format!(r#"resolve("example.test", type: "aaaa", server: "{server}", timeout: 1);"#),
+        NaslValue::Array(vec![].into()),
+    );
+    t.async_verify().await;
+}
+
+#[tokio::test]
+async fn resolve_mx_and_txt() {
+    let server = stub_resolver().await;
+    let mut t = TestBuilder::default();
+    t.ok(
+        format!(r#"resolve("example.test", "MX", server: "{server}", timeout: 1);"#),
+        NaslValue::from(vec![NaslValue::String("10 mail.example.test".into())]),
+    );
+    t.ok(
+        format!(r#"resolve(hostname: "example.test", type: "TXT", server: "{server}");"#),
+        NaslValue::from(vec![NaslValue::String("v=spf1 -all".into())]),
+    );
+    t.async_verify().await;
+}
+
+#[tokio::test]
+async fn resolve_errors() {
+    let server = stub_resolver().await;
+    // Nobody answers on this socket.
+    let silent = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).await.unwrap();
+    let silent = silent.local_addr().unwrap();
+    let mut t = TestBuilder::default();
+    check_err_matches!(
+        t,
+        format!(r#"resolve("missing.test", server: "{server}", timeout: 1);"#),
+        DnsError::NxDomain(_)
+    );
+    check_err_matches!(
+        t,
+        format!(r#"resolve("broken.test", server: "{server}", timeout: 1);"#),
+        DnsError::ServFail(_)
+    );
+    check_err_matches!(
+        t,
+        format!(r#"resolve("example.test", server: "{silent}", timeout: 1);"#),
+        DnsError::Timeout(_)
+    );
+    check_err_matches!(
+        t,
+        format!(r#"resolve("example.test", type: "SRV", server: "{server}");"#),
+        ArgumentError::WrongArgument(_)
+    );
+    check_err_matches!(
+        t,
+        r#"resolve("example.test", server: "localhost");"#,
+        ArgumentError::WrongArgument(_)
+    );
+    t.async_verify().await;
+}
+
+#[tokio::test]
+async fn gethostbyname() {
+    let server = stub_resolver().await;
+    let mut t = TestBuilder::default();
+    t.ok(
+        format!(r#"gethostbyname("example.test", server: "{server}", timeout: 1);"#),
+        "192.0.2.1",
+    );
+    t.ok(
+        format!(r#"gethostbyname("v6.test", server: "{server}", timeout: 1);"#),
+        "2001:db8::1",
+    );
+    t.ok(
+        format!(r#"gethostbyname("missing.test", server: "{server}", timeout: 1);"#),
+        NaslValue::Null,
+    );
+    t.async_verify().await;
+}
+
+#[tokio::test]
+async fn gethostbyaddr() {
+    let server = stub_resolver().await;
+    let mut t = TestBuilder::default();
+    t.ok(
+        format!(r#"gethostbyaddr("192.0.2.1", server: "{server}", timeout: 1);"#),
+        "example.test",
+    );
+    t.ok(
+        format!(r#"gethostbyaddr("2001:db8::1", server: "{server}", timeout: 1);"#),
+        "v6.test",
+    );
+    t.ok(
+        format!(r#"gethostbyaddr("192.0.2.99", server: "{server}", timeout: 1);"#),
+        NaslValue::Null,
+    );
+    check_err_matches!(
+        t,
+        "gethostbyaddr(\"example.test\");",
+        ArgumentError::WrongArgument(_)
+    );
+    t.async_verify().await;
+}
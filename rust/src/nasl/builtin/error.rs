@@ -9,16 +9,22 @@ use crate::nasl::utils::error::FnErrorKind;
 
 use super::cert::CertError;
 use super::cryptographic::CryptographicError;
+use super::dns::DnsError;
 use super::host::HostError;
 use super::http::HttpError;
 use super::isotime::IsotimeError;
+use super::krb5::Krb5Error;
 use super::regex::RegexError;
+use super::snmp::SnmpError;
+#[cfg(feature = "nasl-builtin-ssh")]
+use super::ssh::SshError;
 use super::sys::SysError;
 use super::KBError;
-use super::{misc::MiscError, network::socket::SocketError, ssh::SshError, string::StringError};
+use super::{misc::MiscError, network::socket::SocketError, string::StringError};
 
 #[derive(Debug, Error)]
 pub enum BuiltinError {
+    #[cfg(feature = "nasl-builtin-ssh")]
     #[error("{0}")]
     Ssh(SshError),
     #[error("{0}")]
@@ -40,6 +46,12 @@ pub enum BuiltinError {
     #[error("{0}")]
     Host(HostError),
     #[error("{0}")]
+    Dns(DnsError),
+    #[error("{0}")]
+    Snmp(SnmpError),
+    #[error("{0}")]
+    Krb5(Krb5Error),
+    #[error("{0}")]
     Cert(CertError),
     #[error("{0}")]
     Sys(SysError),
@@ -81,12 +93,16 @@ builtin_error_variant!(StringError, String);
 builtin_error_variant!(MiscError, Misc);
 builtin_error_variant!(SocketError, Socket);
 builtin_error_variant!(CryptographicError, Cryptographic);
+#[cfg(feature = "nasl-builtin-ssh")]
 builtin_error_variant!(SshError, Ssh);
 builtin_error_variant!(HttpError, Http);
 builtin_error_variant!(IsotimeError, Isotime);
 builtin_error_variant!(RegexError, Regex);
 builtin_error_variant!(KBError, KB);
 builtin_error_variant!(HostError, Host);
+builtin_error_variant!(DnsError, Dns);
+builtin_error_variant!(SnmpError, Snmp);
+builtin_error_variant!(Krb5Error, Krb5);
 builtin_error_variant!(CertError, Cert);
 builtin_error_variant!(SysError, Sys);
 
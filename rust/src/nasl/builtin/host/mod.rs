@@ -47,9 +47,9 @@ fn get_host_names(context: &Context) -> Result<NaslValue, FnError> {
             .into_iter()
             .map(|(h, _s)| NaslValue::String(h))
             .collect::<Vec<_>>();
-        return Ok(NaslValue::Array(hns));
+        return Ok(NaslValue::from(hns));
     };
-    Ok(NaslValue::Array(vec![NaslValue::String(
+    Ok(NaslValue::from(vec![NaslValue::String(
         context.target().to_string(),
     )]))
 }
@@ -151,8 +151,8 @@ fn resolve_hostname_to_multiple_ips(hostname: Hostname) -> Result<NaslValue, FnE
     let ips = resolve(hostname.0)?
         .into_iter()
         .map(|x| NaslValue::String(x.to_string()))
-        .collect();
-    Ok(NaslValue::Array(ips))
+        .collect::<Vec<_>>();
+    Ok(NaslValue::from(ips))
 }
 
 /// Check if the currently scanned target is an IPv6 address.
@@ -14,6 +14,10 @@ pub enum HttpError {
     H2(String),
     #[error("Handle ID {0} not found.")]
     HandleIdNotFound(i32),
+    #[error("Malformed HTTP response: {0}")]
+    MalformedResponse(String),
+    #[error("Timeout while waiting for the HTTP response.")]
+    Timeout,
 }
 
 impl From<io::Error> for HttpError {
@@ -125,6 +125,22 @@ impl ServerCertVerifier for NoVerifier {
     }
 }
 
+/// Returns the status line and the headers of the response followed by its body.
+fn format_response(head: &Parts, body: &str) -> String {
+    let mut header_str = String::new();
+    header_str.push_str(format!("{:?} ", head.version).as_str());
+    header_str.push_str(format!("{:?}\n", head.status).as_str());
+    for (k, v) in head.headers.iter() {
+        header_str.push_str(&format!(
+            "{}: {}\n",
+            k.as_str(),
+            String::from_utf8_lossy(v.as_bytes())
+        ))
+    }
+    header_str.push_str(body);
+    header_str
+}
+
 impl NaslHttp {
     async fn request(
         &self,
@@ -257,23 +273,22 @@ impl NaslHttp {
 
         uri = format!("{}{}", uri, item);
 
-        let (head, body) = self
-            .request(&ip_str, port, uri, data, method, handle)
+        let description = format!(
+            "http2 {method} {uri} {:?} {}",
+            handle.header_items,
+            hex::encode(&data)
+        );
+        let (code, response): (u16, String) = ctx
+            .network_mode()
+            .interact(description, async {
+                let (head, body) = self
+                    .request(&ip_str, port, uri, data, method, handle)
+                    .await?;
+                Ok::<_, HttpError>((head.status.as_u16(), format_response(&head, &body)))
+            })
             .await?;
-        handle.http_code = head.status.as_u16();
-        let mut header_str = String::new();
-        header_str.push_str(format!("{:?} ", head.version).as_str());
-        header_str.push_str(format!("{:?}\n", head.status).as_str());
-        for (k, v) in head.headers.iter() {
-            header_str.push_str(&format!(
-                "{}: {}\n",
-                k.as_str(),
-                String::from_utf8_lossy(v.as_bytes())
-            ))
-        }
-        //let _ = head.headers.iter().map(|(k,v)| header_str.push_str(&format!("{}: {}\n", k.as_str(), String::from_utf8_lossy(v.as_bytes()))));
-        header_str.push_str(&body);
-        Ok(NaslValue::String(header_str))
+        handle.http_code = code;
+        Ok(NaslValue::String(response))
     }
 
     /// Wrapper function for GET request. See http2_req
@@ -100,8 +100,10 @@ fn isotime_is_valid(time: &str) -> bool {
 }
 
 #[nasl_function]
-fn isotime_now() -> String {
-    chrono::Utc::now().format("%Y%m%dT%H%M%S").to_string()
+fn isotime_now(context: &Context) -> String {
+    chrono::DateTime::<chrono::Utc>::from(context.clock().now())
+        .format("%Y%m%dT%H%M%S")
+        .to_string()
 }
 
 #[nasl_function]
@@ -96,9 +96,9 @@ fn get_kb_list(c: &Context, key: NaslValue) -> Result<NaslValue, FnError> {
                     Field::NVT(_) | Field::NotusAdvisory(_) | Field::Result(_) => None,
                     Field::KB(kb) => Some(kb.value.into()),
                 })
-                .collect::<Vec<_>>()
+                .collect::<Vec<NaslValue>>()
         })
-        .map(NaslValue::Array)
+        .map(NaslValue::from)
         .map_err(|e| e.into())
 }
 
@@ -0,0 +1,161 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+//! Minimal DER encoding of the Kerberos AS-REQ and decoding of the AS-REP and KRB-ERROR
+//! messages as defined in RFC 4120, section 5.
+
+use crate::nasl::utils::ber::expect;
+pub use crate::nasl::utils::ber::{
+    decode, decode_integer, encode, encode_integer, INTEGER, OCTET_STRING, SEQUENCE,
+};
+
+pub const BIT_STRING: u8 = 0x03;
+pub const GENERAL_STRING: u8 = 0x1b;
+pub const GENERALIZED_TIME: u8 = 0x18;
+
+pub const AS_REQ: u8 = 0x6a;
+pub const AS_REP: u8 = 0x6b;
+pub const KRB_ERROR: u8 = 0x7e;
+
+/// KDC_ERR_PREAUTH_REQUIRED
+pub const PREAUTH_REQUIRED: i64 = 25;
+
+const NT_PRINCIPAL: i64 = 1;
+const NT_SRV_INST: i64 = 2;
+const PA_PAC_REQUEST: i64 = 128;
+
+/// forwardable, renewable and proxiable, preceded by the number of unused bits
+const KDC_OPTIONS: [u8; 5] = [0x00, 0x50, 0x80, 0x00, 0x00];
+
+/// The end time requested for the ticket, which is the usual "no end" of MIT and Windows.
+const TILL: &[u8] = b"20370913024805Z";
+
+/// Returns the tag of the context specific element `[n]`.
+const fn context(n: u8) -> u8 {
+    0xa0 | n
+}
+
+/// An AS-REQ for a ticket granting ticket of `user` without pre-authentication data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AsReq {
+    pub realm: String,
+    pub user: String,
+    pub nonce: u32,
+    pub etypes: Vec<i64>,
+}
+
+impl AsReq {
+    pub fn encode(&self) -> Vec<u8> {
+        let pac_request = encode(SEQUENCE, &explicit(0, &encode(0x01, &[0xff])));
+        let padata = encode(
+            SEQUENCE,
+            &encode(
+                SEQUENCE,
+                &[
+                    explicit(1, &encode_integer(PA_PAC_REQUEST)),
+                    explicit(2, &encode(OCTET_STRING, &pac_request)),
+                ]
+                .concat(),
+            ),
+        );
+        let etypes: Vec<u8> = self
+            .etypes
+            .iter()
+            .flat_map(|x| encode_integer(*x))
+            .collect();
+        let body = encode(
+            SEQUENCE,
+            &[
+                explicit(0, &encode(BIT_STRING, &KDC_OPTIONS)),
+                explicit(1, &principal(NT_PRINCIPAL, &[&self.user])),
+                explicit(2, &encode(GENERAL_STRING, self.realm.as_bytes())),
+                explicit(3, &principal(NT_SRV_INST, &["krbtgt", &self.realm])),
+                explicit(5, &encode(GENERALIZED_TIME, TILL)),
+                explicit(7, &encode_integer(self.nonce as i64)),
+                explicit(8, &encode(SEQUENCE, &etypes)),
+            ]
+            .concat(),
+        );
+        encode(
+            AS_REQ,
+            &encode(
+                SEQUENCE,
+                &[
+                    explicit(1, &encode_integer(5)),
+                    explicit(2, &encode_integer(10)),
+                    explicit(3, &padata),
+                    explicit(4, &body),
+                ]
+                .concat(),
+            ),
+        )
+    }
+}
+
+/// The answer of the KDC to an [`AsReq`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reply {
+    /// The KDC issued a ticket. Contains the etype and the cipher of the encrypted part, which is
+    /// encrypted with the key of the user.
+    AsRep { etype: i64, cipher: Vec<u8> },
+    /// The KDC answered with the error code.
+    Error(i64),
+}
+
+impl Reply {
+    /// Returns None if `data` is neither a valid AS-REP nor KRB-ERROR.
+    pub fn decode(data: &[u8]) -> Option<Self> {
+        match decode(data)? {
+            (AS_REP, content, _) => {
+                let (fields, _) = expect(content, SEQUENCE)?;
+                let enc_part = field(fields, 6)?;
+                let (enc_part, _) = expect(enc_part, SEQUENCE)?;
+                let (etype, _) = expect(field(enc_part, 0)?, INTEGER)?;
+                let (cipher, _) = expect(field(enc_part, 2)?, OCTET_STRING)?;
+                Some(Reply::AsRep {
+                    etype: decode_integer(etype)?,
+                    cipher: cipher.to_vec(),
+                })
+            }
+            (KRB_ERROR, content, _) => {
+                let (fields, _) = expect(content, SEQUENCE)?;
+                let (code, _) = expect(field(fields, 6)?, INTEGER)?;
+                Some(Reply::Error(decode_integer(code)?))
+            }
+            _ => None,
+        }
+    }
+}
+
+fn principal(name_type: i64, names: &[&str]) -> Vec<u8> {
+    let names: Vec<u8> = names
+        .iter()
+        .flat_map(|x| encode(GENERAL_STRING, x.as_bytes()))
+        .collect();
+    encode(
+        SEQUENCE,
+        &[
+            explicit(0, &encode_integer(name_type)),
+            explicit(1, &encode(SEQUENCE, &names)),
+        ]
+        .concat(),
+    )
+}
+
+/// Encodes `content` as the explicitly tagged element `[n]`.
+fn explicit(n: u8, content: &[u8]) -> Vec<u8> {
+    encode(context(n), content)
+}
+
+/// Returns the content of the explicitly tagged element `[n]` within the elements of a sequence.
+fn field(mut fields: &[u8], n: u8) -> Option<&[u8]> {
+    while !fields.is_empty() {
+        let (tag, content, rest) = decode(fields)?;
+        if tag == context(n) {
+            return Some(content);
+        }
+        fields = rest;
+    }
+    None
+}
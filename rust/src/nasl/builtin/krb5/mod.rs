@@ -0,0 +1,272 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+#![doc = include_str!("README.md")]
+
+mod message;
+#[cfg(test)]
+mod tests;
+
+use std::collections::BTreeMap;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::time::Duration;
+
+use thiserror::Error;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpStream, UdpSocket};
+
+use crate::nasl::prelude::*;
+use crate::nasl::utils::random;
+
+use message::{AsReq, Reply};
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+const KERBEROS_PORT: u16 = 88;
+/// Replies to an AS-REQ are far smaller, so a larger length prefix is not allocated.
+const MAX_TCP_RESPONSE_LEN: usize = 65535;
+
+const AES128_CTS_HMAC_SHA1_96: i64 = 17;
+const AES256_CTS_HMAC_SHA1_96: i64 = 18;
+const RC4_HMAC: i64 = 23;
+
+/// The etypes requested if none are given, in the order of preference.
+const DEFAULT_ETYPES: [i64; 3] = [AES256_CTS_HMAC_SHA1_96, AES128_CTS_HMAC_SHA1_96, RC4_HMAC];
+
+/// Length of the HMAC-SHA1-96 checksum of the AES etypes, which trails the cipher.
+const AES_CHECKSUM_LEN: usize = 12;
+/// Length of the HMAC-MD5 checksum of RC4-HMAC, which precedes the cipher.
+const RC4_CHECKSUM_LEN: usize = 16;
+
+#[derive(Debug, Error)]
+pub enum Krb5Error {
+    #[error("No Kerberos response from {0} before the timeout.")]
+    Timeout(String),
+    #[error("Unable to send the AS-REQ to {0}: {1}")]
+    Io(String, String),
+    #[error("Invalid Kerberos response from {0}.")]
+    InvalidResponse(String),
+    #[error("The KDC {0} answered with the error {1}.")]
+    Kdc(String, String),
+}
+
+/// The transport of the AS-REQ, given as `tcp` or `udp`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Transport {
+    Tcp,
+    Udp,
+}
+
+impl FromNaslValue<'_> for Transport {
+    fn from_nasl_value(value: &NaslValue) -> Result<Self, FnError> {
+        let transport = String::from_nasl_value(value)?;
+        match transport.to_ascii_lowercase().as_str() {
+            "tcp" => Ok(Transport::Tcp),
+            "udp" => Ok(Transport::Udp),
+            _ => Err(ArgumentError::wrong_argument("transport", "tcp or udp", &transport).into()),
+        }
+    }
+}
+
+/// The requested etypes, given as number, name or array of those.
+struct Etypes(Vec<i64>);
+
+impl FromNaslValue<'_> for Etypes {
+    fn from_nasl_value(value: &NaslValue) -> Result<Self, FnError> {
+        let etype = |value: &NaslValue| -> Result<i64, FnError> {
+            match value {
+                NaslValue::Number(x) => Ok(*x),
+                _ => {
+                    let name = String::from_nasl_value(value)?;
+                    match name.to_ascii_lowercase().as_str() {
+                        "aes128-cts-hmac-sha1-96" | "aes128" => Ok(AES128_CTS_HMAC_SHA1_96),
+                        "aes256-cts-hmac-sha1-96" | "aes256" => Ok(AES256_CTS_HMAC_SHA1_96),
+                        "rc4-hmac" | "arcfour-hmac" | "rc4" => Ok(RC4_HMAC),
+                        _ => Err(ArgumentError::wrong_argument(
+                            "etype",
+                            "an etype number or name",
+                            &name,
+                        )
+                        .into()),
+                    }
+                }
+            }
+        };
+        let etypes = match value {
+            NaslValue::Array(x) => x.iter().map(etype).collect::<Result<Vec<_>, FnError>>()?,
+            x => vec![etype(x)?],
+        };
+        if etypes.is_empty() {
+            return Err(ArgumentError::wrong_argument("etype", "at least one etype", "[]").into());
+        }
+        Ok(Etypes(etypes))
+    }
+}
+
+fn error_name(code: i64) -> String {
+    match code {
+        6 => "KDC_ERR_C_PRINCIPAL_UNKNOWN".into(),
+        12 => "KDC_ERR_POLICY".into(),
+        14 => "KDC_ERR_ETYPE_NOSUPP".into(),
+        18 => "KDC_ERR_CLIENT_REVOKED".into(),
+        52 => "KRB_ERR_RESPONSE_TOO_BIG".into(),
+        68 => "KDC_ERR_WRONG_REALM".into(),
+        x => format!("error code {x}"),
+    }
+}
+
+/// Returns the encrypted part in the format of hashcat, which separates the checksum from the
+/// rest of the cipher. None for etypes other than AES and RC4-HMAC.
+fn roasting_hash(user: &str, realm: &str, etype: i64, cipher: &[u8]) -> Option<String> {
+    match etype {
+        RC4_HMAC if cipher.len() > RC4_CHECKSUM_LEN => {
+            let (checksum, edata) = cipher.split_at(RC4_CHECKSUM_LEN);
+            Some(format!(
+                "$krb5asrep${etype}${user}@{realm}:{}${}",
+                hex::encode(checksum),
+                hex::encode(edata)
+            ))
+        }
+        AES128_CTS_HMAC_SHA1_96 | AES256_CTS_HMAC_SHA1_96 if cipher.len() > AES_CHECKSUM_LEN => {
+            let (edata, checksum) = cipher.split_at(cipher.len() - AES_CHECKSUM_LEN);
+            Some(format!(
+                "$krb5asrep${etype}${user}${realm}${}${}",
+                hex::encode(checksum),
+                hex::encode(edata)
+            ))
+        }
+        _ => None,
+    }
+}
+
+async fn exchange_udp(addr: SocketAddr, request: &[u8]) -> std::io::Result<Vec<u8>> {
+    let local: SocketAddr = match addr.ip() {
+        IpAddr::V4(_) => (Ipv4Addr::UNSPECIFIED, 0).into(),
+        IpAddr::V6(_) => (Ipv6Addr::UNSPECIFIED, 0).into(),
+    };
+    let socket = UdpSocket::bind(local).await?;
+    socket.send_to(request, addr).await?;
+    let mut buf = vec![0; u16::MAX as usize];
+    let (n, _) = socket.recv_from(&mut buf).await?;
+    buf.truncate(n);
+    Ok(buf)
+}
+
+/// Sends the request with the four byte length prefix of RFC 4120, section 7.2.2.
+async fn exchange_tcp(addr: SocketAddr, request: &[u8]) -> std::io::Result<Vec<u8>> {
+    let mut stream = TcpStream::connect(addr).await?;
+    stream.write_u32(request.len() as u32).await?;
+    stream.write_all(request).await?;
+    let len = stream.read_u32().await? as usize;
+    if len > MAX_TCP_RESPONSE_LEN {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("invalid response length {len}"),
+        ));
+    }
+    let mut response = vec![0; len];
+    stream.read_exact(&mut response).await?;
+    Ok(response)
+}
+
+async fn exchange(
+    addr: SocketAddr,
+    transport: Transport,
+    request: &AsReq,
+    timeout: Duration,
+) -> Result<Vec<u8>, Krb5Error> {
+    let request = request.encode();
+    let exchange = async {
+        match transport {
+            Transport::Tcp => exchange_tcp(addr, &request).await,
+            Transport::Udp => exchange_udp(addr, &request).await,
+        }
+    };
+    tokio::time::timeout(timeout, exchange)
+        .await
+        .map_err(|_| Krb5Error::Timeout(addr.to_string()))?
+        .map_err(|e| Krb5Error::Io(addr.to_string(), e.to_string()))
+}
+
+/// Requests a ticket granting ticket for `user` of `realm` without pre-authentication, to check
+/// whether the account is vulnerable to AS-REP roasting.
+///
+/// `kdc` defaults to the target, `port` to 88 and `transport` to `tcp`. The realm is converted to
+/// upper case. `etype` is an etype number, a name like `rc4-hmac` or an array of those and
+/// defaults to the AES etypes and RC4-HMAC. `timeout` is given in seconds and defaults to 5.
+///
+/// Returns an array with `preauth_required`. If pre-authentication is not required, the array
+/// additionally contains the `etype` and the `cipher` of the encrypted part of the AS-REP, along
+/// with the encrypted part as `hash` in the format of hashcat for the AES etypes and RC4-HMAC.
+/// Any other error of the KDC, e.g. an unknown principal, as well as a missing response result in
+/// distinct errors.
+#[nasl_function(named(realm, user, kdc, port, etype, transport, timeout))]
+async fn krb5_asreq(
+    ctx: &Context<'_>,
+    realm: &str,
+    user: &str,
+    kdc: Option<&str>,
+    port: Option<u16>,
+    etype: Option<Etypes>,
+    transport: Option<Transport>,
+    timeout: Option<i64>,
+) -> Result<NaslValue, FnError> {
+    let port = port.unwrap_or(KERBEROS_PORT);
+    let addr = match kdc {
+        Some(kdc) => tokio::net::lookup_host((kdc, port))
+            .await
+            .ok()
+            .and_then(|mut x| x.next())
+            .ok_or_else(|| ArgumentError::wrong_argument("kdc", "a resolvable host", kdc))?,
+        None => SocketAddr::new(ctx.target_ip(), port),
+    };
+    let transport = transport.unwrap_or(Transport::Tcp);
+    let timeout = timeout
+        .filter(|x| *x > 0)
+        .map(|x| Duration::from_secs(x as u64))
+        .unwrap_or(DEFAULT_TIMEOUT);
+    let realm = realm.to_uppercase();
+    let request = AsReq {
+        realm: realm.clone(),
+        user: user.to_string(),
+        nonce: u32::from_be_bytes(random::random_bytes()?) & i32::MAX as u32,
+        etypes: etype.map(|x| x.0).unwrap_or(DEFAULT_ETYPES.to_vec()),
+    };
+    // The nonce is not part of the description, so the request can be replayed.
+    let description = format!(
+        "krb5_asreq {addr} {transport:?} {user}@{realm} {:?}",
+        request.etypes
+    );
+    let response = ctx
+        .network_mode()
+        .interact(description, exchange(addr, transport, &request, timeout))
+        .await?;
+    match Reply::decode(&response) {
+        Some(Reply::Error(message::PREAUTH_REQUIRED)) => Ok(NaslValue::Dict(BTreeMap::from([(
+            "preauth_required".to_string(),
+            NaslValue::Boolean(true),
+        )]))),
+        Some(Reply::Error(code)) => Err(Krb5Error::Kdc(addr.to_string(), error_name(code)).into()),
+        Some(Reply::AsRep { etype, cipher }) => {
+            let mut result = BTreeMap::from([
+                ("preauth_required".to_string(), NaslValue::Boolean(false)),
+                ("etype".to_string(), NaslValue::Number(etype)),
+            ]);
+            if let Some(hash) = roasting_hash(user, &realm, etype, &cipher) {
+                result.insert("hash".to_string(), NaslValue::String(hash));
+            }
+            result.insert("cipher".to_string(), NaslValue::Data(cipher));
+            Ok(NaslValue::Dict(result))
+        }
+        None => Err(Krb5Error::InvalidResponse(addr.to_string()).into()),
+    }
+}
+
+pub struct Krb5;
+
+function_set! {
+    Krb5,
+    (
+        krb5_asreq,
+    )
+}
@@ -0,0 +1,212 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+use std::collections::BTreeMap;
+use std::net::{Ipv4Addr, SocketAddr};
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, UdpSocket};
+
+use super::message::{self, AsReq, Reply};
+use super::Krb5Error;
+use crate::nasl::test_prelude::*;
+use crate::nasl::utils::recording::{Interaction, NetworkMode, Replayer};
+
+/// KRB-ERROR KDC_ERR_PREAUTH_REQUIRED for a user of EXAMPLE.COM, with the supported etypes in the
+/// e-data.
+const PREAUTH_REQUIRED: &str = "7e819b308198a003020105a10302011ea411180f32303235313031343139333833365aa50502030a1b2ca603020119a90d1b0b4558414d504c452e434f4daa20301ea003020102a11730151b066b72627467741b0b4558414d504c452e434f4dac3c043a3038302ba103020113a224042230203017a003020112a1101b0e4558414d504c452e434f4d626f623005a0030201173009a103020102a2020400";
+
+/// KRB-ERROR KDC_ERR_C_PRINCIPAL_UNKNOWN.
+const PRINCIPAL_UNKNOWN: &str = "7e5c305aa003020105a10302011ea411180f32303235313031343139333833375aa5050203019999a603020106a90d1b0b4558414d504c452e434f4daa20301ea003020102a11730151b066b72627467741b0b4558414d504c452e434f4d";
+
+/// AS-REP for alice@EXAMPLE.COM with the encrypted part in RC4-HMAC.
+const AS_REP: &str = "6b82010430820100a003020105a10302010ba30d1b0b4558414d504c452e434f4da4123010a003020101a10930071b05616c696365a57c617a3078a003020105a10d1b0b4558414d504c452e434f4da220301ea003020102a11730151b066b72627467741b0b4558414d504c452e434f4da340303ea003020112a103020102a2320430eac9b917502734043db669033ff5f67ad14c9176cbc96dd17cbd13c9eb51e589d8744828aaf731c8158e1e317e0868f7a6533051a003020117a24a044816dfcba6ac315efc5952eb72fc8f3ecfd1f60bea35f1fbf2ae2b06ff914a028919135fc131d0db9f71c99c46e43cbe852e644df72c761a8fa0a15b4e372175d72d711642b726b044";
+
+/// The cipher of the encrypted part of [`AS_REP`].
+const CIPHER: &str = "16dfcba6ac315efc5952eb72fc8f3ecfd1f60bea35f1fbf2ae2b06ff914a028919135fc131d0db9f71c99c46e43cbe852e644df72c761a8fa0a15b4e372175d72d711642b726b044";
+
+fn interaction(request: &str, response: &str) -> Interaction {
+    Interaction {
+        request: request.to_string(),
+        response: Ok(serde_json::to_value(hex::decode(response).unwrap()).unwrap()),
+    }
+}
+
+fn as_rep_result() -> NaslValue {
+    NaslValue::Dict(BTreeMap::from([
+        ("preauth_required".to_string(), NaslValue::Boolean(false)),
+        ("etype".to_string(), NaslValue::Number(23)),
+        (
+            "cipher".to_string(),
+            NaslValue::Data(hex::decode(CIPHER).unwrap()),
+        ),
+        (
+            "hash".to_string(),
+            NaslValue::String(format!(
+                "$krb5asrep$23$alice@EXAMPLE.COM:{}${}",
+                &CIPHER[..32],
+                &CIPHER[32..]
+            )),
+        ),
+    ]))
+}
+
+#[test]
+fn krb5_asreq_replay() {
+    let replayer = Replayer::new([
+        interaction(
+            "krb5_asreq 127.0.0.1:88 Tcp alice@EXAMPLE.COM [18, 17, 23]",
+            AS_REP,
+        ),
+        interaction(
+            "krb5_asreq 127.0.0.1:88 Udp bob@EXAMPLE.COM [23]",
+            PREAUTH_REQUIRED,
+        ),
+        interaction(
+            "krb5_asreq 127.0.0.1:88 Tcp carol@EXAMPLE.COM [18, 17, 23]",
+            PRINCIPAL_UNKNOWN,
+        ),
+        interaction(
+            "krb5_asreq 127.0.0.1:88 Tcp dave@EXAMPLE.COM [18, 17, 23]",
+            "3000",
+        ),
+    ]);
+    let mut t = TestBuilder::default().with_network_mode(NetworkMode::Replay(replayer));
+    t.ok(
+        r#"krb5_asreq(realm: "example.com", user: "alice", kdc: "127.0.0.1");"#,
+        as_rep_result(),
+    );
+    t.ok(
+        r#"krb5_asreq(realm: "EXAMPLE.COM", user: "bob", kdc: "127.0.0.1", etype: "rc4-hmac", transport: "udp");"#,
+        NaslValue::Dict(BTreeMap::from([(
+            "preauth_required".to_string(),
+            NaslValue::Boolean(true),
+        )])),
+    );
+    check_err_matches!(
+        t,
+        r#"krb5_asreq(realm: "EXAMPLE.COM", user: "carol", kdc: "127.0.0.1");"#,
+        Krb5Error::Kdc(..)
+    );
+    check_err_matches!(
+        t,
+        r#"krb5_asreq(realm: "EXAMPLE.COM", user: "dave", kdc: "127.0.0.1");"#,
+        Krb5Error::InvalidResponse(_)
+    );
+}
+
+/// Starts a KDC on localhost answering every AS-REQ over TCP with [`AS_REP`].
+async fn stub_kdc() -> SocketAddr {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            let len = stream.read_u32().await.unwrap();
+            let mut request = vec![0; len as usize];
+            stream.read_exact(&mut request).await.unwrap();
+            assert_eq!(request[0], message::AS_REQ);
+            let response = hex::decode(AS_REP).unwrap();
+            stream.write_u32(response.len() as u32).await.unwrap();
+            stream.write_all(&response).await.unwrap();
+        }
+    });
+    addr
+}
+
+#[tokio::test]
+async fn krb5_asreq_tcp() {
+    let kdc = stub_kdc().await;
+    let mut t = TestBuilder::default();
+    t.ok(
+        format!(
+            r#"krb5_asreq(realm: "EXAMPLE.COM", user: "alice", kdc: "{}", port: {}, etype: [23, "aes256"], timeout: 1);"#,
+            kdc.ip(),
+            kdc.port()
+        ),
+        as_rep_result(),
+    );
+    t.async_verify().await;
+}
+
+#[tokio::test]
+async fn krb5_asreq_oversized_tcp_response() {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let len = stream.read_u32().await.unwrap();
+        let mut request = vec![0; len as usize];
+        stream.read_exact(&mut request).await.unwrap();
+        // Announces a response of 4 GiB which must not be allocated.
+        stream.write_u32(u32::MAX).await.unwrap();
+    });
+    let mut t = TestBuilder::default();
+    check_err_matches!(
+        t,
+        format!(
+            r#"krb5_asreq(realm: "EXAMPLE.COM", user: "alice", kdc: "{}", port: {}, timeout: 1);"#,
+            addr.ip(),
+            addr.port()
+        ),
+        Krb5Error::Io(_, _)
+    );
+    t.async_verify().await;
+}
+
+#[tokio::test]
+async fn krb5_asreq_errors() {
+    // The KDC does not answer at all.
+    let socket = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).await.unwrap();
+    let addr = socket.local_addr().unwrap();
+    let kdc = format!(r#"kdc: "{}", port: {}"#, addr.ip(), addr.port());
+    let mut t = TestBuilder::default();
+    check_err_matches!(
+        t,
+        format!(
+            r#"krb5_asreq(realm: "EXAMPLE.COM", user: "alice", {kdc}, transport: "udp", timeout: 1);"#
+        ),
+        Krb5Error::Timeout(_)
+    );
+    check_err_matches!(
+        t,
+        format!(r#"krb5_asreq(realm: "EXAMPLE.COM", user: "alice", {kdc}, transport: "sctp");"#),
+        ArgumentError::WrongArgument(_)
+    );
+    check_err_matches!(
+        t,
+        format!(r#"krb5_asreq(realm: "EXAMPLE.COM", user: "alice", {kdc}, etype: "des");"#),
+        ArgumentError::WrongArgument(_)
+    );
+    t.async_verify().await;
+    drop(socket);
+}
+
+#[test]
+fn encoding() {
+    let request = AsReq {
+        realm: "EXAMPLE.COM".to_string(),
+        user: "alice".to_string(),
+        nonce: 0x12345678,
+        etypes: vec![18, 23],
+    }
+    .encode();
+    let (tag, content, rest) = message::decode(&request).unwrap();
+    assert_eq!((tag, rest), (message::AS_REQ, &[][..]));
+    assert_eq!(message::decode(content).unwrap().0, message::SEQUENCE);
+    assert!(request
+        .windows(b"alice".len())
+        .any(|x| x == b"alice".as_slice()));
+    assert_eq!(
+        Reply::decode(&hex::decode(PREAUTH_REQUIRED).unwrap()),
+        Some(Reply::Error(message::PREAUTH_REQUIRED))
+    );
+    assert_eq!(
+        Reply::decode(&hex::decode(AS_REP).unwrap()),
+        Some(Reply::AsRep {
+            etype: 23,
+            cipher: hex::decode(CIPHER).unwrap()
+        })
+    );
+    assert_eq!(Reply::decode(&request), None);
+}
@@ -11,7 +11,6 @@ use std::{
     collections::HashMap,
     fs::File,
     io::{self, Read, Write},
-    thread,
     time::{self, Duration, UNIX_EPOCH},
 };
 
@@ -21,9 +20,13 @@ use chrono::{
 use nasl_function_proc_macro::nasl_function;
 use thiserror::Error;
 
-use crate::nasl::{prelude::*, utils::function::Maybe};
+use crate::nasl::{
+    prelude::*,
+    utils::function::{str_to_bytes, Maybe},
+};
 use flate2::{
-    read::GzDecoder, read::ZlibDecoder, write::GzEncoder, write::ZlibEncoder, Compression,
+    read::DeflateDecoder, read::GzDecoder, read::ZlibDecoder, write::DeflateEncoder,
+    write::GzEncoder, write::ZlibEncoder, Compression,
 };
 
 #[derive(Debug, Error)]
@@ -32,6 +35,25 @@ pub enum MiscError {
     IO(#[from] io::Error),
     #[error("Encountered time before 1970. {0}")]
     TimeBefore1970(String),
+    #[error("Reading {size} bytes at offset {offset} exceeds the data of length {len}.")]
+    OutOfRange {
+        size: usize,
+        offset: usize,
+        len: usize,
+    },
+    #[error("Unable to decompress the data: {0}")]
+    Decompress(String),
+    #[error("The execution deadline was reached while sleeping.")]
+    DeadlineExceeded,
+    #[error("Decompressed data exceeds the limit of {limit} bytes.")]
+    DecompressionLimit { limit: usize },
+}
+
+/// Default limit of the decompressed data as a multiple of the compressed size.
+pub const DEFAULT_DECOMPRESSION_RATIO: usize = 100;
+
+fn output_limit(max_output: Option<usize>, input_len: usize) -> usize {
+    max_output.unwrap_or_else(|| input_len.saturating_mul(DEFAULT_DECOMPRESSION_RATIO))
 }
 
 #[inline]
@@ -57,51 +79,141 @@ fn get_byte_order() -> bool {
     cfg!(target_endian = "little")
 }
 
+/// Packs the lowest two bytes of a number in big-endian order.
+#[nasl_function]
+fn mkword(n: i64) -> Vec<u8> {
+    (n as u16).to_be_bytes().to_vec()
+}
+
+/// Packs the lowest four bytes of a number in big-endian order.
+#[nasl_function]
+fn mkdword(n: i64) -> Vec<u8> {
+    (n as u32).to_be_bytes().to_vec()
+}
+
+/// Packs the lowest two bytes of a number in little-endian order.
+#[nasl_function]
+fn mkword_le(n: i64) -> Vec<u8> {
+    (n as u16).to_le_bytes().to_vec()
+}
+
+/// Packs the lowest four bytes of a number in little-endian order.
+#[nasl_function]
+fn mkdword_le(n: i64) -> Vec<u8> {
+    (n as u32).to_le_bytes().to_vec()
+}
+
+/// Returns the `N` bytes of `data` at `offset`.
+fn read_at<const N: usize>(data: &[u8], offset: Option<usize>) -> Result<[u8; N], MiscError> {
+    let offset = offset.unwrap_or(0);
+    offset
+        .checked_add(N)
+        .and_then(|end| data.get(offset..end))
+        .map(|x| x.try_into().unwrap())
+        .ok_or(MiscError::OutOfRange {
+            size: N,
+            offset,
+            len: data.len(),
+        })
+}
+
+/// Reads a big-endian 16 bit number from `data` at the optional `pos`, which defaults to 0.
+#[nasl_function(maybe_named(data, pos))]
+fn getword(data: &[u8], pos: Option<usize>) -> Result<u16, MiscError> {
+    read_at(data, pos).map(u16::from_be_bytes)
+}
+
+/// Reads a big-endian 32 bit number from `data` at the optional `pos`, which defaults to 0.
+#[nasl_function(maybe_named(data, pos))]
+fn getdword(data: &[u8], pos: Option<usize>) -> Result<u32, MiscError> {
+    read_at(data, pos).map(u32::from_be_bytes)
+}
+
+/// Reads a little-endian 16 bit number from `data` at the optional `pos`, which defaults to 0.
+#[nasl_function(maybe_named(data, pos))]
+fn getword_le(data: &[u8], pos: Option<usize>) -> Result<u16, MiscError> {
+    read_at(data, pos).map(u16::from_le_bytes)
+}
+
+/// Reads a little-endian 32 bit number from `data` at the optional `pos`, which defaults to 0.
+#[nasl_function(maybe_named(data, pos))]
+fn getdword_le(data: &[u8], pos: Option<usize>) -> Result<u32, MiscError> {
+    read_at(data, pos).map(u32::from_le_bytes)
+}
+
 /// NASL function to convert given number to string
 #[nasl_function(named(num))]
 fn dec2str(num: i64) -> String {
     num.to_string()
 }
 
+/// Waits for `duration` on the clock of the context, see [`crate::nasl::utils::Clock::sleep`].
+///
+/// If the deadline of the context is reached first, the wait ends there with an error.
+async fn wait(context: &Context<'_>, duration: Duration) -> Result<(), MiscError> {
+    let remaining = context
+        .deadline()
+        .map(|x| x.saturating_duration_since(time::Instant::now()));
+    let (duration, exceeded) = match remaining {
+        Some(remaining) if remaining < duration => (remaining, true),
+        _ => (duration, false),
+    };
+    context.clock().sleep(duration).await;
+    if exceeded {
+        return Err(MiscError::DeadlineExceeded);
+    }
+    Ok(())
+}
+
 /// takes an integer and sleeps the amount of seconds
 #[nasl_function]
-fn sleep(secs: u64) {
-    thread::sleep(Duration::from_secs(secs))
+async fn sleep(context: &Context<'_>, secs: u64) -> Result<(), MiscError> {
+    wait(context, Duration::from_secs(secs)).await
 }
 
 /// takes an integer and sleeps the amount of microseconds
 #[nasl_function]
-fn usleep(micros: u64) {
-    thread::sleep(Duration::from_micros(micros))
+async fn usleep(context: &Context<'_>, micros: u64) -> Result<(), MiscError> {
+    wait(context, Duration::from_micros(micros)).await
 }
 
 /// Returns the type of given unnamed argument.
 // typeof is a reserved keyword, therefore it is prefixed with "nasl_"
 #[nasl_function]
 fn nasl_typeof(val: NaslValue) -> String {
-    match val {
-        NaslValue::Null => "undef",
-        NaslValue::String(_) => "string",
-        NaslValue::Array(_) => "array",
-        NaslValue::Dict(_) => "array",
-        NaslValue::Boolean(_) => "int",
-        NaslValue::Number(_) => "int",
-        NaslValue::Data(_) => "data",
-        _ => "unknown",
-    }
-    .into()
+    val.value_type().name().into()
 }
 
-/// Returns true when the given unnamed argument is null.
+/// Returns true when the given unnamed argument is null or not given at all.
 #[nasl_function]
-fn isnull(val: NaslValue) -> bool {
-    matches!(val, NaslValue::Null)
+fn isnull(val: Option<NaslValue>) -> bool {
+    matches!(val, None | Some(NaslValue::Null))
+}
+
+/// Returns the length of the given unnamed argument.
+///
+/// That is the number of bytes of a string or data, the number of elements of an array and the
+/// number of digits, including the sign, of an integer. Anything else, including an undefined
+/// value, has a length of 0.
+#[nasl_function]
+fn length(val: Option<NaslValue>) -> usize {
+    match val {
+        Some(NaslValue::String(x)) => str_to_bytes(&x).len(),
+        Some(NaslValue::Data(x)) => x.len(),
+        Some(NaslValue::Array(x)) => x.len(),
+        Some(NaslValue::Dict(x)) => x.len(),
+        Some(NaslValue::Number(x)) => x.to_string().len(),
+        Some(NaslValue::Boolean(_)) => 1,
+        _ => 0,
+    }
 }
 
 /// Returns the seconds counted from 1st January 1970 as an integer.
 #[nasl_function]
-fn unixtime() -> Result<u64, MiscError> {
-    std::time::SystemTime::now()
+fn unixtime(context: &Context) -> Result<u64, MiscError> {
+    context
+        .clock()
+        .now()
         .duration_since(UNIX_EPOCH)
         .map(|t| t.as_secs())
         .map_err(|e| MiscError::TimeBefore1970(e.to_string()))
@@ -122,23 +234,99 @@ fn gzip(data: NaslValue, headformat: Option<&str>) -> Option<Vec<u8>> {
 }
 
 /// uncompress given data with gzip, when headformat is set to 'gzip' it uses gzipheader.
-#[nasl_function(named(data))]
-fn gunzip(data: NaslValue) -> Option<String> {
+///
+/// Returns NULL for corrupt data. The decompressed data is limited by max_output like for
+/// `zlib_decompress`.
+#[nasl_function(named(data, max_output))]
+fn gunzip(data: NaslValue, max_output: Option<usize>) -> Result<Option<String>, MiscError> {
     let data = Vec::<u8>::from(data);
-    let mut uncompress = ZlibDecoder::new(&data[..]);
-    let mut uncompressed = String::new();
-    match uncompress.read_to_string(&mut uncompressed) {
-        Ok(_) => Some(uncompressed),
-        Err(_) => {
-            let mut uncompress = GzDecoder::new(&data[..]);
-            let mut uncompressed = String::new();
-            if uncompress.read_to_string(&mut uncompressed).is_ok() {
-                Some(uncompressed)
-            } else {
-                None
-            }
-        }
+    let max_output = output_limit(max_output, data.len());
+    let uncompressed = match decompress(ZlibDecoder::new(&data[..]), max_output) {
+        Err(MiscError::Decompress(_)) => decompress(GzDecoder::new(&data[..]), max_output),
+        x => x,
+    };
+    match uncompressed {
+        Ok(x) => Ok(String::from_utf8(x).ok()),
+        Err(MiscError::Decompress(_)) => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+fn compress<W: Write>(mut encoder: W, data: NaslValue) -> Result<W, MiscError> {
+    encoder.write_all(&Vec::<u8>::from(data))?;
+    Ok(encoder)
+}
+
+/// Reads the decompressed data and aborts as soon as it exceeds `max_output` bytes.
+fn decompress<R: Read>(decoder: R, max_output: usize) -> Result<Vec<u8>, MiscError> {
+    let mut out = Vec::new();
+    decoder
+        .take(max_output as u64 + 1)
+        .read_to_end(&mut out)
+        .map_err(|e| MiscError::Decompress(e.to_string()))?;
+    if out.len() > max_output {
+        // Decompression stops right after the limit, so the real size stays unknown.
+        return Err(MiscError::DecompressionLimit { limit: max_output });
     }
+    Ok(out)
+}
+
+/// Compresses data in the gzip format (RFC 1952).
+#[nasl_function(maybe_named(data))]
+fn gzip_compress(data: NaslValue) -> Result<Vec<u8>, MiscError> {
+    Ok(compress(GzEncoder::new(Vec::new(), Compression::default()), data)?.finish()?)
+}
+
+/// Decompresses data in the gzip format (RFC 1952).
+///
+/// The optional named argument max_output limits the size of the decompressed data in bytes, it
+/// defaults to 100 times the size of the compressed data. Corrupt data or exceeding the limit
+/// results in an error.
+#[nasl_function(maybe_named(data), named(max_output))]
+fn gzip_decompress(data: NaslValue, max_output: Option<usize>) -> Result<Vec<u8>, MiscError> {
+    let data = Vec::<u8>::from(data);
+    let max_output = output_limit(max_output, data.len());
+    decompress(GzDecoder::new(&data[..]), max_output)
+}
+
+/// Compresses data in the zlib format (RFC 1950).
+#[nasl_function(maybe_named(data))]
+fn zlib_compress(data: NaslValue) -> Result<Vec<u8>, MiscError> {
+    Ok(compress(ZlibEncoder::new(Vec::new(), Compression::default()), data)?.finish()?)
+}
+
+/// Decompresses data in the zlib format (RFC 1950).
+///
+/// The optional named argument max_output limits the size of the decompressed data in bytes, it
+/// defaults to 100 times the size of the compressed data. Corrupt data or exceeding the limit
+/// results in an error.
+#[nasl_function(maybe_named(data), named(max_output))]
+fn zlib_decompress(data: NaslValue, max_output: Option<usize>) -> Result<Vec<u8>, MiscError> {
+    let data = Vec::<u8>::from(data);
+    let max_output = output_limit(max_output, data.len());
+    decompress(ZlibDecoder::new(&data[..]), max_output)
+}
+
+/// Compresses data as a raw deflate stream without header (RFC 1951).
+#[nasl_function(maybe_named(data))]
+fn deflate_compress(data: NaslValue) -> Result<Vec<u8>, MiscError> {
+    Ok(compress(
+        DeflateEncoder::new(Vec::new(), Compression::default()),
+        data,
+    )?
+    .finish()?)
+}
+
+/// Decompresses a raw deflate stream without header (RFC 1951).
+///
+/// The optional named argument max_output limits the size of the decompressed data in bytes, it
+/// defaults to 100 times the size of the compressed data. Corrupt data or exceeding the limit
+/// results in an error.
+#[nasl_function(maybe_named(data), named(max_output))]
+fn deflate_decompress(data: NaslValue, max_output: Option<usize>) -> Result<Vec<u8>, MiscError> {
+    let data = Vec::<u8>::from(data);
+    let max_output = output_limit(max_output, data.len());
+    decompress(DeflateDecoder::new(&data[..]), max_output)
 }
 
 /// Takes seven named arguments sec, min, hour, mday, mon, year, isdst and returns the Unix time.
@@ -186,19 +374,24 @@ where
 
 /// Returns an dict(mday, mon, min, wday, sec, yday, isdst, year, hour) based on optional given time in seconds and optional flag if utc or not.
 #[nasl_function(named(utc))]
-fn localtime(secs: Option<i64>, utc: Option<NaslValue>) -> HashMap<String, NaslValue> {
+fn localtime(
+    context: &Context,
+    secs: Option<i64>,
+    utc: Option<NaslValue>,
+) -> HashMap<String, NaslValue> {
     let utc_flag = match utc {
         Some(NaslValue::Number(x)) => x != 0,
         Some(NaslValue::Boolean(x)) => x,
         _ => false,
     };
+    let now = DateTime::<Utc>::from(context.clock().now());
 
     match (utc_flag, secs) {
-        (true, None) => create_localtime_map(Utc::now()),
-        (false, None) => create_localtime_map(Local::now()),
+        (true, None) => create_localtime_map(now),
+        (false, None) => create_localtime_map(now.with_timezone(&Local)),
         (true, Some(secs)) => match Utc.timestamp_opt(secs, 0) {
             LocalResult::Single(x) => create_localtime_map(x),
-            _ => create_localtime_map(Utc::now()),
+            _ => create_localtime_map(now),
         },
 
         (false, Some(secs)) => match DateTime::from_timestamp(secs, 0) {
@@ -207,7 +400,7 @@ fn localtime(secs: Option<i64>, utc: Option<NaslValue>) -> HashMap<String, NaslV
                 let dt: DateTime<FixedOffset> = (dt + offset).into();
                 create_localtime_map(dt)
             }
-            _ => create_localtime_map(Local::now()),
+            _ => create_localtime_map(now.with_timezone(&Local)),
         },
     }
 }
@@ -233,8 +426,8 @@ fn defined_func(ctx: &Context, register: &Register, fn_name: Option<Maybe<&str>>
 ///
 /// For example: “1067352015.030757” means 1067352015 seconds and 30757 microseconds.
 #[nasl_function]
-fn gettimeofday() -> Result<String, MiscError> {
-    match time::SystemTime::now().duration_since(time::SystemTime::UNIX_EPOCH) {
+fn gettimeofday(context: &Context) -> Result<String, MiscError> {
+    match context.clock().now().duration_since(UNIX_EPOCH) {
         Ok(time) => {
             let time = time.as_micros();
             Ok(format!("{}.{:06}", time / 1000000, time % 1000000))
@@ -257,9 +450,18 @@ function_set! {
     (
         rand,
         get_byte_order,
+        mkword,
+        mkdword,
+        mkword_le,
+        mkdword_le,
+        getword,
+        getdword,
+        getword_le,
+        getdword_le,
         dec2str,
         (nasl_typeof, "typeof"),
         isnull,
+        length,
         unixtime,
         localtime,
         mktime,
@@ -267,6 +469,12 @@ function_set! {
         sleep,
         gzip,
         gunzip,
+        gzip_compress,
+        gzip_decompress,
+        zlib_compress,
+        zlib_decompress,
+        deflate_compress,
+        deflate_decompress,
         defined_func,
         gettimeofday,
         dump_ctxt,
@@ -6,9 +6,13 @@
 mod tests {
     use chrono::Offset;
 
+    use crate::nasl::builtin::misc::MiscError;
     use crate::nasl::test_prelude::*;
+    use crate::nasl::utils::MockClock;
 
-    use std::time::Instant;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+    use std::time::{Duration, Instant, UNIX_EPOCH};
 
     #[test]
     fn rand() {
@@ -21,6 +25,43 @@ mod tests {
         check_code_result_matches!("get_byte_order();", NaslValue::Boolean(_));
     }
 
+    #[test]
+    fn pack_big_endian() {
+        check_code_result("mkword(0x1234);", vec![0x12u8, 0x34]);
+        check_code_result("mkdword(0x12345678);", vec![0x12u8, 0x34, 0x56, 0x78]);
+        // only the lowest bytes are kept
+        check_code_result("mkword(0x10203);", vec![0x02u8, 0x03]);
+        check_code_result("getword(mkword(0x1234));", 0x1234);
+        check_code_result("getdword(mkdword(0xdeadbeef));", 0xdeadbeef_i64);
+    }
+
+    #[test]
+    fn pack_little_endian() {
+        check_code_result("mkword_le(0x1234);", vec![0x34u8, 0x12]);
+        check_code_result("mkdword_le(0x12345678);", vec![0x78u8, 0x56, 0x34, 0x12]);
+        check_code_result("getword_le(mkword_le(0x1234));", 0x1234);
+        check_code_result("getdword_le(mkdword_le(0xdeadbeef));", 0xdeadbeef_i64);
+    }
+
+    #[test]
+    fn get_at_offset() {
+        let mut t = TestBuilder::default();
+        t.run("data = raw_string(0x00, 0x01, 0x02, 0x03, 0x04, 0x05);");
+        t.ok("getword(data, 1);", 0x0102);
+        t.ok("getword_le(data: data, pos: 4);", 0x0504);
+        t.ok("getdword(data, 2);", 0x02030405);
+        t.ok("getdword_le(data, 2);", 0x05040302);
+    }
+
+    #[test]
+    fn get_out_of_range() {
+        let mut t = TestBuilder::default();
+        t.run("data = raw_string(0x00, 0x01, 0x02);");
+        check_err_matches!(t, "getword(data, 2);", MiscError::OutOfRange { .. });
+        check_err_matches!(t, "getdword(data);", MiscError::OutOfRange { .. });
+        check_err_matches!(t, "getdword_le(data, 100);", MiscError::OutOfRange { .. });
+    }
+
     #[test]
     fn dec2str() {
         check_code_result("dec2str(num: 23);", "23");
@@ -31,6 +72,10 @@ mod tests {
         let mut t = TestBuilder::default();
         t.ok(r#"typeof("AA");"#, "string");
         t.ok(r#"typeof(1);"#, "int");
+        t.ok(r#"typeof(TRUE);"#, "int");
+        t.ok(r#"typeof(1 == 2);"#, "int");
+        t.ok(r#"typeof("");"#, "string");
+        t.ok(r#"typeof(make_list(1, 2));"#, "array");
         t.ok(r#"typeof('AA');"#, "data");
         t.ok(r#"typeof(make_array());"#, "array");
         t.ok(r#"typeof(NULL);"#, "undef");
@@ -48,6 +93,30 @@ mod tests {
     fn isnull() {
         check_code_result(r#"isnull(42);"#, false);
         check_code_result(r#"isnull(Null);"#, true);
+        check_code_result(r#"isnull(NULL);"#, true);
+        check_code_result(r#"isnull(undefined);"#, true);
+        check_code_result(r#"isnull();"#, true);
+        check_code_result(r#"isnull("");"#, false);
+        check_code_result(r#"isnull('');"#, false);
+        check_code_result(r#"isnull(0);"#, false);
+        check_code_result(r#"isnull(make_list());"#, false);
+    }
+
+    #[test]
+    fn length() {
+        let mut t = TestBuilder::default();
+        t.ok(r#"length("hallo\n");"#, 7);
+        t.ok(r#"length('hallo\n');"#, 6);
+        t.ok(r#"length(string(raw_string(0xff, 0x41)));"#, 2);
+        t.ok(r#"length(raw_string(0xff, 0x41));"#, 2);
+        t.ok(r#"length(make_list(1, "a", 'b'));"#, 3);
+        t.ok(r#"length(make_array("a", 1, "b", 2));"#, 2);
+        t.ok(r#"length(12345);"#, 5);
+        t.ok(r#"length(-12);"#, 3);
+        t.ok(r#"length(TRUE);"#, 1);
+        t.ok(r#"length(NULL);"#, 0);
+        t.ok(r#"length(undefined);"#, 0);
+        t.ok(r#"length();"#, 0);
     }
 
     #[test]
@@ -80,6 +149,83 @@ mod tests {
         t.ok(r#"gunzip(data: ngz);"#, "ngz");
     }
 
+    #[test]
+    fn compress_round_trip() {
+        let mut t = TestBuilder::default();
+        t.run(r#"data = raw_string(0x00, 0xff, 0x10) + "compress me";"#);
+        for format in ["gzip", "zlib", "deflate"] {
+            t.run(format!(r#"c = {format}_compress(data);"#));
+            t.ok(format!(r#"{format}_decompress(c) == data;"#), true);
+            t.ok(
+                format!(r#"{format}_decompress(data: c, max_output: length(data)) == data;"#),
+                true,
+            );
+        }
+        t.ok(r#"gunzip(data: gzip_compress("z"));"#, "z");
+        t.ok(
+            r#"zlib_compress('z');"#,
+            vec![120u8, 156, 171, 2, 0, 0, 123, 0, 123],
+        );
+        t.ok(r#"deflate_compress('z');"#, vec![171u8, 2, 0]);
+    }
+
+    #[test]
+    fn decompress_errors() {
+        let mut t = TestBuilder::default();
+        for format in ["gzip", "zlib", "deflate"] {
+            check_err_matches!(
+                t,
+                format!(r#"{format}_decompress("not compressed at all");"#),
+                MiscError::Decompress(_),
+            );
+        }
+        check_err_matches!(
+            t,
+            r#"zlib_decompress(substr(zlib_compress("truncated data"), 0, 6));"#,
+            MiscError::Decompress(_),
+        );
+    }
+
+    #[test]
+    fn decompress_max_output() {
+        let mut t = TestBuilder::default();
+        t.run(r#"bomb = gzip_compress(crap(length: 100000, data: "A"));"#);
+        t.ok(r#"length(bomb) < 1000;"#, true);
+        check_err_matches!(
+            t,
+            r#"gzip_decompress(bomb, max_output: 1000);"#,
+            MiscError::DecompressionLimit { limit: 1000 },
+        );
+        t.ok(
+            r#"length(gzip_decompress(bomb, max_output: 100000));"#,
+            100000,
+        );
+        // The default limit is a multiple of the compressed size.
+        check_err_matches!(
+            t,
+            r#"gzip_decompress(bomb);"#,
+            MiscError::DecompressionLimit { .. },
+        );
+        check_err_matches!(
+            t,
+            r#"gunzip(data: bomb);"#,
+            MiscError::DecompressionLimit { .. },
+        );
+        t.ok(r#"strlen(gunzip(data: bomb, max_output: 100000));"#, 100000);
+        t.run(r#"bomb = zlib_compress(crap(length: 100000, data: "A"));"#);
+        check_err_matches!(
+            t,
+            r#"zlib_decompress(bomb, max_output: 99999);"#,
+            MiscError::DecompressionLimit { limit: 99999 },
+        );
+        t.run(r#"bomb = deflate_compress(crap(length: 100000, data: "A"));"#);
+        check_err_matches!(
+            t,
+            r#"deflate_decompress(bomb);"#,
+            MiscError::DecompressionLimit { .. },
+        );
+    }
+
     #[test]
     fn localtime() {
         let mut t = TestBuilder::default();
@@ -178,6 +324,47 @@ mod tests {
         assert!(now.elapsed().as_micros() >= 1000);
     }
 
+    #[tokio::test(flavor = "current_thread")]
+    async fn sleep_does_not_block() {
+        let done = AtomicBool::new(false);
+        let mut t = TestBuilder::default();
+        t.ok(r#"usleep(200000);"#, NaslValue::Null);
+        let script = async {
+            t.async_verify().await;
+            done.store(true, Ordering::SeqCst);
+        };
+        // Runs during the sleep, unless it blocks the only thread of the runtime.
+        let other = async {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            !done.load(Ordering::SeqCst)
+        };
+        let ((), ran_concurrently) = tokio::join!(script, other);
+        assert!(ran_concurrently);
+    }
+
+    #[test]
+    fn sleep_advances_mock_clock() {
+        let now = Instant::now();
+        let clock = Arc::new(MockClock::new(UNIX_EPOCH));
+        let mut t = TestBuilder::default().with_clock(clock);
+        t.ok("sleep(3600);", NaslValue::Null);
+        t.ok("usleep(2000000);", NaslValue::Null);
+        t.ok("unixtime();", 3602);
+        drop(t);
+        assert!(now.elapsed() < Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn sleep_honors_deadline() {
+        let now = Instant::now();
+        let mut t = TestBuilder::default().with_deadline(now + Duration::from_millis(100));
+        check_err_matches!(t, r#"sleep(10);"#, MiscError::DeadlineExceeded);
+        check_err_matches!(t, r#"usleep(1);"#, MiscError::DeadlineExceeded);
+        t.async_verify().await;
+        assert!(now.elapsed() >= Duration::from_millis(100));
+        assert!(now.elapsed() < Duration::from_secs(5));
+    }
+
     #[test]
     fn defined_func() {
         let mut t = TestBuilder::default();
@@ -187,5 +374,11 @@ mod tests {
         t.ok("a = 12;", 12i64);
         t.ok(r#"defined_func("a");"#, false);
         t.ok("defined_func(a);", false);
+        t.ok(r#"defined_func("isnull");"#, true);
+        t.ok(r#"defined_func("does_not_exist");"#, false);
+        t.ok(r#"defined_func("");"#, false);
+        t.ok("defined_func();", false);
+        t.ok("defined_func(NULL);", false);
+        t.ok("defined_func(make_list(1));", false);
     }
 }
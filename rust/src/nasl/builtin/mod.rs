@@ -8,17 +8,23 @@ mod array;
 mod cert;
 mod cryptographic;
 mod description;
+mod dns;
 mod error;
 mod host;
 mod http;
 mod isotime;
 mod knowledge_base;
+mod krb5;
 mod misc;
 mod network;
+mod packet;
 #[cfg(feature = "nasl-builtin-raw-ip")]
 pub mod raw_ip;
 mod regex;
+mod registry;
 mod report_functions;
+mod snmp;
+#[cfg(feature = "nasl-builtin-ssh")]
 mod ssh;
 mod string;
 mod sys;
@@ -26,15 +32,28 @@ mod sys;
 #[cfg(test)]
 mod tests;
 
+pub use cryptographic::password::{Passwords, DEFAULT_COMMON_PASSWORDS};
 pub use error::BuiltinError;
 pub use host::HostError;
 pub use knowledge_base::KBError;
+pub use registry::{BuiltinRegistry, RegistryError};
+#[cfg(feature = "test-support")]
+pub use ssh::test_utils as ssh_test_utils;
+
+use std::sync::Arc;
+use std::time::Instant;
+
+use once_cell::sync::Lazy;
 
 use crate::nasl::syntax::{Loader, NoOpLoader};
-use crate::nasl::utils::{Context, Executor, NaslVarRegister, NaslVarRegisterBuilder, Register};
+use crate::nasl::utils::function::FnSignature;
+use crate::nasl::utils::{
+    Clock, Context, Executor, NaslVarRegister, NaslVarRegisterBuilder, NetworkMode, Register,
+    SystemClock, UnknownFunctions,
+};
 use crate::storage::{ContextKey, DefaultDispatcher, Storage};
 
-use super::utils::context::Target;
+use super::utils::context::{Target, DEFAULT_BLOCKING_THRESHOLD};
 
 /// Creates a new Executor and adds all the functions to it.
 ///
@@ -50,25 +69,75 @@ pub fn nasl_std_functions() -> Executor {
         .add_set(knowledge_base::KnowledgeBase)
         .add_set(misc::Misc)
         .add_set(string::NaslString)
+        .add_set(packet::Packet)
         .add_set(host::Host)
+        .add_set(dns::Dns)
+        .add_set(snmp::Snmp)
+        .add_set(krb5::Krb5)
         .add_set(http::NaslHttp::default())
         .add_set(network::socket::NaslSockets::default())
         .add_set(network::network::Network)
-        .add_set(regex::RegularExpressions)
+        .add_set(network::inet::Inet)
+        .add_set(network::async_socket::AsyncSockets::default())
+        .add_set(regex::RegularExpressions::default())
         .add_set(cryptographic::Cryptographic)
         .add_set(description::Description)
         .add_set(isotime::NaslIsotime)
         .add_set(cryptographic::rc4::CipherHandlers::default())
+        .add_set(cryptographic::aes_ctr::AesCtrHandles::default())
+        .add_set(cryptographic::password::Passwords::default())
         .add_set(sys::Sys)
-        .add_set(ssh::Ssh::default())
         .add_set(cert::NaslCerts::default());
 
+    #[cfg(feature = "nasl-builtin-ssh")]
+    executor.add_set(ssh::Ssh::new());
+
     #[cfg(feature = "nasl-builtin-raw-ip")]
     executor.add_set(raw_ip::RawIp);
 
     executor
 }
 
+/// The names of the functions of [nasl_std_functions] along with their signatures, sorted by name.
+static AVAILABLE_FUNCTIONS: Lazy<Vec<(String, Option<FnSignature>)>> = Lazy::new(|| {
+    let executor = nasl_std_functions();
+    executor
+        .function_names()
+        .into_iter()
+        .map(|name| (name.to_string(), executor.function_signature(name).cloned()))
+        .collect()
+});
+
+fn find_function(name: &str) -> Option<&'static (String, Option<FnSignature>)> {
+    AVAILABLE_FUNCTIONS
+        .binary_search_by(|(x, _)| x.as_str().cmp(name))
+        .ok()
+        .map(|index| &AVAILABLE_FUNCTIONS[index])
+}
+
+/// Returns the sorted names of all builtin functions of [nasl_std_functions].
+///
+/// Functions of disabled features are not contained.
+pub fn available_functions() -> Vec<&'static str> {
+    AVAILABLE_FUNCTIONS
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect()
+}
+
+/// Checks if `name` is a builtin function of [nasl_std_functions].
+pub fn has_function(name: &str) -> bool {
+    find_function(name).is_some()
+}
+
+/// Returns the signature of the builtin function `name` of [nasl_std_functions].
+///
+/// `None` is returned for unknown functions as well as for functions taking the `Register`
+/// directly without describing their arguments, see [FnSignature].
+pub fn function_signature(name: &str) -> Option<FnSignature> {
+    find_function(name).and_then(|(_, signature)| signature.clone())
+}
+
 /// Creates a new NaslVarRegister and adds all the predefined nasl variables.
 ///
 /// To add new variables to the register, add it to the builder by calling `push_register`.
@@ -108,6 +177,17 @@ pub struct ContextFactory<Loader, Storage> {
     pub loader: Loader,
     /// The functions available to the nasl script.
     pub functions: Executor,
+    /// The deadline of the execution, see [`Context::deadline`].
+    pub deadline: Option<Instant>,
+    /// The clock used by time related builtins, see [`Context::clock`].
+    pub clock: Arc<dyn Clock>,
+    /// Whether network builtins talk to the target, see [`Context::network_mode`].
+    pub network_mode: Arc<NetworkMode>,
+    /// How calls of unknown functions are handled, see [`Context::unknown_functions`].
+    pub unknown_functions: UnknownFunctions,
+    /// The input size from which hashes are calculated on a blocking thread, see
+    /// [`Context::blocking_threshold`].
+    pub blocking_threshold: usize,
 }
 
 impl Default for ContextFactory<NoOpLoader, DefaultDispatcher> {
@@ -116,6 +196,11 @@ impl Default for ContextFactory<NoOpLoader, DefaultDispatcher> {
             loader: NoOpLoader::default(),
             functions: nasl_std_functions(),
             storage: DefaultDispatcher::default(),
+            deadline: None,
+            clock: Arc::new(SystemClock),
+            network_mode: Arc::default(),
+            unknown_functions: UnknownFunctions::default(),
+            blocking_threshold: DEFAULT_BLOCKING_THRESHOLD,
         }
     }
 }
@@ -133,6 +218,11 @@ where
             storage,
             loader,
             functions: nasl_std_functions(),
+            deadline: None,
+            clock: Arc::new(SystemClock),
+            network_mode: Arc::default(),
+            unknown_functions: UnknownFunctions::default(),
+            blocking_threshold: DEFAULT_BLOCKING_THRESHOLD,
         }
     }
 
@@ -142,6 +232,55 @@ where
         self
     }
 
+    /// Sets the point in time at which the execution of the script should be aborted.
+    pub fn deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Sets the clock used by time related builtins, e.g. a [`crate::nasl::utils::MockClock`] in
+    /// tests.
+    pub fn clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Sets whether network builtins talk to the target, record or replay their interactions.
+    pub fn network_mode(mut self, network_mode: NetworkMode) -> Self {
+        self.network_mode = Arc::new(network_mode);
+        self
+    }
+
+    /// Sets the number of input bytes from which hashes and HMACs are calculated on a blocking
+    /// thread.
+    pub fn blocking_threshold(mut self, bytes: usize) -> Self {
+        self.blocking_threshold = bytes;
+        self
+    }
+
+    /// Sets the list of common passwords used by `is_common_password` instead of the bundled one.
+    pub fn common_passwords(mut self, passwords: Passwords) -> Self {
+        self.functions.prepend_set(passwords);
+        self
+    }
+
+    /// Keeps authenticated SSH sessions open for `max_idle` after a script disconnected them, so
+    /// that a later script of this factory connecting to the same server with the same
+    /// credentials reuses the connection. Pooling is disabled by default and not supported with
+    /// libssh.
+    #[cfg(all(feature = "nasl-builtin-ssh", not(feature = "nasl-builtin-libssh")))]
+    pub fn ssh_pool(mut self, max_idle: std::time::Duration) -> Self {
+        self.functions.prepend_set(ssh::Ssh::with_pool(max_idle));
+        self
+    }
+
+    /// Sets how calls of functions which are neither builtins nor defined by the script are
+    /// handled.
+    pub fn unknown_functions(mut self, unknown_functions: UnknownFunctions) -> Self {
+        self.unknown_functions = unknown_functions;
+        self
+    }
+
     /// Creates a new Context with the shared loader, logger and function register
     pub fn build(&self, key: ContextKey) -> Context {
         let mut target = Target::default();
@@ -150,14 +289,20 @@ where
             ContextKey::Scan(_, None) => String::default(),
             ContextKey::FileName(target) => target.clone(),
         });
-        Context::new(
+        let mut context = Context::new(
             key,
             target,
             self.storage.as_dispatcher(),
             self.storage.as_retriever(),
             &self.loader,
             &self.functions,
-        )
+        );
+        context.set_deadline(self.deadline);
+        context.set_clock(&*self.clock);
+        context.set_network_mode(&self.network_mode);
+        context.set_unknown_functions(self.unknown_functions);
+        context.set_blocking_threshold(self.blocking_threshold);
+        context
     }
 }
 
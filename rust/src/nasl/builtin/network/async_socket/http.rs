@@ -0,0 +1,292 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+//! HTTP/1.1 requests and responses over an established [Stream](super::tcp::Stream).
+
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::time::Instant;
+
+use crate::nasl::builtin::http::HttpError;
+use crate::nasl::prelude::*;
+
+/// Headers larger than this are considered malformed.
+const MAX_HEADER_SIZE: usize = 64 * 1024;
+const MAX_HEADERS: usize = 128;
+
+fn is_valid_token(s: &str) -> bool {
+    !s.is_empty()
+        && !s
+            .bytes()
+            .any(|b| b.is_ascii_whitespace() || b.is_ascii_control())
+}
+
+fn is_valid_value(s: &str) -> bool {
+    !s.bytes().any(|b| b == b'\r' || b == b'\n')
+}
+
+/// Serializes a request.
+///
+/// A `Host` header is added when missing and a `Content-Length` header when a body is given.
+/// Headers are written in the order of their names.
+pub fn build_request(
+    method: &str,
+    path: &str,
+    host: &str,
+    headers: &HashMap<String, String>,
+    body: &[u8],
+) -> Result<Vec<u8>, FnError> {
+    if !is_valid_token(method) {
+        return Err(ArgumentError::WrongArgument(format!("Invalid method {method:?}.")).into());
+    }
+    if !is_valid_token(path) {
+        return Err(ArgumentError::WrongArgument(format!("Invalid path {path:?}.")).into());
+    }
+    let mut headers: Vec<_> = headers.iter().collect();
+    headers.sort();
+    let has = |name: &str| headers.iter().any(|(k, _)| k.eq_ignore_ascii_case(name));
+    let mut request = format!("{method} {path} HTTP/1.1\r\n");
+    if !has("host") {
+        request.push_str(&format!("Host: {host}\r\n"));
+    }
+    if !body.is_empty() && !has("content-length") {
+        request.push_str(&format!("Content-Length: {}\r\n", body.len()));
+    }
+    for (name, value) in headers.iter() {
+        if !is_valid_token(name) || name.contains(':') || !is_valid_value(value) {
+            return Err(ArgumentError::WrongArgument(format!(
+                "Invalid header {name:?}: {value:?}."
+            ))
+            .into());
+        }
+        request.push_str(&format!("{name}: {value}\r\n"));
+    }
+    request.push_str("\r\n");
+    let mut request = request.into_bytes();
+    request.extend_from_slice(body);
+    Ok(request)
+}
+
+fn malformed(reason: impl Into<String>) -> HttpError {
+    HttpError::MalformedResponse(reason.into())
+}
+
+/// Reads from a stream into a buffer, failing when the deadline passed.
+struct Reader<'a, S> {
+    stream: &'a mut S,
+    buf: Vec<u8>,
+    deadline: Instant,
+}
+
+impl<S: AsyncRead + Unpin> Reader<'_, S> {
+    /// Reads more data and returns false on EOF.
+    async fn fill(&mut self) -> Result<bool, HttpError> {
+        let mut chunk = [0; 4096];
+        match tokio::time::timeout_at(self.deadline, self.stream.read(&mut chunk)).await {
+            Err(_) => Err(HttpError::Timeout),
+            Ok(Err(e)) => Err(e.into()),
+            Ok(Ok(0)) => Ok(false),
+            Ok(Ok(n)) => {
+                self.buf.extend_from_slice(&chunk[..n]);
+                Ok(true)
+            }
+        }
+    }
+
+    /// Takes the first `n` bytes, reading more if necessary.
+    async fn take(&mut self, n: usize) -> Result<Vec<u8>, HttpError> {
+        while self.buf.len() < n {
+            if !self.fill().await? {
+                return Err(malformed("Connection closed before the end of the body."));
+            }
+        }
+        Ok(self.buf.drain(..n).collect())
+    }
+
+    /// Takes a line without its line ending.
+    async fn line(&mut self) -> Result<Vec<u8>, HttpError> {
+        loop {
+            if let Some(pos) = self.buf.windows(2).position(|x| x == b"\r\n") {
+                let line = self.buf.drain(..pos + 2).take(pos).collect();
+                return Ok(line);
+            }
+            if self.buf.len() > MAX_HEADER_SIZE {
+                return Err(malformed("Line too long."));
+            }
+            if !self.fill().await? {
+                return Err(malformed("Connection closed within a line."));
+            }
+        }
+    }
+
+    async fn rest(&mut self) -> Result<Vec<u8>, HttpError> {
+        while self.fill().await? {}
+        Ok(std::mem::take(&mut self.buf))
+    }
+}
+
+struct Head {
+    version: String,
+    status: u16,
+    reason: String,
+    headers: Vec<(String, String)>,
+}
+
+impl Head {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    fn has_body(&self) -> bool {
+        !(100..200).contains(&self.status) && self.status != 204 && self.status != 304
+    }
+}
+
+async fn read_head<S: AsyncRead + Unpin>(reader: &mut Reader<'_, S>) -> Result<Head, HttpError> {
+    loop {
+        let mut headers = [httparse::EMPTY_HEADER; MAX_HEADERS];
+        let mut response = httparse::Response::new(&mut headers);
+        match response.parse(&reader.buf) {
+            Ok(httparse::Status::Complete(len)) => {
+                let head = Head {
+                    version: format!("HTTP/1.{}", response.version.unwrap_or(1)),
+                    status: response.code.unwrap_or_default(),
+                    reason: response.reason.unwrap_or_default().to_string(),
+                    headers: response
+                        .headers
+                        .iter()
+                        .map(|h| {
+                            (
+                                h.name.to_string(),
+                                String::from_utf8_lossy(h.value).into_owned(),
+                            )
+                        })
+                        .collect(),
+                };
+                reader.buf.drain(..len);
+                return Ok(head);
+            }
+            Ok(httparse::Status::Partial) if reader.buf.len() <= MAX_HEADER_SIZE => {
+                if !reader.fill().await? {
+                    return Err(malformed("Connection closed within the header."));
+                }
+            }
+            Ok(httparse::Status::Partial) => return Err(malformed("Header too large.")),
+            Err(e) => return Err(malformed(e.to_string())),
+        }
+    }
+}
+
+async fn read_chunked<S: AsyncRead + Unpin>(
+    reader: &mut Reader<'_, S>,
+) -> Result<Vec<u8>, HttpError> {
+    let mut body = vec![];
+    loop {
+        let line = reader.line().await?;
+        let line = String::from_utf8_lossy(&line);
+        // chunk extensions are ignored
+        let size = line.split(';').next().unwrap_or_default().trim();
+        let size = usize::from_str_radix(size, 16)
+            .map_err(|_| malformed(format!("Invalid chunk size {size:?}.")))?;
+        if size == 0 {
+            // skip the trailers
+            while !reader.line().await?.is_empty() {}
+            return Ok(body);
+        }
+        body.extend(reader.take(size).await?);
+        if !reader.take(2).await?.eq(b"\r\n") {
+            return Err(malformed("Chunk not terminated by CRLF."));
+        }
+    }
+}
+
+/// A response as returned by [`read_response`].
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Response {
+    version: String,
+    status: u16,
+    reason: String,
+    headers: BTreeMap<String, String>,
+    body: Vec<u8>,
+}
+
+/// Converts the response to a dictionary with the keys `version`, `status`, `reason`, `headers`
+/// and `body`.
+impl From<Response> for NaslValue {
+    fn from(response: Response) -> Self {
+        let headers = response
+            .headers
+            .into_iter()
+            .map(|(name, value)| (name, NaslValue::String(value)))
+            .collect();
+        NaslValue::Dict(BTreeMap::from([
+            ("version".to_string(), NaslValue::String(response.version)),
+            (
+                "status".to_string(),
+                NaslValue::Number(response.status as i64),
+            ),
+            ("reason".to_string(), NaslValue::String(response.reason)),
+            ("headers".to_string(), NaslValue::Dict(headers)),
+            ("body".to_string(), NaslValue::Data(response.body)),
+        ]))
+    }
+}
+
+/// Reads a response.
+///
+/// Header names are lower case, repeated headers are joined by `, `. Bytes following the
+/// response are discarded.
+pub async fn read_response<S: AsyncRead + Unpin>(
+    stream: &mut S,
+    timeout: Duration,
+) -> Result<Response, HttpError> {
+    let mut reader = Reader {
+        stream,
+        buf: vec![],
+        deadline: Instant::now() + timeout,
+    };
+    let head = read_head(&mut reader).await?;
+    let chunked = head
+        .header("transfer-encoding")
+        .is_some_and(|x| x.to_ascii_lowercase().contains("chunked"));
+    let body = if !head.has_body() {
+        vec![]
+    } else if chunked {
+        read_chunked(&mut reader).await?
+    } else if let Some(len) = head.header("content-length") {
+        let len = len
+            .trim()
+            .parse()
+            .map_err(|_| malformed(format!("Invalid Content-Length {len:?}.")))?;
+        reader.take(len).await?
+    } else {
+        reader.rest().await?
+    };
+    let mut headers: BTreeMap<String, String> = BTreeMap::new();
+    for (name, value) in head.headers {
+        let name = name.to_ascii_lowercase();
+        match headers.get_mut(&name) {
+            Some(existing) => {
+                existing.push_str(", ");
+                existing.push_str(&value);
+            }
+            None => {
+                headers.insert(name, value);
+            }
+        }
+    }
+    Ok(Response {
+        version: head.version,
+        status: head.status,
+        reason: head.reason,
+        headers,
+        body,
+    })
+}
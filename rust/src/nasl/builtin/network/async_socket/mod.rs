@@ -0,0 +1,449 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+//! Sockets based on the tokio runtime.
+//!
+//! In contrast to the sockets in [super::socket], waiting for data does not block the runtime.
+//! As with the SSH functions, the sockets are stored within the function set state and are
+//! referenced by an id within the scripts.
+//!
+//! All interactions with the peer pass through
+//! [`NetworkMode::interact`](crate::nasl::utils::NetworkMode::interact), so they can be recorded
+//! and replayed. A replayed socket gets an id but has no stream behind it.
+
+mod http;
+mod tcp;
+mod tls;
+mod udp;
+
+#[cfg(test)]
+mod tests;
+
+use std::collections::{BTreeMap, HashMap};
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use tokio::io::AsyncWriteExt;
+use tokio::net::TcpStream;
+use tokio_rustls::client::TlsStream;
+
+use crate::nasl::prelude::*;
+
+use super::socket::SocketError;
+use super::Port;
+
+pub type SocketId = usize;
+
+/// The first id given to a socket. The ids of the sockets in [super::socket] are indices
+/// starting at 0, using different ranges makes it easier to spot mixed up ids.
+pub const MIN_SOCKET_ID: SocketId = 10000;
+
+/// Used when a script does not pass a timeout.
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Default)]
+pub struct AsyncSockets {
+    udp: HashMap<SocketId, tokio::net::UdpSocket>,
+    tcp: HashMap<SocketId, tcp::Stream>,
+    next_id: Option<SocketId>,
+}
+
+impl AsyncSockets {
+    fn next_id(&mut self) -> SocketId {
+        let id = self.next_id.unwrap_or(MIN_SOCKET_ID);
+        self.next_id = Some(id + 1);
+        id
+    }
+
+    fn udp(&self, id: SocketId) -> Result<&tokio::net::UdpSocket, SocketError> {
+        self.udp
+            .get(&id)
+            .ok_or_else(|| SocketError::WrongArgument(format!("Socket {id} does not exist.")))
+    }
+
+    fn tcp(&mut self, id: SocketId) -> Result<&mut tcp::Stream, SocketError> {
+        self.tcp
+            .get_mut(&id)
+            .ok_or_else(|| SocketError::WrongArgument(format!("Socket {id} does not exist.")))
+    }
+}
+
+/// Resolves the host and returns the first found address.
+async fn resolve(host: &str, port: u16) -> Result<SocketAddr, SocketError> {
+    tokio::net::lookup_host((host, port))
+        .await
+        .map_err(|_| SocketError::HostnameLookupFailed(host.to_string()))?
+        .next()
+        .ok_or_else(|| SocketError::HostnameNoIpFound(host.to_string()))
+}
+
+/// Converts a timeout in seconds as given by the scripts.
+fn timeout_or_default(timeout: Option<i64>) -> Duration {
+    super::network_utils::convert_timeout(timeout).unwrap_or(DEFAULT_TIMEOUT)
+}
+
+impl AsyncSockets {
+    /// Opens a UDP socket to the given host and port and returns its id.
+    #[nasl_function(maybe_named(host, port))]
+    async fn udp_open(
+        &mut self,
+        context: &Context<'_>,
+        host: &str,
+        port: Port,
+    ) -> Result<SocketId, FnError> {
+        let mut socket = None;
+        context
+            .network_mode()
+            .interact(format!("udp_open {host} {}", port.0), async {
+                socket = Some(udp::open(resolve(host, port.0).await?).await?);
+                Ok::<_, SocketError>(())
+            })
+            .await?;
+        let id = self.next_id();
+        if let Some(socket) = socket {
+            self.udp.insert(id, socket);
+        }
+        Ok(id)
+    }
+
+    /// Sends the data as a single datagram and returns the number of bytes sent.
+    #[nasl_function(maybe_named(sock, data))]
+    async fn udp_send(
+        &self,
+        context: &Context<'_>,
+        sock: SocketId,
+        data: &[u8],
+    ) -> Result<usize, FnError> {
+        let request = format!("udp_send {sock} {}", hex::encode(data));
+        context
+            .network_mode()
+            .interact(request, async {
+                Ok::<_, SocketError>(self.udp(sock)?.send(data).await?)
+            })
+            .await
+    }
+
+    /// Receives a single datagram of at most `len` bytes. Returns empty data when nothing
+    /// arrives within `timeout` seconds.
+    #[nasl_function(maybe_named(sock), named(len, timeout))]
+    async fn udp_recv(
+        &self,
+        context: &Context<'_>,
+        sock: SocketId,
+        len: Option<usize>,
+        timeout: Option<i64>,
+    ) -> Result<NaslValue, FnError> {
+        let len = len.unwrap_or(udp::MAX_DATAGRAM_SIZE);
+        let data = context
+            .network_mode()
+            .interact(format!("udp_recv {sock} {len}"), async {
+                udp::recv(self.udp(sock)?, len, timeout_or_default(timeout)).await
+            })
+            .await?;
+        Ok(NaslValue::Data(data))
+    }
+
+    /// Closes the socket.
+    #[nasl_function(maybe_named(sock))]
+    async fn udp_close(&mut self, context: &Context<'_>, sock: SocketId) -> Result<(), FnError> {
+        context
+            .network_mode()
+            .interact(format!("udp_close {sock}"), async {
+                self.udp
+                    .remove(&sock)
+                    .map(|_| ())
+                    .ok_or(SocketError::SocketClosed(sock))
+            })
+            .await
+    }
+}
+
+impl AsyncSockets {
+    /// Opens a TCP connection to the given host and port and returns its id.
+    #[nasl_function(maybe_named(host, port), named(timeout))]
+    async fn tcp_open(
+        &mut self,
+        context: &Context<'_>,
+        host: &str,
+        port: Port,
+        timeout: Option<i64>,
+    ) -> Result<SocketId, FnError> {
+        let mut stream = None;
+        context
+            .network_mode()
+            .interact(format!("tcp_open {host} {}", port.0), async {
+                let addr = resolve(host, port.0).await?;
+                stream = Some(tcp::connect(addr, timeout_or_default(timeout)).await?);
+                Ok::<_, SocketError>(())
+            })
+            .await?;
+        let id = self.next_id();
+        if let Some(stream) = stream {
+            self.tcp.insert(id, tcp::Stream::Tcp(stream));
+        }
+        Ok(id)
+    }
+
+    /// Sends all of the data and returns the number of bytes sent.
+    #[nasl_function(maybe_named(sock, data))]
+    async fn tcp_send(
+        &mut self,
+        context: &Context<'_>,
+        sock: SocketId,
+        data: &[u8],
+    ) -> Result<usize, FnError> {
+        let request = format!("tcp_send {sock} {}", hex::encode(data));
+        context
+            .network_mode()
+            .interact(request, async {
+                self.tcp(sock)?.write_all(data).await?;
+                Ok::<_, SocketError>(data.len())
+            })
+            .await
+    }
+
+    /// Receives up to `len` bytes or, without `len`, everything until `timeout` seconds passed
+    /// or the peer closed the connection.
+    #[nasl_function(maybe_named(sock), named(len, timeout))]
+    async fn tcp_recv(
+        &mut self,
+        context: &Context<'_>,
+        sock: SocketId,
+        len: Option<usize>,
+        timeout: Option<i64>,
+    ) -> Result<NaslValue, FnError> {
+        let data = context
+            .network_mode()
+            .interact(format!("tcp_recv {sock} {len:?}"), async {
+                tcp::recv(self.tcp(sock)?, len, timeout_or_default(timeout)).await
+            })
+            .await?;
+        Ok(NaslValue::Data(data))
+    }
+
+    /// Closes the connection.
+    #[nasl_function(maybe_named(sock))]
+    async fn tcp_close(&mut self, context: &Context<'_>, sock: SocketId) -> Result<(), FnError> {
+        context
+            .network_mode()
+            .interact(format!("tcp_close {sock}"), async {
+                let mut stream = self
+                    .tcp
+                    .remove(&sock)
+                    .ok_or(SocketError::SocketClosed(sock))?;
+                // The socket is dropped either way, a failed shutdown only means that the peer is
+                // gone.
+                let _ = stream.shutdown().await;
+                Ok::<_, SocketError>(())
+            })
+            .await
+    }
+}
+
+impl AsyncSockets {
+    fn tls(&mut self, id: SocketId) -> Result<&TlsStream<TcpStream>, SocketError> {
+        match self.tcp(id)? {
+            tcp::Stream::Tls(x) => Ok(x),
+            tcp::Stream::Tcp(_) => Err(SocketError::NotTls(id)),
+        }
+    }
+
+    /// Starts a TLS session on the TCP connection `sock`.
+    ///
+    /// Returns a new id to be used with `tcp_send` and `tcp_recv`, `sock` is no longer valid
+    /// afterwards. The versions default to TLSv1.2 and TLSv1.3 respectively. With `verify`
+    /// set to FALSE any certificate is accepted.
+    #[nasl_function(maybe_named(sock), named(sni, min_version, max_version, verify))]
+    async fn tls_connect(
+        &mut self,
+        context: &Context<'_>,
+        sock: SocketId,
+        sni: &str,
+        min_version: Option<tls::TlsVersion>,
+        max_version: Option<tls::TlsVersion>,
+        verify: Option<bool>,
+    ) -> Result<SocketId, FnError> {
+        let min_version = min_version.unwrap_or(tls::TLS12);
+        let max_version = max_version.unwrap_or(tls::TLS13);
+        let verify = verify.unwrap_or(true);
+        let request = format!("tls_connect {sock} {sni} {min_version:?} {max_version:?} {verify}");
+        let mut stream = None;
+        context
+            .network_mode()
+            .interact(request, async {
+                let tcp = match self.tcp.remove(&sock) {
+                    Some(tcp::Stream::Tcp(x)) => x,
+                    Some(x @ tcp::Stream::Tls(_)) => {
+                        self.tcp.insert(sock, x);
+                        return Err(SocketError::WrongArgument(format!(
+                            "Socket {sock} is already using TLS."
+                        )));
+                    }
+                    None => return Err(SocketError::SocketClosed(sock)),
+                };
+                stream = Some(tls::connect(tcp, sni, min_version, max_version, verify).await?);
+                Ok(())
+            })
+            .await?;
+        let id = self.next_id();
+        if let Some(stream) = stream {
+            self.tcp.insert(id, tcp::Stream::Tls(Box::new(stream)));
+        }
+        Ok(id)
+    }
+
+    /// Returns the negotiated protocol version of a TLS connection, e.g. `TLSv1.3`.
+    #[nasl_function(maybe_named(sock))]
+    async fn tls_version(
+        &mut self,
+        context: &Context<'_>,
+        sock: SocketId,
+    ) -> Result<Option<String>, FnError> {
+        context
+            .network_mode()
+            .interact(format!("tls_version {sock}"), async {
+                let (_, connection) = self.tls(sock)?.get_ref();
+                Ok::<_, SocketError>(connection.protocol_version().map(tls::version_name))
+            })
+            .await
+    }
+
+    /// Returns the name of the negotiated cipher suite of a TLS connection.
+    #[nasl_function(maybe_named(sock))]
+    async fn tls_cipher(
+        &mut self,
+        context: &Context<'_>,
+        sock: SocketId,
+    ) -> Result<Option<String>, FnError> {
+        context
+            .network_mode()
+            .interact(format!("tls_cipher {sock}"), async {
+                let (_, connection) = self.tls(sock)?.get_ref();
+                Ok::<_, SocketError>(
+                    connection
+                        .negotiated_cipher_suite()
+                        .map(|x| format!("{:?}", x.suite())),
+                )
+            })
+            .await
+    }
+
+    /// Returns the DER encoded certificates sent by the peer, starting with the leaf.
+    #[nasl_function(maybe_named(sock))]
+    async fn tls_peer_certs(
+        &mut self,
+        context: &Context<'_>,
+        sock: SocketId,
+    ) -> Result<NaslValue, FnError> {
+        let certs: Vec<Vec<u8>> = context
+            .network_mode()
+            .interact(format!("tls_peer_certs {sock}"), async {
+                let (_, connection) = self.tls(sock)?.get_ref();
+                Ok::<_, SocketError>(
+                    connection
+                        .peer_certificates()
+                        .unwrap_or_default()
+                        .iter()
+                        .map(|cert| cert.to_vec())
+                        .collect(),
+                )
+            })
+            .await?;
+        Ok(NaslValue::from(
+            certs.into_iter().map(NaslValue::Data).collect::<Vec<_>>(),
+        ))
+    }
+}
+
+impl AsyncSockets {
+    /// Sends an HTTP/1.1 request over the TCP or TLS connection `sock`.
+    ///
+    /// `headers` is an array mapping header names to values. A `Host` header containing the
+    /// address of the peer is added if missing, as is the `Content-Length` of a given `body`.
+    /// Returns the number of bytes sent.
+    #[nasl_function(maybe_named(sock), named(method, path, headers, body))]
+    async fn http_request(
+        &mut self,
+        context: &Context<'_>,
+        sock: SocketId,
+        method: Option<&str>,
+        path: Option<&str>,
+        headers: Option<HashMap<String, String>>,
+        body: Option<&NaslValue>,
+    ) -> Result<usize, FnError> {
+        let body = match body {
+            None => vec![],
+            Some(NaslValue::String(x)) => x.as_bytes().to_vec(),
+            Some(NaslValue::Data(x)) => x.clone(),
+            Some(x) => {
+                return Err(
+                    ArgumentError::wrong_argument("body", "string or data", &x.to_string()).into(),
+                )
+            }
+        };
+        let method = method.unwrap_or("GET");
+        let path = path.unwrap_or("/");
+        let headers = headers.unwrap_or_default();
+        let description = format!(
+            "http_request {sock} {method} {path} {:?} {}",
+            headers.iter().collect::<BTreeMap<_, _>>(),
+            hex::encode(&body)
+        );
+        context
+            .network_mode()
+            .interact(description, async {
+                let stream = self.tcp(sock)?;
+                let host = stream.peer_addr().map_err(SocketError::from)?.to_string();
+                let request = http::build_request(method, path, &host, &headers, &body)?;
+                stream
+                    .write_all(&request)
+                    .await
+                    .map_err(SocketError::from)?;
+                Ok::<_, FnError>(request.len())
+            })
+            .await
+    }
+
+    /// Reads an HTTP/1.1 response from `sock`.
+    ///
+    /// Returns an array with the keys `version`, `status`, `reason`, `headers` and `body`.
+    /// Fails when the response is malformed or not completely received within `timeout`
+    /// seconds.
+    #[nasl_function(maybe_named(sock), named(timeout))]
+    async fn http_response(
+        &mut self,
+        context: &Context<'_>,
+        sock: SocketId,
+        timeout: Option<i64>,
+    ) -> Result<NaslValue, FnError> {
+        let response: http::Response = context
+            .network_mode()
+            .interact(format!("http_response {sock}"), async {
+                let stream = self.tcp(sock)?;
+                Ok::<_, FnError>(http::read_response(stream, timeout_or_default(timeout)).await?)
+            })
+            .await?;
+        Ok(response.into())
+    }
+}
+
+function_set! {
+    AsyncSockets,
+    (
+        (AsyncSockets::udp_open, "udp_open"),
+        (AsyncSockets::udp_send, "udp_send"),
+        (AsyncSockets::udp_recv, "udp_recv"),
+        (AsyncSockets::udp_close, "udp_close"),
+        (AsyncSockets::tcp_open, "tcp_open"),
+        (AsyncSockets::tcp_send, "tcp_send"),
+        (AsyncSockets::tcp_recv, "tcp_recv"),
+        (AsyncSockets::tcp_close, "tcp_close"),
+        (AsyncSockets::tls_connect, "tls_connect"),
+        (AsyncSockets::tls_version, "tls_version"),
+        (AsyncSockets::tls_cipher, "tls_cipher"),
+        (AsyncSockets::tls_peer_certs, "tls_peer_certs"),
+        (AsyncSockets::http_request, "http_request"),
+        (AsyncSockets::http_response, "http_response"),
+    )
+}
@@ -0,0 +1,118 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+use std::io;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, ReadBuf};
+use tokio::net::TcpStream;
+use tokio::time::Instant;
+use tokio_rustls::client::TlsStream;
+
+use super::super::socket::SocketError;
+
+/// Size of the chunks read when no length is given.
+const CHUNK_SIZE: usize = 4096;
+
+/// Connects to the given address.
+///
+/// A refused connection and a connection attempt that did not finish within the timeout are
+/// reported as different errors.
+pub async fn connect(addr: SocketAddr, timeout: Duration) -> Result<TcpStream, SocketError> {
+    match tokio::time::timeout(timeout, TcpStream::connect(addr)).await {
+        Err(_) => Err(SocketError::ConnectTimeout(addr)),
+        Ok(Err(e)) if e.kind() == io::ErrorKind::ConnectionRefused => {
+            Err(SocketError::ConnectionRefused(addr))
+        }
+        Ok(Err(e)) => Err(e.into()),
+        Ok(Ok(stream)) => Ok(stream),
+    }
+}
+
+/// Reads from the stream until either `len` bytes are read, the peer closed the connection or
+/// the timeout expired. Without a `len`, reads until the peer closed the connection or the
+/// timeout expired.
+///
+/// The data read so far is returned when the timeout expires, so an empty result means that
+/// nothing was received in time.
+pub async fn recv<S: AsyncRead + Unpin>(
+    stream: &mut S,
+    len: Option<usize>,
+    timeout: Duration,
+) -> Result<Vec<u8>, SocketError> {
+    let deadline = Instant::now() + timeout;
+    let mut data = vec![];
+    let mut chunk = vec![0; len.unwrap_or(CHUNK_SIZE).min(CHUNK_SIZE)];
+    loop {
+        let wanted = match len {
+            Some(len) if data.len() >= len => break,
+            Some(len) => (len - data.len()).min(chunk.len()),
+            None => chunk.len(),
+        };
+        match tokio::time::timeout_at(deadline, stream.read(&mut chunk[..wanted])).await {
+            Err(_) | Ok(Ok(0)) => break,
+            Ok(Ok(n)) => data.extend_from_slice(&chunk[..n]),
+            Ok(Err(e)) => return Err(e.into()),
+        }
+    }
+    Ok(data)
+}
+
+/// A TCP connection with an optional TLS session on top.
+pub enum Stream {
+    Tcp(TcpStream),
+    Tls(Box<TlsStream<TcpStream>>),
+}
+
+impl Stream {
+    pub fn peer_addr(&self) -> io::Result<SocketAddr> {
+        match self {
+            Stream::Tcp(x) => x.peer_addr(),
+            Stream::Tls(x) => x.get_ref().0.peer_addr(),
+        }
+    }
+}
+
+impl AsyncRead for Stream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            Stream::Tcp(x) => Pin::new(x).poll_read(cx, buf),
+            Stream::Tls(x) => Pin::new(x.as_mut()).poll_read(cx, buf),
+        }
+    }
+}
+
+impl AsyncWrite for Stream {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        match self.get_mut() {
+            Stream::Tcp(x) => Pin::new(x).poll_write(cx, buf),
+            Stream::Tls(x) => Pin::new(x.as_mut()).poll_write(cx, buf),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            Stream::Tcp(x) => Pin::new(x).poll_flush(cx),
+            Stream::Tls(x) => Pin::new(x.as_mut()).poll_flush(cx),
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.get_mut() {
+            Stream::Tcp(x) => Pin::new(x).poll_shutdown(cx),
+            Stream::Tls(x) => Pin::new(x.as_mut()).poll_shutdown(cx),
+        }
+    }
+}
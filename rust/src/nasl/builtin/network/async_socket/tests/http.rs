@@ -0,0 +1,147 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+use crate::nasl::builtin::http::HttpError;
+use crate::nasl::test_prelude::*;
+
+/// Starts a server which reads a request and answers with the response
+/// returned by `respond`, which is given the received bytes.
+async fn http_server(respond: fn(&[u8]) -> Vec<u8>) -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                let mut request = vec![];
+                let mut buf = [0; 1024];
+                while !request.windows(4).any(|x| x == b"\r\n\r\n") {
+                    match stream.read(&mut buf).await {
+                        Ok(0) | Err(_) => return,
+                        Ok(n) => request.extend_from_slice(&buf[..n]),
+                    }
+                }
+                let _ = stream.write_all(&respond(&request)).await;
+                // Keep the connection open so that framing, not EOF, ends the response.
+                let _ = stream.read(&mut buf).await;
+            });
+        }
+    });
+    port
+}
+
+fn echo_request(request: &[u8]) -> Vec<u8> {
+    let mut response = format!(
+        "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nX-Test: a\r\nx-test: b\r\n\r\n",
+        request.len()
+    )
+    .into_bytes();
+    response.extend_from_slice(request);
+    response
+}
+
+fn chunked(_: &[u8]) -> Vec<u8> {
+    b"HTTP/1.1 201 Created\r\nTransfer-Encoding: chunked\r\n\r\n\
+      5\r\nhello\r\n7;ext=1\r\n, world\r\n0\r\nTrailer: x\r\n\r\n"
+        .to_vec()
+}
+
+fn malformed(_: &[u8]) -> Vec<u8> {
+    b"HTTP/1.1 abc OK\r\n\r\n".to_vec()
+}
+
+fn truncated(_: &[u8]) -> Vec<u8> {
+    b"HTTP/1.1 200 OK\r\nContent-Length: 100\r\n\r\nshort".to_vec()
+}
+
+#[tokio::test]
+async fn http_content_length() {
+    let port = http_server(echo_request).await;
+    let mut t = TestBuilder::default();
+    t.run(format!(
+        r#"sock = tcp_open("127.0.0.1", {port}, timeout: 1);"#
+    ));
+    let request = format!(
+        "POST /index.html HTTP/1.1\r\nHost: 127.0.0.1:{port}\r\nContent-Length: 3\r\nX-Custom: 1\r\n\r\n"
+    );
+    t.ok(
+        r#"http_request(sock, method: "POST", path: "/index.html", headers: make_array("X-Custom", "1"), body: "abc");"#,
+        request.len() + 3,
+    );
+    t.run("r = http_response(sock, timeout: 1);");
+    t.ok(r#"r["status"];"#, 200);
+    t.ok(r#"r["reason"];"#, "OK");
+    t.ok(r#"r["version"];"#, "HTTP/1.1");
+    t.run(r#"h = r["headers"];"#);
+    t.ok(r#"h["x-test"];"#, "a, b");
+    t.ok(
+        r#"r["body"];"#,
+        NaslValue::Data(format!("{request}abc").into_bytes()),
+    );
+    t.async_verify().await;
+}
+
+#[tokio::test]
+async fn http_chunked() {
+    let port = http_server(chunked).await;
+    let mut t = TestBuilder::default();
+    t.run(format!(
+        r#"sock = tcp_open("127.0.0.1", {port}, timeout: 1);"#
+    ));
+    t.run("http_request(sock);");
+    t.run("r = http_response(sock, timeout: 1);");
+    t.ok(r#"r["status"];"#, 201);
+    t.ok(r#"r["body"];"#, NaslValue::Data(b"hello, world".to_vec()));
+    t.async_verify().await;
+}
+
+#[tokio::test]
+async fn http_malformed_response() {
+    let port = http_server(malformed).await;
+    let mut t = TestBuilder::default();
+    t.run(format!(
+        r#"sock = tcp_open("127.0.0.1", {port}, timeout: 1);"#
+    ));
+    t.run("http_request(sock);");
+    check_err_matches!(
+        t,
+        "http_response(sock, timeout: 1);",
+        HttpError::MalformedResponse(_)
+    );
+    t.async_verify().await;
+}
+
+#[tokio::test]
+async fn http_truncated_response_times_out() {
+    let port = http_server(truncated).await;
+    let mut t = TestBuilder::default();
+    t.run(format!(
+        r#"sock = tcp_open("127.0.0.1", {port}, timeout: 1);"#
+    ));
+    t.run("http_request(sock);");
+    check_err_matches!(t, "http_response(sock, timeout: 1);", HttpError::Timeout);
+    t.async_verify().await;
+}
+
+#[tokio::test]
+async fn http_invalid_request() {
+    let port = http_server(echo_request).await;
+    let mut t = TestBuilder::default();
+    t.run(format!(
+        r#"sock = tcp_open("127.0.0.1", {port}, timeout: 1);"#
+    ));
+    check_err_matches!(
+        t,
+        r#"http_request(sock, headers: make_array("X-Evil", 'a\r\nb'));"#,
+        ArgumentError::WrongArgument(_)
+    );
+    check_err_matches!(
+        t,
+        r#"http_request(sock, path: "/a b");"#,
+        ArgumentError::WrongArgument(_)
+    );
+    t.async_verify().await;
+}
@@ -0,0 +1,8 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+mod http;
+mod tcp;
+mod tls;
+mod udp;
@@ -0,0 +1,114 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+use crate::nasl::builtin::network::async_socket::MIN_SOCKET_ID;
+use crate::nasl::builtin::network::socket::SocketError;
+use crate::nasl::test_prelude::*;
+use crate::nasl::utils::recording::{NetworkMode, Recorder, RecordingError, Replayer};
+
+/// Starts a server echoing everything back on each accepted connection.
+async fn echo_server() -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move {
+        while let Ok((mut stream, _)) = listener.accept().await {
+            tokio::spawn(async move {
+                let mut buf = [0; 1024];
+                while let Ok(n) = stream.read(&mut buf).await {
+                    if n == 0 || stream.write_all(&buf[..n]).await.is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+    });
+    port
+}
+
+#[tokio::test]
+async fn tcp_round_trip() {
+    let port = echo_server().await;
+    let mut t = TestBuilder::default();
+    t.ok(
+        format!(r#"sock = tcp_open("127.0.0.1", {port}, timeout: 1);"#),
+        MIN_SOCKET_ID,
+    );
+    t.ok("tcp_send(sock, 'hello world');", 11);
+    t.ok(
+        "tcp_recv(sock, len: 5, timeout: 1);",
+        NaslValue::Data(b"hello".to_vec()),
+    );
+    // without a length everything until the timeout is returned
+    t.ok(
+        "tcp_recv(sock, timeout: 1);",
+        NaslValue::Data(b" world".to_vec()),
+    );
+    t.ok("tcp_recv(sock, timeout: 1);", NaslValue::Data(vec![]));
+    t.ok("tcp_close(sock);", NaslValue::Null);
+    check_err_matches!(t, "tcp_close(sock);", SocketError::SocketClosed(_));
+    check_err_matches!(t, "tcp_send(sock, 'a');", SocketError::WrongArgument(_));
+    t.async_verify().await;
+}
+
+#[tokio::test]
+async fn tcp_connection_refused() {
+    // Bind and drop a listener to get a port nobody listens on.
+    let port = TcpListener::bind("127.0.0.1:0")
+        .await
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let mut t = TestBuilder::default();
+    check_err_matches!(
+        t,
+        format!(r#"tcp_open("127.0.0.1", {port}, timeout: 1);"#),
+        SocketError::ConnectionRefused(_)
+    );
+    t.async_verify().await;
+}
+
+fn echo_session(t: &mut DefaultTestBuilder, port: u16) {
+    t.ok(
+        format!(r#"sock = tcp_open("127.0.0.1", {port}, timeout: 1);"#),
+        MIN_SOCKET_ID,
+    );
+    t.ok("tcp_send(sock, 'hello');", 5);
+    t.ok(
+        "tcp_recv(sock, len: 5, timeout: 1);",
+        NaslValue::Data(b"hello".to_vec()),
+    );
+    t.ok("tcp_close(sock);", NaslValue::Null);
+}
+
+#[tokio::test]
+async fn tcp_record_and_replay() {
+    let path = std::env::temp_dir().join(format!("tcp_recording_{}.jsonl", std::process::id()));
+    let port = echo_server().await;
+    let recorder = Recorder::create(&path).unwrap();
+    let mut t = TestBuilder::default().with_network_mode(NetworkMode::Record(recorder));
+    echo_session(&mut t, port);
+    check_err_matches!(t, "tcp_close(sock);", SocketError::SocketClosed(_));
+    t.async_verify().await;
+
+    let replayer = Replayer::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let mut t = TestBuilder::default().with_network_mode(NetworkMode::Replay(replayer));
+    echo_session(&mut t, port);
+    // The error is replayed with the message it was recorded with.
+    check_err_matches!(
+        t,
+        "tcp_close(sock);",
+        InternalError::Recording(RecordingError::Failed(..)),
+    );
+    check_err_matches!(
+        t,
+        "tcp_close(sock);",
+        InternalError::Recording(RecordingError::Exhausted(_)),
+    );
+    t.async_verify().await;
+}
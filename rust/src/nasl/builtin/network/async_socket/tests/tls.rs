@@ -0,0 +1,170 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+use std::sync::Arc;
+
+use rcgen::{BasicConstraints, CertificateParams, DnType, IsCa, KeyPair};
+use rustls::pki_types::{CertificateDer, PrivateKeyDer, PrivatePkcs8KeyDer};
+use rustls::ServerConfig;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio_rustls::TlsAcceptor;
+
+use crate::nasl::builtin::network::socket::SocketError;
+use crate::nasl::test_prelude::*;
+
+/// A certificate chain consisting of a leaf for `localhost` and an intermediate, without the
+/// root, as a server would send it.
+struct Chain {
+    certs: Vec<CertificateDer<'static>>,
+    key: PrivateKeyDer<'static>,
+}
+
+fn ca_params(name: &str) -> CertificateParams {
+    let mut params = CertificateParams::new(vec![]).unwrap();
+    params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+    params.distinguished_name.push(DnType::CommonName, name);
+    params
+}
+
+fn chain() -> Chain {
+    let root_key = KeyPair::generate().unwrap();
+    let root = ca_params("Test Root").self_signed(&root_key).unwrap();
+    let intermediate_key = KeyPair::generate().unwrap();
+    let intermediate = ca_params("Test Intermediate")
+        .signed_by(&intermediate_key, &root, &root_key)
+        .unwrap();
+    let leaf_key = KeyPair::generate().unwrap();
+    let leaf = CertificateParams::new(vec!["localhost".to_string()])
+        .unwrap()
+        .signed_by(&leaf_key, &intermediate, &intermediate_key)
+        .unwrap();
+    Chain {
+        certs: vec![leaf.der().clone(), intermediate.der().clone()],
+        key: PrivateKeyDer::Pkcs8(PrivatePkcs8KeyDer::from(leaf_key.serialize_der())),
+    }
+}
+
+/// Starts a TLS server echoing everything back and returns its port.
+async fn tls_echo_server(chain: Chain) -> u16 {
+    let config = ServerConfig::builder()
+        .with_no_client_auth()
+        .with_single_cert(chain.certs, chain.key)
+        .unwrap();
+    let acceptor = TlsAcceptor::from(Arc::new(config));
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            let acceptor = acceptor.clone();
+            tokio::spawn(async move {
+                let Ok(mut stream) = acceptor.accept(stream).await else {
+                    return;
+                };
+                let mut buf = [0; 1024];
+                while let Ok(n) = stream.read(&mut buf).await {
+                    if n == 0 || stream.write_all(&buf[..n]).await.is_err() {
+                        break;
+                    }
+                    let _ = stream.flush().await;
+                }
+            });
+        }
+    });
+    port
+}
+
+#[tokio::test]
+async fn tls_round_trip() {
+    let port = tls_echo_server(chain()).await;
+    let mut t = TestBuilder::default();
+    t.run(format!(r#"sock = tcp_open("127.0.0.1", {port});"#));
+    t.check(
+        r#"tls = tls_connect(sock, sni: "localhost", verify: FALSE);"#,
+        |r| matches!(r, Ok(NaslValue::Number(_))),
+        Some("a new socket id"),
+    );
+    t.ok("tls_version(tls);", "TLSv1.3");
+    t.check(
+        "tls_cipher(tls);",
+        |r| matches!(r, Ok(NaslValue::String(x)) if x.starts_with("TLS13_")),
+        Some("a TLSv1.3 cipher suite"),
+    );
+    t.ok("tcp_send(tls, 'hello');", 5);
+    t.ok(
+        "tcp_recv(tls, len: 5, timeout: 1);",
+        NaslValue::Data(b"hello".to_vec()),
+    );
+    // the TCP socket is now owned by the TLS session
+    check_err_matches!(t, "tcp_send(sock, 'a');", SocketError::WrongArgument(_));
+    t.ok("tcp_close(tls);", NaslValue::Null);
+    t.async_verify().await;
+}
+
+#[tokio::test]
+async fn tls_max_version() {
+    let port = tls_echo_server(chain()).await;
+    let mut t = TestBuilder::default();
+    t.run(format!(r#"sock = tcp_open("127.0.0.1", {port});"#));
+    t.run(r#"tls = tls_connect(sock, sni: "localhost", max_version: "TLSv1.2", verify: FALSE);"#);
+    t.ok("tls_version(tls);", "TLSv1.2");
+    t.ok("tcp_send(tls, 'hello');", 5);
+    t.ok(
+        "tcp_recv(tls, len: 5, timeout: 1);",
+        NaslValue::Data(b"hello".to_vec()),
+    );
+    t.async_verify().await;
+}
+
+#[tokio::test]
+async fn tls_verify_rejects_unknown_issuer() {
+    let port = tls_echo_server(chain()).await;
+    let mut t = TestBuilder::default();
+    t.run(format!(r#"sock = tcp_open("127.0.0.1", {port});"#));
+    check_err_matches!(
+        t,
+        r#"tls_connect(sock, sni: "localhost");"#,
+        SocketError::Tls(_)
+    );
+    t.async_verify().await;
+}
+
+#[tokio::test]
+async fn tls_functions_require_tls_socket() {
+    let port = tls_echo_server(chain()).await;
+    let mut t = TestBuilder::default();
+    t.run(format!(r#"sock = tcp_open("127.0.0.1", {port});"#));
+    check_err_matches!(t, "tls_version(sock);", SocketError::NotTls(_));
+    check_err_matches!(t, "tls_peer_certs(sock);", SocketError::NotTls(_));
+    check_err_matches!(
+        t,
+        r#"tls_connect(sock, sni: "localhost", min_version: "TLSv1.0");"#,
+        ArgumentError::WrongArgument(_)
+    );
+    t.async_verify().await;
+}
+
+#[tokio::test]
+async fn tls_peer_certs_returns_chain() {
+    let chain = chain();
+    let expected: Vec<_> = chain
+        .certs
+        .iter()
+        .map(|x| NaslValue::Data(x.to_vec()))
+        .collect();
+    let port = tls_echo_server(chain).await;
+    let mut t = TestBuilder::default();
+    t.run(format!(r#"sock = tcp_open("127.0.0.1", {port});"#));
+    t.run(r#"tls = tls_connect(sock, sni: "localhost", verify: FALSE);"#);
+    // leaf followed by the intermediate
+    t.ok("tls_peer_certs(tls);", NaslValue::from(expected));
+    t.run("certs = tls_peer_certs(tls);");
+    t.run("leaf = cert_open(certs[0]);");
+    t.check(
+        r#"cert_query(leaf, "hostnames");"#,
+        |r| matches!(r, Ok(NaslValue::Array(x)) if x.contains(&NaslValue::String("localhost".into()))),
+        Some("hostnames containing localhost"),
+    );
+    t.async_verify().await;
+}
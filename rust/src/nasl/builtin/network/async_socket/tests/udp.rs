@@ -0,0 +1,79 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+use tokio::net::UdpSocket;
+
+use crate::nasl::builtin::network::async_socket::MIN_SOCKET_ID;
+use crate::nasl::builtin::network::socket::SocketError;
+use crate::nasl::test_prelude::*;
+
+/// Starts a server echoing every datagram back to its sender.
+async fn echo_server() -> u16 {
+    let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+    let port = socket.local_addr().unwrap().port();
+    tokio::spawn(async move {
+        let mut buf = [0; 1024];
+        while let Ok((n, addr)) = socket.recv_from(&mut buf).await {
+            socket.send_to(&buf[..n], addr).await.unwrap();
+        }
+    });
+    port
+}
+
+#[tokio::test]
+async fn udp_round_trip() {
+    let port = echo_server().await;
+    let mut t = TestBuilder::default();
+    t.ok(
+        format!(r#"sock = udp_open("127.0.0.1", {port});"#),
+        MIN_SOCKET_ID,
+    );
+    t.ok(r#"udp_send(sock, 'hello');"#, 5);
+    t.ok(
+        "udp_recv(sock, timeout: 1);",
+        NaslValue::Data(b"hello".to_vec()),
+    );
+    t.ok(r#"udp_send(sock: sock, data: 'hello');"#, 5);
+    t.ok(
+        "udp_recv(sock, len: 2, timeout: 1);",
+        NaslValue::Data(b"he".to_vec()),
+    );
+    t.ok(r#"udp_send(sock, 'hello');"#, 5);
+    t.ok(
+        "udp_recv(sock, len: 1099511627776, timeout: 1);",
+        NaslValue::Data(b"hello".to_vec()),
+    );
+    t.ok("udp_close(sock);", NaslValue::Null);
+    check_err_matches!(t, "udp_send(sock, 'a');", SocketError::WrongArgument(_));
+    t.async_verify().await;
+}
+
+#[tokio::test]
+async fn udp_recv_timeout_returns_empty_data() {
+    let port = echo_server().await;
+    let mut t = TestBuilder::default();
+    t.run(format!(r#"sock = udp_open("127.0.0.1", {port});"#));
+    t.ok("udp_recv(sock, timeout: 1);", NaslValue::Data(vec![]));
+    t.async_verify().await;
+}
+
+#[tokio::test]
+async fn udp_port_unreachable() {
+    // Bind and drop a socket to get a port nobody listens on.
+    let port = UdpSocket::bind("127.0.0.1:0")
+        .await
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let mut t = TestBuilder::default();
+    t.run(format!(r#"sock = udp_open("127.0.0.1", {port});"#));
+    t.ok(r#"udp_send(sock, 'hello');"#, 5);
+    check_err_matches!(
+        t,
+        "udp_recv(sock, timeout: 1);",
+        SocketError::PortUnreachable(_)
+    );
+    t.async_verify().await;
+}
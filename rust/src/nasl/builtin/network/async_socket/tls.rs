@@ -0,0 +1,112 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+use std::sync::Arc;
+
+use rustls::pki_types::ServerName;
+use rustls::{ClientConfig, ProtocolVersion, RootCertStore, SupportedProtocolVersion};
+use tokio::net::TcpStream;
+use tokio_rustls::client::TlsStream;
+use tokio_rustls::TlsConnector;
+
+use crate::nasl::prelude::*;
+
+use super::super::super::http::NoVerifier;
+use super::super::{socket::SocketError, OpenvasEncaps};
+
+/// A TLS version given by a script, either as name (`"TLSv1.2"`) or as one of the
+/// `ENCAPS_TLSv*` numbers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TlsVersion(u8);
+
+pub const TLS12: TlsVersion = TlsVersion(2);
+pub const TLS13: TlsVersion = TlsVersion(3);
+
+impl TlsVersion {
+    fn supported(&self) -> &'static SupportedProtocolVersion {
+        match *self {
+            TLS12 => &rustls::version::TLS12,
+            _ => &rustls::version::TLS13,
+        }
+    }
+}
+
+impl FromNaslValue<'_> for TlsVersion {
+    fn from_nasl_value(value: &NaslValue) -> Result<Self, FnError> {
+        let unsupported = || -> FnError {
+            ArgumentError::WrongArgument(format!(
+                "Unsupported TLS version {value}. Only TLSv1.2 and TLSv1.3 are supported."
+            ))
+            .into()
+        };
+        match value {
+            NaslValue::Number(x) => match OpenvasEncaps::from_i64(*x) {
+                Some(OpenvasEncaps::Tls12) => Ok(TLS12),
+                Some(OpenvasEncaps::Tls13) => Ok(TLS13),
+                _ => Err(unsupported()),
+            },
+            NaslValue::String(x) => match x.as_str() {
+                "TLSv1.2" => Ok(TLS12),
+                "TLSv1.3" => Ok(TLS13),
+                _ => Err(unsupported()),
+            },
+            _ => Err(unsupported()),
+        }
+    }
+}
+
+fn root_store() -> RootCertStore {
+    let mut store = RootCertStore::empty();
+    store.add_parsable_certificates(rustls_native_certs::load_native_certs().certs);
+    store
+}
+
+/// Starts a TLS session on an established TCP connection.
+///
+/// When `verify` is false, any certificate is accepted. The peer certificates are still
+/// available on the returned stream.
+pub async fn connect(
+    stream: TcpStream,
+    sni: &str,
+    min_version: TlsVersion,
+    max_version: TlsVersion,
+    verify: bool,
+) -> Result<TlsStream<TcpStream>, SocketError> {
+    let versions: Vec<_> = [TLS12, TLS13]
+        .into_iter()
+        .filter(|v| (min_version..=max_version).contains(v))
+        .map(|v| v.supported())
+        .collect();
+    if versions.is_empty() {
+        return Err(SocketError::WrongArgument(
+            "min_version must not be greater than max_version.".into(),
+        ));
+    }
+    let builder = ClientConfig::builder_with_protocol_versions(&versions);
+    let config = if verify {
+        builder
+            .with_root_certificates(root_store())
+            .with_no_client_auth()
+    } else {
+        builder
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(NoVerifier))
+            .with_no_client_auth()
+    };
+    let server_name = ServerName::try_from(sni.to_owned())
+        .map_err(|e| SocketError::WrongArgument(format!("Invalid sni {sni}: {e}")))?;
+    TlsConnector::from(Arc::new(config))
+        .connect(server_name, stream)
+        .await
+        .map_err(|e| SocketError::Tls(e.to_string()))
+}
+
+/// Returns the name of the negotiated protocol version, e.g. `TLSv1.3`.
+pub fn version_name(version: ProtocolVersion) -> String {
+    match version {
+        ProtocolVersion::TLSv1_2 => "TLSv1.2".to_string(),
+        ProtocolVersion::TLSv1_3 => "TLSv1.3".to_string(),
+        x => format!("{x:?}"),
+    }
+}
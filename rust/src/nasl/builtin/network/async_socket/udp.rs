@@ -0,0 +1,76 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+use std::io;
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use tokio::io::Interest;
+use tokio::net::UdpSocket;
+
+use super::super::socket::SocketError;
+
+/// The maximum payload of a UDP datagram, used when no length is given.
+pub const MAX_DATAGRAM_SIZE: usize = 65507;
+
+/// No datagram is longer than this, larger lengths requested by a script are reduced to it.
+const MAX_RECV_LEN: usize = 65535;
+
+/// Opens a UDP socket connected to the given address.
+pub async fn open(addr: SocketAddr) -> Result<UdpSocket, SocketError> {
+    let local = if addr.is_ipv4() {
+        "0.0.0.0:0"
+    } else {
+        "[::]:0"
+    };
+    let socket = UdpSocket::bind(local)
+        .await
+        .map_err(|e| SocketError::FailedToBindSocket(e, addr))?;
+    socket.connect(addr).await?;
+    Ok(socket)
+}
+
+/// Receives a single datagram of at most `len` bytes, but never more than 65535.
+///
+/// Returns empty data when nothing was received within the timeout. When the peer rejected a
+/// previously sent datagram with an ICMP port unreachable a `PortUnreachable` error is returned.
+pub async fn recv(
+    socket: &UdpSocket,
+    len: usize,
+    timeout: Duration,
+) -> Result<Vec<u8>, SocketError> {
+    let mut buf = vec![0; len.min(MAX_RECV_LEN)];
+    match tokio::time::timeout(timeout, recv_or_error(socket, &mut buf)).await {
+        Err(_) => Ok(vec![]),
+        Ok(Ok(n)) => {
+            buf.truncate(n);
+            Ok(buf)
+        }
+        Ok(Err(e)) if e.kind() == io::ErrorKind::ConnectionRefused => {
+            Err(SocketError::PortUnreachable(socket.peer_addr()?))
+        }
+        Ok(Err(e)) => Err(e.into()),
+    }
+}
+
+/// Waits for either data or a pending socket error.
+///
+/// An ICMP error only sets the error state of the socket without making it readable, so
+/// waiting for readability alone would never see it.
+async fn recv_or_error(socket: &UdpSocket, buf: &mut [u8]) -> io::Result<usize> {
+    loop {
+        let ready = socket.ready(Interest::READABLE | Interest::ERROR).await?;
+        if ready.is_error() {
+            if let Some(e) = socket.take_error()? {
+                return Err(e);
+            }
+        }
+        if ready.is_readable() {
+            match socket.try_recv(buf) {
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => continue,
+                result => return result,
+            }
+        }
+    }
+}
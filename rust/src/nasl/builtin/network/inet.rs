@@ -0,0 +1,181 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later
+
+//! Conversion and comparison of IP addresses.
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use crate::nasl::prelude::*;
+
+fn parse_ip(arg: &str, ip: &str) -> Result<IpAddr, ArgumentError> {
+    ip.trim()
+        .parse()
+        .map_err(|_| ArgumentError::wrong_argument(arg, "an IPv4 or IPv6 address", ip))
+}
+
+fn parse_ipv4(arg: &str, ip: &str) -> Result<Ipv4Addr, ArgumentError> {
+    ip.trim()
+        .parse()
+        .map_err(|_| ArgumentError::wrong_argument(arg, "an IPv4 address", ip))
+}
+
+/// Returns the network address and the prefix length of a CIDR notation like `192.168.0.0/16`.
+fn parse_cidr(cidr: &str) -> Result<(IpAddr, u32), ArgumentError> {
+    let invalid = || ArgumentError::wrong_argument("cidr", "an address with prefix length", cidr);
+    let (ip, len) = cidr.split_once('/').ok_or_else(invalid)?;
+    let ip = parse_ip("cidr", ip)?;
+    let len: u32 = len.trim().parse().map_err(|_| invalid())?;
+    let max = if ip.is_ipv4() { 32 } else { 128 };
+    if len > max {
+        return Err(invalid());
+    }
+    Ok((ip, len))
+}
+
+fn to_u128(ip: IpAddr) -> u128 {
+    match ip {
+        IpAddr::V4(ip) => u32::from(ip) as u128,
+        IpAddr::V6(ip) => u128::from(ip),
+    }
+}
+
+/// Converts an IPv4 address in dotted notation into a number in host byte order.
+#[nasl_function(maybe_named(ip))]
+fn inet_aton(ip: &str) -> Result<i64, ArgumentError> {
+    Ok(u32::from(parse_ipv4("ip", ip)?) as i64)
+}
+
+/// Converts a number between 0 and 4294967295 into an IPv4 address in dotted notation.
+#[nasl_function(maybe_named(ip))]
+fn inet_ntoa(ip: i64) -> Result<String, ArgumentError> {
+    u32::try_from(ip)
+        .map(|x| Ipv4Addr::from(x).to_string())
+        .map_err(|_| {
+            ArgumentError::wrong_argument(
+                "ip",
+                "a number between 0 and 4294967295",
+                &ip.to_string(),
+            )
+        })
+}
+
+/// Converts an IPv4 or IPv6 address into its 4 or 16 bytes in network byte order.
+#[nasl_function(maybe_named(ip))]
+fn inet_pton(ip: &str) -> Result<Vec<u8>, ArgumentError> {
+    Ok(match parse_ip("ip", ip)? {
+        IpAddr::V4(ip) => ip.octets().to_vec(),
+        IpAddr::V6(ip) => ip.octets().to_vec(),
+    })
+}
+
+/// Converts 4 or 16 bytes in network byte order into an IPv4 or IPv6 address.
+#[nasl_function(maybe_named(ip))]
+fn inet_ntop(ip: &[u8]) -> Result<String, ArgumentError> {
+    if let Ok(ip) = <[u8; 4]>::try_from(ip) {
+        return Ok(Ipv4Addr::from(ip).to_string());
+    }
+    if let Ok(ip) = <[u8; 16]>::try_from(ip) {
+        return Ok(Ipv6Addr::from(ip).to_string());
+    }
+    Err(ArgumentError::wrong_argument(
+        "ip",
+        "4 or 16 bytes",
+        &format!("{} bytes", ip.len()),
+    ))
+}
+
+/// Checks whether `ip` is within the network given in CIDR notation, e.g. `10.0.0.0/8` or
+/// `2001:db8::/32`. An address of the other family is never contained.
+#[nasl_function(maybe_named(cidr, ip))]
+fn cidr_contains(cidr: &str, ip: &str) -> Result<bool, ArgumentError> {
+    let (network, len) = parse_cidr(cidr)?;
+    let ip = parse_ip("ip", ip)?;
+    if network.is_ipv4() != ip.is_ipv4() {
+        return Ok(false);
+    }
+    let bits = if ip.is_ipv4() { 32 } else { 128 };
+    let shift = bits - len;
+    let mask = u128::MAX.checked_shl(shift).unwrap_or(0);
+    Ok(to_u128(network) & mask == to_u128(ip) & mask)
+}
+
+pub struct Inet;
+
+function_set! {
+    Inet,
+    (
+        inet_aton,
+        inet_ntoa,
+        inet_pton,
+        inet_ntop,
+        cidr_contains,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::nasl::test_prelude::*;
+
+    #[test]
+    fn ipv4() {
+        let mut t = TestBuilder::default();
+        t.ok(r#"inet_aton("192.168.1.10");"#, 0xc0a8010a_i64);
+        t.ok(r#"inet_ntoa(3232235786);"#, "192.168.1.10");
+        t.ok(r#"inet_ntoa(inet_aton("0.0.0.0"));"#, "0.0.0.0");
+        t.ok(r#"inet_pton("10.0.0.1");"#, vec![10u8, 0, 0, 1]);
+        t.ok(r#"inet_ntop(raw_string(127, 0, 0, 1));"#, "127.0.0.1");
+    }
+
+    #[test]
+    fn ipv6() {
+        let mut t = TestBuilder::default();
+        t.ok(
+            r#"inet_pton("2001:db8::1");"#,
+            vec![0x20u8, 0x01, 0x0d, 0xb8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1],
+        );
+        t.ok(r#"inet_ntop(inet_pton("2001:DB8:0:0::1"));"#, "2001:db8::1");
+        t.ok(
+            r#"inet_ntop(inet_pton("::ffff:192.0.2.1"));"#,
+            "::ffff:192.0.2.1",
+        );
+    }
+
+    #[test]
+    fn malformed() {
+        let mut t = TestBuilder::default();
+        for code in [
+            r#"inet_aton("192.168.1.256");"#,
+            r#"inet_aton("2001:db8::1");"#,
+            r#"inet_ntoa(4294967296);"#,
+            r#"inet_ntoa(-1);"#,
+            r#"inet_pton("localhost");"#,
+            r#"inet_ntop(raw_string(1, 2, 3));"#,
+            r#"cidr_contains("10.0.0.0", "10.0.0.1");"#,
+            r#"cidr_contains("10.0.0.0/33", "10.0.0.1");"#,
+            r#"cidr_contains("10.0.0.0/8", "10.0.0");"#,
+        ] {
+            check_err_matches!(t, code, ArgumentError::WrongArgument(_));
+        }
+    }
+
+    #[test]
+    fn cidr_contains() {
+        let mut t = TestBuilder::default();
+        t.ok(r#"cidr_contains("10.0.0.0/8", "10.255.1.2");"#, true);
+        t.ok(r#"cidr_contains("10.0.0.0/8", "11.0.0.1");"#, false);
+        t.ok(r#"cidr_contains("192.168.1.0/24", "192.168.1.255");"#, true);
+        t.ok(
+            r#"cidr_contains("192.168.1.128/25", "192.168.1.127");"#,
+            false,
+        );
+        t.ok(r#"cidr_contains("0.0.0.0/0", "1.2.3.4");"#, true);
+        t.ok(r#"cidr_contains("1.2.3.4/32", "1.2.3.4");"#, true);
+        t.ok(
+            r#"cidr_contains(cidr: "2001:db8::/32", ip: "2001:db8:ffff::1");"#,
+            true,
+        );
+        t.ok(r#"cidr_contains("2001:db8::/32", "2001:db9::1");"#, false);
+        t.ok(r#"cidr_contains("10.0.0.0/8", "::ffff:10.0.0.1");"#, false);
+    }
+}
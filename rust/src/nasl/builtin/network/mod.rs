@@ -6,6 +6,8 @@ use std::{fmt::Display, net::IpAddr};
 
 use crate::nasl::prelude::*;
 
+pub mod async_socket;
+pub mod inet;
 #[allow(clippy::module_inception)]
 pub mod network;
 pub mod network_utils;
@@ -58,6 +58,16 @@ pub enum SocketError {
     FailedToBindSocket(io::Error, SocketAddr),
     #[error("No route to destination: {0}.")]
     NoRouteToDestination(IpAddr),
+    #[error("Port unreachable: {0}.")]
+    PortUnreachable(SocketAddr),
+    #[error("Connection refused: {0}.")]
+    ConnectionRefused(SocketAddr),
+    #[error("Timeout while connecting to {0}.")]
+    ConnectTimeout(SocketAddr),
+    #[error("TLS error: {0}")]
+    Tls(String),
+    #[error("Socket {0} is not a TLS connection.")]
+    NotTls(usize),
 }
 
 /// Interval used for timing tcp requests. Any tcp request has to wait at least
@@ -245,10 +255,10 @@ impl NaslSockets {
         let min = min
             .map(|min| if min < 0 { length } else { min as usize })
             .unwrap_or(length);
-        let mut data = vec![0; length];
 
         match self.get_open_socket_mut(socket)? {
             NaslSocket::Tcp(conn) => {
+                let mut data = vec![0; length];
                 let mut pos = match convert_timeout(timeout) {
                     Some(timeout) => conn.read_with_timeout(&mut data, timeout),
                     None => conn.read(&mut data),
@@ -264,6 +274,8 @@ impl NaslSockets {
                 Ok(NaslValue::Data(data[..pos].to_vec()))
             }
             NaslSocket::Udp(conn) => {
+                // No datagram is longer than this.
+                let mut data = vec![0; length.min(65535)];
                 let pos = match convert_timeout(timeout) {
                     Some(timeout) => conn.read_with_timeout(&mut data, timeout),
                     None => conn.read(&mut data),
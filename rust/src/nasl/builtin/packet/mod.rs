@@ -0,0 +1,312 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+#![doc = include_str!("README.md")]
+
+#[cfg(test)]
+mod tests;
+
+use std::collections::BTreeMap;
+
+use crate::nasl::prelude::*;
+use crate::nasl::utils::function::{bytes_to_str, str_to_bytes};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Endian {
+    Big,
+    Little,
+}
+
+/// The type of a field, given as `u8`, `u16`, `u16le`, `u32`, `u32le`, `bytes` or `string`.
+/// Numbers without suffix, as well as those with the suffix `be`, are in network byte order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FieldType {
+    U8,
+    U16(Endian),
+    U32(Endian),
+    Bytes,
+    String,
+}
+
+impl FieldType {
+    fn parse(s: &str) -> Result<Self, ArgumentError> {
+        Ok(match s.to_ascii_lowercase().as_str() {
+            "u8" => FieldType::U8,
+            "u16" | "u16be" => FieldType::U16(Endian::Big),
+            "u16le" => FieldType::U16(Endian::Little),
+            "u32" | "u32be" => FieldType::U32(Endian::Big),
+            "u32le" => FieldType::U32(Endian::Little),
+            "bytes" | "data" => FieldType::Bytes,
+            "string" => FieldType::String,
+            _ => {
+                return Err(ArgumentError::wrong_argument(
+                    "type",
+                    "one of u8, u16, u16le, u32, u32le, bytes or string",
+                    s,
+                ))
+            }
+        })
+    }
+
+    /// The size of a number, None for bytes and strings.
+    fn size(self) -> Option<usize> {
+        match self {
+            FieldType::U8 => Some(1),
+            FieldType::U16(_) => Some(2),
+            FieldType::U32(_) => Some(4),
+            FieldType::Bytes | FieldType::String => None,
+        }
+    }
+}
+
+/// The length of a bytes or string field. In a schema it may refer to a number parsed before.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Len {
+    Fixed(usize),
+    Field(String),
+}
+
+/// A descriptor of a field, given as array with the keys `type`, `value`, `len` and `name`.
+#[derive(Debug)]
+struct Field {
+    name: Option<String>,
+    ty: FieldType,
+    value: Option<NaslValue>,
+    len: Option<Len>,
+}
+
+impl Field {
+    fn from_descriptor(index: usize, descriptor: &NaslValue) -> Result<Self, ArgumentError> {
+        let NaslValue::Dict(descriptor) = descriptor else {
+            return Err(ArgumentError::wrong_argument(
+                &format!("field {index}"),
+                "an array with the keys type, value and len",
+                &descriptor.to_string(),
+            ));
+        };
+        let ty = match descriptor.get("type") {
+            Some(NaslValue::String(x)) => FieldType::parse(x)?,
+            _ => {
+                return Err(ArgumentError::MissingNamed(vec![format!(
+                    "type of field {index}"
+                )]))
+            }
+        };
+        let len = match descriptor.get("len") {
+            None | Some(NaslValue::Null) => None,
+            Some(NaslValue::Number(x)) => Some(Len::Fixed(usize::try_from(*x).map_err(|_| {
+                ArgumentError::wrong_argument("len", "a positive number", &x.to_string())
+            })?)),
+            Some(NaslValue::String(x)) => Some(Len::Field(x.clone())),
+            Some(x) => {
+                return Err(ArgumentError::wrong_argument(
+                    "len",
+                    "a number or the name of a field",
+                    &x.to_string(),
+                ))
+            }
+        };
+        if len.is_some() && ty.size().is_some() {
+            return Err(ArgumentError::wrong_argument(
+                "len",
+                "only given for bytes and strings",
+                &format!("given for field {index}"),
+            ));
+        }
+        Ok(Self {
+            name: descriptor.get("name").map(|x| x.to_string()),
+            ty,
+            value: descriptor.get("value").cloned(),
+            len,
+        })
+    }
+
+    fn description(&self, index: usize) -> String {
+        match &self.name {
+            Some(name) => format!("field {name}"),
+            None => format!("field {index}"),
+        }
+    }
+}
+
+fn fields(value: &NaslValue, key: &str) -> Result<Vec<Field>, ArgumentError> {
+    match value {
+        NaslValue::Array(x) => x
+            .iter()
+            .enumerate()
+            .map(|(i, x)| Field::from_descriptor(i, x))
+            .collect(),
+        x => Err(ArgumentError::wrong_argument(
+            key,
+            "a list of field descriptors",
+            &x.to_string(),
+        )),
+    }
+}
+
+fn encode_number(ty: FieldType, value: i64) -> Option<Vec<u8>> {
+    Some(match ty {
+        FieldType::U8 => vec![u8::try_from(value).ok()?],
+        FieldType::U16(Endian::Big) => u16::try_from(value).ok()?.to_be_bytes().to_vec(),
+        FieldType::U16(Endian::Little) => u16::try_from(value).ok()?.to_le_bytes().to_vec(),
+        FieldType::U32(Endian::Big) => u32::try_from(value).ok()?.to_be_bytes().to_vec(),
+        FieldType::U32(Endian::Little) => u32::try_from(value).ok()?.to_le_bytes().to_vec(),
+        FieldType::Bytes | FieldType::String => return None,
+    })
+}
+
+fn decode_number(ty: FieldType, bytes: &[u8]) -> i64 {
+    match ty {
+        FieldType::U8 => bytes[0] as i64,
+        FieldType::U16(Endian::Big) => u16::from_be_bytes([bytes[0], bytes[1]]) as i64,
+        FieldType::U16(Endian::Little) => u16::from_le_bytes([bytes[0], bytes[1]]) as i64,
+        FieldType::U32(Endian::Big) => u32::from_be_bytes(bytes.try_into().unwrap()) as i64,
+        FieldType::U32(Endian::Little) => u32::from_le_bytes(bytes.try_into().unwrap()) as i64,
+        FieldType::Bytes | FieldType::String => unreachable!("not a number"),
+    }
+}
+
+/// NASL function to assemble a packet from a list of field descriptors.
+///
+/// Each descriptor is an array with the `type` of the field, its `value` and, for bytes and
+/// strings, an optional `len`. The type is one of `u8`, `u16`, `u32`, `u16le`, `u32le`, `bytes`
+/// and `string`. Numbers are in network byte order unless the type has the suffix `le`.
+///
+/// A number which does not fit into its type and a value which differs from the given `len` are
+/// errors.
+#[nasl_function(named(fields))]
+fn build_packet(fields: &NaslValue) -> Result<Vec<u8>, ArgumentError> {
+    let mut packet = vec![];
+    for (index, field) in self::fields(fields, "fields")?.into_iter().enumerate() {
+        let name = field.description(index);
+        match (field.ty, field.value) {
+            (FieldType::Bytes | FieldType::String, Some(value)) => {
+                let bytes = match value {
+                    NaslValue::String(x) => str_to_bytes(&x).into_owned(),
+                    NaslValue::Data(x) => x,
+                    x => {
+                        return Err(ArgumentError::wrong_argument(
+                            &name,
+                            "a string or data",
+                            &x.to_string(),
+                        ))
+                    }
+                };
+                match field.len {
+                    Some(Len::Fixed(len)) if len != bytes.len() => {
+                        return Err(ArgumentError::wrong_argument(
+                            &name,
+                            &format!("{len} bytes long"),
+                            &format!("{} bytes long", bytes.len()),
+                        ))
+                    }
+                    Some(Len::Field(_)) => {
+                        return Err(ArgumentError::wrong_argument(
+                            &name,
+                            "a number as len",
+                            "the name of a field",
+                        ))
+                    }
+                    _ => packet.extend(bytes),
+                }
+            }
+            (ty, Some(NaslValue::Number(x))) => {
+                let bytes = encode_number(ty, x).ok_or_else(|| {
+                    ArgumentError::wrong_argument(
+                        &name,
+                        &format!("between 0 and {}", (1u64 << (8 * ty.size().unwrap())) - 1),
+                        &x.to_string(),
+                    )
+                })?;
+                packet.extend(bytes);
+            }
+            (_, Some(x)) => {
+                return Err(ArgumentError::wrong_argument(
+                    &name,
+                    "a number",
+                    &x.to_string(),
+                ))
+            }
+            (_, None) => {
+                return Err(ArgumentError::MissingNamed(vec![format!(
+                    "value of {name}"
+                )]))
+            }
+        }
+    }
+    Ok(packet)
+}
+
+/// NASL function to split a packet into its fields according to a schema.
+///
+/// The schema is a list of field descriptors like those of `build_packet`, with a `name` instead
+/// of a `value`. The `len` of bytes and strings can also be the name of a number parsed before,
+/// which is how the value of a TLV structure is parsed. The last field may omit `len` to take the
+/// rest of the data.
+///
+/// Returns an array mapping the names to the values. Data which is too short for the schema or
+/// longer than it are errors.
+#[nasl_function(named(data, schema))]
+fn parse_packet(data: &NaslValue, schema: &NaslValue) -> Result<NaslValue, ArgumentError> {
+    let fields = fields(schema, "schema")?;
+    let data = Vec::<u8>::from(data);
+    let mut result = BTreeMap::new();
+    let mut rest = data.as_slice();
+    for (index, field) in fields.iter().enumerate() {
+        let name = field.description(index);
+        let len = match (field.ty.size(), &field.len) {
+            (Some(size), _) => size,
+            (None, Some(Len::Fixed(len))) => *len,
+            (None, Some(Len::Field(other))) => match result.get(other) {
+                Some(NaslValue::Number(x)) => *x as usize,
+                _ => {
+                    return Err(ArgumentError::wrong_argument(
+                        &name,
+                        &format!("the length given by the number {other} parsed before"),
+                        "no such number",
+                    ))
+                }
+            },
+            (None, None) if index == fields.len() - 1 => rest.len(),
+            (None, None) => {
+                return Err(ArgumentError::MissingNamed(vec![format!("len of {name}")]))
+            }
+        };
+        if len > rest.len() {
+            return Err(ArgumentError::wrong_argument(
+                &name,
+                &format!("{len} bytes long"),
+                &format!("only {} bytes left", rest.len()),
+            ));
+        }
+        let (bytes, remaining) = rest.split_at(len);
+        rest = remaining;
+        let value = match field.ty {
+            FieldType::Bytes => NaslValue::Data(bytes.to_vec()),
+            FieldType::String => NaslValue::String(bytes_to_str(bytes)),
+            ty => NaslValue::Number(decode_number(ty, bytes)),
+        };
+        if let Some(key) = &field.name {
+            result.insert(key.clone(), value);
+        }
+    }
+    if !rest.is_empty() {
+        return Err(ArgumentError::wrong_argument(
+            "data",
+            "as long as the schema",
+            &format!("{} bytes longer", rest.len()),
+        ));
+    }
+    Ok(NaslValue::Dict(result))
+}
+
+pub struct Packet;
+
+function_set! {
+    Packet,
+    (
+        build_packet,
+        parse_packet,
+    )
+}
@@ -0,0 +1,115 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+use std::collections::BTreeMap;
+
+use crate::nasl::test_prelude::*;
+
+fn tlv(ty: i64, value: &str) -> NaslValue {
+    NaslValue::Dict(BTreeMap::from([
+        ("type".to_string(), NaslValue::Number(ty)),
+        ("len".to_string(), NaslValue::Number(value.len() as i64)),
+        ("value".to_string(), NaslValue::String(value.to_string())),
+    ]))
+}
+
+#[test]
+fn build_and_parse_tlv() {
+    let mut t = TestBuilder::default();
+    t.run(
+        r#"fields = [
+            make_array("type", "u8", "value", 1),
+            make_array("type", "u16", "value", 5),
+            make_array("type", "string", "value", "hello", "len", 5)
+        ];"#,
+    );
+    t.ok(
+        "packet = build_packet(fields: fields);",
+        vec![0x01u8, 0x00, 0x05, b'h', b'e', b'l', b'l', b'o'],
+    );
+    t.run(
+        r#"schema = [
+            make_array("type", "u8", "name", "type"),
+            make_array("type", "u16", "name", "len"),
+            make_array("type", "string", "name", "value", "len", "len")
+        ];"#,
+    );
+    t.ok(
+        "parse_packet(data: packet, schema: schema);",
+        tlv(1, "hello"),
+    );
+}
+
+#[test]
+fn byte_order() {
+    let mut t = TestBuilder::default();
+    t.ok(
+        r#"build_packet(fields: [
+            make_array("type", "u16le", "value", 0x0102),
+            make_array("type", "u32", "value", 0x03040506),
+            make_array("type", "u32le", "value", 0x0708090a),
+            make_array("type", "bytes", "value", raw_string(0xff))
+        ]);"#,
+        vec![
+            0x02u8, 0x01, 0x03, 0x04, 0x05, 0x06, 0x0a, 0x09, 0x08, 0x07, 0xff,
+        ],
+    );
+    t.ok(
+        r#"parse_packet(data: raw_string(0x02, 0x01, 0xaa, 0xbb), schema: [
+            make_array("type", "u16le", "name", "a"),
+            make_array("type", "bytes", "name", "rest")
+        ]);"#,
+        NaslValue::Dict(BTreeMap::from([
+            ("a".to_string(), NaslValue::Number(0x0102)),
+            ("rest".to_string(), NaslValue::Data(vec![0xaa, 0xbb])),
+        ])),
+    );
+}
+
+#[test]
+fn length_mismatches() {
+    let mut t = TestBuilder::default();
+    for field in [
+        r#"make_array("type", "u8", "value", 256)"#,
+        r#"make_array("type", "u16", "value", -1)"#,
+        r#"make_array("type", "u32", "value", 0x100000000)"#,
+        r#"make_array("type", "string", "value", "hello", "len", 4)"#,
+        r#"make_array("type", "u8", "value", "hello")"#,
+        r#"make_array("type", "u64", "value", 1)"#,
+    ] {
+        check_err_matches!(
+            t,
+            format!("build_packet(fields: [{field}]);"),
+            ArgumentError::WrongArgument(_)
+        );
+    }
+    check_err_matches!(
+        t,
+        r#"build_packet(fields: [make_array("type", "u8")]);"#,
+        ArgumentError::MissingNamed(_)
+    );
+    t.run(
+        r#"schema = [
+            make_array("type", "u8", "name", "len"),
+            make_array("type", "bytes", "name", "value", "len", "len")
+        ];"#,
+    );
+    // The length field claims more bytes than there are.
+    check_err_matches!(
+        t,
+        "parse_packet(data: raw_string(3, 1, 2), schema: schema);",
+        ArgumentError::WrongArgument(_)
+    );
+    // There are more bytes than the schema describes.
+    check_err_matches!(
+        t,
+        "parse_packet(data: raw_string(1, 1, 2), schema: schema);",
+        ArgumentError::WrongArgument(_)
+    );
+    check_err_matches!(
+        t,
+        r#"parse_packet(data: raw_string(1), schema: [make_array("type", "u16", "name", "a")]);"#,
+        ArgumentError::WrongArgument(_)
+    );
+}
@@ -0,0 +1,143 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later
+
+//! A least recently used cache for compiled regular expressions.
+
+use std::{collections::HashMap, sync::Arc};
+
+use regex::{Regex, RegexBuilder};
+
+use super::RegexError;
+
+/// The default number of compiled patterns that are kept.
+const DEFAULT_CAPACITY: usize = 128;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct RegexKey {
+    pattern: String,
+    icase: bool,
+    multiline: bool,
+}
+
+/// Caches compiled regular expressions keyed by the pattern and the
+/// flags used to compile them.
+///
+/// Scripts often apply the same pattern to many lines within a loop.
+/// Compiling a pattern is considerably more expensive than matching it,
+/// so the result is kept and handed out again on the next request.
+/// When the cache is full, the least recently used entry is evicted.
+/// Patterns that fail to compile are not cached.
+pub struct RegexCache {
+    capacity: usize,
+    tick: u64,
+    entries: HashMap<RegexKey, (Arc<Regex>, u64)>,
+}
+
+impl Default for RegexCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl RegexCache {
+    /// Creates a new cache holding up to `capacity` compiled patterns.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            tick: 0,
+            entries: HashMap::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the compiled regular expression for the given pattern and
+    /// flags, compiling it if it is not yet cached.
+    pub fn get(
+        &mut self,
+        pattern: &str,
+        icase: bool,
+        multiline: bool,
+    ) -> Result<Arc<Regex>, RegexError> {
+        self.tick += 1;
+        let key = RegexKey {
+            pattern: pattern.to_string(),
+            icase,
+            multiline,
+        };
+        if let Some((re, last_used)) = self.entries.get_mut(&key) {
+            *last_used = self.tick;
+            return Ok(re.clone());
+        }
+        let re = Arc::new(
+            RegexBuilder::new(pattern)
+                .case_insensitive(icase)
+                .multi_line(multiline)
+                .build()
+                .map_err(RegexError::BuildingError)?,
+        );
+        if self.entries.len() >= self.capacity {
+            self.evict_least_recently_used();
+        }
+        self.entries.insert(key, (re.clone(), self.tick));
+        Ok(re)
+    }
+
+    fn evict_least_recently_used(&mut self) {
+        if let Some(key) = self
+            .entries
+            .iter()
+            .min_by_key(|(_, (_, last_used))| *last_used)
+            .map(|(key, _)| key.clone())
+        {
+            self.entries.remove(&key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::RegexCache;
+
+    #[test]
+    fn returns_same_instance() {
+        let mut cache = RegexCache::default();
+        let a = cache.get("foo.+bar", false, false).unwrap();
+        let b = cache.get("foo.+bar", false, false).unwrap();
+        assert!(Arc::ptr_eq(&a, &b));
+        assert_eq!(cache.entries.len(), 1);
+    }
+
+    #[test]
+    fn flags_are_part_of_the_key() {
+        let mut cache = RegexCache::default();
+        let a = cache.get("foo", false, false).unwrap();
+        let b = cache.get("foo", true, false).unwrap();
+        let c = cache.get("foo", false, true).unwrap();
+        assert!(!Arc::ptr_eq(&a, &b));
+        assert!(!Arc::ptr_eq(&a, &c));
+        assert_eq!(cache.entries.len(), 3);
+    }
+
+    #[test]
+    fn errors_are_not_cached() {
+        let mut cache = RegexCache::default();
+        assert!(cache.get("(foo", false, false).is_err());
+        assert!(cache.entries.is_empty());
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let mut cache = RegexCache::new(2);
+        let a = cache.get("a", false, false).unwrap();
+        cache.get("b", false, false).unwrap();
+        // Touch "a" so that "b" is the least recently used entry.
+        cache.get("a", false, false).unwrap();
+        cache.get("c", false, false).unwrap();
+        assert_eq!(cache.entries.len(), 2);
+        assert!(Arc::ptr_eq(&a, &cache.get("a", false, false).unwrap()));
+        let b = cache.get("b", false, false).unwrap();
+        assert!(!Arc::ptr_eq(&a, &b));
+    }
+}
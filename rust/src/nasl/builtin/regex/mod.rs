@@ -2,13 +2,20 @@
 //
 // SPDX-License-Identifier: GPL-2.0-or-later
 
+mod cache;
 #[cfg(test)]
 mod tests;
 
 use crate::nasl::prelude::*;
-use regex::{Regex, RegexBuilder};
+use regex::Regex;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
 use thiserror::Error;
 
+use cache::RegexCache;
+
 #[derive(Debug, Error)]
 pub enum RegexError {
     #[error("Error building regular expression pattern: {0}")]
@@ -26,152 +33,217 @@ fn parse_search_string(mut s: &str, rnul: bool, multiline: bool) -> &str {
     s
 }
 
-fn make_regex(pattern: &str, icase: bool, multiline: bool) -> Result<Regex, RegexError> {
-    match RegexBuilder::new(pattern.to_string().as_str())
-        .case_insensitive(icase)
-        .multi_line(multiline)
-        .build()
-    {
-        Ok(re) => Ok(re),
-        Err(e) => Err(RegexError::BuildingError(e)),
-    }
+/// The regular expression functions.
+///
+/// Compiled patterns are kept in a [`RegexCache`] that is shared by all
+/// regular expression functions of this set.
+#[derive(Default)]
+pub struct RegularExpressions {
+    cache: Mutex<RegexCache>,
 }
 
-/// Matches a string against a regular expression.
-/// - string  String to search the pattern in
-/// - pattern the pattern that should be matched
-/// - icase   case insensitive flag
-/// - rnul    replace the null char in the string. Default TRUE.
-/// - multiline Is FALSE by default (string is truncated at the first
-///                “end of line”), and can be set to TRUE for multiline search.
-/// Return true if matches, false otherwise
-#[nasl_function(named(string, pattern, icase, rnul, multiline))]
-fn ereg(
-    string: NaslValue,
-    pattern: NaslValue,
-    icase: Option<bool>,
-    rnul: Option<bool>,
-    multiline: Option<bool>,
-) -> Result<bool, FnError> {
-    let icase = icase.unwrap_or(false);
-    let rnul = rnul.unwrap_or(true);
-    let multiline = multiline.unwrap_or(false);
-
-    let string = string.to_string();
-    let string = parse_search_string(&string, rnul, multiline);
-
-    let re = make_regex(&pattern.to_string(), icase, multiline)?;
-    Ok(re.is_match(string))
-}
+impl RegularExpressions {
+    fn make_regex(
+        &self,
+        pattern: &str,
+        icase: bool,
+        multiline: bool,
+    ) -> Result<Arc<Regex>, RegexError> {
+        // A poisoned lock only means that another thread panicked while
+        // holding it, the cache itself is still valid.
+        let mut cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+        cache.get(pattern, icase, multiline)
+    }
 
-/// Search for a pattern in a string and replace it.
-/// - string  String to search the pattern in
-/// - pattern pattern to search in the string for
-/// - replace string to replace the pattern with
-/// - icase   case insensitive flag
-/// - rnul    replace the null char in the string. Default TRUE.
-///
-/// Return the new string with the pattern replaced with replace.
-#[nasl_function(named(string, pattern, replace, icase, rnul))]
-fn ereg_replace(
-    string: NaslValue,
-    pattern: NaslValue,
-    replace: NaslValue,
-    icase: Option<bool>,
-    rnul: Option<bool>,
-) -> Result<String, FnError> {
-    let icase = icase.unwrap_or(false);
-    let rnul = rnul.unwrap_or(true);
-
-    let string = string.to_string();
-    let string = parse_search_string(&string, rnul, true);
-    let re = make_regex(&pattern.to_string(), icase, false)?;
-
-    let out = re
-        .replace_all(string, replace.to_string().as_str())
-        .to_string();
-    Ok(out)
-}
+    /// Matches a string against a regular expression.
+    /// - string  String to search the pattern in
+    /// - pattern the pattern that should be matched
+    /// - icase   case insensitive flag
+    /// - rnul    replace the null char in the string. Default TRUE.
+    /// - multiline Is FALSE by default (string is truncated at the first
+    ///                “end of line”), and can be set to TRUE for multiline search.
+    /// Return true if matches, false otherwise
+    #[nasl_function(named(string, pattern, icase, rnul, multiline))]
+    pub fn ereg(
+        &self,
+        string: NaslValue,
+        pattern: NaslValue,
+        icase: Option<bool>,
+        rnul: Option<bool>,
+        multiline: Option<bool>,
+    ) -> Result<bool, FnError> {
+        let icase = icase.unwrap_or(false);
+        let rnul = rnul.unwrap_or(true);
+        let multiline = multiline.unwrap_or(false);
+
+        let string = string.to_string();
+        let string = parse_search_string(&string, rnul, multiline);
+
+        let re = self.make_regex(&pattern.to_string(), icase, multiline)?;
+        Ok(re.is_match(string))
+    }
 
-/// Looks for a pattern in a string, line by line.
-///
-/// - string  String to search the pattern in
-/// - pattern the pattern that should be matched
-/// - icase   case insensitive flag
-/// - rnul    replace the null char in the string. Default TRUE.
-///
-/// Returns the concatenation of all lines that match. Null otherwise.
-#[nasl_function(named(string, pattern, icase, rnul))]
-fn egrep(
-    string: NaslValue,
-    pattern: NaslValue,
-    icase: Option<bool>,
-    rnul: Option<bool>,
-) -> Result<String, FnError> {
-    let icase = icase.unwrap_or(false);
-    let rnul = rnul.unwrap_or(true);
-
-    let string = string.to_string();
-    let string = parse_search_string(&string, rnul, true);
-    let re = make_regex(&pattern.to_string(), icase, true)?;
-
-    let lines: Vec<&str> = string
-        .split_inclusive('\n')
-        .filter(|l| re.is_match(l))
-        .collect();
-
-    Ok(lines.concat())
-}
+    /// Search for a pattern in a string and replace it.
+    /// - string  String to search the pattern in
+    /// - pattern pattern to search in the string for
+    /// - replace string to replace the pattern with. Captured groups can
+    ///           be referenced by `$1` or `${1}`, named groups by `${name}`.
+    ///           `$$` is a literal `$`.
+    /// - icase   case insensitive flag
+    /// - rnul    replace the null char in the string. Default TRUE.
+    /// - multiline Is FALSE by default. If TRUE, `^` and `$` match at the
+    ///             beginning and end of each line instead of the whole string.
+    ///
+    /// Return the new string with the pattern replaced with replace.
+    #[nasl_function(named(string, pattern, replace, icase, rnul, multiline))]
+    pub fn ereg_replace(
+        &self,
+        string: NaslValue,
+        pattern: NaslValue,
+        replace: NaslValue,
+        icase: Option<bool>,
+        rnul: Option<bool>,
+        multiline: Option<bool>,
+    ) -> Result<String, FnError> {
+        let icase = icase.unwrap_or(false);
+        let rnul = rnul.unwrap_or(true);
+        let multiline = multiline.unwrap_or(false);
+
+        let string = string.to_string();
+        let string = parse_search_string(&string, rnul, true);
+        let re = self.make_regex(&pattern.to_string(), icase, multiline)?;
+
+        let out = re
+            .replace_all(string, replace.to_string().as_str())
+            .to_string();
+        Ok(out)
+    }
 
-/// Does extended regular expression pattern matching.
-///
-/// - pattern An regex pattern
-/// - string A string
-/// - icase Boolean, for case sensitive
-/// - find_all Boolean, to find all matches
-/// - rnul replace the null char in the string. Default TRUE.
-///
-/// Return an array with the first match (find_all: False)
-/// or an array with all matches (find_all: TRUE).
-/// NULL or empty if no match was found.
-#[nasl_function(named(string, pattern, find_all, icase, rnul))]
-fn eregmatch(
-    string: NaslValue,
-    pattern: NaslValue,
-    find_all: Option<bool>,
-    icase: Option<bool>,
-    rnul: Option<bool>,
-) -> Result<NaslValue, FnError> {
-    let icase = icase.unwrap_or(false);
-    let rnul = rnul.unwrap_or(true);
-    let find_all = find_all.unwrap_or(false);
-
-    let string = string.to_string();
-    let string = parse_search_string(&string, rnul, true);
-    let re = make_regex(&pattern.to_string(), icase, true)?;
-
-    let matches = match find_all {
-        true => re
-            .find_iter(string)
-            .map(|m| NaslValue::String(m.as_str().to_string()))
-            .collect(),
-        false => match re.find(string) {
-            Some(s) => vec![NaslValue::String(s.as_str().to_string())],
-            None => vec![],
-        },
-    };
-
-    Ok(NaslValue::Array(matches))
-}
+    /// Looks for a pattern in a string, line by line.
+    ///
+    /// - string  String to search the pattern in
+    /// - pattern the pattern that should be matched
+    /// - icase   case insensitive flag
+    /// - rnul    replace the null char in the string. Default TRUE.
+    ///
+    /// Returns the concatenation of all lines that match. Null otherwise.
+    #[nasl_function(named(string, pattern, icase, rnul))]
+    pub fn egrep(
+        &self,
+        string: NaslValue,
+        pattern: NaslValue,
+        icase: Option<bool>,
+        rnul: Option<bool>,
+    ) -> Result<String, FnError> {
+        let icase = icase.unwrap_or(false);
+        let rnul = rnul.unwrap_or(true);
+
+        let string = string.to_string();
+        let string = parse_search_string(&string, rnul, true);
+        let re = self.make_regex(&pattern.to_string(), icase, true)?;
+
+        let lines: Vec<&str> = string
+            .split_inclusive('\n')
+            .filter(|l| re.is_match(l))
+            .collect();
+
+        Ok(lines.concat())
+    }
+
+    /// Does extended regular expression pattern matching.
+    ///
+    /// - pattern An regex pattern
+    /// - string A string
+    /// - icase Boolean, for case sensitive
+    /// - find_all Boolean, to find all matches
+    /// - rnul replace the null char in the string. Default TRUE.
+    /// - multiline Is TRUE by default, so that `^` and `$` match at the beginning
+    ///             and end of each line. If FALSE, they only match at the
+    ///             beginning and end of the whole string.
+    ///
+    /// Return an array with the first match (find_all: False)
+    /// or an array with all matches (find_all: TRUE).
+    /// NULL or empty if no match was found.
+    #[nasl_function(named(string, pattern, find_all, icase, rnul, multiline))]
+    pub fn eregmatch(
+        &self,
+        string: NaslValue,
+        pattern: NaslValue,
+        find_all: Option<bool>,
+        icase: Option<bool>,
+        rnul: Option<bool>,
+        multiline: Option<bool>,
+    ) -> Result<NaslValue, FnError> {
+        let icase = icase.unwrap_or(false);
+        let rnul = rnul.unwrap_or(true);
+        let find_all = find_all.unwrap_or(false);
+        let multiline = multiline.unwrap_or(true);
+
+        let string = string.to_string();
+        let string = parse_search_string(&string, rnul, true);
+        let re = self.make_regex(&pattern.to_string(), icase, multiline)?;
+
+        let matches = match find_all {
+            true => re
+                .find_iter(string)
+                .map(|m| NaslValue::String(m.as_str().to_string()))
+                .collect(),
+            false => match re.find(string) {
+                Some(s) => vec![NaslValue::String(s.as_str().to_string())],
+                None => vec![],
+            },
+        };
+
+        Ok(NaslValue::from(matches))
+    }
 
-pub struct RegularExpressions;
+    /// Matches a string against a regular expression containing named capture groups.
+    ///
+    /// - string  String to search the pattern in
+    /// - pattern An regex pattern containing named groups, e.g. `(?P<name>...)`
+    /// - icase   case insensitive flag
+    /// - rnul    replace the null char in the string. Default TRUE.
+    /// - multiline Is TRUE by default, see `eregmatch`.
+    ///
+    /// Return an array mapping each named group of the first match to its value.
+    /// Named groups that did not participate in the match are omitted.
+    /// An empty array is returned if no match was found.
+    #[nasl_function(named(string, pattern, icase, rnul, multiline))]
+    pub fn eregmatch_named(
+        &self,
+        string: NaslValue,
+        pattern: NaslValue,
+        icase: Option<bool>,
+        rnul: Option<bool>,
+        multiline: Option<bool>,
+    ) -> Result<HashMap<String, NaslValue>, FnError> {
+        let icase = icase.unwrap_or(false);
+        let rnul = rnul.unwrap_or(true);
+        let multiline = multiline.unwrap_or(true);
+
+        let string = string.to_string();
+        let string = parse_search_string(&string, rnul, true);
+        let re = self.make_regex(&pattern.to_string(), icase, multiline)?;
+
+        let mut groups = HashMap::new();
+        if let Some(captures) = re.captures(string) {
+            for name in re.capture_names().flatten() {
+                if let Some(m) = captures.name(name) {
+                    groups.insert(name.to_string(), NaslValue::String(m.as_str().to_string()));
+                }
+            }
+        }
+        Ok(groups)
+    }
+}
 
 function_set! {
     RegularExpressions,
     (
-        ereg,
-        egrep,
-        ereg_replace,
-        eregmatch
+        (RegularExpressions::ereg, "ereg"),
+        (RegularExpressions::egrep, "egrep"),
+        (RegularExpressions::ereg_replace, "ereg_replace"),
+        (RegularExpressions::eregmatch, "eregmatch"),
+        (RegularExpressions::eregmatch_named, "eregmatch_named"),
     )
 }
@@ -4,6 +4,9 @@
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
+
+    use crate::nasl::builtin::regex::RegexError;
     use crate::nasl::test_prelude::*;
 
     #[test]
@@ -95,6 +98,57 @@ mod tests {
         );
     }
 
+    #[test]
+    fn ereg_replace_backreference() {
+        let mut t = TestBuilder::default();
+        t.ok(
+            r#"ereg_replace(string: "user@example", pattern: "([a-z]+)@([a-z]+)", replace: "$2 at $1");"#,
+            "example at user",
+        );
+        t.ok(
+            r#"ereg_replace(string: "ab", pattern: "(a)", replace: "${1}1$$");"#,
+            "a1$b",
+        );
+    }
+
+    #[test]
+    fn ereg_replace_invalid_pattern() {
+        let mut t = TestBuilder::default();
+        check_err_matches!(
+            t,
+            r#"ereg_replace(string: "foo", pattern: "(foo", replace: "bar");"#,
+            RegexError::BuildingError(_),
+        );
+    }
+
+    #[test]
+    fn ereg_replace_icase() {
+        let mut t = TestBuilder::default();
+        t.run(r#"string = "Greenbone NETWORK Gmbh";"#);
+        t.ok(
+            r#"ereg_replace(string:string, pattern:"network gmbh", replace: "AG", icase: TRUE);"#,
+            "Greenbone AG",
+        );
+        t.ok(
+            r#"ereg_replace(string:string, pattern:"network gmbh", replace: "AG");"#,
+            "Greenbone NETWORK Gmbh",
+        );
+    }
+
+    #[test]
+    fn ereg_replace_multiline_anchor() {
+        let mut t = TestBuilder::default();
+        t.run(r#"string = 'foo\nbar';"#);
+        t.ok(
+            r#"ereg_replace(string:string, pattern:"^bar", replace: "baz", multiline: TRUE);"#,
+            "foo\nbaz",
+        );
+        t.ok(
+            r#"ereg_replace(string:string, pattern:"^bar", replace: "baz");"#,
+            "foo\nbar",
+        );
+    }
+
     #[test]
     fn egrep() {
         let t = TestBuilder::from_code(
@@ -148,4 +202,65 @@ mod tests {
             vec!["Bar".to_string()],
         );
     }
+
+    #[test]
+    fn eregmatch_icase() {
+        let mut t = TestBuilder::default();
+        t.run(r#"string = "Foo BAR Bee";"#);
+        t.ok(
+            r#"eregmatch(string: string, pattern: "bar", icase: TRUE);"#,
+            vec!["BAR".to_string()],
+        );
+        t.ok(
+            r#"eregmatch(string: string, pattern: "bar");"#,
+            NaslValue::Array(vec![].into()),
+        );
+    }
+
+    #[test]
+    fn eregmatch_multiline_anchor() {
+        let mut t = TestBuilder::default();
+        t.run(r#"string = 'Foo\nBar';"#);
+        t.ok(
+            r#"eregmatch(string: string, pattern: "^Bar$");"#,
+            vec!["Bar".to_string()],
+        );
+        t.ok(
+            r#"eregmatch(string: string, pattern: "^Bar$", multiline: FALSE);"#,
+            NaslValue::Array(vec![].into()),
+        );
+    }
+
+    #[test]
+    fn eregmatch_named() {
+        let mut t = TestBuilder::default();
+        t.run(r#"string = "OpenSSH_8.9p1 Ubuntu";"#);
+        t.ok(
+            r#"eregmatch_named(string: string, pattern: "(?P<product>[A-Za-z]+)_(?P<version>[0-9.]+)");"#,
+            NaslValue::Dict(BTreeMap::from([
+                ("product".to_string(), NaslValue::String("OpenSSH".to_string())),
+                ("version".to_string(), NaslValue::String("8.9".to_string())),
+            ])),
+        );
+    }
+
+    #[test]
+    fn eregmatch_named_no_match() {
+        let mut t = TestBuilder::default();
+        t.run(r#"string = "Foo Bar";"#);
+        t.ok(
+            r#"eregmatch_named(string: string, pattern: "(?P<num>[0-9]+)");"#,
+            NaslValue::Dict(BTreeMap::new()),
+        );
+    }
+
+    #[test]
+    fn eregmatch_named_invalid_pattern() {
+        let mut t = TestBuilder::default();
+        check_err_matches!(
+            t,
+            r#"eregmatch_named(string: "foo", pattern: "(?P<a>");"#,
+            RegexError::BuildingError(_),
+        );
+    }
 }
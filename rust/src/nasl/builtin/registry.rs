@@ -0,0 +1,143 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+//! Allows embedders to add their own builtin functions.
+
+use thiserror::Error;
+
+use crate::nasl::utils::{Executor, NaslFunction, StoredFunctionSet};
+
+use super::nasl_std_functions;
+
+/// Errors that occur while registering a custom builtin.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum RegistryError {
+    /// A function with the given name is already known.
+    #[error("A function named '{0}' is already defined.")]
+    AlreadyDefined(String),
+}
+
+/// A registry of custom builtin functions.
+///
+/// The registered functions are consulted before the builtins they were chained to. As a
+/// name can only be registered when neither a builtin nor another custom function uses it yet,
+/// a custom function never shadows a builtin.
+///
+/// ```
+/// use scannerlib::nasl::{BuiltinRegistry, ContextFactory, Context, NaslFunction, NaslResult, NaslValue, Register};
+///
+/// fn answer(_: &Register, _: &Context) -> NaslResult {
+///     Ok(NaslValue::Number(42))
+/// }
+///
+/// let mut registry = BuiltinRegistry::default();
+/// registry
+///     .register("answer", NaslFunction::SyncStateless(answer))
+///     .unwrap();
+/// let factory = ContextFactory::default().functions(registry.into_executor());
+/// ```
+pub struct BuiltinRegistry {
+    builtins: Executor,
+    custom: StoredFunctionSet<()>,
+}
+
+impl Default for BuiltinRegistry {
+    /// Creates a registry chained ahead of [nasl_std_functions].
+    fn default() -> Self {
+        Self::new(nasl_std_functions())
+    }
+}
+
+impl BuiltinRegistry {
+    /// Creates a registry that is chained ahead of the given builtins.
+    pub fn new(builtins: Executor) -> Self {
+        Self {
+            builtins,
+            custom: StoredFunctionSet::new(()),
+        }
+    }
+
+    /// Registers a function under the given name.
+    ///
+    /// Returns an error when the name is already used by a builtin or a previously registered
+    /// function.
+    pub fn register(
+        &mut self,
+        name: &str,
+        function: NaslFunction<()>,
+    ) -> Result<&mut Self, RegistryError> {
+        if self.contains(name) {
+            return Err(RegistryError::AlreadyDefined(name.to_owned()));
+        }
+        self.custom.add_nasl_function(name, function);
+        Ok(self)
+    }
+
+    /// Checks if a function is either registered or a builtin.
+    pub fn contains(&self, name: &str) -> bool {
+        self.custom.contains_key(name) || self.builtins.contains(name)
+    }
+
+    /// Creates the executor that looks up the registered functions first and falls back to the
+    /// builtins afterwards.
+    pub fn into_executor(self) -> Executor {
+        let mut executor = self.builtins;
+        executor.prepend_set(self.custom);
+        executor
+    }
+}
+
+impl From<BuiltinRegistry> for Executor {
+    fn from(registry: BuiltinRegistry) -> Self {
+        registry.into_executor()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::nasl::test_prelude::*;
+    use crate::nasl::utils::{resolve_positional_arguments, NaslFunction};
+
+    use super::{BuiltinRegistry, RegistryError};
+
+    fn my_echo(register: &Register, _: &Context) -> NaslResult {
+        Ok(resolve_positional_arguments(register)
+            .into_iter()
+            .next()
+            .unwrap_or(NaslValue::Null))
+    }
+
+    #[test]
+    fn custom_function_is_callable() {
+        let mut registry = BuiltinRegistry::default();
+        registry
+            .register("my_echo", NaslFunction::SyncStateless(my_echo))
+            .unwrap();
+        let mut t = TestBuilder::default().with_executor(registry.into_executor());
+        t.ok(r#"my_echo("hello");"#, "hello");
+        t.ok("my_echo(42);", 42);
+        // builtins are still available
+        t.ok(r#"strlen("abc");"#, 3);
+    }
+
+    #[test]
+    fn collisions_are_rejected() {
+        let mut registry = BuiltinRegistry::default();
+        assert_eq!(
+            registry
+                .register("strlen", NaslFunction::SyncStateless(my_echo))
+                .err(),
+            Some(RegistryError::AlreadyDefined("strlen".to_owned()))
+        );
+        registry
+            .register("my_echo", NaslFunction::SyncStateless(my_echo))
+            .unwrap();
+        assert_eq!(
+            registry
+                .register("my_echo", NaslFunction::SyncStateless(my_echo))
+                .err(),
+            Some(RegistryError::AlreadyDefined("my_echo".to_owned()))
+        );
+    }
+}
@@ -0,0 +1,317 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+#![doc = include_str!("README.md")]
+
+mod pdu;
+#[cfg(test)]
+mod tests;
+
+use std::collections::BTreeMap;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::sync::atomic::{AtomicI32, Ordering};
+use std::time::Duration;
+
+use thiserror::Error;
+use tokio::net::UdpSocket;
+
+use crate::nasl::prelude::*;
+
+use pdu::{Message, VarBind};
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+const SNMP_PORT: u16 = 161;
+const DEFAULT_COMMUNITY: &str = "public";
+
+static REQUEST_ID: AtomicI32 = AtomicI32::new(1);
+
+#[derive(Debug, Error)]
+pub enum SnmpError {
+    #[error("No SNMP response from {0} before the timeout.")]
+    Timeout(String),
+    #[error("Unable to query {0} via SNMP: {1}")]
+    Io(String, String),
+    #[error("Invalid SNMP response from {0}.")]
+    InvalidResponse(String),
+    #[error("The SNMP agent {0} answered with the error {1}.")]
+    ErrorStatus(String, String),
+}
+
+/// The SNMP version, given as 1 or 2 or as string `1` or `2c`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Version {
+    V1,
+    V2c,
+}
+
+impl Version {
+    fn number(self) -> i64 {
+        match self {
+            Version::V1 => 0,
+            Version::V2c => 1,
+        }
+    }
+}
+
+impl FromNaslValue<'_> for Version {
+    fn from_nasl_value(value: &NaslValue) -> Result<Self, FnError> {
+        let version = match value {
+            NaslValue::Number(x) => x.to_string(),
+            _ => String::from_nasl_value(value)?.to_ascii_lowercase(),
+        };
+        match version.trim_start_matches('v') {
+            "1" => Ok(Version::V1),
+            "2" | "2c" => Ok(Version::V2c),
+            _ => Err(ArgumentError::wrong_argument("version", "1 or 2c", &version).into()),
+        }
+    }
+}
+
+/// An object identifier in dotted notation, e.g. `1.3.6.1.2.1.1.1.0`.
+struct Oid(Vec<u32>);
+
+impl FromNaslValue<'_> for Oid {
+    fn from_nasl_value(value: &NaslValue) -> Result<Self, FnError> {
+        let s = String::from_nasl_value(value)?;
+        parse_oid(&s)
+            .map(Oid)
+            .ok_or_else(|| ArgumentError::wrong_argument("oid", "an OID like 1.3.6.1", &s).into())
+    }
+}
+
+fn parse_oid(s: &str) -> Option<Vec<u32>> {
+    let arcs = s
+        .strip_prefix('.')
+        .unwrap_or(s)
+        .split('.')
+        .map(|x| x.parse::<u32>().ok())
+        .collect::<Option<Vec<_>>>()?;
+    match arcs[..] {
+        [first, second, ..] if first < 2 && second < 40 => Some(arcs),
+        [2, _, ..] => Some(arcs),
+        _ => None,
+    }
+}
+
+fn format_oid(oid: &[u32]) -> String {
+    oid.iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+fn error_status(status: i64) -> String {
+    match status {
+        1 => "tooBig".into(),
+        2 => "noSuchName".into(),
+        3 => "badValue".into(),
+        4 => "readOnly".into(),
+        5 => "genErr".into(),
+        x => format!("error status {x}"),
+    }
+}
+
+/// Returns the name of the type of the value along with the value.
+fn to_nasl_value(varbind: &VarBind) -> Option<(&'static str, NaslValue)> {
+    let value = &varbind.value;
+    Some(match varbind.tag {
+        pdu::INTEGER => ("INTEGER", NaslValue::Number(pdu::decode_integer(value)?)),
+        pdu::OCTET_STRING => ("OCTET STRING", NaslValue::Data(value.clone())),
+        pdu::NULL => ("NULL", NaslValue::Null),
+        pdu::OBJECT_IDENTIFIER => (
+            "OBJECT IDENTIFIER",
+            NaslValue::String(format_oid(&pdu::decode_oid(value)?)),
+        ),
+        pdu::IP_ADDRESS => (
+            "IpAddress",
+            NaslValue::String(Ipv4Addr::from(<[u8; 4]>::try_from(&value[..]).ok()?).to_string()),
+        ),
+        pdu::COUNTER32 => ("Counter32", unsigned(value)?),
+        pdu::GAUGE32 => ("Gauge32", unsigned(value)?),
+        pdu::TIME_TICKS => ("TimeTicks", unsigned(value)?),
+        pdu::COUNTER64 => ("Counter64", unsigned(value)?),
+        pdu::OPAQUE => ("Opaque", NaslValue::Data(value.clone())),
+        pdu::NO_SUCH_OBJECT => ("noSuchObject", NaslValue::Null),
+        pdu::NO_SUCH_INSTANCE => ("noSuchInstance", NaslValue::Null),
+        pdu::END_OF_MIB_VIEW => ("endOfMibView", NaslValue::Null),
+        _ => return None,
+    })
+}
+
+fn unsigned(value: &[u8]) -> Option<NaslValue> {
+    pdu::decode_unsigned(value).map(|x| NaslValue::Number(x as i64))
+}
+
+/// Sends `request` to `addr` and waits for the response with the same request id.
+async fn exchange(
+    addr: SocketAddr,
+    request: &Message,
+    timeout: Duration,
+) -> Result<Vec<u8>, SnmpError> {
+    let io = |e: std::io::Error| SnmpError::Io(addr.to_string(), e.to_string());
+    let local: SocketAddr = match addr.ip() {
+        IpAddr::V4(_) => (Ipv4Addr::UNSPECIFIED, 0).into(),
+        IpAddr::V6(_) => (Ipv6Addr::UNSPECIFIED, 0).into(),
+    };
+    let socket = UdpSocket::bind(local).await.map_err(io)?;
+    socket.send_to(&request.encode(), addr).await.map_err(io)?;
+    let receive = async {
+        let mut buf = vec![0; u16::MAX as usize];
+        loop {
+            let (n, _) = socket.recv_from(&mut buf).await?;
+            let response = &buf[..n];
+            // Answers to earlier requests which timed out are skipped.
+            let id = Message::decode(response).map(|x| x.request_id);
+            if id.is_none() || id == Some(request.request_id) {
+                return Ok(response.to_vec());
+            }
+        }
+    };
+    tokio::time::timeout(timeout, receive)
+        .await
+        .map_err(|_| SnmpError::Timeout(addr.to_string()))?
+        .map_err(io)
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn query(
+    ctx: &Context<'_>,
+    pdu: u8,
+    host: Option<&str>,
+    port: Option<u16>,
+    community: Option<&str>,
+    oid: Oid,
+    version: Option<Version>,
+    timeout: Option<i64>,
+) -> Result<NaslValue, FnError> {
+    let port = port.unwrap_or(SNMP_PORT);
+    // The host is resolved as part of the interaction, so that a replay neither depends on the
+    // name resolution nor on the address the name resolved to while recording.
+    let agent = match host {
+        Some(host) => format!("{host}:{port}"),
+        None => SocketAddr::new(ctx.target_ip(), port).to_string(),
+    };
+    let version = version.unwrap_or(Version::V2c);
+    let timeout = timeout
+        .filter(|x| *x > 0)
+        .map(|x| Duration::from_secs(x as u64))
+        .unwrap_or(DEFAULT_TIMEOUT);
+    let request = Message {
+        version: version.number(),
+        community: community.unwrap_or(DEFAULT_COMMUNITY).as_bytes().to_vec(),
+        pdu,
+        request_id: (REQUEST_ID.fetch_add(1, Ordering::Relaxed) & i32::MAX) as i64,
+        error_status: 0,
+        error_index: 0,
+        varbinds: vec![VarBind {
+            oid: oid.0,
+            tag: pdu::NULL,
+            value: vec![],
+        }],
+    };
+    let description = format!(
+        "snmp {pdu:#x} {agent} {version:?} {}",
+        format_oid(&request.varbinds[0].oid)
+    );
+    let live = async {
+        let addr = match host {
+            Some(host) => tokio::net::lookup_host((host, port))
+                .await
+                .ok()
+                .and_then(|mut x| x.next())
+                .ok_or_else(|| ArgumentError::wrong_argument("host", "a resolvable host", host))?,
+            None => SocketAddr::new(ctx.target_ip(), port),
+        };
+        Ok::<_, FnError>(exchange(addr, &request, timeout).await?)
+    };
+    let response = ctx.network_mode().interact(description, live).await?;
+    let invalid = || SnmpError::InvalidResponse(agent.clone());
+    let response = Message::decode(&response)
+        .filter(|x| x.pdu == pdu::GET_RESPONSE)
+        .ok_or_else(invalid)?;
+    if response.error_status != 0 {
+        return Err(SnmpError::ErrorStatus(agent, error_status(response.error_status)).into());
+    }
+    let varbind = response.varbinds.first().ok_or_else(invalid)?;
+    let (ty, value) = to_nasl_value(varbind).ok_or_else(invalid)?;
+    Ok(NaslValue::Dict(BTreeMap::from([
+        (
+            "oid".to_string(),
+            NaslValue::String(format_oid(&varbind.oid)),
+        ),
+        ("type".to_string(), NaslValue::String(ty.to_string())),
+        ("value".to_string(), value),
+    ])))
+}
+
+/// Queries the value of `oid` via SNMPv1 or SNMPv2c over UDP.
+///
+/// `host` defaults to the target, `port` to 161, `community` to `public` and `version` to `2c`.
+/// `timeout` is given in seconds and defaults to 5.
+///
+/// Returns an array with the `oid`, the `type`, e.g. `INTEGER`, `OCTET STRING` or
+/// `noSuchObject`, and the `value` of the variable. Numbers are returned as integer, OCTET STRINGs
+/// as data and OIDs and IP addresses as strings. A missing response results in a timeout error,
+/// which is distinct from an error returned by the agent.
+#[nasl_function(named(host, port, community, oid, version, timeout))]
+async fn snmp_get(
+    ctx: &Context<'_>,
+    host: Option<&str>,
+    port: Option<u16>,
+    community: Option<&str>,
+    oid: Oid,
+    version: Option<Version>,
+    timeout: Option<i64>,
+) -> Result<NaslValue, FnError> {
+    query(
+        ctx,
+        pdu::GET_REQUEST,
+        host,
+        port,
+        community,
+        oid,
+        version,
+        timeout,
+    )
+    .await
+}
+
+/// Queries the variable following `oid` via SNMPv1 or SNMPv2c over UDP, which is a single step
+/// of a walk.
+///
+/// Takes the same arguments and returns the same array as `snmp_get`. The `oid` of the result is
+/// the one to pass to the next call. The end of a walk is indicated by the type `endOfMibView`
+/// for SNMPv2c and by the error `noSuchName` for SNMPv1.
+#[nasl_function(named(host, port, community, oid, version, timeout))]
+async fn snmp_getnext(
+    ctx: &Context<'_>,
+    host: Option<&str>,
+    port: Option<u16>,
+    community: Option<&str>,
+    oid: Oid,
+    version: Option<Version>,
+    timeout: Option<i64>,
+) -> Result<NaslValue, FnError> {
+    query(
+        ctx,
+        pdu::GET_NEXT_REQUEST,
+        host,
+        port,
+        community,
+        oid,
+        version,
+        timeout,
+    )
+    .await
+}
+
+pub struct Snmp;
+
+function_set! {
+    Snmp,
+    (
+        snmp_get,
+        snmp_getnext,
+    )
+}
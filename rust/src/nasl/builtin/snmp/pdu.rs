@@ -0,0 +1,171 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+//! Minimal BER encoding and decoding of SNMPv1 and SNMPv2c messages.
+
+use crate::nasl::utils::ber::expect;
+pub use crate::nasl::utils::ber::{
+    decode, decode_integer, encode, encode_integer, INTEGER, OCTET_STRING, SEQUENCE,
+};
+
+pub const NULL: u8 = 0x05;
+pub const OBJECT_IDENTIFIER: u8 = 0x06;
+pub const IP_ADDRESS: u8 = 0x40;
+pub const COUNTER32: u8 = 0x41;
+pub const GAUGE32: u8 = 0x42;
+pub const TIME_TICKS: u8 = 0x43;
+pub const OPAQUE: u8 = 0x44;
+pub const COUNTER64: u8 = 0x46;
+pub const NO_SUCH_OBJECT: u8 = 0x80;
+pub const NO_SUCH_INSTANCE: u8 = 0x81;
+pub const END_OF_MIB_VIEW: u8 = 0x82;
+
+pub const GET_REQUEST: u8 = 0xa0;
+pub const GET_NEXT_REQUEST: u8 = 0xa1;
+pub const GET_RESPONSE: u8 = 0xa2;
+
+/// A variable of a PDU. `tag` and `value` are the type and raw content of the value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VarBind {
+    pub oid: Vec<u32>,
+    pub tag: u8,
+    pub value: Vec<u8>,
+}
+
+/// An SNMP message containing a single PDU.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Message {
+    /// 0 for SNMPv1, 1 for SNMPv2c.
+    pub version: i64,
+    pub community: Vec<u8>,
+    /// The tag of the PDU, e.g. [`GET_REQUEST`].
+    pub pdu: u8,
+    pub request_id: i64,
+    pub error_status: i64,
+    pub error_index: i64,
+    pub varbinds: Vec<VarBind>,
+}
+
+impl Message {
+    pub fn encode(&self) -> Vec<u8> {
+        let varbinds: Vec<u8> = self
+            .varbinds
+            .iter()
+            .flat_map(|x| {
+                encode(
+                    SEQUENCE,
+                    &[encode_oid(&x.oid), encode(x.tag, &x.value)].concat(),
+                )
+            })
+            .collect();
+        let pdu = encode(
+            self.pdu,
+            &[
+                encode_integer(self.request_id),
+                encode_integer(self.error_status),
+                encode_integer(self.error_index),
+                encode(SEQUENCE, &varbinds),
+            ]
+            .concat(),
+        );
+        encode(
+            SEQUENCE,
+            &[
+                encode_integer(self.version),
+                encode(OCTET_STRING, &self.community),
+                pdu,
+            ]
+            .concat(),
+        )
+    }
+
+    /// Returns None if `data` is not a valid message.
+    pub fn decode(data: &[u8]) -> Option<Self> {
+        let (message, _) = expect(data, SEQUENCE)?;
+        let (version, rest) = expect(message, INTEGER)?;
+        let (community, rest) = expect(rest, OCTET_STRING)?;
+        let (pdu, content, _) = decode(rest)?;
+        let (request_id, rest) = expect(content, INTEGER)?;
+        let (error_status, rest) = expect(rest, INTEGER)?;
+        let (error_index, rest) = expect(rest, INTEGER)?;
+        let (mut list, _) = expect(rest, SEQUENCE)?;
+        let mut varbinds = vec![];
+        while !list.is_empty() {
+            let (varbind, rest) = expect(list, SEQUENCE)?;
+            let (oid, value) = expect(varbind, OBJECT_IDENTIFIER)?;
+            let (tag, value, _) = decode(value)?;
+            varbinds.push(VarBind {
+                oid: decode_oid(oid)?,
+                tag,
+                value: value.to_vec(),
+            });
+            list = rest;
+        }
+        Some(Self {
+            version: decode_integer(version)?,
+            community: community.to_vec(),
+            pdu,
+            request_id: decode_integer(request_id)?,
+            error_status: decode_integer(error_status)?,
+            error_index: decode_integer(error_index)?,
+            varbinds,
+        })
+    }
+}
+
+pub fn encode_oid(arcs: &[u32]) -> Vec<u8> {
+    let mut content = vec![];
+    let first =
+        arcs.first().copied().unwrap_or(0) as u64 * 40 + arcs.get(1).copied().unwrap_or(0) as u64;
+    for arc in std::iter::once(first).chain(arcs.iter().skip(2).map(|x| *x as u64)) {
+        let mut chunks = vec![(arc & 0x7f) as u8];
+        let mut rest = arc >> 7;
+        while rest > 0 {
+            chunks.push(0x80 | (rest & 0x7f) as u8);
+            rest >>= 7;
+        }
+        content.extend(chunks.iter().rev());
+    }
+    encode(OBJECT_IDENTIFIER, &content)
+}
+
+pub fn decode_unsigned(content: &[u8]) -> Option<u64> {
+    let content = match content {
+        [0, rest @ ..] => rest,
+        _ => content,
+    };
+    if content.len() > 8 {
+        return None;
+    }
+    Some(content.iter().fold(0, |acc, x| (acc << 8) | *x as u64))
+}
+
+pub fn decode_oid(content: &[u8]) -> Option<Vec<u32>> {
+    let mut values = vec![];
+    let mut value: u64 = 0;
+    for x in content {
+        value = (value << 7) | (x & 0x7f) as u64;
+        if value > u32::MAX as u64 + 80 {
+            return None;
+        }
+        if x & 0x80 == 0 {
+            values.push(value);
+            value = 0;
+        }
+    }
+    if content.last().is_some_and(|x| x & 0x80 != 0) {
+        return None;
+    }
+    let (first, rest) = values.split_first()?;
+    let (a, b) = match *first {
+        x if x < 40 => (0, x),
+        x if x < 80 => (1, x - 40),
+        x => (2, x - 80),
+    };
+    let mut arcs = vec![a, u32::try_from(b).ok()?];
+    for x in rest {
+        arcs.push(u32::try_from(*x).ok()?);
+    }
+    Some(arcs)
+}
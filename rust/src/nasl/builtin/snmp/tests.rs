@@ -0,0 +1,253 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+use std::collections::BTreeMap;
+use std::net::{Ipv4Addr, SocketAddr};
+
+use tokio::net::UdpSocket;
+
+use super::pdu::{self, Message, VarBind};
+use super::{parse_oid, SnmpError};
+use crate::nasl::test_prelude::*;
+use crate::nasl::utils::recording::{NetworkMode, Recorder, Replayer};
+
+const COMMUNITY: &[u8] = b"secret";
+
+/// The variables of the stub agent, sorted by OID.
+fn mib() -> Vec<(Vec<u32>, u8, Vec<u8>)> {
+    vec![
+        (
+            parse_oid("1.3.6.1.2.1.1.1.0").unwrap(),
+            pdu::OCTET_STRING,
+            b"Stub switch".to_vec(),
+        ),
+        (
+            parse_oid("1.3.6.1.2.1.1.2.0").unwrap(),
+            pdu::OBJECT_IDENTIFIER,
+            pdu::encode_oid(&parse_oid("1.3.6.1.4.1.9.1.1").unwrap())[2..].to_vec(),
+        ),
+        (
+            parse_oid("1.3.6.1.2.1.1.3.0").unwrap(),
+            pdu::TIME_TICKS,
+            vec![0x00, 0x98, 0x96, 0x80],
+        ),
+        (
+            parse_oid("1.3.6.1.2.1.2.1.0").unwrap(),
+            pdu::INTEGER,
+            vec![0xff],
+        ),
+    ]
+}
+
+/// Answers a request like an SNMP agent with the variables of `mib`.
+fn answer(request: Message) -> Message {
+    let mib = mib();
+    let oid = &request.varbinds[0].oid;
+    let found = match request.pdu {
+        pdu::GET_REQUEST => mib.iter().find(|(x, _, _)| x == oid),
+        _ => mib.iter().find(|(x, _, _)| x > oid),
+    };
+    let (error_status, varbind) = match (found, request.version) {
+        (Some((oid, tag, value)), _) => (
+            0,
+            VarBind {
+                oid: oid.clone(),
+                tag: *tag,
+                value: value.clone(),
+            },
+        ),
+        // SNMPv1 reports missing variables as noSuchName error.
+        (None, 0) => (2, request.varbinds[0].clone()),
+        (None, _) => (
+            0,
+            VarBind {
+                oid: oid.clone(),
+                tag: match request.pdu {
+                    pdu::GET_REQUEST => pdu::NO_SUCH_OBJECT,
+                    _ => pdu::END_OF_MIB_VIEW,
+                },
+                value: vec![],
+            },
+        ),
+    };
+    Message {
+        pdu: pdu::GET_RESPONSE,
+        error_status,
+        error_index: if error_status == 0 { 0 } else { 1 },
+        varbinds: vec![varbind],
+        ..request
+    }
+}
+
+/// Starts an SNMP agent on localhost answering according to `mib`. Requests with another
+/// community than `COMMUNITY` are ignored.
+async fn stub_agent() -> SocketAddr {
+    let socket = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).await.unwrap();
+    let addr = socket.local_addr().unwrap();
+    tokio::spawn(async move {
+        let mut buf = [0; 1500];
+        while let Ok((n, peer)) = socket.recv_from(&mut buf).await {
+            let request = Message::decode(&buf[..n]).unwrap();
+            if request.community != COMMUNITY {
+                continue;
+            }
+            let _ = socket.send_to(&answer(request).encode(), peer).await;
+        }
+    });
+    addr
+}
+
+fn variable(oid: &str, ty: &str, value: impl Into<NaslValue>) -> NaslValue {
+    NaslValue::Dict(BTreeMap::from([
+        ("oid".to_string(), NaslValue::String(oid.to_string())),
+        ("type".to_string(), NaslValue::String(ty.to_string())),
+        ("value".to_string(), value.into()),
+    ]))
+}
+
+#[tokio::test]
+async fn snmp_get() {
+    let agent = stub_agent().await;
+    let args = format!(
+        r#"host: "{}", port: {}, community: "secret", timeout: 1"#,
+        agent.ip(),
+        agent.port()
+    );
+    let mut t = TestBuilder::default();
+    t.ok(
+        format!(r#"snmp_get({args}, oid: "1.3.6.1.2.1.1.1.0");"#),
+        variable(
+            "1.3.6.1.2.1.1.1.0",
+            "OCTET STRING",
+            "Stub switch".as_bytes(),
+        ),
+    );
+    t.ok(
+        format!(r#"snmp_get({args}, oid: ".1.3.6.1.2.1.1.3.0", version: 1);"#),
+        variable("1.3.6.1.2.1.1.3.0", "TimeTicks", 10_000_000),
+    );
+    t.ok(
+        format!(r#"snmp_get({args}, oid: "1.3.6.1.2.1.2.1.0", version: "2c");"#),
+        variable("1.3.6.1.2.1.2.1.0", "INTEGER", -1),
+    );
+    t.ok(
+        format!(r#"snmp_get({args}, oid: "1.3.6.1.2.1.1.9.0");"#),
+        variable("1.3.6.1.2.1.1.9.0", "noSuchObject", NaslValue::Null),
+    );
+    check_err_matches!(
+        t,
+        format!(r#"snmp_get({args}, oid: "1.3.6.1.2.1.1.9.0", version: 1);"#),
+        SnmpError::ErrorStatus(..)
+    );
+    t.async_verify().await;
+}
+
+#[tokio::test]
+async fn snmp_getnext() {
+    let agent = stub_agent().await;
+    let args = format!(
+        r#"host: "{}", port: {}, community: "secret", timeout: 1"#,
+        agent.ip(),
+        agent.port()
+    );
+    let mut t = TestBuilder::default();
+    t.run(format!(
+        r#"next = snmp_getnext({args}, oid: "1.3.6.1.2.1.1.1.0");"#
+    ));
+    t.ok(
+        "next;",
+        variable(
+            "1.3.6.1.2.1.1.2.0",
+            "OBJECT IDENTIFIER",
+            "1.3.6.1.4.1.9.1.1",
+        ),
+    );
+    t.ok(
+        format!(r#"snmp_getnext({args}, oid: next["oid"], version: 1);"#),
+        variable("1.3.6.1.2.1.1.3.0", "TimeTicks", 10_000_000),
+    );
+    t.ok(
+        format!(r#"snmp_getnext({args}, oid: "1.3.6.1.2.1.2.1.0");"#),
+        variable("1.3.6.1.2.1.2.1.0", "endOfMibView", NaslValue::Null),
+    );
+    t.async_verify().await;
+}
+
+#[tokio::test]
+async fn snmp_errors() {
+    let agent = stub_agent().await;
+    let host = format!(r#"host: "{}", port: {}"#, agent.ip(), agent.port());
+    let mut t = TestBuilder::default();
+    // The agent does not answer requests with the wrong community.
+    check_err_matches!(
+        t,
+        format!(r#"snmp_get({host}, community: "public", oid: "1.3.6.1.2.1.1.1.0", timeout: 1);"#),
+        SnmpError::Timeout(_)
+    );
+    for oid in ["", "1", "1.3.6.x.1", "3.1.1", "1.40.1", "1..3"] {
+        check_err_matches!(
+            t,
+            format!(r#"snmp_get({host}, oid: "{oid}");"#),
+            ArgumentError::WrongArgument(_)
+        );
+    }
+    check_err_matches!(
+        t,
+        format!(r#"snmp_get({host}, oid: "1.3.6.1", version: 3);"#),
+        ArgumentError::WrongArgument(_)
+    );
+    t.async_verify().await;
+}
+
+#[tokio::test]
+async fn snmp_replay_without_name_resolution() {
+    let agent = stub_agent().await;
+    let path = std::env::temp_dir().join(format!("snmp_replay_{}.jsonl", std::process::id()));
+    let get = |host: &str| {
+        format!(
+            r#"snmp_get(host: "{host}", port: {}, community: "secret", oid: "1.3.6.1.2.1.1.1.0");"#,
+            agent.port()
+        )
+    };
+    let expected = variable(
+        "1.3.6.1.2.1.1.1.0",
+        "OCTET STRING",
+        "Stub switch".as_bytes(),
+    );
+    let recorder = Recorder::create(&path).unwrap();
+    let mut t = TestBuilder::default().with_network_mode(NetworkMode::Record(recorder));
+    t.ok(get("127.0.0.1"), expected.clone());
+    t.async_verify().await;
+    // The interaction is keyed on the given host, the replay does not resolve it.
+    let recorded = std::fs::read_to_string(&path).unwrap();
+    std::fs::write(&path, recorded.replace("127.0.0.1", "agent.invalid")).unwrap();
+    let replayer = Replayer::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let mut t = TestBuilder::default().with_network_mode(NetworkMode::Replay(replayer));
+    t.ok(get("agent.invalid"), expected);
+    t.async_verify().await;
+}
+
+#[test]
+fn encoding() {
+    for value in [0, 1, 127, 128, 255, 256, -1, -128, -129, i64::MAX, i64::MIN] {
+        let encoded = pdu::encode_integer(value);
+        let (tag, content, rest) = pdu::decode(&encoded).unwrap();
+        assert_eq!((tag, rest), (pdu::INTEGER, &[][..]));
+        assert_eq!(pdu::decode_integer(content), Some(value));
+    }
+    assert_eq!(pdu::encode_integer(128), vec![0x02, 0x02, 0x00, 0x80]);
+    let oid = parse_oid("1.3.6.1.4.1.2636.3.1.13.1.8.9.1.0.0").unwrap();
+    let encoded = pdu::encode_oid(&oid);
+    assert_eq!(&encoded[..6], &[0x06, 0x10, 0x2b, 0x06, 0x01, 0x04]);
+    assert_eq!(pdu::decode_oid(&encoded[2..]), Some(oid));
+    let long = vec![0xab; 300];
+    let encoded = pdu::encode(pdu::OCTET_STRING, &long);
+    assert_eq!(&encoded[..4], &[0x04, 0x82, 0x01, 0x2c]);
+    assert_eq!(
+        pdu::decode(&encoded),
+        Some((pdu::OCTET_STRING, &long[..], &[][..]))
+    );
+    assert_eq!(pdu::decode(&encoded[..100]), None);
+}
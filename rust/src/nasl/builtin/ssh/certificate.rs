@@ -0,0 +1,122 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+//! Parsing of OpenSSH user certificates as described in the
+//! `PROTOCOL.certkeys` file of OpenSSH.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+
+use super::error::SshErrorKind;
+
+const USER_CERT: u32 = 1;
+
+/// The parts of a certificate which are checked before it is presented to
+/// the server. The signature of the CA is left to the server.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Certificate {
+    pub principals: Vec<String>,
+    pub valid_after: u64,
+    pub valid_before: u64,
+}
+
+/// Reads the fields of the SSH wire format.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn bytes(&mut self, len: usize) -> Option<&'a [u8]> {
+        if self.0.len() < len {
+            return None;
+        }
+        let (bytes, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Some(bytes)
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        Some(u32::from_be_bytes(self.bytes(4)?.try_into().ok()?))
+    }
+
+    fn u64(&mut self) -> Option<u64> {
+        Some(u64::from_be_bytes(self.bytes(8)?.try_into().ok()?))
+    }
+
+    fn string(&mut self) -> Option<&'a [u8]> {
+        let len = self.u32()? as usize;
+        self.bytes(len)
+    }
+}
+
+/// The number of fields of the public key contained in a certificate of the
+/// given type.
+fn public_key_fields(key_type: &str) -> Option<usize> {
+    match key_type.strip_suffix("-cert-v01@openssh.com")? {
+        "ssh-ed25519" => Some(1),
+        "ssh-rsa" => Some(2),
+        "ecdsa-sha2-nistp256" | "ecdsa-sha2-nistp384" | "ecdsa-sha2-nistp521" => Some(2),
+        "ssh-dss" => Some(4),
+        _ => None,
+    }
+}
+
+impl Certificate {
+    /// Parses a certificate in the format of a `-cert.pub` file, that is the
+    /// key type, the base64 encoded certificate and an optional comment.
+    pub fn parse(certificate: &str) -> Option<Self> {
+        let mut parts = certificate.split_whitespace();
+        let key_type = parts.next()?;
+        let blob = STANDARD.decode(parts.next()?).ok()?;
+        let mut reader = Reader(&blob);
+        if reader.string()? != key_type.as_bytes() {
+            return None;
+        }
+        // nonce, public key and serial
+        reader.string()?;
+        for _ in 0..public_key_fields(key_type)? {
+            reader.string()?;
+        }
+        reader.u64()?;
+        if reader.u32()? != USER_CERT {
+            return None;
+        }
+        // key id
+        reader.string()?;
+        let mut principals = vec![];
+        let mut list = Reader(reader.string()?);
+        while !list.0.is_empty() {
+            principals.push(String::from_utf8(list.string()?.to_vec()).ok()?);
+        }
+        let valid_after = reader.u64()?;
+        let valid_before = reader.u64()?;
+        Some(Self {
+            principals,
+            valid_after,
+            valid_before,
+        })
+    }
+
+    /// Checks whether the certificate is valid for `login` at the time
+    /// `now`, given in seconds since the epoch. An empty list of principals
+    /// allows any login.
+    pub fn check_at(&self, login: &str, now: u64) -> Result<(), SshErrorKind> {
+        if now < self.valid_after || now >= self.valid_before {
+            return Err(SshErrorKind::CertificateExpired);
+        }
+        if !self.principals.is_empty() && !self.principals.iter().any(|x| x == login) {
+            return Err(SshErrorKind::CertificatePrincipal(login.to_string()));
+        }
+        Ok(())
+    }
+
+    /// Checks whether the certificate is valid for `login` at the time
+    /// `now`, which is taken from the clock of the context.
+    pub fn check(&self, login: &str, now: SystemTime) -> Result<(), SshErrorKind> {
+        let now = now
+            .duration_since(UNIX_EPOCH)
+            .map(|x| x.as_secs())
+            .unwrap_or(0);
+        self.check_at(login, now)
+    }
+}
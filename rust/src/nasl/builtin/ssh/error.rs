@@ -58,10 +58,18 @@ pub enum SshErrorKind {
     NewSession,
     #[error("Invalid SSH session ID.")]
     InvalidSessionId,
+    #[error("The SSH session is already disconnected.")]
+    SessionClosed,
     #[error("Poisoned lock.")]
     PoisonedLock,
     #[error("Failed to connect.")]
     Connect,
+    #[error("The connection to the server was lost.")]
+    ConnectionLost,
+    #[error("The server refused the connection or did not answer.")]
+    Unreachable,
+    #[error("Failed to bind to the local address {0}.")]
+    Bind(String),
     #[error("Failed to open a new channel.")]
     OpenChannel,
     #[error("No available channel.")]
@@ -74,6 +82,8 @@ pub enum SshErrorKind {
     OpenSession,
     #[error("Failed to close channel.")]
     Close,
+    #[error("Failed to disconnect.")]
+    Disconnect,
     #[error("Failed to request PTY.")]
     RequestPty,
     #[error("Failed to request command execution.")]
@@ -114,12 +124,27 @@ pub enum SshErrorKind {
     NoAuthenticationGiven,
     #[error("Error while converting private key")]
     ConvertPrivateKey,
+    #[error("Invalid OpenSSH user certificate.")]
+    InvalidCertificate,
+    #[error("The certificate is expired or not yet valid.")]
+    CertificateExpired,
+    #[error("The certificate is not valid for the user {0}.")]
+    CertificatePrincipal(String),
+    #[error("Diffie-Hellman group exchange failed: {0}")]
+    DhGex(String),
     #[error("Not yet implemented.")]
     Unimplemented,
     #[error("Unexpected authentication status")]
     UnexpectedAuthenticationStatus(String),
 }
 
+impl SshErrorKind {
+    /// Whether the failed operation may succeed when it is repeated later.
+    pub fn is_transient(&self) -> bool {
+        matches!(self, SshErrorKind::Unreachable)
+    }
+}
+
 impl WithErrorInfo<SessionId> for SshError {
     type Error = SshError;
 
@@ -0,0 +1,231 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+//! Just enough of the SSH transport protocol (RFC 4253) to request a
+//! Diffie-Hellman group as described in RFC 4419. The exchange ends after
+//! the server sent the group, before any key is exchanged, so everything
+//! is unencrypted.
+
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use rsa::BigUint;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+
+use super::error::{Result, SshErrorKind};
+
+pub const MSG_DISCONNECT: u8 = 1;
+pub const MSG_IGNORE: u8 = 2;
+pub const MSG_DEBUG: u8 = 4;
+pub const MSG_KEXINIT: u8 = 20;
+pub const MSG_KEX_DH_GEX_GROUP: u8 = 31;
+pub const MSG_KEX_DH_GEX_REQUEST: u8 = 34;
+
+pub const GEX_ALGORITHMS: &str =
+    "diffie-hellman-group-exchange-sha256,diffie-hellman-group-exchange-sha1";
+
+const CLIENT_ID: &str = "SSH-2.0-OpenVAS";
+const TIMEOUT: Duration = Duration::from_secs(10);
+/// Larger packets are not expected before the key exchange.
+const MAX_PACKET_LEN: usize = 35000;
+/// The maximal length of an identification line, including CR LF
+/// (RFC 4253, section 4.2).
+const MAX_ID_LINE_LEN: usize = 255;
+
+/// The algorithms offered besides the group exchange, so that servers do not
+/// refuse the negotiation for lack of a common algorithm.
+const HOST_KEY_ALGORITHMS: &str =
+    "ssh-ed25519,ecdsa-sha2-nistp256,ecdsa-sha2-nistp384,ecdsa-sha2-nistp521,rsa-sha2-512,rsa-sha2-256,ssh-rsa,ssh-dss";
+const CIPHERS: &str = "chacha20-poly1305@openssh.com,aes128-gcm@openssh.com,aes256-gcm@openssh.com,aes128-ctr,aes192-ctr,aes256-ctr,aes128-cbc,aes256-cbc,3des-cbc";
+const MACS: &str = "hmac-sha2-256,hmac-sha2-512,hmac-sha1";
+
+fn error(message: impl ToString) -> super::SshError {
+    SshErrorKind::DhGex(message.to_string()).into()
+}
+
+/// Appends an SSH string to `out`.
+pub fn put_string(out: &mut Vec<u8>, data: &[u8]) {
+    out.extend((data.len() as u32).to_be_bytes());
+    out.extend(data);
+}
+
+/// Splits an SSH string off `data`.
+pub fn take_string<'a>(data: &mut &'a [u8]) -> Option<&'a [u8]> {
+    let len = u32::from_be_bytes(data.get(..4)?.try_into().ok()?) as usize;
+    let string = data.get(4..4 + len)?;
+    *data = &data[4 + len..];
+    Some(string)
+}
+
+/// Returns the payload of a KEXINIT message offering the given key exchange
+/// algorithms.
+pub fn kexinit(kex_algorithms: &str) -> Vec<u8> {
+    let mut payload = vec![MSG_KEXINIT];
+    payload.extend(rand::random::<[u8; 16]>());
+    for list in [
+        kex_algorithms,
+        HOST_KEY_ALGORITHMS,
+        CIPHERS,
+        CIPHERS,
+        MACS,
+        MACS,
+        "none",
+        "none",
+        "",
+        "",
+    ] {
+        put_string(&mut payload, list.as_bytes());
+    }
+    // first_kex_packet_follows and reserved
+    payload.push(0);
+    payload.extend(0u32.to_be_bytes());
+    payload
+}
+
+/// Writes `payload` as binary packet without encryption and MAC.
+pub async fn write_packet(
+    stream: &mut (impl AsyncWrite + Unpin),
+    payload: &[u8],
+) -> std::io::Result<()> {
+    // The padding is at least four bytes and aligns the packet to eight bytes.
+    let mut padding = 8 - (5 + payload.len()) % 8;
+    if padding < 4 {
+        padding += 8;
+    }
+    let mut packet = Vec::with_capacity(5 + payload.len() + padding);
+    packet.extend(((1 + payload.len() + padding) as u32).to_be_bytes());
+    packet.push(padding as u8);
+    packet.extend(payload);
+    packet.resize(packet.len() + padding, 0);
+    stream.write_all(&packet).await
+}
+
+/// Reads a binary packet without encryption and MAC and returns its payload.
+pub async fn read_packet(stream: &mut (impl AsyncRead + Unpin)) -> std::io::Result<Vec<u8>> {
+    let len = stream.read_u32().await? as usize;
+    if !(2..=MAX_PACKET_LEN).contains(&len) {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("invalid packet length {len}"),
+        ));
+    }
+    let mut packet = vec![0; len];
+    stream.read_exact(&mut packet).await?;
+    let padding = packet[0] as usize;
+    if padding + 1 > len {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("invalid padding length {padding}"),
+        ));
+    }
+    packet.truncate(len - padding);
+    packet.remove(0);
+    Ok(packet)
+}
+
+/// Sends the identification string and reads the one of the other side,
+/// skipping the lines a server may send before it. Lines longer than 255
+/// bytes are rejected.
+pub async fn exchange_ids<S: AsyncRead + AsyncWrite + Unpin>(
+    stream: &mut BufReader<S>,
+    id: &str,
+) -> std::io::Result<String> {
+    stream
+        .get_mut()
+        .write_all(format!("{id}\r\n").as_bytes())
+        .await?;
+    let mut line = String::new();
+    loop {
+        line.clear();
+        (&mut *stream)
+            .take(MAX_ID_LINE_LEN as u64)
+            .read_line(&mut line)
+            .await?;
+        if !line.ends_with('\n') {
+            if line.len() < MAX_ID_LINE_LEN {
+                return Err(std::io::ErrorKind::UnexpectedEof.into());
+            }
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("identification line longer than {MAX_ID_LINE_LEN} bytes"),
+            ));
+        }
+        if line.starts_with("SSH-") {
+            return Ok(line.trim_end().to_string());
+        }
+    }
+}
+
+/// Reads the next packet that is neither IGNORE nor DEBUG. A closed
+/// connection is an error, a DISCONNECT message is returned.
+async fn next_message<S: AsyncRead + Unpin>(stream: &mut S) -> Result<Vec<u8>> {
+    loop {
+        let payload = read_packet(stream).await.map_err(error)?;
+        match payload.first() {
+            Some(&MSG_IGNORE) | Some(&MSG_DEBUG) => {}
+            Some(_) => return Ok(payload),
+            None => return Err(error("empty packet")),
+        }
+    }
+}
+
+async fn request_group(addr: SocketAddr, min: u32, pref: u32, max: u32) -> Result<Option<usize>> {
+    let stream = TcpStream::connect(addr).await.map_err(error)?;
+    let mut stream = BufReader::new(stream);
+    exchange_ids(&mut stream, CLIENT_ID).await.map_err(error)?;
+    write_packet(stream.get_mut(), &kexinit(GEX_ALGORITHMS))
+        .await
+        .map_err(error)?;
+    let kexinit = next_message(&mut stream).await?;
+    if kexinit[0] == MSG_DISCONNECT {
+        return Err(error("the server disconnected before KEXINIT"));
+    }
+    if kexinit[0] != MSG_KEXINIT {
+        return Err(error(format!(
+            "expected KEXINIT, got message {}",
+            kexinit[0]
+        )));
+    }
+    let mut lists = kexinit.get(17..).unwrap_or_default();
+    let kex_algorithms = take_string(&mut lists).ok_or_else(|| error("invalid KEXINIT"))?;
+    let offered = String::from_utf8_lossy(kex_algorithms);
+    if !offered
+        .split(',')
+        .any(|x| GEX_ALGORITHMS.split(',').any(|y| x == y))
+    {
+        return Ok(None);
+    }
+    let mut request = vec![MSG_KEX_DH_GEX_REQUEST];
+    for x in [min, pref, max] {
+        request.extend(x.to_be_bytes());
+    }
+    write_packet(stream.get_mut(), &request)
+        .await
+        .map_err(error)?;
+    let group = next_message(&mut stream).await?;
+    // Servers refuse requests for groups they do not have by disconnecting.
+    if group[0] == MSG_DISCONNECT {
+        return Ok(None);
+    }
+    if group[0] != MSG_KEX_DH_GEX_GROUP {
+        return Err(error(format!(
+            "expected KEX_DH_GEX_GROUP, got message {}",
+            group[0]
+        )));
+    }
+    let mut fields = &group[1..];
+    let prime = take_string(&mut fields).ok_or_else(|| error("invalid KEX_DH_GEX_GROUP"))?;
+    Ok(Some(BigUint::from_bytes_be(prime).bits()))
+}
+
+/// Requests a group with a prime of at least `min`, preferably `pref` and
+/// at most `max` bits. Returns the size of the prime the server selected,
+/// or None if the server does not offer the group exchange or refused the
+/// request.
+pub async fn group_size(addr: SocketAddr, min: u32, pref: u32, max: u32) -> Result<Option<usize>> {
+    tokio::time::timeout(TIMEOUT, request_group(addr, min, pref, max))
+        .await
+        .map_err(|_| error(format!("no answer from {addr} before the timeout")))?
+}
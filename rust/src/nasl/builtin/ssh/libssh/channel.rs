@@ -48,6 +48,13 @@ impl Channel {
             .map_err(|e| SshErrorKind::Close.with(self.session_id).with(e))
     }
 
+    /// The exit status of the executed command, if the server sent one.
+    pub fn exit_status(&self) -> Option<u32> {
+        self.channel
+            .get_exit_status()
+            .and_then(|x| u32::try_from(x).ok())
+    }
+
     pub fn stdin(&self) -> impl std::io::Write + '_ {
         self.channel.stdin()
     }
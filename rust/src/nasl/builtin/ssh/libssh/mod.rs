@@ -7,7 +7,7 @@
 mod channel;
 mod session;
 
-use std::net::{IpAddr, UdpSocket};
+use std::net::{IpAddr, SocketAddr, UdpSocket};
 use std::{os::fd::AsRawFd, time::Duration};
 
 use libssh_rs::{LogLevel, SshOption};
@@ -16,7 +16,7 @@ use russh_keys::key;
 use tokio::sync::{Mutex, MutexGuard};
 use tracing::debug;
 
-use super::error::Result;
+use super::error::{Result, SshErrorKind};
 use super::Ssh;
 
 pub use libssh_rs::AuthMethods;
@@ -62,6 +62,21 @@ fn to_comma_separated_string<T: AsRef<str>>(items: &[T]) -> Option<String> {
 
 type BorrowedSession<'a> = MutexGuard<'a, SshSession>;
 
+/// Returns the names of the methods in the order of SSH_MSG_USERAUTH_FAILURE.
+pub fn auth_method_names(methods: AuthMethods) -> Vec<&'static str> {
+    [
+        (AuthMethods::NONE, "none"),
+        (AuthMethods::PASSWORD, "password"),
+        (AuthMethods::PUBLIC_KEY, "publickey"),
+        (AuthMethods::HOST_BASED, "hostbased"),
+        (AuthMethods::INTERACTIVE, "keyboard-interactive"),
+    ]
+    .into_iter()
+    .filter(|(method, _)| methods.contains(*method))
+    .map(|(_, name)| name)
+    .collect()
+}
+
 impl Ssh {
     pub async fn find_id<'a>(
         &'a self,
@@ -88,7 +103,15 @@ impl Ssh {
         csciphers: Vec<cipher::Name>,
         scciphers: Vec<cipher::Name>,
         timeout: Option<Duration>,
+        source: Option<SocketAddr>,
+        keepalive: Option<Duration>,
+        attempts: usize,
+        _backoff: Duration,
     ) -> Result<SessionId> {
+        // Retrying transient connection failures is only implemented with russh.
+        if attempts > 1 {
+            return Err(SshErrorKind::Unimplemented.into());
+        }
         let id = self.next_session_id()?;
         let session = Mutex::new(SshSession::new(id)?);
         {
@@ -103,6 +126,8 @@ impl Ssh {
                     csciphers,
                     scciphers,
                     timeout,
+                    source,
+                    keepalive,
                 )
                 .await
             {
@@ -125,6 +150,8 @@ impl Ssh {
         csciphers: Vec<cipher::Name>,
         scciphers: Vec<cipher::Name>,
         timeout: Option<Duration>,
+        source: Option<SocketAddr>,
+        keepalive: Option<Duration>,
     ) -> Result<()> {
         let ip_str = ip.to_string();
         session.set_option(SshOption::LogLevel(get_log_level()))?;
@@ -143,6 +170,19 @@ impl Ssh {
             session.set_option(SshOption::CiphersSC(scciphers))?;
         }
         session.set_option(SshOption::Port(port))?;
+        if let Some(source) = source {
+            // libssh only binds to an address, the port is chosen by the system.
+            if source.port() != 0 {
+                return Err(SshErrorKind::Unimplemented.with(session.id()));
+            }
+            session
+                .set_option(SshOption::BindAddress(source.ip().to_string()))
+                .map_err(|_| SshErrorKind::Bind(source.to_string()).with(session.id()))?;
+        }
+        // libssh-rs does not expose sending keepalives.
+        if keepalive.is_some() {
+            return Err(SshErrorKind::Unimplemented.with(session.id()));
+        }
 
         if let Some(socket) = socket {
             // This is a fake raw socket.
@@ -166,11 +206,45 @@ impl Ssh {
         Ok(())
     }
 
-    pub async fn disconnect_and_remove(&mut self, session_id: SessionId) -> Result<()> {
+    /// Connects a session which is not added to the list of active
+    /// sessions, to request the authentication methods for `user`.
+    pub async fn auth_methods(&mut self, ip: IpAddr, port: u16, user: &str) -> Result<Vec<String>> {
+        let id = self.next_session_id()?;
+        let session = Mutex::new(SshSession::new(id)?);
+        let mut session = session.lock().await;
+        let result = match self
+            .try_connect(
+                &mut session,
+                None,
+                ip,
+                port,
+                vec![],
+                vec![],
+                vec![],
+                None,
+                None,
+                None,
+            )
+            .await
         {
-            let mut session = self.get_by_id(session_id).await?;
-            session.disconnect().await?;
+            Ok(()) => session
+                .ensure_user_set(Some(user))
+                .and_then(|_| session.get_authmethods_cached()),
+            Err(e) => Err(e),
+        };
+        session.disconnect().await?;
+        result.map(|methods| {
+            auth_method_names(methods)
+                .into_iter()
+                .map(String::from)
+                .collect()
+        })
+    }
+
+    pub async fn disconnect_and_remove(&mut self, session_id: SessionId) -> Result<()> {
+        match self.take_open(session_id)? {
+            Some(mut session) => session.disconnect().await,
+            None => Ok(()),
         }
-        self.remove(session_id)
     }
 }
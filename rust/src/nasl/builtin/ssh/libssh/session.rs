@@ -8,7 +8,7 @@ use tokio::sync::{Mutex, MutexGuard};
 use tracing::{debug, info};
 
 use super::super::error::{Result, SshErrorKind};
-use super::super::Output;
+use super::super::CommandOutput;
 use super::SessionId;
 use super::{channel::Channel, Socket};
 use crate::nasl::utils::error::WithErrorInfo;
@@ -135,12 +135,15 @@ impl SshSession {
         Ok(())
     }
 
+    /// Closes the channel and disconnects. The session is disconnected even
+    /// if the channel cannot be closed, disconnecting again does nothing.
     pub async fn disconnect(&mut self) -> Result<()> {
-        if let Some(ref channel) = self.channel().await {
-            channel.close()?;
-        }
+        let result = match self.channel.take() {
+            Some(channel) => channel.into_inner().close(),
+            None => Ok(()),
+        };
         self.session().disconnect();
-        Ok(())
+        result
     }
 
     /// Request and set a shell. It set the pty if necessary.
@@ -152,7 +155,7 @@ impl SshSession {
         }
     }
 
-    pub async fn exec_ssh_cmd(&self, cmd: &str) -> Result<Output> {
+    pub async fn exec_ssh_cmd(&self, cmd: &str) -> Result<CommandOutput> {
         let channel = self.new_channel()?;
         channel.open_session()?;
         channel.request_pty("xterm", 80, 24)?;
@@ -161,7 +164,12 @@ impl SshSession {
         let timeout = Duration::from_millis(15000);
         let stderr = channel.read_timeout(timeout, true)?;
         let stdout = channel.read_timeout(timeout, false)?;
-        Ok(Output { stdout, stderr })
+        Ok(CommandOutput {
+            stdout,
+            stderr,
+            exit_code: channel.exit_status(),
+            signal: None,
+        })
     }
 
     pub async fn auth_method_allowed(&mut self, method: AuthMethods) -> Result<bool> {
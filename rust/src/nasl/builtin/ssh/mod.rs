@@ -2,7 +2,9 @@
 //
 // SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
 
+mod certificate;
 mod error;
+mod gex;
 mod sessions;
 mod utils;
 
@@ -16,19 +18,26 @@ mod russh;
 #[cfg(not(feature = "nasl-builtin-libssh"))]
 pub use russh::{AuthMethods, SessionId, Socket, SshSession};
 
+#[cfg(any(test, feature = "test-support"))]
+#[cfg_attr(not(feature = "test-support"), allow(dead_code))]
+pub mod test_utils;
 #[cfg(test)]
 mod tests;
 
 pub use error::SshError;
 pub use sessions::SshSessions as Ssh;
 
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
 use std::time::Duration;
 
+use serde::{Deserialize, Serialize};
+
 use ::russh::{cipher, Preferred};
 use russh_keys::key;
 
 use crate::nasl::prelude::*;
 
+use certificate::Certificate;
 use error::SshErrorKind;
 use utils::CommaSeparated;
 
@@ -46,13 +55,58 @@ pub use libssh_uses::*;
 type Result<T> = std::result::Result<T, FnError>;
 
 const DEFAULT_SSH_PORT: u16 = 22;
+const DEFAULT_CONNECT_BACKOFF: Duration = Duration::from_secs(1);
+const DEFAULT_GEX_MIN: u32 = 1024;
+const DEFAULT_GEX_PREF: u32 = 2048;
+const DEFAULT_GEX_MAX: u32 = 8192;
+
+/// Returns the name of the server a request is recorded for, the given
+/// host or the IP of the target.
+fn server_name(ctx: &Context<'_>, host: Option<&str>) -> String {
+    match host {
+        Some(host) => host.to_string(),
+        None => ctx.target_ip().to_string(),
+    }
+}
+
+/// Resolves the given host or returns the IP of the target. The host is
+/// resolved within the recorded interaction, so that a replay does not
+/// depend on the name resolution.
+async fn resolve(ctx: &Context<'_>, host: Option<&str>, port: u16) -> Result<IpAddr> {
+    match host {
+        Some(host) => tokio::net::lookup_host((host, port))
+            .await
+            .ok()
+            .and_then(|mut x| x.next())
+            .map(|x| x.ip())
+            .ok_or_else(|| ArgumentError::wrong_argument("host", "a resolvable host", host).into()),
+        None => Ok(ctx.target_ip()),
+    }
+}
 
-pub struct Output {
+/// The signal an executed command was killed by.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExitSignal {
+    /// The name of the signal without the "SIG" prefix, e.g. "TERM".
+    pub name: String,
+    pub core_dumped: bool,
+    pub error_message: String,
+}
+
+/// The separate streams and the exit code of an executed command.
+#[derive(Serialize, Deserialize)]
+pub struct CommandOutput {
     stdout: String,
     stderr: String,
+    /// None if the command did not exit cleanly. For a command killed by a
+    /// signal, this is 128 plus the number of the signal, as in a shell.
+    #[serde(default)]
+    exit_code: Option<u32>,
+    #[serde(default)]
+    signal: Option<ExitSignal>,
 }
 
-impl Output {
+impl CommandOutput {
     fn combine(&self, to_stdout: bool, to_stderr: bool, compat_mode: bool) -> String {
         let mut response = String::new();
         if to_stderr {
@@ -68,14 +122,56 @@ impl Output {
     }
 }
 
+impl From<CommandOutput> for NaslValue {
+    fn from(output: CommandOutput) -> Self {
+        let exit_code = output
+            .exit_code
+            .map(|x| NaslValue::Number(x as i64))
+            .unwrap_or(NaslValue::Null);
+        let mut dict: std::collections::BTreeMap<_, _> = [
+            ("stdout".to_string(), NaslValue::String(output.stdout)),
+            ("stderr".to_string(), NaslValue::String(output.stderr)),
+            ("exit_code".to_string(), exit_code),
+        ]
+        .into();
+        if let Some(signal) = output.signal {
+            dict.insert("signal".to_string(), NaslValue::String(signal.name));
+        }
+        NaslValue::Dict(dict)
+    }
+}
+
+/// Returns the local address to bind the connection to `target` to, if one is given. Without
+/// `bind_addr` the unspecified address of the family of `target` is used.
+fn source_addr(
+    target: IpAddr,
+    bind_addr: Option<&str>,
+    bind_port: Option<u16>,
+) -> std::result::Result<Option<SocketAddr>, SshError> {
+    if bind_addr.is_none() && bind_port.is_none() {
+        return Ok(None);
+    }
+    let ip = match bind_addr {
+        Some(addr) => addr
+            .parse()
+            .map_err(|e| SshErrorKind::InvalidIpAddr(addr.to_string(), e))?,
+        None if target.is_ipv4() => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+        None => IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+    };
+    Ok(Some(SocketAddr::new(ip, bind_port.unwrap_or(0))))
+}
+
 #[cfg(feature = "nasl-builtin-libssh")]
 function_set! {
     Ssh,
     (
         (Ssh::nasl_ssh_connect, "ssh_connect"),
         (Ssh::nasl_ssh_request_exec, "ssh_request_exec"),
+        (Ssh::nasl_ssh_exec, "ssh_exec"),
         (Ssh::nasl_ssh_userauth, "ssh_userauth"),
         (Ssh::nasl_ssh_disconnect, "ssh_disconnect"),
+        (Ssh::nasl_ssh_auth_methods, "ssh_auth_methods"),
+        (Ssh::nasl_ssh_dh_gex, "ssh_dh_gex"),
         (Ssh::nasl_ssh_session_id_from_sock, "ssh_session_id_from_sock"),
         (Ssh::nasl_ssh_get_sock, "ssh_get_sock"),
         (Ssh::nasl_ssh_set_login, "ssh_set_login"),
@@ -100,8 +196,10 @@ function_set! {
     (
         (Ssh::nasl_ssh_connect, "ssh_connect"),
         (Ssh::nasl_ssh_request_exec, "ssh_request_exec"),
+        (Ssh::nasl_ssh_exec, "ssh_exec"),
         (Ssh::nasl_ssh_userauth, "ssh_userauth"),
         (Ssh::nasl_ssh_disconnect, "ssh_disconnect"),
+        (Ssh::nasl_ssh_dh_gex, "ssh_dh_gex"),
     )
 }
 
@@ -137,8 +235,40 @@ impl Ssh {
     /// - timeout Set a timeout for the connection in seconds. Defaults to 10
     /// seconds (defined by libssh internally) if not given.
     ///
+    /// - bind_addr The local address the connection originates from, e.g.
+    ///             the address of a specific interface.
+    ///
+    /// - bind_port The local port the connection originates from.
+    ///
+    /// - keepalive_interval Send a keepalive every given number of seconds
+    ///                      while the connection is idle, so that it is not
+    ///                      dropped by stateful firewalls.  If three
+    ///                      keepalives in a row are unanswered, the
+    ///                      connection counts as lost.  0, the default,
+    ///                      disables keepalives.
+    ///
+    /// - retries How often a connection which failed transiently, e.g.
+    ///           because it was refused while the target reboots, is
+    ///           retried.  Failures like an unsupported key type are not
+    ///           retried.  Defaults to 0.  Only supported with russh.
+    ///
+    /// - backoff The delay before the first retry in milliseconds, which
+    ///           doubles with every further retry.  Defaults to 1000.
+    ///
     /// nasl return An integer to identify the ssh session. Zero on error.
-    #[nasl_function(named(socket, port, keytype, csciphers, scciphers, timeout))]
+    #[nasl_function(named(
+        socket,
+        port,
+        keytype,
+        csciphers,
+        scciphers,
+        timeout,
+        bind_addr,
+        bind_port,
+        keepalive_interval,
+        retries,
+        backoff
+    ))]
     pub async fn nasl_ssh_connect(
         &mut self,
         ctx: &Context<'_>,
@@ -148,12 +278,21 @@ impl Ssh {
         csciphers: Option<CommaSeparated<cipher::Name>>,
         scciphers: Option<CommaSeparated<cipher::Name>>,
         timeout: Option<u64>,
+        bind_addr: Option<&str>,
+        bind_port: Option<u16>,
+        keepalive_interval: Option<u64>,
+        retries: Option<usize>,
+        backoff: Option<u64>,
     ) -> Result<SessionId> {
         let port = port
             .filter(|_| socket.is_none())
             .unwrap_or(DEFAULT_SSH_PORT);
         let ip = ctx.target_ip();
+        let source = source_addr(ip, bind_addr, bind_port)?;
         let timeout = timeout.map(Duration::from_secs);
+        let keepalive = keepalive_interval
+            .filter(|x| *x > 0)
+            .map(Duration::from_secs);
         let keytype = keytype
             .map(|keytype| keytype.0)
             .unwrap_or(Preferred::DEFAULT.key[..].to_vec());
@@ -163,10 +302,21 @@ impl Ssh {
         let scciphers = scciphers
             .map(|sccipher| sccipher.0)
             .unwrap_or(Preferred::DEFAULT.cipher[..].to_vec());
-
-        Ok(self
-            .connect(socket, ip, port, keytype, csciphers, scciphers, timeout)
-            .await?)
+        let attempts = retries.unwrap_or(0).saturating_add(1);
+        let backoff = backoff
+            .map(Duration::from_millis)
+            .unwrap_or(DEFAULT_CONNECT_BACKOFF);
+
+        let request = format!("ssh_connect {ip} {port} socket={socket:?}");
+        ctx.network_mode()
+            .interact(
+                request,
+                self.connect(
+                    socket, ip, port, keytype, csciphers, scciphers, timeout, source, keepalive,
+                    attempts, backoff,
+                ),
+            )
+            .await
     }
 
     /// Run a command via ssh.
@@ -199,15 +349,23 @@ impl Ssh {
     #[nasl_function(named(cmd, stdout, stderr))]
     pub async fn nasl_ssh_request_exec(
         &self,
+        ctx: &Context<'_>,
         session_id: SessionId,
         cmd: &str,
         stdout: Option<bool>,
         stderr: Option<bool>,
     ) -> Result<Option<String>> {
-        let session = self.get_by_id(session_id).await?;
-        if cmd.is_empty() {
+        let exec = async {
+            let session = self.get_by_id(session_id).await?;
+            if cmd.is_empty() {
+                return Ok(None);
+            }
+            session.exec_ssh_cmd(cmd).await.map(Some)
+        };
+        let request = format!("ssh_request_exec {session_id} {cmd:?}");
+        let Some(output) = ctx.network_mode().interact(request, exec).await? else {
             return Ok(None);
-        }
+        };
         let (to_stdout, to_stderr, compat_mode) = match (stdout, stderr) {
             (None, None) => (true, false, false),
             (Some(false), Some(false)) => (true, false, true),
@@ -215,11 +373,36 @@ impl Ssh {
         };
         // TODO: Currently the compat mode above is not implemented as described
         // but instead we receive stderr and stdout until EOF and then combine the two.
-        Ok(Some(session.exec_ssh_cmd(cmd).await?.combine(
-            to_stdout,
-            to_stderr,
-            compat_mode,
-        )))
+        Ok(Some(output.combine(to_stdout, to_stderr, compat_mode)))
+    }
+
+    /// Run a command via ssh and return its output as array.
+    ///
+    /// Unlike ssh_request_exec the streams are not combined. The array
+    /// contains the output to stdout as "stdout", the output to stderr
+    /// as "stderr" and the exit status of the command as "exit_code",
+    /// which is NULL if the command did not exit cleanly.  If the command
+    /// was killed by a signal, the array contains the name of the signal,
+    /// e.g. "TERM", as "signal" and "exit_code" is 128 plus the number of
+    /// the signal, as in a shell.
+    ///
+    /// The first unnamed argument is the session id, the command is
+    /// expected in the named argument "cmd". An unknown or already
+    /// disconnected session id is an error.
+    #[nasl_function(named(cmd))]
+    pub async fn nasl_ssh_exec(
+        &self,
+        ctx: &Context<'_>,
+        session_id: SessionId,
+        cmd: &str,
+    ) -> Result<NaslValue> {
+        let exec = async {
+            let session = self.get_by_id(session_id).await?;
+            session.exec_ssh_cmd(cmd).await
+        };
+        let request = format!("ssh_exec {session_id} {cmd:?}");
+        let output: CommandOutput = ctx.network_mode().interact(request, exec).await?;
+        Ok(output.into())
     }
 
     /// Authenticate a user on an ssh connection
@@ -250,6 +433,14 @@ impl Ssh {
     /// named argument "passphrase" or, if not given, taken from the KB
     /// ("Secret/SSH/passphrase").
     ///
+    /// To authenticate with an OpenSSH user certificate issued by a CA, the
+    /// named argument "certificate" must contain the certificate of
+    /// "privatekey" as found in a -cert.pub file.  A certificate that is
+    /// expired or not valid for the login results in an error before the
+    /// server is contacted, as does a certificate the server rejects.
+    /// Certificates can only be presented with russh, libssh-rs does not
+    /// support them.
+    ///
     /// Note that the named argument "publickey" and the KB item
     /// ("Secret/SSH/publickey") are ignored - they are not longer required
     /// because they can be derived from the private key.
@@ -269,65 +460,200 @@ impl Ssh {
     ///
     /// - passphrase: A string with the passphrase used to unprotect privatekey.
     ///
+    /// - certificate: An OpenSSH user certificate of privatekey.
+    ///
     /// return An integer as status value; 0 indicates success.
-    #[nasl_function(named(login, password, privatekey, passphrase))]
+    #[nasl_function(named(login, password, privatekey, passphrase, certificate))]
     pub async fn nasl_ssh_userauth(
         &self,
+        ctx: &Context<'_>,
         session_id: SessionId,
         login: Option<&str>,
         password: Option<&str>,
         privatekey: Option<&str>,
         passphrase: Option<&str>,
+        certificate: Option<&str>,
     ) -> Result<()> {
         if password.is_none() && privatekey.is_none() && passphrase.is_none() {
             //TODO: Get values from KB
             return Err(SshErrorKind::NoAuthenticationGiven.with(session_id).into());
         }
         let login = login.unwrap_or("");
-        let mut session = self.get_by_id(session_id).await?;
-        // Check whether a password has been given.  If so, try to
-        // authenticate using that password.  Note that the OpenSSH client
-        // uses a different order: it first tries the public key and then the
-        // password.  However, the old NASL SSH protocol implementation tries
-        // the password before the public key authentication.  Because we
-        // want to be compatible, we do it in that order.
-        if let Some(password) = password {
-            if session.auth_method_allowed(AuthMethods::PASSWORD).await?
-                && session.auth_password(login, password).await.is_ok()
-            {
-                return Ok(());
-            }
-            if session
-                .auth_method_allowed(AuthMethods::INTERACTIVE)
-                .await?
-                && session
-                    .auth_keyboard_interactive(login, password)
-                    .await
-                    .is_ok()
-            {
-                return Ok(());
+        if let Some(certificate) = certificate {
+            if privatekey.is_none() {
+                return Err(SshErrorKind::NoAuthenticationGiven.with(session_id).into());
             }
+            let certificate = Certificate::parse(certificate)
+                .ok_or_else(|| SshErrorKind::InvalidCertificate.with(session_id))?;
+            certificate
+                .check(login, ctx.clock().now())
+                .map_err(|kind| kind.with(session_id))?;
+            #[cfg(feature = "nasl-builtin-libssh")]
+            return Err(SshErrorKind::Unimplemented.with(session_id).into());
         }
-        Ok(())
+        let auth = async {
+            #[cfg(not(feature = "nasl-builtin-libssh"))]
+            if let (Some(privatekey), Some(certificate)) = (privatekey, certificate) {
+                let credentials =
+                    russh::Credentials::with_certificate(login, privatekey, certificate);
+                self.claim_pooled(session_id, &credentials).await?;
+                let mut session = self.get_by_id(session_id).await?;
+                return session
+                    .auth_certificate(login, privatekey, passphrase, certificate)
+                    .await;
+            }
+            #[cfg(not(feature = "nasl-builtin-libssh"))]
+            if let Some(password) = password {
+                let credentials = russh::Credentials::new(login, password);
+                self.claim_pooled(session_id, &credentials).await?;
+            }
+            let mut session = self.get_by_id(session_id).await?;
+            // Check whether a password has been given.  If so, try to
+            // authenticate using that password.  Note that the OpenSSH client
+            // uses a different order: it first tries the public key and then the
+            // password.  However, the old NASL SSH protocol implementation tries
+            // the password before the public key authentication.  Because we
+            // want to be compatible, we do it in that order.
+            if let Some(password) = password {
+                if session.auth_method_allowed(AuthMethods::PASSWORD).await?
+                    && session.auth_password(login, password).await.is_ok()
+                {
+                    return Ok(());
+                }
+                if session
+                    .auth_method_allowed(AuthMethods::INTERACTIVE)
+                    .await?
+                    && session
+                        .auth_keyboard_interactive(login, password)
+                        .await
+                        .is_ok()
+                {
+                    return Ok(());
+                }
+            }
+            Ok::<_, SshError>(())
+        };
+        // The credentials are not part of the request, so they do not end up in recordings.
+        let request = format!("ssh_userauth {session_id} {login:?}");
+        ctx.network_mode().interact(request, auth).await
     }
 
     /// Disconnect an ssh connection
     /// This function takes the ssh session id (as returned by ssh_connect)
     /// as its only unnamed argument.  Passing 0 as session id is
-    /// explicitly allowed and does nothing.  If there are any open
-    /// channels they are closed as well and their ids will be marked as
-    /// invalid.
+    /// explicitly allowed and does nothing, as is disconnecting a session
+    /// twice.  If there are any open channels they are closed as well and
+    /// their ids will be marked as invalid.  A session whose disconnect
+    /// message cannot be sent is still disconnected, but the failure is
+    /// returned as error.
     #[nasl_function]
-    pub async fn nasl_ssh_disconnect(&mut self, session_id: SessionId) -> Result<()> {
+    pub async fn nasl_ssh_disconnect(
+        &mut self,
+        ctx: &Context<'_>,
+        session_id: SessionId,
+    ) -> Result<()> {
         if session_id != 0 {
-            self.disconnect_and_remove(session_id).await?;
+            let request = format!("ssh_disconnect {session_id}");
+            ctx.network_mode()
+                .interact(request, self.disconnect_and_remove(session_id))
+                .await?;
         }
         Ok(())
     }
+
+    /// Request the parameters of a Diffie-Hellman group exchange
+    /// (RFC 4419) without completing the key exchange.
+    ///
+    /// nasl named params
+    ///
+    /// - host: The host to connect to, defaults to the target.
+    ///
+    /// - port: The port to connect to, defaults to 22.
+    ///
+    /// - min: The minimal size of the prime in bits, defaults to 1024.
+    ///
+    /// - pref: The preferred size of the prime in bits, defaults to 2048.
+    ///
+    /// - max: The maximal size of the prime in bits, defaults to 8192.
+    ///
+    /// return The size of the prime the server selected in bits, or 0 if
+    /// the server does not offer the group exchange or refused the
+    /// request. A failed connection is an error.
+    #[nasl_function(named(host, port, min, pref, max))]
+    pub async fn nasl_ssh_dh_gex(
+        &self,
+        ctx: &Context<'_>,
+        host: Option<&str>,
+        port: Option<u16>,
+        min: Option<u32>,
+        pref: Option<u32>,
+        max: Option<u32>,
+    ) -> Result<usize> {
+        let port = port.unwrap_or(DEFAULT_SSH_PORT);
+        let min = min.unwrap_or(DEFAULT_GEX_MIN);
+        let pref = pref.unwrap_or(DEFAULT_GEX_PREF);
+        let max = max.unwrap_or(DEFAULT_GEX_MAX);
+        if min > pref || pref > max {
+            return Err(ArgumentError::wrong_argument(
+                "pref",
+                "between min and max",
+                &format!("min={min}, pref={pref}, max={max}"),
+            )
+            .into());
+        }
+        let server = server_name(ctx, host);
+        let request = format!("ssh_dh_gex {server} {port} {min} {pref} {max}");
+        ctx.network_mode()
+            .interact(request, async {
+                let ip = resolve(ctx, host, port).await?;
+                Ok::<_, FnError>(
+                    gex::group_size(SocketAddr::new(ip, port), min, pref, max)
+                        .await
+                        .map(|bits| bits.unwrap_or(0))?,
+                )
+            })
+            .await
+    }
 }
 
 #[cfg(feature = "nasl-builtin-libssh")]
 impl Ssh {
+    /// Return the authentication methods the server offers to a user.
+    ///
+    /// The function connects to the server, attempts the "none"
+    /// authentication to elicit the list of methods the server supports
+    /// and disconnects again. No session id is involved.
+    ///
+    /// nasl named params
+    ///
+    /// - host: The host to connect to, defaults to the target.
+    ///
+    /// - port: The port to connect to, defaults to 22.
+    ///
+    /// - user: The user whose methods are requested.
+    ///
+    /// return An array with the names of the methods, e.g. "publickey",
+    /// "password" and "keyboard-interactive". The array is empty if the
+    /// server offers no method, a failed connection is an error.
+    #[nasl_function(named(host, port, user))]
+    pub async fn nasl_ssh_auth_methods(
+        &mut self,
+        ctx: &Context<'_>,
+        host: Option<&str>,
+        port: Option<u16>,
+        user: &str,
+    ) -> Result<Vec<String>> {
+        let port = port.unwrap_or(DEFAULT_SSH_PORT);
+        let server = server_name(ctx, host);
+        let request = format!("ssh_auth_methods {server} {port} {user:?}");
+        ctx.network_mode()
+            .interact(request, async {
+                let ip = resolve(ctx, host, port).await?;
+                self.auth_methods(ip, port, user).await
+            })
+            .await
+    }
+
     /// Given a socket, return the corresponding session id if available.
     #[nasl_function]
     pub async fn nasl_ssh_session_id_from_sock(&self, socket: Socket) -> Result<Option<SessionId>> {
@@ -565,25 +891,7 @@ impl Ssh {
     pub async fn nasl_ssh_get_auth_methods(&self, session_id: SessionId) -> Result<Option<String>> {
         let mut session = self.get_by_id(session_id).await?;
         session.ensure_user_set(None)?;
-        let authmethods = session.get_authmethods_cached()?;
-
-        let mut methods = vec![];
-        if authmethods.contains(AuthMethods::NONE) {
-            methods.push("none");
-        }
-        if authmethods.contains(AuthMethods::PASSWORD) {
-            methods.push("password");
-        }
-        if authmethods.contains(AuthMethods::PUBLIC_KEY) {
-            methods.push("publickey");
-        }
-        if authmethods.contains(AuthMethods::HOST_BASED) {
-            methods.push("hostbased");
-        }
-        if authmethods.contains(AuthMethods::INTERACTIVE) {
-            methods.push("keyboard-interactive");
-        }
-
+        let methods = libssh::auth_method_names(session.get_authmethods_cached()?);
         if methods.is_empty() {
             return Ok(None);
         }
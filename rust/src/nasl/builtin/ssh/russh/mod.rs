@@ -2,16 +2,24 @@
 //
 // SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
 
+mod pool;
 mod session;
 
-pub use session::SshSession;
+pub use pool::SessionPool;
+pub use session::{ConnectParams, Credentials, SshSession};
 use tokio::sync::Mutex;
 
-use std::{net::IpAddr, time::Duration};
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
 
 use russh::cipher;
 use russh_keys::key;
 
+use tracing::debug;
+
+use crate::nasl::utils::error::{Retryable, WithErrorInfo};
+use crate::nasl::utils::{ArgumentError, FnError};
+
 use super::error::Result;
 
 use super::sessions::SshSessions;
@@ -30,6 +38,18 @@ pub enum AuthMethods {
 }
 
 impl SshSessions {
+    /// Keeps the authenticated sessions disconnected by a script open for
+    /// `max_idle`, so that a later `ssh_connect` with the same parameters
+    /// reuses them. A pooled session is only usable after `ssh_userauth`
+    /// presented the credentials it is authenticated with. Otherwise it is
+    /// exchanged for a pooled session authenticated with the presented
+    /// credentials or for a new connection.
+    pub fn with_pool(max_idle: Duration) -> Self {
+        let mut sessions = Self::default();
+        sessions.pool = Some(Mutex::new(SessionPool::new(max_idle)));
+        sessions
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub async fn connect(
         &mut self,
@@ -40,19 +60,117 @@ impl SshSessions {
         csciphers: Vec<cipher::Name>,
         scciphers: Vec<cipher::Name>,
         timeout: Option<Duration>,
-    ) -> Result<SessionId> {
+        source: Option<SocketAddr>,
+        keepalive: Option<Duration>,
+        attempts: usize,
+        backoff: Duration,
+    ) -> std::result::Result<SessionId, FnError> {
+        let params = ConnectParams {
+            ip_addr,
+            port,
+            timeout,
+            keytype,
+            csciphers,
+            scciphers,
+            source,
+            keepalive,
+        };
+        self.connect_with_retry(params, socket, attempts, backoff)
+            .await
+            .map(|(id, _)| id)
+    }
+
+    async fn open(&mut self, params: ConnectParams, socket: Option<Socket>) -> Result<SessionId> {
         let id = self.next_session_id()?;
-        let session = Mutex::new(
-            SshSession::new(
-                id, ip_addr, port, timeout, keytype, csciphers, scciphers, socket,
-            )
-            .await?,
-        );
-        self.insert(id, session);
+        let pooled = match (&self.pool, socket) {
+            (Some(pool), None) => pool.lock().await.reserve(&params).await,
+            _ => None,
+        };
+        let session = match pooled {
+            Some(mut session) => {
+                session.hand_out(id);
+                session
+            }
+            None => SshSession::new(id, params, socket).await?,
+        };
+        self.insert(id, Mutex::new(session));
         Ok(id)
     }
 
+    /// Connects with `params` and retries up to `attempts` times in total
+    /// as long as the failure is transient, e.g. because the target is
+    /// rebooting and refuses connections. The delay before the first retry
+    /// is `backoff` and doubles with every further retry. Failures like
+    /// an unsupported key type are returned immediately.
+    ///
+    /// Returns the session id and the number of attempts made. A transient
+    /// failure of the last attempt is marked as retryable.
+    pub async fn connect_with_retry(
+        &mut self,
+        params: ConnectParams,
+        socket: Option<Socket>,
+        attempts: usize,
+        backoff: Duration,
+    ) -> std::result::Result<(SessionId, usize), FnError> {
+        if attempts == 0 {
+            return Err(ArgumentError::wrong_argument("attempts", "at least 1", "0").into());
+        }
+        let mut attempt = 1;
+        let mut delay = backoff;
+        loop {
+            match self.open(params.clone(), socket).await {
+                Ok(id) => return Ok((id, attempt)),
+                Err(e) if e.kind.is_transient() && attempt < attempts => {
+                    debug!("Connection attempt {attempt} failed, retrying: {e}");
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                    delay *= 2;
+                }
+                Err(e) if e.kind.is_transient() => return Err(e.with(Retryable)),
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+
+    /// Exchanges a handed out pooled session for one authenticated with the
+    /// given credentials before they are used to authenticate. The previous
+    /// session goes back to the pool.
+    pub async fn claim_pooled(
+        &self,
+        session_id: SessionId,
+        credentials: &Credentials,
+    ) -> Result<()> {
+        let Some(pool) = &self.pool else {
+            return Ok(());
+        };
+        let mut session = self.get_by_id(session_id).await?;
+        match session.pooled_credentials() {
+            Some(pooled) if pooled != credentials => {}
+            _ => return Ok(()),
+        }
+        let mut pool = pool.lock().await;
+        let replacement = match pool.take(session.params(), credentials).await {
+            Some(mut found) => {
+                found.hand_out(session_id);
+                found
+            }
+            None => SshSession::new(session_id, session.params().clone(), None).await?,
+        };
+        pool.park(std::mem::replace(&mut *session, replacement));
+        Ok(())
+    }
+
     pub async fn disconnect_and_remove(&mut self, session_id: SessionId) -> Result<()> {
-        self.remove(session_id)
+        // The session is unusable even if the disconnect message could not be sent.
+        let Some(mut session) = self.take_open(session_id)? else {
+            return Ok(());
+        };
+        match &self.pool {
+            Some(pool) if session.is_authenticated() && !session.is_closed() => {
+                pool.lock().await.park(session);
+                Ok(())
+            }
+            _ => session.disconnect().await,
+        }
     }
 }
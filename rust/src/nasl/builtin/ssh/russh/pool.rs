@@ -0,0 +1,76 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+use std::time::{Duration, Instant};
+
+use tracing::debug;
+
+use super::session::{ConnectParams, Credentials, SshSession};
+
+/// Authenticated sessions that were disconnected by a script, kept open
+/// to be handed out to the next `ssh_connect` to the same server.
+pub struct SessionPool {
+    max_idle: Duration,
+    idle: Vec<(Instant, SshSession)>,
+}
+
+impl SessionPool {
+    pub fn new(max_idle: Duration) -> Self {
+        Self {
+            max_idle,
+            idle: vec![],
+        }
+    }
+
+    pub fn park(&mut self, mut session: SshSession) {
+        session.park();
+        self.idle.push((Instant::now(), session));
+    }
+
+    /// Removes and returns the most recently parked session connected with
+    /// `params`, to be handed out before the credentials are known. Sessions
+    /// idle for longer than the maximum and sessions whose connection is
+    /// gone are disconnected beforehand.
+    pub async fn reserve(&mut self, params: &ConnectParams) -> Option<SshSession> {
+        self.expire().await;
+        let index = self
+            .idle
+            .iter()
+            .rposition(|(_, session)| session.params() == params)?;
+        Some(self.idle.remove(index).1)
+    }
+
+    /// Like [`Self::reserve`], but only returns a session authenticated
+    /// with `credentials`.
+    pub async fn take(
+        &mut self,
+        params: &ConnectParams,
+        credentials: &Credentials,
+    ) -> Option<SshSession> {
+        self.expire().await;
+        let index = self.idle.iter().rposition(|(_, session)| {
+            session.params() == params && session.credentials() == Some(credentials)
+        })?;
+        Some(self.idle.remove(index).1)
+    }
+
+    async fn expire(&mut self) {
+        let now = Instant::now();
+        let (expired, idle): (Vec<_>, Vec<_>) = std::mem::take(&mut self.idle)
+            .into_iter()
+            .partition(|(since, session)| {
+                session.is_closed() || now.duration_since(*since) > self.max_idle
+            });
+        self.idle = idle;
+        for (_, mut session) in expired {
+            if let Err(e) = session.disconnect().await {
+                debug!("Failed to disconnect an expired pooled session: {e}");
+            }
+        }
+    }
+
+    pub fn drain(&mut self) -> impl Iterator<Item = SshSession> + '_ {
+        self.idle.drain(..).map(|(_, session)| session)
+    }
+}
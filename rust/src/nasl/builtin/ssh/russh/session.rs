@@ -3,23 +3,31 @@
 // SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
 
 use std::borrow::Cow;
+use std::net::{IpAddr, SocketAddr};
+use std::ops::{Deref, DerefMut};
+use std::sync::Arc;
 use std::time::Duration;
-use std::{net::IpAddr, sync::Arc};
 
 use async_trait::async_trait;
-use client::{connect, DisconnectReason, Session};
+use client::{connect, connect_stream, DisconnectReason, Session};
 use russh::keys::*;
 use russh::*;
-use tracing::{error, warn};
+use sha2::{Digest, Sha256};
+use tokio::net::{TcpSocket, TcpStream};
+use tracing::{debug, error, warn};
 
 use crate::nasl::builtin::ssh::error::SshErrorKind;
-use crate::nasl::builtin::ssh::Output;
+use crate::nasl::builtin::ssh::{CommandOutput, ExitSignal};
 use crate::nasl::utils::error::WithErrorInfo;
 use crate::nasl::utils::function::bytes_to_str;
 
 use super::super::error::SshError;
 use super::{AuthMethods, Port, SessionId, Socket};
 
+/// The number of unanswered keepalives after which the connection is
+/// considered lost.
+const KEEPALIVE_MAX: usize = 3;
+
 struct Client {}
 
 #[async_trait]
@@ -64,6 +72,9 @@ impl client::Handler for Client {
             DisconnectReason::Error(e) => {
                 match e {
                     russh::Error::Disconnect => {}
+                    russh::Error::KeepaliveTimeout => {
+                        warn!("SSH session disconnected, the keepalives went unanswered");
+                    }
                     _ => {
                         error!("SSH session disconnected due to error: {}", e);
                     }
@@ -74,100 +85,377 @@ impl client::Handler for Client {
     }
 }
 
+/// The parameters a session is connected with. A pooled session is only
+/// handed out again for the same parameters.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConnectParams {
+    pub ip_addr: IpAddr,
+    pub port: Port,
+    pub timeout: Option<Duration>,
+    pub keytype: Vec<key::Name>,
+    pub csciphers: Vec<cipher::Name>,
+    pub scciphers: Vec<cipher::Name>,
+    pub source: Option<SocketAddr>,
+    pub keepalive: Option<Duration>,
+}
+
+/// The user a session is authenticated as, along with a digest of the
+/// password or of the private key and certificate, so that the secrets
+/// themselves are not kept.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Credentials {
+    login: String,
+    digest: Vec<u8>,
+}
+
+impl Credentials {
+    pub fn new(login: &str, password: &str) -> Self {
+        Self {
+            login: login.to_string(),
+            digest: Sha256::digest(password.as_bytes()).to_vec(),
+        }
+    }
+
+    pub fn with_certificate(login: &str, privatekey: &str, certificate: &str) -> Self {
+        let digest = Sha256::new()
+            .chain_update((privatekey.len() as u64).to_be_bytes())
+            .chain_update(privatekey.as_bytes())
+            .chain_update(certificate.as_bytes())
+            .finalize();
+        Self {
+            login: login.to_string(),
+            digest: digest.to_vec(),
+        }
+    }
+}
+
+/// The handle of a connection. If it is dropped without being closed,
+/// e.g. because a script failed, the disconnect message is sent in the
+/// background.
+struct Connection {
+    handle: Option<client::Handle<Client>>,
+    disconnected: bool,
+}
+
+impl Connection {
+    fn new(handle: client::Handle<Client>) -> Self {
+        Self {
+            handle: Some(handle),
+            disconnected: false,
+        }
+    }
+
+    /// Sends the disconnect message. The connection counts as closed even
+    /// if the message cannot be sent, closing again does nothing.
+    async fn close(&mut self) -> Result<(), russh::Error> {
+        if std::mem::replace(&mut self.disconnected, true) || self.is_closed() {
+            return Ok(());
+        }
+        self.disconnect(Disconnect::ByApplication, "", "en").await
+    }
+}
+
+impl Deref for Connection {
+    type Target = client::Handle<Client>;
+
+    fn deref(&self) -> &Self::Target {
+        self.handle
+            .as_ref()
+            .expect("the handle is only taken on drop")
+    }
+}
+
+impl DerefMut for Connection {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.handle
+            .as_mut()
+            .expect("the handle is only taken on drop")
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        let Some(handle) = self.handle.take() else {
+            return;
+        };
+        if self.disconnected || handle.is_closed() {
+            return;
+        }
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        runtime.spawn(async move {
+            if let Err(e) = handle.disconnect(Disconnect::ByApplication, "", "en").await {
+                debug!("Failed to disconnect a dropped session: {e}");
+            }
+        });
+    }
+}
+
 /// This struct is a convenience wrapper
 /// around a russh client
 pub struct SshSession {
     id: SessionId,
-    session: client::Handle<Client>,
+    session: Connection,
+    params: ConnectParams,
+    credentials: Option<Credentials>,
+    /// The credentials of a pooled session that was handed out again. The
+    /// session cannot be used until they are presented by `ssh_userauth`.
+    pooled_credentials: Option<Credentials>,
 }
 
 impl SshSession {
-    // The alternative to many arguments here is making
-    // the fields public or creating some sort of builder struct
-    // both of which feel like much worse alternatives.
-    #[allow(clippy::too_many_arguments)]
     pub async fn new(
         id: SessionId,
-        ip_addr: IpAddr,
-        port: Port,
-        timeout: Option<Duration>,
-        keytype: Vec<key::Name>,
-        csciphers: Vec<cipher::Name>,
-        scciphers: Vec<cipher::Name>,
+        params: ConnectParams,
         socket: Option<Socket>,
     ) -> Result<Self, SshError> {
         if socket.is_some() {
             error!("Using custom sockets not yet implemented.");
             return Err(SshErrorKind::Unimplemented.with(id));
         }
-        let preferred = construct_preferred(keytype, csciphers, scciphers);
-        let config = client::Config {
-            inactivity_timeout: timeout,
-            preferred,
-            ..Default::default()
-        };
+        let session = Connection::new(open(id, &params).await?);
+        Ok(Self {
+            session,
+            id,
+            params,
+            credentials: None,
+            pooled_credentials: None,
+        })
+    }
 
-        let config = Arc::new(config);
-        let sh = Client {};
+    pub fn params(&self) -> &ConnectParams {
+        &self.params
+    }
 
-        let session = connect(config, (ip_addr, port), sh)
-            .await
-            .map_err(|e| SshErrorKind::Connect.with(id).with(e))?;
+    /// Hands out a pooled session under a new id. It stays unusable until
+    /// the same credentials are presented again.
+    pub fn hand_out(&mut self, id: SessionId) {
+        self.id = id;
+        self.pooled_credentials = self.credentials.take().or(self.pooled_credentials.take());
+    }
 
-        Ok(Self { session, id })
+    /// Prepares the session for the pool, whether or not the credentials
+    /// were presented after it was handed out.
+    pub fn park(&mut self) {
+        self.credentials = self.credentials.take().or(self.pooled_credentials.take());
     }
 
-    pub async fn exec_ssh_cmd(&self, command: &str) -> Result<Output, SshError> {
-        let (stdout, stderr) = self.call(command).await.map_err(|e| {
-            SshErrorKind::RequestExec(command.to_string())
-                .with(self.id)
-                .with(e)
-        })?;
-        Ok(Output { stdout, stderr })
+    pub fn credentials(&self) -> Option<&Credentials> {
+        self.credentials.as_ref()
     }
 
-    pub async fn call(&self, command: &str) -> Result<(String, String), russh::Error> {
-        let mut channel = self.session.channel_open_session().await?;
-        channel.exec(true, command).await?;
+    /// The credentials a handed out pooled session still waits for.
+    pub fn pooled_credentials(&self) -> Option<&Credentials> {
+        self.pooled_credentials.as_ref()
+    }
 
-        let mut code = None;
+    pub fn is_authenticated(&self) -> bool {
+        self.credentials.is_some() || self.pooled_credentials.is_some()
+    }
+
+    /// Whether the session was disconnected or the connection to the
+    /// server is gone.
+    pub fn is_closed(&self) -> bool {
+        self.session.disconnected || self.session.is_closed()
+    }
+
+    /// Prepares an authentication with the given credentials. Returns true
+    /// if the session is already authenticated with them, which is the case
+    /// for pooled sessions. A session authenticated as someone else is
+    /// replaced by a new connection, as the user cannot be changed.
+    async fn prepare_auth(&mut self, credentials: &Credentials) -> Result<bool, SshError> {
+        if self.pooled_credentials.as_ref() == Some(credentials) {
+            self.credentials = self.pooled_credentials.take();
+        }
+        match self
+            .credentials
+            .as_ref()
+            .or(self.pooled_credentials.as_ref())
+        {
+            Some(current) if current == credentials => Ok(true),
+            Some(_) => {
+                let session = Connection::new(open(self.id, &self.params).await?);
+                let mut previous = std::mem::replace(&mut self.session, session);
+                self.credentials = None;
+                self.pooled_credentials = None;
+                if let Err(e) = previous.close().await {
+                    warn!("Failed to disconnect the replaced session: {e}");
+                }
+                Ok(false)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Sends the disconnect message. The session counts as disconnected
+    /// even if the message cannot be sent, disconnecting again does nothing.
+    pub async fn disconnect(&mut self) -> Result<(), SshError> {
+        self.session
+            .close()
+            .await
+            .map_err(|e| SshErrorKind::Disconnect.with(self.id).with(e))
+    }
+
+    pub async fn exec_ssh_cmd(&self, command: &str) -> Result<CommandOutput, SshError> {
+        self.call(command).await
+    }
+
+    /// Returns [`SshErrorKind::ConnectionLost`] instead of `kind` if the
+    /// connection is gone, e.g. because the keepalives went unanswered.
+    fn request_error(&self, kind: SshErrorKind) -> SshError {
+        if self.session.is_closed() && !self.session.disconnected {
+            SshErrorKind::ConnectionLost.with(self.id)
+        } else {
+            kind.with(self.id)
+        }
+    }
+
+    pub async fn call(&self, command: &str) -> Result<CommandOutput, SshError> {
         let mut stdout = String::new();
         let mut stderr = String::new();
+        let (code, signal) = self
+            .call_streaming(
+                command,
+                |data| {
+                    stdout.push_str(&bytes_to_str(data));
+                    Ok(())
+                },
+                |data| {
+                    stderr.push_str(&bytes_to_str(data));
+                    Ok(())
+                },
+            )
+            .await?;
+        if code.is_none() {
+            warn!("Program did not exit cleanly: {}", command);
+        }
+        Ok(CommandOutput {
+            stdout,
+            stderr,
+            exit_code: code,
+            signal,
+        })
+    }
+
+    /// Executes `command` and passes each chunk of its stdout and stderr to
+    /// the callbacks as soon as it arrives, instead of collecting the whole
+    /// output. Returns the exit code, if the server sent one, and the signal
+    /// the command was killed by. In the latter case the exit code is 128
+    /// plus the number of the signal, as in a shell.
+    ///
+    /// If a callback returns an error, the channel is closed to abort the
+    /// command and the error is returned.
+    pub async fn call_streaming(
+        &self,
+        command: &str,
+        mut on_stdout: impl FnMut(&[u8]) -> Result<(), SshError>,
+        mut on_stderr: impl FnMut(&[u8]) -> Result<(), SshError>,
+    ) -> Result<(Option<u32>, Option<ExitSignal>), SshError> {
+        let make_err = |e: russh::Error| {
+            self.request_error(SshErrorKind::RequestExec(command.to_string()))
+                .with(e)
+        };
+        // The connection is authenticated on behalf of an earlier script,
+        // which must not be used without presenting the same credentials.
+        if self.pooled_credentials.is_some() {
+            return Err(self.request_error(SshErrorKind::RequestExec(command.to_string())));
+        }
+        let mut channel = self
+            .session
+            .channel_open_session()
+            .await
+            .map_err(make_err)?;
+        channel.exec(true, command).await.map_err(make_err)?;
 
+        let mut code = None;
+        let mut signal = None;
         loop {
             // There's an event available on the session channel
             let Some(msg) = channel.wait().await else {
                 break;
             };
-            match msg {
-                // Write data to the terminal
-                ChannelMsg::Data { ref data } => {
-                    stdout.push_str(&bytes_to_str(data));
-                }
-                ChannelMsg::ExtendedData { ref data, .. } => {
-                    stderr.push_str(&bytes_to_str(data));
-                }
+            let result = match msg {
+                ChannelMsg::Data { ref data } => on_stdout(&data[..]),
+                ChannelMsg::ExtendedData { ref data, .. } => on_stderr(&data[..]),
                 // The command has returned an exit code
                 ChannelMsg::ExitStatus { exit_status } => {
                     code = Some(exit_status);
                     // cannot leave the loop immediately, there might still be more data to receive
-                    channel.eof().await?;
+                    channel.eof().await.map_err(make_err)
+                }
+                // A command killed by a signal has no exit status.
+                ChannelMsg::ExitSignal {
+                    signal_name,
+                    core_dumped,
+                    error_message,
+                    ..
+                } => {
+                    let (name, number) = signal_name_and_number(signal_name);
+                    code = code.or(number.map(|x| 128 + x));
+                    signal = Some(ExitSignal {
+                        name,
+                        core_dumped,
+                        error_message,
+                    });
+                    channel.eof().await.map_err(make_err)
                 }
-                _ => {}
+                _ => Ok(()),
+            };
+            if let Err(e) = result {
+                if let Err(e) = channel.close().await {
+                    debug!("Failed to close the channel of an aborted command: {e}");
+                }
+                return Err(e);
             }
         }
-        if code.is_none() {
-            warn!("Program did not exit cleanly: {}", command);
-        }
-        Ok((stdout.to_string(), stderr.to_string()))
+        Ok((code, signal))
     }
 
     pub async fn auth_password(&mut self, login: &str, password: &str) -> Result<(), SshError> {
-        self.session
-            .authenticate_password(login, password)
+        let credentials = Credentials::new(login, password);
+        if self.prepare_auth(&credentials).await? {
+            return Ok(());
+        }
+        match self.session.authenticate_password(login, password).await {
+            Ok(true) => {
+                self.credentials = Some(credentials);
+                Ok(())
+            }
+            _ => Err(SshErrorKind::UserAuthPassword.with(self.id)),
+        }
+    }
+
+    /// Authenticates with an OpenSSH user certificate of `privatekey`.
+    pub async fn auth_certificate(
+        &mut self,
+        login: &str,
+        privatekey: &str,
+        passphrase: Option<&str>,
+        certificate: &str,
+    ) -> Result<(), SshError> {
+        let key = decode_secret_key(privatekey, passphrase).map_err(|e| {
+            SshErrorKind::ConvertPrivateKey
+                .with(self.id)
+                .with(russh::Error::from(e))
+        })?;
+        let cert = ssh_key::Certificate::from_openssh(certificate)
+            .map_err(|_| SshErrorKind::InvalidCertificate.with(self.id))?;
+        let credentials = Credentials::with_certificate(login, privatekey, certificate);
+        if self.prepare_auth(&credentials).await? {
+            return Ok(());
+        }
+        match self
+            .session
+            .authenticate_openssh_cert(login, Arc::new(key), cert)
             .await
-            .map_err(|_| SshErrorKind::UserAuthPassword.with(self.id))
-            .map(|_| ())
+        {
+            Ok(true) => {
+                self.credentials = Some(credentials);
+                Ok(())
+            }
+            _ => Err(SshErrorKind::UserAuthPublicKey.with(self.id)),
+        }
     }
 
     pub async fn auth_keyboard_interactive(
@@ -175,13 +463,17 @@ impl SshSession {
         login: &str,
         password: &str,
     ) -> Result<(), SshError> {
+        let credentials = Credentials::new(login, password);
+        if self.prepare_auth(&credentials).await? {
+            return Ok(());
+        }
         let make_err = || SshErrorKind::UserAuthKeyboardInteractive.with(self.id);
         let response = self
             .session
             .authenticate_keyboard_interactive_start(login, None)
             .await
             .map_err(|_| make_err())?;
-        match response {
+        let result = match response {
             client::KeyboardInteractiveAuthResponse::Success => Ok(()),
             client::KeyboardInteractiveAuthResponse::Failure => Err(make_err()),
             client::KeyboardInteractiveAuthResponse::InfoRequest { prompts, .. } => {
@@ -193,13 +485,20 @@ impl SshSession {
                         answers.push(String::new());
                     };
                 }
-                self.session
+                match self
+                    .session
                     .authenticate_keyboard_interactive_respond(answers)
                     .await
-                    .map_err(|_| make_err())
-                    .map(|_| ())
+                {
+                    Ok(client::KeyboardInteractiveAuthResponse::Success) => Ok(()),
+                    _ => Err(make_err()),
+                }
             }
+        };
+        if result.is_ok() {
+            self.credentials = Some(credentials);
         }
+        result
     }
 
     pub async fn auth_method_allowed(&mut self, _method: AuthMethods) -> Result<bool, SshError> {
@@ -209,6 +508,101 @@ impl SshSession {
     }
 }
 
+/// Returns the name of the signal as sent by the server, without the "SIG"
+/// prefix, and its number on Linux. Signals unknown to russh have no number.
+fn signal_name_and_number(signal: Sig) -> (String, Option<u32>) {
+    let (name, number) = match signal {
+        Sig::HUP => ("HUP", 1),
+        Sig::INT => ("INT", 2),
+        Sig::QUIT => ("QUIT", 3),
+        Sig::ILL => ("ILL", 4),
+        Sig::ABRT => ("ABRT", 6),
+        Sig::FPE => ("FPE", 8),
+        Sig::KILL => ("KILL", 9),
+        Sig::USR1 => ("USR1", 10),
+        Sig::SEGV => ("SEGV", 11),
+        Sig::PIPE => ("PIPE", 13),
+        Sig::ALRM => ("ALRM", 14),
+        Sig::TERM => ("TERM", 15),
+        Sig::Custom(name) => return (name, None),
+    };
+    (name.to_string(), Some(number))
+}
+
+/// Connects to the server given by `params`.
+async fn open(id: SessionId, params: &ConnectParams) -> Result<client::Handle<Client>, SshError> {
+    let preferred = construct_preferred(
+        params.keytype.clone(),
+        params.csciphers.clone(),
+        params.scciphers.clone(),
+    );
+    let config = client::Config {
+        inactivity_timeout: params.timeout,
+        keepalive_interval: params.keepalive,
+        keepalive_max: KEEPALIVE_MAX,
+        preferred,
+        ..Default::default()
+    };
+
+    let config = Arc::new(config);
+    let sh = Client {};
+
+    let target = SocketAddr::new(params.ip_addr, params.port);
+    match params.source {
+        Some(source) => {
+            let stream = connect_from(id, source, target).await?;
+            connect_stream(config, stream, sh).await
+        }
+        None => connect(config, target, sh).await,
+    }
+    .map_err(|e| connect_error(id, e))
+}
+
+/// Opens a TCP connection to `target` originating from `source`.
+async fn connect_from(
+    id: SessionId,
+    source: SocketAddr,
+    target: SocketAddr,
+) -> Result<TcpStream, SshError> {
+    let socket = match target {
+        SocketAddr::V4(_) => TcpSocket::new_v4(),
+        SocketAddr::V6(_) => TcpSocket::new_v6(),
+    }
+    .and_then(|socket| socket.bind(source).map(|_| socket))
+    .map_err(|e| {
+        SshErrorKind::Bind(source.to_string())
+            .with(id)
+            .with(russh::Error::from(e))
+    })?;
+    socket
+        .connect(target)
+        .await
+        .map_err(|e| connect_error(id, russh::Error::from(e)))
+}
+
+/// Returns [`SshErrorKind::Unreachable`] for failures that may go away,
+/// e.g. while the target reboots, and [`SshErrorKind::Connect`] otherwise.
+fn connect_error(id: SessionId, e: russh::Error) -> SshError {
+    let transient = match &e {
+        russh::Error::IO(e) => matches!(
+            e.kind(),
+            std::io::ErrorKind::ConnectionRefused
+                | std::io::ErrorKind::ConnectionReset
+                | std::io::ErrorKind::ConnectionAborted
+                | std::io::ErrorKind::BrokenPipe
+                | std::io::ErrorKind::TimedOut
+        ),
+        russh::Error::ConnectionTimeout => true,
+        _ => false,
+    };
+    let kind = if transient {
+        SshErrorKind::Unreachable
+    } else {
+        SshErrorKind::Connect
+    };
+    kind.with(id).with(e)
+}
+
 fn construct_preferred(
     keytype: Vec<key::Name>,
     csciphers: Vec<cipher::Name>,
@@ -20,16 +20,35 @@ pub struct SshSessions {
     // 1. a libssh::Channel, which is not `Send`.
     // 2. a russh::Channel, which is not `Send`.
     sessions: HashMap<SessionId, Mutex<SshSession>>,
+    // The ids of disconnected sessions, which are not handed out again
+    // so that a stale id does not refer to another session.
+    closed: HashSet<SessionId>,
+    #[cfg(not(feature = "nasl-builtin-libssh"))]
+    pub(super) pool: Option<Mutex<super::russh::SessionPool>>,
 }
 
 impl SshSessions {
+    /// Creates the sessions without a pool, see `with_pool`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
     pub async fn get_by_id(&self, id: SessionId) -> Result<BorrowedSession> {
-        Ok(self
-            .sessions
-            .get(&id)
-            .ok_or(SshErrorKind::InvalidSessionId.with(id))?
-            .lock()
-            .await)
+        match self.sessions.get(&id) {
+            Some(session) => Ok(session.lock().await),
+            None if self.closed.contains(&id) => Err(SshErrorKind::SessionClosed.with(id)),
+            None => Err(SshErrorKind::InvalidSessionId.with(id)),
+        }
+    }
+
+    /// Like [`Self::take`], but returns None for an already disconnected
+    /// session, so that disconnecting twice is not an error.
+    pub fn take_open(&mut self, id: SessionId) -> Result<Option<SshSession>> {
+        match self.take(id) {
+            Ok(session) => Ok(Some(session)),
+            Err(e) if matches!(e.kind, SshErrorKind::SessionClosed) => Ok(None),
+            Err(e) => Err(e),
+        }
     }
 
     /// Return the next available session ID
@@ -37,7 +56,7 @@ impl SshSessions {
         // Note that the first session ID we will
         // hand out is an arbitrary high number, this is only to help
         // debugging.
-        let taken_ids: HashSet<_> = self.sessions.keys().collect();
+        let taken_ids: HashSet<_> = self.sessions.keys().chain(self.closed.iter()).collect();
         if taken_ids.is_empty() {
             Ok(MIN_SESSION_ID)
         } else {
@@ -52,9 +71,16 @@ impl SshSessions {
         self.sessions.insert(session_id, session);
     }
 
-    pub fn remove(&mut self, session_id: SessionId) -> Result<()> {
-        self.sessions.remove(&session_id);
-        Ok(())
+    /// Removes the session and returns it. Its id is not handed out again.
+    pub fn take(&mut self, id: SessionId) -> Result<SshSession> {
+        match self.sessions.remove(&id) {
+            Some(session) => {
+                self.closed.insert(id);
+                Ok(session.into_inner())
+            }
+            None if self.closed.contains(&id) => Err(SshErrorKind::SessionClosed.with(id)),
+            None => Err(SshErrorKind::InvalidSessionId.with(id)),
+        }
     }
 
     #[cfg(feature = "nasl-builtin-libssh")]
@@ -62,3 +88,31 @@ impl SshSessions {
         self.sessions.keys()
     }
 }
+
+impl Drop for SshSessions {
+    /// Disconnects the sessions a script left open once the interpreter
+    /// is torn down. Without a runtime the sessions are only dropped.
+    fn drop(&mut self) {
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        let sessions = self
+            .sessions
+            .drain()
+            .map(|(_, session)| session.into_inner());
+        #[cfg(not(feature = "nasl-builtin-libssh"))]
+        let sessions = sessions.chain(
+            self.pool
+                .as_mut()
+                .map(|pool| pool.get_mut().drain().collect::<Vec<_>>())
+                .unwrap_or_default(),
+        );
+        for mut session in sessions {
+            runtime.spawn(async move {
+                if let Err(e) = session.disconnect().await {
+                    tracing::debug!("Failed to disconnect on teardown: {e}");
+                }
+            });
+        }
+    }
+}
@@ -0,0 +1,581 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+//! An in-process SSH server to test the SSH builtins against.
+//!
+//! ```ignore
+//! let server = MockSshServerConfig::default()
+//!     .with_password("user", "pass")
+//!     .with_command("echo hello", CommandResponse::stdout("hello\n"))
+//!     .spawn()
+//!     .await?;
+//! // connect to server.addr()
+//! ```
+
+use std::collections::HashMap;
+use std::io;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use russh::server::{Auth, Config, Msg, Server as _, Session};
+use russh::{Channel, ChannelId, CryptoVec, MethodSet, Sig};
+use russh_keys::key::{KeyPair, PublicKey};
+use tokio::io::{AsyncRead, AsyncWrite, BufReader, ReadBuf};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+use super::gex;
+
+/// Reads closer together than this belong to the same packet.
+const PACKET_GAP: Duration = Duration::from_millis(10);
+
+/// The response of the server to an executed command.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CommandResponse {
+    /// Written to stdout one after another, each in a packet of its own.
+    pub chunks: Vec<Vec<u8>>,
+    pub stdout: Vec<u8>,
+    pub stderr: Vec<u8>,
+    pub exit_status: u32,
+    /// The name of the signal the command is killed by, e.g. "TERM". If
+    /// given, it is sent instead of the exit status.
+    pub signal: Option<String>,
+}
+
+impl CommandResponse {
+    /// A successful response writing the given data to stdout.
+    pub fn stdout(data: impl Into<Vec<u8>>) -> Self {
+        Self {
+            stdout: data.into(),
+            ..Default::default()
+        }
+    }
+
+    /// A successful response writing the given chunks to stdout as separate packets.
+    pub fn chunked(chunks: &[&str]) -> Self {
+        Self {
+            chunks: chunks.iter().map(|x| x.as_bytes().to_vec()).collect(),
+            ..Default::default()
+        }
+    }
+
+    /// A response writing the given data to stderr with the given exit status.
+    pub fn stderr(data: impl Into<Vec<u8>>, exit_status: u32) -> Self {
+        Self {
+            stderr: data.into(),
+            exit_status,
+            ..Default::default()
+        }
+    }
+
+    /// A response of a command killed by the given signal, e.g. "TERM".
+    pub fn killed(signal: &str) -> Self {
+        Self {
+            signal: Some(signal.to_owned()),
+            ..Default::default()
+        }
+    }
+}
+
+/// Returns a new ed25519 private key in the OpenSSH format along with a
+/// user certificate of it for the given principals, signed by a new CA.
+pub fn signed_user_certificate(principals: &[&str]) -> (String, String) {
+    use ssh_key::certificate::{Builder, CertType};
+    use ssh_key::{Algorithm, LineEnding, PrivateKey};
+
+    let mut rng = rand::rngs::OsRng;
+    let ca = PrivateKey::random(&mut rng, Algorithm::Ed25519).unwrap();
+    let key = PrivateKey::random(&mut rng, Algorithm::Ed25519).unwrap();
+    let mut builder =
+        Builder::new_with_random_nonce(&mut rng, key.public_key(), 0, i64::MAX as u64).unwrap();
+    builder.cert_type(CertType::User).unwrap();
+    builder.key_id("test").unwrap();
+    if principals.is_empty() {
+        builder.all_principals_valid().unwrap();
+    }
+    for principal in principals {
+        builder.valid_principal(*principal).unwrap();
+    }
+    let certificate = builder.sign(&ca).unwrap();
+    (
+        key.to_openssh(LineEnding::LF).unwrap().to_string(),
+        certificate.to_openssh().unwrap(),
+    )
+}
+
+/// Returns an OpenSSH user certificate for the given principals and validity
+/// in the format of a `-cert.pub` file. The key and the signature are dummies,
+/// so the certificate is only fit to test checks on the client side.
+pub fn user_certificate(principals: &[&str], valid_after: u64, valid_before: u64) -> String {
+    fn string(out: &mut Vec<u8>, data: &[u8]) {
+        out.extend((data.len() as u32).to_be_bytes());
+        out.extend(data);
+    }
+    let key_type = "ssh-ed25519-cert-v01@openssh.com";
+    let mut list = vec![];
+    for principal in principals {
+        string(&mut list, principal.as_bytes());
+    }
+    let mut blob = vec![];
+    string(&mut blob, key_type.as_bytes());
+    // nonce and public key
+    string(&mut blob, &[0; 32]);
+    string(&mut blob, &[0; 32]);
+    // serial and type
+    blob.extend(0u64.to_be_bytes());
+    blob.extend(1u32.to_be_bytes());
+    string(&mut blob, b"test");
+    string(&mut blob, &list);
+    blob.extend(valid_after.to_be_bytes());
+    blob.extend(valid_before.to_be_bytes());
+    // critical options, extensions, reserved, signature key and signature
+    for _ in 0..5 {
+        string(&mut blob, &[]);
+    }
+    format!("{key_type} {} test", STANDARD.encode(blob))
+}
+
+/// Configuration of a [MockSshServer].
+///
+/// By default only password authentication is offered, no users are known and a new ed25519 host
+/// key is generated.
+pub struct MockSshServerConfig {
+    methods: MethodSet,
+    passwords: HashMap<String, String>,
+    public_keys: Vec<PublicKey>,
+    host_key: KeyPair,
+    commands: HashMap<String, CommandResponse>,
+    refused: usize,
+}
+
+impl Default for MockSshServerConfig {
+    fn default() -> Self {
+        Self {
+            methods: MethodSet::PASSWORD,
+            passwords: HashMap::new(),
+            public_keys: vec![],
+            host_key: KeyPair::generate_ed25519(),
+            commands: HashMap::new(),
+            refused: 0,
+        }
+    }
+}
+
+impl MockSshServerConfig {
+    /// Sets the authentication methods offered by the server.
+    pub fn with_methods(mut self, methods: MethodSet) -> Self {
+        self.methods = methods;
+        self
+    }
+
+    /// Accepts the given user with the given password.
+    pub fn with_password(mut self, user: &str, password: &str) -> Self {
+        self.passwords.insert(user.to_owned(), password.to_owned());
+        self
+    }
+
+    /// Accepts the given public key for any user.
+    pub fn with_public_key(mut self, key: PublicKey) -> Self {
+        self.public_keys.push(key);
+        self
+    }
+
+    /// Sets the host key of the server.
+    pub fn with_host_key(mut self, key: KeyPair) -> Self {
+        self.host_key = key;
+        self
+    }
+
+    /// Responds with `response` when `command` is executed.
+    ///
+    /// Unknown commands are answered with an error on stderr and exit status 127.
+    pub fn with_command(mut self, command: &str, response: CommandResponse) -> Self {
+        self.commands.insert(command.to_owned(), response);
+        self
+    }
+
+    /// Resets the first `count` connections right after accepting them, like a host that is
+    /// still booting.
+    pub fn with_refused(mut self, count: usize) -> Self {
+        self.refused = count;
+        self
+    }
+
+    /// Starts the server on an unused port of the loopback interface.
+    ///
+    /// The server runs on the current tokio runtime until the returned handle is dropped.
+    pub async fn spawn(self) -> std::io::Result<MockSshServer> {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await?;
+        let addr = listener.local_addr()?;
+        let config = Arc::new(Config {
+            methods: self.methods,
+            keys: vec![self.host_key],
+            inactivity_timeout: Some(Duration::from_secs(3600)),
+            auth_rejection_time: Duration::from_millis(10),
+            auth_rejection_time_initial: Some(Duration::from_secs(0)),
+            ..Default::default()
+        });
+        let settings = Arc::new(Settings {
+            methods: self.methods,
+            passwords: self.passwords,
+            public_keys: self.public_keys,
+            commands: self.commands,
+            peers: Mutex::default(),
+            received: Mutex::default(),
+            authenticated: Mutex::default(),
+            disconnected: Mutex::default(),
+            disconnect_messages: Mutex::default(),
+        });
+        let mut handler = Handler {
+            settings: settings.clone(),
+        };
+        let mut refused = self.refused;
+        let task = tokio::spawn(async move {
+            loop {
+                let (stream, peer) = match listener.accept().await {
+                    Ok(x) => x,
+                    Err(e) => {
+                        tracing::warn!(%e, "mock ssh server stopped");
+                        return;
+                    }
+                };
+                if refused > 0 {
+                    refused -= 1;
+                    // Closing without lingering resets the connection.
+                    let _ = stream.set_linger(Some(Duration::ZERO));
+                    drop(stream);
+                    continue;
+                }
+                let stream = RecordingStream {
+                    inner: stream,
+                    settings: handler.settings.clone(),
+                    shut_down: false,
+                };
+                let client = handler.new_client(Some(peer));
+                let config = config.clone();
+                let settings = handler.settings.clone();
+                tokio::spawn(async move {
+                    let result = match russh::server::run_stream(config, stream, client).await {
+                        Ok(session) => session.await,
+                        Err(e) => Err(e),
+                    };
+                    if let Err(e) = result {
+                        tracing::debug!(%e, "mock ssh session ended");
+                    }
+                    *settings.disconnected.lock().unwrap() += 1;
+                });
+            }
+        });
+        Ok(MockSshServer {
+            addr,
+            task,
+            settings,
+        })
+    }
+}
+
+/// A running mock SSH server. The server is stopped when this is dropped.
+pub struct MockSshServer {
+    addr: SocketAddr,
+    task: JoinHandle<()>,
+    settings: Arc<Settings>,
+}
+
+impl MockSshServer {
+    /// The address the server is bound to.
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// The port the server is bound to.
+    pub fn port(&self) -> u16 {
+        self.addr.port()
+    }
+
+    /// The addresses of the clients that connected so far, in the order of their connections.
+    pub fn peers(&self) -> Vec<SocketAddr> {
+        self.settings.peers.lock().unwrap().clone()
+    }
+
+    /// The number of client connections that ended so far.
+    pub fn disconnected(&self) -> usize {
+        *self.settings.disconnected.lock().unwrap()
+    }
+
+    /// The number of client connections that ended with a disconnect
+    /// message. russh does not pass the reason on, but the server only
+    /// shuts its side of the connection down after the message, while a
+    /// connection which is just closed ends the session with an error.
+    pub fn disconnect_messages(&self) -> usize {
+        *self.settings.disconnect_messages.lock().unwrap()
+    }
+
+    /// The times at which clients authenticated successfully.
+    pub fn authenticated(&self) -> Vec<Instant> {
+        self.settings.authenticated.lock().unwrap().clone()
+    }
+
+    /// The number of packets received from the clients from `since` until
+    /// before `until`.
+    pub fn received_between(&self, since: Instant, until: Instant) -> usize {
+        self.settings
+            .received
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|x| (since..until).contains(*x))
+            .count()
+    }
+
+    /// Waits until the clients sent `count` packets after `since`, but at
+    /// most for `timeout`. Returns the number of packets received by then.
+    pub async fn wait_for_received(
+        &self,
+        since: Instant,
+        count: usize,
+        timeout: Duration,
+    ) -> usize {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let received = self.received_between(since, Instant::now());
+            if received >= count || Instant::now() >= deadline {
+                return received;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    }
+}
+
+impl Drop for MockSshServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// A server which answers a Diffie-Hellman group exchange request, which
+/// russh does not implement, and disconnects afterwards.
+pub struct MockGexServer {
+    addr: SocketAddr,
+    task: JoinHandle<()>,
+}
+
+impl MockGexServer {
+    /// Spawns a server offering a group with a prime of `bits` bits, which
+    /// must be a multiple of 8. Requests which do not allow this size are
+    /// refused. If `bits` is None, the group exchange is not offered.
+    pub async fn spawn(bits: Option<usize>) -> io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        let task = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(Self::serve(stream, bits));
+            }
+        });
+        Ok(Self { addr, task })
+    }
+
+    /// The port the server is bound to.
+    pub fn port(&self) -> u16 {
+        self.addr.port()
+    }
+
+    async fn serve(stream: TcpStream, bits: Option<usize>) -> io::Result<()> {
+        let mut stream = BufReader::new(stream);
+        gex::exchange_ids(&mut stream, "SSH-2.0-MockGex").await?;
+        gex::read_packet(&mut stream).await?;
+        let Some(bits) = bits else {
+            return gex::write_packet(stream.get_mut(), &gex::kexinit("curve25519-sha256")).await;
+        };
+        gex::write_packet(stream.get_mut(), &gex::kexinit(gex::GEX_ALGORITHMS)).await?;
+        let request = gex::read_packet(&mut stream).await?;
+        let field =
+            |i: usize| u32::from_be_bytes(request[1 + 4 * i..5 + 4 * i].try_into().unwrap());
+        let (min, max) = (field(0) as usize, field(2) as usize);
+        let reply = if (min..=max).contains(&bits) {
+            let mut reply = vec![gex::MSG_KEX_DH_GEX_GROUP];
+            // The leading zero keeps the mpint positive.
+            let mut prime = vec![0];
+            prime.resize(1 + bits / 8, 0xff);
+            gex::put_string(&mut reply, &prime);
+            gex::put_string(&mut reply, &[2]);
+            reply
+        } else {
+            let mut reply = vec![gex::MSG_DISCONNECT];
+            reply.extend(3u32.to_be_bytes());
+            gex::put_string(&mut reply, b"DH_GEX group out of range");
+            gex::put_string(&mut reply, b"");
+            reply
+        };
+        gex::write_packet(stream.get_mut(), &reply).await
+    }
+}
+
+impl Drop for MockGexServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+struct Settings {
+    methods: MethodSet,
+    passwords: HashMap<String, String>,
+    public_keys: Vec<PublicKey>,
+    commands: HashMap<String, CommandResponse>,
+    peers: Mutex<Vec<SocketAddr>>,
+    received: Mutex<Vec<Instant>>,
+    authenticated: Mutex<Vec<Instant>>,
+    disconnected: Mutex<usize>,
+    disconnect_messages: Mutex<usize>,
+}
+
+/// A connection of a client which records when data arrives, to observe
+/// packets that are not visible to the handler, like keepalives.
+struct RecordingStream {
+    inner: TcpStream,
+    settings: Arc<Settings>,
+    shut_down: bool,
+}
+
+impl AsyncRead for RecordingStream {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        let filled = buf.filled().len();
+        let result = Pin::new(&mut self.inner).poll_read(cx, buf);
+        if matches!(result, Poll::Ready(Ok(()))) && buf.filled().len() > filled {
+            let now = Instant::now();
+            let mut received = self.settings.received.lock().unwrap();
+            // A packet may take several reads.
+            if received
+                .last()
+                .is_none_or(|x| now.duration_since(*x) > PACKET_GAP)
+            {
+                received.push(now);
+            }
+        }
+        result
+    }
+}
+
+impl AsyncWrite for RecordingStream {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        if !std::mem::replace(&mut self.shut_down, true) {
+            *self.settings.disconnect_messages.lock().unwrap() += 1;
+        }
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+}
+
+#[derive(Clone)]
+struct Handler {
+    settings: Arc<Settings>,
+}
+
+impl Handler {
+    fn accept_if(&self, method: MethodSet, accept: bool) -> Auth {
+        if self.settings.methods.contains(method) && accept {
+            self.settings
+                .authenticated
+                .lock()
+                .unwrap()
+                .push(Instant::now());
+            Auth::Accept
+        } else {
+            Auth::Reject {
+                proceed_with_methods: None,
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl russh::server::Server for Handler {
+    type Handler = Self;
+
+    fn new_client(&mut self, peer: Option<SocketAddr>) -> Self {
+        if let Some(peer) = peer {
+            self.settings.peers.lock().unwrap().push(peer);
+        }
+        self.clone()
+    }
+}
+
+#[async_trait]
+impl russh::server::Handler for Handler {
+    type Error = russh::Error;
+
+    async fn channel_open_session(
+        &mut self,
+        _: Channel<Msg>,
+        _: &mut Session,
+    ) -> Result<bool, Self::Error> {
+        Ok(true)
+    }
+
+    async fn auth_none(&mut self, _: &str) -> Result<Auth, Self::Error> {
+        Ok(self.accept_if(MethodSet::NONE, true))
+    }
+
+    async fn auth_password(&mut self, user: &str, password: &str) -> Result<Auth, Self::Error> {
+        let known = self.settings.passwords.get(user).map(|x| x.as_str()) == Some(password);
+        Ok(self.accept_if(MethodSet::PASSWORD, known))
+    }
+
+    async fn auth_publickey(&mut self, _: &str, key: &PublicKey) -> Result<Auth, Self::Error> {
+        let known = self.settings.public_keys.contains(key);
+        Ok(self.accept_if(MethodSet::PUBLICKEY, known))
+    }
+
+    async fn exec_request(
+        &mut self,
+        channel: ChannelId,
+        cmd: &[u8],
+        session: &mut Session,
+    ) -> Result<(), Self::Error> {
+        let cmd = String::from_utf8_lossy(cmd);
+        let response = self
+            .settings
+            .commands
+            .get(cmd.as_ref())
+            .cloned()
+            .unwrap_or_else(|| CommandResponse::stderr(format!("{cmd}: command not found\n"), 127));
+        for chunk in response.chunks {
+            session.data(channel, CryptoVec::from(chunk));
+        }
+        if !response.stdout.is_empty() {
+            session.data(channel, CryptoVec::from(response.stdout));
+        }
+        if !response.stderr.is_empty() {
+            session.extended_data(channel, 1, CryptoVec::from(response.stderr));
+        }
+        match response.signal {
+            Some(signal) => {
+                session.exit_signal_request(channel, Sig::Custom(signal), false, "", "")
+            }
+            None => session.exit_status_request(channel, response.exit_status),
+        }
+        session.eof(channel);
+        session.close(channel);
+        Ok(())
+    }
+}
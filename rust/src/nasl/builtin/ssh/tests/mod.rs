@@ -6,19 +6,30 @@ mod server;
 
 use std::sync::Arc;
 use std::sync::Mutex;
-use std::time::Duration;
+use std::time::{Duration, Instant, UNIX_EPOCH};
 
 use russh::server::Config as ServerConfig;
 use russh::server::Server as _;
+use russh::MethodSet;
 use russh_keys::key::KeyPair;
 use server::AuthConfig;
 use server::TestServer;
 
+use super::test_utils::{
+    signed_user_certificate, user_certificate, CommandResponse, MockGexServer, MockSshServer,
+    MockSshServerConfig,
+};
+
 use crate::check_err_matches;
 use crate::nasl::builtin::ssh::error::SshErrorKind;
+use crate::nasl::builtin::ssh::gex;
 use crate::nasl::builtin::ssh::sessions::MIN_SESSION_ID;
 use crate::nasl::builtin::ssh::SshError;
 use crate::nasl::test_prelude::*;
+use crate::nasl::utils::recording::{NetworkMode, Recorder, RecordingError, Replayer};
+#[cfg(not(feature = "nasl-builtin-libssh"))]
+use crate::nasl::utils::Executor;
+use crate::nasl::utils::MockClock;
 use crate::nasl::NoOpLoader;
 use crate::storage::DefaultDispatcher;
 
@@ -60,12 +71,16 @@ async fn run_test(
     res.unwrap()
 }
 
+fn run_client(f: impl Fn(&mut TestBuilder<NoOpLoader, DefaultDispatcher>) + Send + Sync + 'static) {
+    run_client_with(TestBuilder::default(), f)
+}
+
 #[tokio::main]
-async fn run_client(
+async fn run_client_with(
+    mut t: TestBuilder<NoOpLoader, DefaultDispatcher>,
     f: impl Fn(&mut TestBuilder<NoOpLoader, DefaultDispatcher>) + Send + Sync + 'static,
 ) {
     std::thread::sleep(Duration::from_millis(100));
-    let mut t = TestBuilder::default();
     f(&mut t);
     t.async_verify().await;
 }
@@ -176,3 +191,911 @@ async fn ssh_request_exec() {
     )
     .await
 }
+
+#[tokio::test]
+#[cfg_attr(feature = "nasl-builtin-libssh", ignore)]
+async fn mock_server_password_auth_and_exec() {
+    let server = MockSshServerConfig::default()
+        .with_password("user", "pass")
+        .with_command("echo hello", CommandResponse::stdout("hello\n"))
+        .spawn()
+        .await
+        .unwrap();
+    let port = server.port();
+    let client = tokio::task::spawn_blocking(move || {
+        run_client(move |t| {
+            t.ok(
+                format!(r#"session_id = ssh_connect(port: {port}, keytype: "ssh-ed25519");"#),
+                MIN_SESSION_ID,
+            );
+            t.ok(
+                r#"ssh_userauth(session_id, login: "user", password: "pass");"#,
+                NaslValue::Null,
+            );
+            t.ok(
+                r#"ssh_request_exec(session_id, cmd: "echo hello");"#,
+                "hello\n",
+            );
+        })
+    });
+    client.await.unwrap();
+}
+
+#[tokio::test]
+#[cfg_attr(feature = "nasl-builtin-libssh", ignore)]
+async fn ssh_exec() {
+    let server = MockSshServerConfig::default()
+        .with_password("user", "pass")
+        .with_command(
+            "ls /root",
+            CommandResponse {
+                stdout: b"partial\n".to_vec(),
+                stderr: b"ls: cannot open directory '/root'\n".to_vec(),
+                exit_status: 2,
+                ..Default::default()
+            },
+        )
+        .with_command("sleep 100", CommandResponse::killed("TERM"))
+        .spawn()
+        .await
+        .unwrap();
+    let port = server.port();
+    let output = |stdout: &str, stderr: &str, exit_code: i64| {
+        NaslValue::Dict(
+            [
+                ("stdout".to_string(), NaslValue::String(stdout.to_string())),
+                ("stderr".to_string(), NaslValue::String(stderr.to_string())),
+                ("exit_code".to_string(), NaslValue::Number(exit_code)),
+            ]
+            .into(),
+        )
+    };
+    let client = tokio::task::spawn_blocking(move || {
+        run_client(move |t| {
+            t.ok(
+                format!(r#"session_id = ssh_connect(port: {port}, keytype: "ssh-ed25519");"#),
+                MIN_SESSION_ID,
+            );
+            t.ok(
+                r#"ssh_userauth(session_id, login: "user", password: "pass");"#,
+                NaslValue::Null,
+            );
+            t.ok(
+                r#"ssh_exec(session_id, cmd: "ls /root");"#,
+                output("partial\n", "ls: cannot open directory '/root'\n", 2),
+            );
+            t.ok(
+                r#"ssh_exec(session_id, cmd: "foo");"#,
+                output("", "foo: command not found\n", 127),
+            );
+            let NaslValue::Dict(mut killed) = output("", "", 128 + 15) else {
+                unreachable!()
+            };
+            killed.insert("signal".to_string(), "TERM".into());
+            t.ok(
+                r#"ssh_exec(session_id, cmd: "sleep 100");"#,
+                NaslValue::Dict(killed),
+            );
+            t.ok(r#"ssh_disconnect(session_id);"#, NaslValue::Null);
+            check_err_matches!(
+                t,
+                r#"ssh_exec(session_id, cmd: "ls /root");"#,
+                SshError {
+                    kind: SshErrorKind::SessionClosed,
+                    ..
+                }
+            );
+        })
+    });
+    client.await.unwrap();
+}
+
+#[tokio::test]
+#[cfg_attr(feature = "nasl-builtin-libssh", ignore)]
+async fn stale_session_ids() {
+    let server = MockSshServerConfig::default()
+        .with_password("user", "pass")
+        .with_command("echo hello", CommandResponse::stdout("hello\n"))
+        .spawn()
+        .await
+        .unwrap();
+    let port = server.port();
+    let client = tokio::task::spawn_blocking(move || {
+        run_client(move |t| {
+            t.ok(
+                format!(r#"session_id = ssh_connect(port: {port}, keytype: "ssh-ed25519");"#),
+                MIN_SESSION_ID,
+            );
+            t.ok(
+                r#"ssh_userauth(session_id, login: "user", password: "pass");"#,
+                NaslValue::Null,
+            );
+            t.ok(
+                r#"ssh_request_exec(session_id, cmd: "echo hello");"#,
+                "hello\n",
+            );
+            t.ok(r#"ssh_disconnect(session_id);"#, NaslValue::Null);
+            check_err_matches!(
+                t,
+                r#"ssh_request_exec(session_id, cmd: "echo hello");"#,
+                SshError {
+                    kind: SshErrorKind::SessionClosed,
+                    ..
+                }
+            );
+            // Disconnecting twice is not an error.
+            t.ok(r#"ssh_disconnect(session_id);"#, NaslValue::Null);
+            check_err_matches!(
+                t,
+                r#"ssh_request_exec(1234, cmd: "echo hello");"#,
+                SshError {
+                    kind: SshErrorKind::InvalidSessionId,
+                    ..
+                }
+            );
+            // The id of the disconnected session is not handed out again.
+            t.ok(
+                format!(r#"ssh_connect(port: {port}, keytype: "ssh-ed25519");"#),
+                MIN_SESSION_ID + 1,
+            );
+        })
+    });
+    client.await.unwrap();
+}
+
+#[tokio::test]
+#[cfg_attr(feature = "nasl-builtin-libssh", ignore)]
+async fn disconnect_twice() {
+    let server = MockSshServerConfig::default()
+        .with_password("user", "pass")
+        .spawn()
+        .await
+        .unwrap();
+    let port = server.port();
+    tokio::task::spawn_blocking(move || {
+        run_client(move |t| {
+            t.ok(
+                format!(r#"session_id = ssh_connect(port: {port}, keytype: "ssh-ed25519");"#),
+                MIN_SESSION_ID,
+            );
+            t.ok(r#"ssh_disconnect(session_id);"#, NaslValue::Null);
+            t.ok(r#"ssh_disconnect(session_id);"#, NaslValue::Null);
+            check_err_matches!(
+                t,
+                r#"ssh_disconnect(1234);"#,
+                SshError {
+                    kind: SshErrorKind::InvalidSessionId,
+                    ..
+                }
+            );
+        })
+    })
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+#[cfg(not(feature = "nasl-builtin-libssh"))]
+async fn keepalive() {
+    let server = MockSshServerConfig::default()
+        .with_password("user", "pass")
+        .spawn()
+        .await
+        .unwrap();
+    let params = super::russh::ConnectParams {
+        keepalive: Some(Duration::from_millis(200)),
+        ..new_session_params(&server)
+    };
+    let mut session = super::SshSession::new(MIN_SESSION_ID, params, None)
+        .await
+        .unwrap();
+    session.auth_password("user", "pass").await.unwrap();
+    let idle = *server.authenticated().last().unwrap();
+    // An idle session sends a keepalive per interval, the timeout only
+    // leaves room for a slow machine.
+    let received = server
+        .wait_for_received(idle, 3, Duration::from_secs(30))
+        .await;
+    assert!(received >= 3, "{received} packets");
+    assert!(!session.is_closed());
+    session.disconnect().await.unwrap();
+    wait_for_disconnect(&server).await;
+
+    let mut session = new_session(&server).await;
+    session.auth_password("user", "pass").await.unwrap();
+    let idle = *server.authenticated().last().unwrap();
+    tokio::time::sleep(Duration::from_secs(1)).await;
+    assert_eq!(server.received_between(idle, Instant::now()), 0);
+}
+
+#[tokio::test]
+#[cfg_attr(feature = "nasl-builtin-libssh", ignore)]
+async fn ssh_userauth_certificate() {
+    let server = MockSshServerConfig::default()
+        .with_methods(MethodSet::PUBLICKEY)
+        .spawn()
+        .await
+        .unwrap();
+    let port = server.port();
+    let valid = user_certificate(&["user"], 0, u64::MAX);
+    let signed = signed_user_certificate(&["user"]);
+    let any_principal = signed_user_certificate(&[]);
+    // The certificates are checked against the clock of the context.
+    let now = 1_000_000;
+    let clock = Arc::new(MockClock::new(UNIX_EPOCH + Duration::from_secs(now)));
+    let expired = user_certificate(&["user"], 0, now);
+    let not_yet_valid = user_certificate(&["user"], now + 1, u64::MAX);
+    tokio::task::spawn_blocking(move || {
+        run_client_with(TestBuilder::default().with_clock(clock), move |t| {
+            t.ok(
+                format!(r#"session_id = ssh_connect(port: {port}, keytype: "ssh-ed25519");"#),
+                MIN_SESSION_ID,
+            );
+            let userauth = |login: &str, certificate: &str| {
+                format!(
+                    r#"ssh_userauth(session_id, login: "{login}", privatekey: "key", certificate: "{certificate}");"#
+                )
+            };
+            for certificate in [&expired, &not_yet_valid] {
+                check_err_matches!(
+                    t,
+                    userauth("user", certificate),
+                    SshError {
+                        kind: SshErrorKind::CertificateExpired,
+                        ..
+                    }
+                );
+            }
+            check_err_matches!(
+                t,
+                userauth("root", &valid),
+                SshError {
+                    kind: SshErrorKind::CertificatePrincipal(_),
+                    ..
+                }
+            );
+            check_err_matches!(
+                t,
+                userauth("user", "ssh-ed25519-cert-v01@openssh.com AAAA"),
+                SshError {
+                    kind: SshErrorKind::InvalidCertificate,
+                    ..
+                }
+            );
+            check_err_matches!(
+                t,
+                userauth("user", &signed.1),
+                SshError {
+                    kind: SshErrorKind::ConvertPrivateKey,
+                    ..
+                }
+            );
+            // Valid certificates pass the checks and are presented to the
+            // server, which does not support them.
+            for (login, (key, certificate)) in [("user", &signed), ("root", &any_principal)] {
+                check_err_matches!(
+                    t,
+                    format!(
+                        r#"ssh_userauth(session_id, login: "{login}", privatekey: "{key}", certificate: "{certificate}");"#
+                    ),
+                    SshError {
+                        kind: SshErrorKind::UserAuthPublicKey,
+                        ..
+                    }
+                );
+            }
+        })
+    })
+    .await
+    .unwrap();
+}
+
+#[test]
+#[cfg(not(feature = "nasl-builtin-libssh"))]
+fn certificate_credentials() {
+    use super::russh::Credentials;
+    let (key, certificate) = signed_user_certificate(&["user"]);
+    let (other_key, other_certificate) = signed_user_certificate(&["user"]);
+    let credentials = Credentials::with_certificate("user", &key, &certificate);
+    assert_eq!(
+        credentials,
+        Credentials::with_certificate("user", &key, &certificate)
+    );
+    // Pooled sessions are only handed out for the same key and certificate.
+    assert_ne!(
+        credentials,
+        Credentials::with_certificate("user", &key, &other_certificate)
+    );
+    assert_ne!(
+        credentials,
+        Credentials::with_certificate("user", &other_key, &certificate)
+    );
+    assert_ne!(
+        credentials,
+        Credentials::with_certificate("root", &key, &certificate)
+    );
+}
+
+#[cfg(feature = "nasl-builtin-libssh")]
+#[tokio::test]
+async fn ssh_auth_methods() {
+    let server = MockSshServerConfig::default()
+        .with_methods(MethodSet::PASSWORD | MethodSet::PUBLICKEY)
+        .spawn()
+        .await
+        .unwrap();
+    let port = server.port();
+    let closed_port = unused_port();
+    let client = tokio::task::spawn_blocking(move || {
+        run_client(move |t| {
+            t.ok(
+                format!(r#"ssh_auth_methods(host: "127.0.0.1", port: {port}, user: "user");"#),
+                vec!["password".to_string(), "publickey".to_string()],
+            );
+            check_err_matches!(
+                t,
+                format!(
+                    r#"ssh_auth_methods(host: "127.0.0.1", port: {closed_port}, user: "user");"#
+                ),
+                SshError {
+                    kind: SshErrorKind::Connect,
+                    ..
+                }
+            );
+        })
+    });
+    client.await.unwrap();
+}
+
+#[tokio::test]
+async fn ssh_dh_gex() {
+    let server = MockGexServer::spawn(Some(2048)).await.unwrap();
+    let port = server.port();
+    let no_gex = MockGexServer::spawn(None).await.unwrap();
+    let no_gex_port = no_gex.port();
+    let closed_port = unused_port();
+    let client = tokio::task::spawn_blocking(move || {
+        run_client(move |t| {
+            t.ok(
+                format!(r#"ssh_dh_gex(host: "127.0.0.1", port: {port});"#),
+                2048,
+            );
+            t.ok(
+                format!(
+                    r#"ssh_dh_gex(host: "127.0.0.1", port: {port}, min: 1024, pref: 1024, max: 1024);"#
+                ),
+                0,
+            );
+            t.ok(
+                format!(r#"ssh_dh_gex(host: "127.0.0.1", port: {no_gex_port});"#),
+                0,
+            );
+            check_err_matches!(
+                t,
+                format!(r#"ssh_dh_gex(host: "127.0.0.1", port: {port}, min: 4096, max: 2048);"#),
+                ArgumentError::WrongArgument(_)
+            );
+            check_err_matches!(
+                t,
+                format!(r#"ssh_dh_gex(host: "127.0.0.1", port: {closed_port});"#),
+                SshError {
+                    kind: SshErrorKind::DhGex(_),
+                    ..
+                }
+            );
+        })
+    });
+    client.await.unwrap();
+}
+
+#[tokio::test]
+async fn ssh_dh_gex_replay_without_name_resolution() {
+    let path = std::env::temp_dir().join(format!("ssh_dh_gex_{}.jsonl", std::process::id()));
+    let server = MockGexServer::spawn(Some(2048)).await.unwrap();
+    let port = server.port();
+    let gex = move |host: &str| format!(r#"ssh_dh_gex(host: "{host}", port: {port});"#);
+    let recorder = Recorder::create(&path).unwrap();
+    let t = TestBuilder::default().with_network_mode(NetworkMode::Record(recorder));
+    tokio::task::spawn_blocking(move || {
+        run_client_with(t, move |t| {
+            t.ok(gex("127.0.0.1"), 2048);
+        })
+    })
+    .await
+    .unwrap();
+    drop(server);
+    // The interaction is keyed on the given host, the replay does not resolve it.
+    let recorded = std::fs::read_to_string(&path).unwrap();
+    std::fs::write(&path, recorded.replace("127.0.0.1", "server.invalid")).unwrap();
+    let replayer = Replayer::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let t = TestBuilder::default().with_network_mode(NetworkMode::Replay(replayer));
+    tokio::task::spawn_blocking(move || {
+        run_client_with(t, move |t| {
+            t.ok(gex("server.invalid"), 2048);
+        })
+    })
+    .await
+    .unwrap();
+}
+
+/// Spawns a server for a single connection, which identifies itself with
+/// `id`, answers each packet of the client with the next of `replies` and
+/// closes the connection afterwards.
+async fn scripted_gex_server(id: String, replies: Vec<Vec<u8>>) -> std::net::SocketAddr {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut stream = tokio::io::BufReader::new(stream);
+        if gex::exchange_ids(&mut stream, &id).await.is_err() {
+            return;
+        }
+        for reply in replies {
+            if gex::read_packet(&mut stream).await.is_err() {
+                return;
+            }
+            gex::write_packet(stream.get_mut(), &reply).await.unwrap();
+        }
+    });
+    addr
+}
+
+#[tokio::test]
+async fn dh_gex_disconnects() {
+    let mut disconnect = vec![gex::MSG_DISCONNECT];
+    disconnect.extend(3u32.to_be_bytes());
+    gex::put_string(&mut disconnect, b"bye");
+    gex::put_string(&mut disconnect, b"");
+    let kexinit = gex::kexinit(gex::GEX_ALGORITHMS);
+    let id = "SSH-2.0-Scripted".to_string();
+    let group_size = |addr| gex::group_size(addr, 1024, 2048, 8192);
+
+    // Refusing the requested group is no error.
+    let addr = scripted_gex_server(id.clone(), vec![kexinit.clone(), disconnect.clone()]).await;
+    assert!(group_size(addr).await.unwrap().is_none());
+    // Disconnecting before or instead of the KEXINIT is.
+    let addr = scripted_gex_server(id.clone(), vec![disconnect]).await;
+    let err = group_size(addr).await.unwrap_err();
+    assert!(matches!(err.kind, SshErrorKind::DhGex(ref x) if x.contains("KEXINIT")));
+    let addr = scripted_gex_server(id.clone(), vec![]).await;
+    assert!(group_size(addr).await.is_err());
+    // So is closing the connection without answering the request.
+    let addr = scripted_gex_server(id, vec![kexinit]).await;
+    assert!(group_size(addr).await.is_err());
+}
+
+#[tokio::test]
+async fn dh_gex_identification_length() {
+    // An identification line of 255 bytes including CR LF is accepted.
+    let id = format!("SSH-2.0-{}", "x".repeat(245));
+    let addr = scripted_gex_server(id, vec![gex::kexinit("curve25519-sha256")]).await;
+    assert!(gex::group_size(addr, 1024, 2048, 8192)
+        .await
+        .unwrap()
+        .is_none());
+    let id = format!("SSH-2.0-{}", "x".repeat(246));
+    let addr = scripted_gex_server(id, vec![gex::kexinit("curve25519-sha256")]).await;
+    let err = gex::group_size(addr, 1024, 2048, 8192).await.unwrap_err();
+    assert!(matches!(err.kind, SshErrorKind::DhGex(ref x) if x.contains("255 bytes")));
+}
+
+/// Returns a port of the loopback interface nobody listens on.
+fn unused_port() -> u16 {
+    std::net::TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port()
+}
+
+#[tokio::test]
+#[cfg_attr(feature = "nasl-builtin-libssh", ignore)]
+async fn ssh_connect_bind() {
+    let server = MockSshServerConfig::default().spawn().await.unwrap();
+    let port = server.port();
+    let source_port = unused_port();
+    let taken = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let taken_port = taken.local_addr().unwrap().port();
+    let client = tokio::task::spawn_blocking(move || {
+        run_client(move |t| {
+            t.ok(
+                format!(
+                    r#"ssh_connect(port: {port}, keytype: "ssh-ed25519", bind_addr: "127.0.0.1", bind_port: {source_port});"#
+                ),
+                MIN_SESSION_ID,
+            );
+            check_err_matches!(
+                t,
+                format!(
+                    r#"ssh_connect(port: {port}, keytype: "ssh-ed25519", bind_addr: "127.0.0.1", bind_port: {taken_port});"#
+                ),
+                SshError {
+                    kind: SshErrorKind::Bind(_),
+                    ..
+                }
+            );
+            // An address of no local interface.
+            check_err_matches!(
+                t,
+                format!(
+                    r#"ssh_connect(port: {port}, keytype: "ssh-ed25519", bind_addr: "192.0.2.1");"#
+                ),
+                SshError {
+                    kind: SshErrorKind::Bind(_),
+                    ..
+                }
+            );
+            check_err_matches!(
+                t,
+                format!(r#"ssh_connect(port: {port}, bind_addr: "eth0");"#),
+                SshError {
+                    kind: SshErrorKind::InvalidIpAddr(..),
+                    ..
+                }
+            );
+        })
+    });
+    client.await.unwrap();
+    drop(taken);
+    assert_eq!(
+        server.peers(),
+        vec![std::net::SocketAddr::from(([127, 0, 0, 1], source_port))]
+    );
+}
+
+#[cfg(not(feature = "nasl-builtin-libssh"))]
+async fn pool_server() -> MockSshServer {
+    MockSshServerConfig::default()
+        .with_password("user", "pass")
+        .with_password("other", "pass")
+        .with_command("echo hello", CommandResponse::stdout("hello\n"))
+        .spawn()
+        .await
+        .unwrap()
+}
+
+/// Connects, authenticates and disconnects once for each login and returns
+/// the number of connections the server saw.
+#[cfg(not(feature = "nasl-builtin-libssh"))]
+async fn run_pooled(max_idle: Duration, logins: &'static [&'static str]) -> usize {
+    let server = pool_server().await;
+    let port = server.port();
+    let functions = ContextFactory::default().ssh_pool(max_idle).functions;
+    let t = TestBuilder::default().with_executor(functions);
+    tokio::task::spawn_blocking(move || {
+        run_client_with(t, move |t| {
+            for (id, login) in (MIN_SESSION_ID..).zip(logins) {
+                t.ok(
+                    format!(r#"session_id = ssh_connect(port: {port}, keytype: "ssh-ed25519");"#),
+                    id,
+                );
+                t.ok(
+                    format!(r#"ssh_userauth(session_id, login: "{login}", password: "pass");"#),
+                    NaslValue::Null,
+                );
+                t.ok(
+                    r#"ssh_request_exec(session_id, cmd: "echo hello");"#,
+                    "hello\n",
+                );
+                t.ok(r#"ssh_disconnect(session_id);"#, NaslValue::Null);
+            }
+        })
+    })
+    .await
+    .unwrap();
+    server.peers().len()
+}
+
+#[tokio::test]
+#[cfg(not(feature = "nasl-builtin-libssh"))]
+async fn pooled_sessions() {
+    // The second connect reuses the session of the first one.
+    assert_eq!(
+        run_pooled(Duration::from_secs(60), &["user", "user"]).await,
+        1
+    );
+    // The pooled session expired.
+    assert_eq!(run_pooled(Duration::ZERO, &["user", "user"]).await, 2);
+    // The pooled session is authenticated as another user and is replaced.
+    assert_eq!(
+        run_pooled(Duration::from_secs(60), &["user", "other"]).await,
+        2
+    );
+    // The pooled sessions are handed out to the user they are authenticated as.
+    assert_eq!(
+        run_pooled(
+            Duration::from_secs(60),
+            &["user", "other", "user", "other", "other"]
+        )
+        .await,
+        2
+    );
+}
+
+#[tokio::test]
+#[cfg(not(feature = "nasl-builtin-libssh"))]
+async fn pooled_sessions_require_userauth() {
+    let server = pool_server().await;
+    let port = server.port();
+    let executor = Executor::single(super::Ssh::with_pool(Duration::from_secs(60)));
+    let t = TestBuilder::default().with_executor(executor);
+    tokio::task::spawn_blocking(move || {
+        run_client_with(t, move |t| {
+            t.run(format!(
+                r#"session_id = ssh_connect(port: {port}, keytype: "ssh-ed25519");"#
+            ));
+            t.run(r#"ssh_userauth(session_id, login: "user", password: "pass");"#);
+            t.run(r#"ssh_disconnect(session_id);"#);
+            t.run(format!(
+                r#"session_id = ssh_connect(port: {port}, keytype: "ssh-ed25519");"#
+            ));
+            // The pooled session is not usable before the credentials are presented.
+            check_err_matches!(
+                t,
+                r#"ssh_request_exec(session_id, cmd: "echo hello");"#,
+                SshError {
+                    kind: SshErrorKind::RequestExec(_),
+                    ..
+                }
+            );
+            t.run(r#"ssh_userauth(session_id, login: "user", password: "pass");"#);
+            t.ok(
+                r#"ssh_request_exec(session_id, cmd: "echo hello");"#,
+                "hello\n",
+            );
+        })
+    })
+    .await
+    .unwrap();
+    assert_eq!(server.peers().len(), 1);
+}
+
+#[cfg(not(feature = "nasl-builtin-libssh"))]
+fn new_session_params(server: &MockSshServer) -> super::russh::ConnectParams {
+    super::russh::ConnectParams {
+        ip_addr: server.addr().ip(),
+        port: server.port(),
+        timeout: None,
+        keytype: vec![russh_keys::key::ED25519],
+        csciphers: russh::Preferred::DEFAULT.cipher.to_vec(),
+        scciphers: russh::Preferred::DEFAULT.cipher.to_vec(),
+        source: None,
+        keepalive: None,
+    }
+}
+
+#[cfg(not(feature = "nasl-builtin-libssh"))]
+async fn new_session(server: &MockSshServer) -> super::SshSession {
+    super::SshSession::new(MIN_SESSION_ID, new_session_params(server), None)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+#[cfg(not(feature = "nasl-builtin-libssh"))]
+async fn connect_with_retry() {
+    let server = MockSshServerConfig::default()
+        .with_refused(2)
+        .spawn()
+        .await
+        .unwrap();
+    let params = new_session_params(&server);
+    let mut sessions = super::Ssh::default();
+    let (id, attempts) = sessions
+        .connect_with_retry(params.clone(), None, 3, Duration::from_millis(10))
+        .await
+        .unwrap();
+    assert_eq!(id, MIN_SESSION_ID);
+    assert_eq!(attempts, 3);
+    assert_eq!(server.peers().len(), 1);
+
+    let server = MockSshServerConfig::default()
+        .with_refused(2)
+        .spawn()
+        .await
+        .unwrap();
+    let err = sessions
+        .connect_with_retry(
+            new_session_params(&server),
+            None,
+            2,
+            Duration::from_millis(10),
+        )
+        .await
+        .unwrap_err();
+    assert!(err.retryable());
+    assert!(server.peers().is_empty());
+
+    // Failures which are not transient are not retried.
+    let server = MockSshServerConfig::default().spawn().await.unwrap();
+    let mut params = new_session_params(&server);
+    params.keytype = vec![russh_keys::key::RSA_SHA2_256];
+    let err = sessions
+        .connect_with_retry(params, None, 3, Duration::from_millis(10))
+        .await
+        .unwrap_err();
+    assert!(!err.retryable());
+    assert_eq!(server.peers().len(), 1);
+
+    let server = MockSshServerConfig::default().spawn().await.unwrap();
+    let err = sessions
+        .connect_with_retry(new_session_params(&server), None, 0, Duration::ZERO)
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err.kind,
+        FnErrorKind::Argument(ArgumentError::WrongArgument(_))
+    ));
+    assert!(server.peers().is_empty());
+}
+
+#[tokio::test]
+#[cfg_attr(feature = "nasl-builtin-libssh", ignore)]
+async fn ssh_connect_retries() {
+    let server = MockSshServerConfig::default()
+        .with_refused(2)
+        .spawn()
+        .await
+        .unwrap();
+    let port = server.port();
+    let start = std::time::Instant::now();
+    tokio::task::spawn_blocking(move || {
+        run_client(move |t| {
+            t.ok(
+                format!(
+                    r#"ssh_connect(port: {port}, keytype: "ssh-ed25519", retries: 2, backoff: 200);"#
+                ),
+                MIN_SESSION_ID,
+            );
+        })
+    })
+    .await
+    .unwrap();
+    // The interpreter repeats statements which failed transiently right
+    // away, only the retries of ssh_connect wait for the backoff.
+    assert!(start.elapsed() >= Duration::from_millis(600));
+    assert_eq!(server.peers().len(), 1);
+}
+
+#[tokio::test]
+#[cfg(not(feature = "nasl-builtin-libssh"))]
+async fn drop_disconnects() {
+    let server = MockSshServerConfig::default().spawn().await.unwrap();
+    let session = new_session(&server).await;
+    assert_eq!(server.disconnected(), 0);
+    drop(session);
+    wait_for_disconnect(&server).await;
+    assert_eq!(server.disconnect_messages(), 1);
+}
+
+#[tokio::test]
+#[cfg(not(feature = "nasl-builtin-libssh"))]
+async fn session_disconnect_twice() {
+    let server = MockSshServerConfig::default().spawn().await.unwrap();
+    let mut session = new_session(&server).await;
+    session.disconnect().await.unwrap();
+    session.disconnect().await.unwrap();
+    wait_for_disconnect(&server).await;
+    assert_eq!(server.disconnect_messages(), 1);
+    drop(session);
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(server.disconnected(), 1);
+}
+
+#[cfg(not(feature = "nasl-builtin-libssh"))]
+async fn wait_for_disconnect(server: &MockSshServer) {
+    for _ in 0..50 {
+        if server.disconnected() == 1 {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    panic!("The session was not disconnected.");
+}
+
+#[tokio::test]
+#[cfg(not(feature = "nasl-builtin-libssh"))]
+async fn call_streaming() {
+    let server = MockSshServerConfig::default()
+        .with_password("user", "pass")
+        .with_command(
+            "tail -f log",
+            CommandResponse::chunked(&["first\n", "second\n", "third\n"]),
+        )
+        .spawn()
+        .await
+        .unwrap();
+    let mut session = new_session(&server).await;
+    session.auth_password("user", "pass").await.unwrap();
+    let mut chunks = vec![];
+    let (code, signal) = session
+        .call_streaming(
+            "tail -f log",
+            |data| {
+                chunks.push(String::from_utf8_lossy(data).into_owned());
+                Ok(())
+            },
+            |_| Ok(()),
+        )
+        .await
+        .unwrap();
+    assert_eq!(chunks, ["first\n", "second\n", "third\n"]);
+    assert_eq!(code, Some(0));
+    assert_eq!(signal, None);
+    // An error of a callback aborts the command.
+    let mut calls = 0;
+    let result = session
+        .call_streaming(
+            "tail -f log",
+            |_| {
+                calls += 1;
+                Err(SshErrorKind::ReadSsh.into())
+            },
+            |_| Ok(()),
+        )
+        .await;
+    assert!(matches!(
+        result,
+        Err(SshError {
+            kind: SshErrorKind::ReadSsh,
+            ..
+        })
+    ));
+    assert_eq!(calls, 1);
+}
+
+fn echo_session(t: &mut DefaultTestBuilder, port: u16) {
+    t.ok(
+        format!(r#"session_id = ssh_connect(port: {port}, keytype: "ssh-ed25519");"#),
+        MIN_SESSION_ID,
+    );
+    t.ok(
+        r#"ssh_userauth(session_id, login: "user", password: "pass");"#,
+        NaslValue::Null,
+    );
+    t.ok(
+        r#"ssh_request_exec(session_id, cmd: "echo hello");"#,
+        "hello\n",
+    );
+    t.ok(
+        r#"ssh_request_exec(session_id, stdout: 0, stderr: 1, cmd: "echo hello");"#,
+        "",
+    );
+    t.ok(r#"ssh_disconnect(session_id);"#, NaslValue::Null);
+}
+
+#[tokio::test]
+#[cfg_attr(feature = "nasl-builtin-libssh", ignore)]
+async fn record_and_replay() {
+    let path = std::env::temp_dir().join(format!("ssh_recording_{}.jsonl", std::process::id()));
+    let server = MockSshServerConfig::default()
+        .with_password("user", "pass")
+        .with_command("echo hello", CommandResponse::stdout("hello\n"))
+        .spawn()
+        .await
+        .unwrap();
+    let port = server.port();
+    let recorder = Recorder::create(&path).unwrap();
+    let t = TestBuilder::default().with_network_mode(NetworkMode::Record(recorder));
+    tokio::task::spawn_blocking(move || run_client_with(t, move |t| echo_session(t, port)))
+        .await
+        .unwrap();
+    drop(server);
+
+    let replayer = Replayer::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let t = TestBuilder::default().with_network_mode(NetworkMode::Replay(replayer));
+    tokio::task::spawn_blocking(move || {
+        run_client_with(t, move |t| {
+            echo_session(t, port);
+            check_err_matches!(
+                t,
+                r#"ssh_request_exec(session_id, cmd: "echo bye");"#,
+                InternalError::Recording(RecordingError::NoResponse(_)),
+            );
+        })
+    })
+    .await
+    .unwrap();
+}
@@ -0,0 +1,97 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+//! Human readable renderings of NASL values for debugging.
+
+use std::fmt::Write;
+
+use crate::nasl::prelude::*;
+use crate::nasl::utils::function::str_to_bytes;
+
+const BYTES_PER_LINE: usize = 16;
+
+fn printable(b: u8) -> char {
+    if b.is_ascii_graphic() || b == b' ' {
+        b as char
+    } else {
+        '.'
+    }
+}
+
+/// Formats `data` like `hexdump -C`: an offset, sixteen bytes in hex split
+/// into two groups of eight and their printable ASCII characters.
+pub fn hexdump(data: &[u8]) -> String {
+    let mut out = String::new();
+    for (i, line) in data.chunks(BYTES_PER_LINE).enumerate() {
+        let mut hex = String::new();
+        for (j, b) in line.iter().enumerate() {
+            if j == BYTES_PER_LINE / 2 {
+                hex.push(' ');
+            }
+            let _ = write!(hex, "{b:02x} ");
+        }
+        let ascii: String = line.iter().map(|b| printable(*b)).collect();
+        let _ = writeln!(out, "{:08x}  {hex:<49} |{ascii}|", i * BYTES_PER_LINE);
+    }
+    out
+}
+
+fn escape(data: &[u8]) -> String {
+    data.iter()
+        .map(|b| match *b {
+            b'"' => "\\\"".to_string(),
+            b'\\' => "\\\\".to_string(),
+            b if b.is_ascii_graphic() || b == b' ' => (b as char).to_string(),
+            b => format!("\\x{b:02x}"),
+        })
+        .collect()
+}
+
+fn write_value(out: &mut String, value: &NaslValue, depth: usize) {
+    let indent = "  ".repeat(depth);
+    match value {
+        NaslValue::Null => out.push_str("NULL"),
+        NaslValue::Boolean(x) => {
+            let _ = write!(out, "bool {}", if *x { "TRUE" } else { "FALSE" });
+        }
+        NaslValue::Number(x) => {
+            let _ = write!(out, "int {x}");
+        }
+        NaslValue::String(x) => {
+            let _ = write!(out, "string({}) \"{}\"", x.len(), escape(&str_to_bytes(x)));
+        }
+        NaslValue::Data(x) => {
+            let _ = write!(out, "data({}) \"{}\"", x.len(), escape(x));
+        }
+        NaslValue::Array(x) => {
+            let _ = writeln!(out, "array({}) {{", x.len());
+            for (i, v) in x.iter().enumerate() {
+                let _ = write!(out, "{indent}  [{i}] => ");
+                write_value(out, v, depth + 1);
+                out.push('\n');
+            }
+            let _ = write!(out, "{indent}}}");
+        }
+        NaslValue::Dict(x) => {
+            let _ = writeln!(out, "dict({}) {{", x.len());
+            for (k, v) in x {
+                let _ = write!(out, "{indent}  [\"{}\"] => ", escape(k.as_bytes()));
+                write_value(out, v, depth + 1);
+                out.push('\n');
+            }
+            let _ = write!(out, "{indent}}}");
+        }
+        x => {
+            let _ = write!(out, "{x}");
+        }
+    }
+}
+
+/// Renders any value including its type, nested arrays are indented.
+/// Keys of dictionaries are sorted to keep the output stable.
+pub fn dump(value: &NaslValue) -> String {
+    let mut out = String::new();
+    write_value(&mut out, value, 0);
+    out
+}
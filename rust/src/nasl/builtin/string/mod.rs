@@ -4,18 +4,20 @@
 
 //! Defines NASL functions that deal with string and their helpers
 
+mod dump;
 #[cfg(test)]
 mod tests;
 
 use crate::nasl::{
     utils::{
-        function::{bytes_to_str, CheckedPositionals, Maybe, StringOrData},
+        function::{bytes_to_str, str_to_bytes, CheckedPositionals, Maybe, StringOrData},
         FnError,
     },
     ArgumentError,
 };
 use core::fmt::Write;
 use glob::{MatchOptions, Pattern};
+use std::borrow::Cow;
 use std::num::ParseIntError;
 use thiserror::Error;
 
@@ -44,23 +46,73 @@ pub fn encode_hex(bytes: &[u8]) -> String {
         .join("")
 }
 
-fn append_nasl_value_as_u8(data: &mut Vec<u8>, p: &NaslValue) {
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// Encodes bytes with the RFC 4648 base32 alphabet. If `padding` is set, the output is filled
+/// up with `=` to a multiple of 8 characters.
+pub fn encode_base32(bytes: &[u8], padding: bool) -> String {
+    let mut result = String::with_capacity(bytes.len().div_ceil(5) * 8);
+    for chunk in bytes.chunks(5) {
+        let mut buf = [0u8; 5];
+        buf[..chunk.len()].copy_from_slice(chunk);
+        let bits = buf.iter().fold(0u64, |acc, b| (acc << 8) | *b as u64);
+        let chars = (chunk.len() * 8).div_ceil(5);
+        for i in 0..8 {
+            if i < chars {
+                let index = (bits >> (35 - i * 5)) & 0x1f;
+                result.push(BASE32_ALPHABET[index as usize] as char);
+            } else if padding {
+                result.push('=');
+            }
+        }
+    }
+    result
+}
+
+/// Decodes a RFC 4648 base32 string. Lower case letters are accepted and the trailing `=`
+/// padding is optional.
+pub fn decode_base32(s: &str) -> Result<Vec<u8>, String> {
+    let trimmed = s.trim_end_matches('=');
+    let mut result = Vec::with_capacity(trimmed.len() * 5 / 8);
+    let mut bits = 0u32;
+    let mut bit_count = 0;
+    for c in trimmed.chars() {
+        let value = match c.to_ascii_uppercase() {
+            x @ 'A'..='Z' => x as u32 - 'A' as u32,
+            x @ '2'..='7' => x as u32 - '2' as u32 + 26,
+            x => return Err(format!("invalid base32 character '{x}'")),
+        };
+        bits = (bits << 5) | value;
+        bit_count += 5;
+        if bit_count >= 8 {
+            bit_count -= 8;
+            result.push((bits >> bit_count) as u8);
+            bits &= (1 << bit_count) - 1;
+        }
+    }
+    Ok(result)
+}
+
+fn append_nasl_value_as_u8(data: &mut Vec<u8>, p: &NaslValue) -> Result<(), ArgumentError> {
     match p {
         NaslValue::String(s) => {
-            data.extend_from_slice(s.as_bytes());
+            data.extend_from_slice(&str_to_bytes(s));
         }
         NaslValue::Data(d) => data.extend_from_slice(d),
         NaslValue::Number(x) => {
-            data.push(*x as u8);
+            let byte = u8::try_from(*x).map_err(|_| {
+                ArgumentError::wrong_argument("number", "between 0 and 255", &x.to_string())
+            })?;
+            data.push(byte);
         }
         NaslValue::Array(x) => {
-            for v in x {
-                append_nasl_value_as_u8(data, v)
+            for v in x.iter() {
+                append_nasl_value_as_u8(data, v)?;
             }
         }
         NaslValue::Dict(x) => {
             for v in x.values() {
-                append_nasl_value_as_u8(data, v)
+                append_nasl_value_as_u8(data, v)?;
             }
         }
         NaslValue::Boolean(x) => match x {
@@ -70,16 +122,20 @@ fn append_nasl_value_as_u8(data: &mut Vec<u8>, p: &NaslValue) {
         NaslValue::AttackCategory(x) => data.push(*x as i32 as u8),
         _ => {}
     }
+    Ok(())
 }
 
 /// NASL function to parse numeric values into characters and combine with additional values
+///
+/// Numbers become a single byte and must be between 0 and 255, strings and data are appended as
+/// they are.
 #[nasl_function]
-fn raw_string(positional: CheckedPositionals<&NaslValue>) -> Vec<u8> {
+fn raw_string(positional: CheckedPositionals<&NaslValue>) -> Result<Vec<u8>, ArgumentError> {
     let mut data: Vec<u8> = vec![];
     for p in positional.iter() {
-        append_nasl_value_as_u8(&mut data, p);
+        append_nasl_value_as_u8(&mut data, p)?;
     }
-    data
+    Ok(data)
 }
 
 fn write_nasl_string(s: &mut String, value: &NaslValue) -> Result<(), StringError> {
@@ -98,7 +154,7 @@ fn write_nasl_string(s: &mut String, value: &NaslValue) -> Result<(), StringErro
             }
         }
         NaslValue::Array(x) => {
-            for p in x {
+            for p in x.iter() {
                 write_nasl_string(s, p)?;
             }
             Ok(())
@@ -133,7 +189,7 @@ fn combine_positionals_to_string(
 fn write_nasl_string_value(s: &mut String, value: &NaslValue) -> Result<(), StringError> {
     match value {
         NaslValue::Array(x) => {
-            for p in x {
+            for p in x.iter() {
                 write_nasl_string(s, p)?;
             }
             Ok(())
@@ -157,22 +213,39 @@ fn write_nasl_string_value(s: &mut String, value: &NaslValue) -> Result<(), Stri
     .map_err(|e| e.into())
 }
 
+/// Applies an ASCII case conversion to a string or data, keeping its type.
+fn convert_ascii_case(s: Option<&NaslValue>, upper: bool) -> NaslValue {
+    match s {
+        Some(NaslValue::String(x)) if upper => NaslValue::String(x.to_ascii_uppercase()),
+        Some(NaslValue::String(x)) => NaslValue::String(x.to_ascii_lowercase()),
+        Some(NaslValue::Data(x)) if upper => NaslValue::Data(x.to_ascii_uppercase()),
+        Some(NaslValue::Data(x)) => NaslValue::Data(x.to_ascii_lowercase()),
+        _ => NaslValue::Null,
+    }
+}
+
 /// NASL function to return uppercase equivalent of a given string
 ///
-/// If this function retrieves anything but a string it returns NULL
+/// Only the ASCII letters a to z are converted, all other bytes, including those of 0x80 and
+/// above, are left untouched. This is not a Unicode case conversion. Strings stay strings and data
+/// stays data.
+///
+/// If this function retrieves anything but a string or data it returns NULL
 #[nasl_function]
-fn toupper(s: Option<Maybe<StringOrData>>) -> Option<String> {
-    s.and_then(Maybe::as_option)
-        .map(|inner| inner.0.to_uppercase())
+fn toupper(s: Option<&NaslValue>) -> NaslValue {
+    convert_ascii_case(s, true)
 }
 
 /// NASL function to return lowercase equivalent of a given string
 ///
-/// If this function retrieves anything but a string it returns NULL
+/// Only the ASCII letters A to Z are converted, all other bytes, including those of 0x80 and
+/// above, are left untouched. This is not a Unicode case conversion. Strings stay strings and data
+/// stays data.
+///
+/// If this function retrieves anything but a string or data it returns NULL
 #[nasl_function]
-fn tolower(s: Option<Maybe<StringOrData>>) -> Option<String> {
-    s.and_then(Maybe::as_option)
-        .map(|inner| inner.0.to_lowercase())
+fn tolower(s: Option<&NaslValue>) -> NaslValue {
+    convert_ascii_case(s, false)
 }
 
 /// NASL function to return the length of string
@@ -185,26 +258,50 @@ fn strlen(s: Option<Maybe<StringOrData>>) -> usize {
         .unwrap_or(0)
 }
 
+/// Resolves a possibly negative index counting from the end of a buffer of
+/// length `len`, clamping it to `0..=len`.
+fn clamp_index(index: i64, len: usize) -> usize {
+    if index < 0 {
+        len.saturating_sub(index.unsigned_abs() as usize)
+    } else {
+        (index as usize).min(len)
+    }
+}
+
 /// NASL function to return a substr of a string.
 ///
-/// The first positional argument is the *string* to get the slice from.
+/// The first positional argument is the *string* or *data* to get the slice from.
 /// As a second positional argument an *int* that contains the start index for the slice is required.
 /// The optional third positional argument is an *int* and contains the end index for the slice.
 /// If not given it is set to the end of the string.
+/// Negative indices count from the end. Indices out of range are clamped, an end before the start
+/// results in an empty slice.
 /// If the start integer is higher than the value of the string NULL is returned.
+/// The result is of the same type as the given string.
 #[nasl_function]
-fn substr(s: StringOrData, start: usize, end: Option<usize>) -> Option<String> {
-    if start > s.0.len() {
-        None
-    } else {
-        Some(
-            match end {
-                Some(end) => &s.0[start..end],
-                None => &s.0[start..],
-            }
-            .into(),
-        )
+fn substr(s: &NaslValue, start: i64, end: Option<i64>) -> Result<NaslValue, ArgumentError> {
+    let bytes = match s {
+        NaslValue::String(x) => str_to_bytes(x),
+        NaslValue::Data(x) => Cow::Borrowed(x.as_slice()),
+        x => {
+            return Err(ArgumentError::wrong_argument(
+                "string",
+                "string or data",
+                &x.to_string(),
+            ))
+        }
+    };
+    let len = bytes.len();
+    if start > len as i64 {
+        return Ok(NaslValue::Null);
     }
+    let start = clamp_index(start, len);
+    let end = end.map(|x| clamp_index(x, len)).unwrap_or(len).max(start);
+    let slice = bytes[start..end].to_vec();
+    Ok(match s {
+        NaslValue::String(_) => NaslValue::String(bytes_to_str(&slice)),
+        _ => NaslValue::Data(slice),
+    })
 }
 
 /// NASL function to return a hex representation of a given positional string argument.
@@ -214,7 +311,7 @@ fn substr(s: StringOrData, start: usize, end: Option<usize>) -> Option<String> {
 #[nasl_function]
 fn hexstr(s: Option<NaslValue>) -> Option<String> {
     match s? {
-        NaslValue::String(s) => Some(encode_hex(s.as_bytes())),
+        NaslValue::String(s) => Some(encode_hex(&str_to_bytes(&s))),
         NaslValue::Data(bytes) => Some(encode_hex(&bytes)),
         _ => None,
     }
@@ -252,6 +349,25 @@ fn data_to_hexstr(bytes: Maybe<&[u8]>) -> Option<String> {
     bytes.map(encode_hex)
 }
 
+/// NASL function to encode data with the RFC 4648 base32 alphabet.
+///
+/// The data can be given as a positional or named argument `data`. The optional named argument
+/// `padding` controls whether the result is filled up with `=`, it defaults to TRUE.
+#[nasl_function(maybe_named(data), named(padding))]
+fn base32_encode(data: &NaslValue, padding: Option<bool>) -> String {
+    encode_base32(&Vec::<u8>::from(data), padding.unwrap_or(true))
+}
+
+/// NASL function to decode a RFC 4648 base32 string into byte data.
+///
+/// The string can be given as a positional or named argument `str`. Padding is optional. If the
+/// string contains characters outside of the base32 alphabet an error is returned.
+#[nasl_function(maybe_named(str))]
+fn base32_decode(str: StringOrData) -> Result<Vec<u8>, ArgumentError> {
+    decode_base32(&str.0)
+        .map_err(|e| ArgumentError::WrongArgument(format!("Expected a base32 encoded string, {e}")))
+}
+
 /// NASL function to return a buffer of required length with repeated occurrences of a specified string
 ///
 /// Length argument is required and can be a named argument or a positional argument.
@@ -274,23 +390,44 @@ fn chomp(s: StringOrData) -> String {
     s.0.trim_end().into()
 }
 
+/// Returns the bytes of a value searched by `stridx` and `strstr` or edited by `insstr`. Strings
+/// are converted with
+/// [`str_to_bytes`], other values than data via their string representation.
+fn searched_bytes(value: &NaslValue) -> Cow<'_, [u8]> {
+    match value {
+        NaslValue::String(x) => str_to_bytes(x),
+        NaslValue::Data(x) => Cow::Borrowed(x),
+        x => Cow::Owned(x.to_string().into_bytes()),
+    }
+}
+
+/// Returns the byte index of the first occurrence of `needle` in `haystack`.
+fn find_bytes(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    if needle.is_empty() {
+        return Some(0);
+    }
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
 /// NASL function to lookup position of a substring within a string
 ///
 /// The first positional argument is the *string* to search through.
 /// The second positional argument is the *string* to search for.
 /// The optional third positional argument is an *int* containing an offset from where to start the search.
+///
+/// Returns the byte index of the match counted from the offset, or -1 if there is none. Data is
+/// searched byte wise.
 #[nasl_function]
 fn stridx(haystack: NaslValue, needle: NaslValue, offset: Option<usize>) -> i64 {
-    let h = haystack.to_string();
-    let haystack = h.as_str();
-    let n = needle.to_string();
-    let needle = n.as_str();
-
+    let haystack = searched_bytes(&haystack);
+    let needle = searched_bytes(&needle);
     let offset = offset.unwrap_or(0);
-    match &haystack[offset..].find(needle) {
-        Some(index) => *index as i64,
-        None => -1,
-    }
+    haystack
+        .get(offset..)
+        .and_then(|haystack| find_bytes(haystack, &needle))
+        .map_or(-1, |index| index as i64)
 }
 
 /// NASL function to display any number of NASL values
@@ -302,14 +439,50 @@ fn display(positional: CheckedPositionals<&NaslValue>) -> Result<NaslValue, Buil
     Ok(NaslValue::Null)
 }
 
-/// NASL function that returns the ASCII code of the first character of a given string.
+/// NASL function to format a *string* or *data* as offset, hex and ASCII
+/// columns, like `hexdump -C` does.
+///
+/// Takes a single positional argument. Anything but a string or data returns NULL.
+#[nasl_function]
+fn hexdump(data: &NaslValue) -> Option<String> {
+    match data {
+        NaslValue::String(x) => Some(dump::hexdump(&str_to_bytes(x))),
+        NaslValue::Data(x) => Some(dump::hexdump(x)),
+        _ => None,
+    }
+}
+
+/// NASL function to render any value, including nested arrays, together
+/// with its type as a string for debugging.
 ///
 /// Takes a single positional argument.
 #[nasl_function]
-fn ord(s: NaslValue) -> Option<u8> {
-    let s = s.to_string();
-    let s = s.as_str();
-    s.chars().next().map(|c| c as u8)
+fn dump(value: &NaslValue) -> String {
+    dump::dump(value)
+}
+
+/// NASL function that returns the ASCII code of the first character of a given string.
+///
+/// Takes a single positional argument. For data the first byte is returned, for an empty string
+/// 0.
+#[nasl_function]
+fn ord(s: NaslValue) -> u8 {
+    let bytes = match s {
+        NaslValue::String(s) => str_to_bytes(&s).into_owned(),
+        NaslValue::Data(d) => d,
+        x => x.to_string().into_bytes(),
+    };
+    bytes.first().copied().unwrap_or(0)
+}
+
+/// NASL function that returns the byte of a given number as data, the counterpart of `ord`.
+///
+/// Takes a single positional argument, which must be between 0 and 255.
+#[nasl_function]
+fn chr(number: i64) -> Result<Vec<u8>, ArgumentError> {
+    u8::try_from(number).map(|byte| vec![byte]).map_err(|_| {
+        ArgumentError::wrong_argument("number", "between 0 and 255", &number.to_string())
+    })
 }
 
 /// NASL function to convert a string to an integer.  This function
@@ -340,33 +513,35 @@ fn str_to_int(s: &str) -> i64 {
 /// 2nd positional argument: string to replace the substring in the 1st argument with
 /// 3rd positional argument: start index in the original string at which to perform the replacement
 /// 4rd positional argument (optional): end index in the original string at which to perform the replacement.
+///
+/// The end index is inclusive and defaults to the end of the string. Indices beyond the end of
+/// the string are clamped, so a start index past the end appends the insertion. The result is
+/// data if the original string is data, a string otherwise.
 #[nasl_function]
 fn insstr(
-    s: NaslValue,
-    to_insert: NaslValue,
+    s: &NaslValue,
+    to_insert: &NaslValue,
     start: usize,
     end: Option<usize>,
-) -> Result<String, ArgumentError> {
-    let mut s = s.to_string();
-
-    let insb = to_insert.to_string();
-    let ins = insb.as_str();
-
-    let end = end.unwrap_or(s.len()).min(s.len());
-    if start > end {
-        return Err(ArgumentError::WrongArgument(format!(
-            "start index ({}) larger than end ({}).",
-            start, end
-        )));
-    }
-
-    if s.len() >= (end + 1) {
-        s.replace_range(start..(end + 1), ins);
-    } else {
-        s.replace_range(start..(end), ins);
+) -> Result<NaslValue, ArgumentError> {
+    if let Some(end) = end {
+        if start > end {
+            return Err(ArgumentError::WrongArgument(format!(
+                "start index ({}) larger than end ({}).",
+                start, end
+            )));
+        }
     }
-
-    Ok(s)
+    let mut bytes = searched_bytes(s).into_owned();
+    let len = bytes.len();
+    let end = end.map(|x| x.saturating_add(1)).unwrap_or(len).min(len);
+    let start = start.min(end);
+    bytes.splice(start..end, searched_bytes(to_insert).iter().copied());
+
+    Ok(match s {
+        NaslValue::Data(_) => NaslValue::Data(bytes),
+        _ => NaslValue::String(bytes_to_str(&bytes)),
+    })
 }
 
 /// Matches a string against a simple shell like pattern.
@@ -403,13 +578,17 @@ fn match_(string: NaslValue, pattern: NaslValue, icase: Option<bool>) -> Result<
 /// The optional named argument *sep* is a NaslValue containing the
 /// separator for splitting the string. The string is split after the
 /// separator. By default the string is split at every line break.
+/// An empty separator returns NULL.
 ///
 /// The optional named argument *keep* is a *bool* and is used as flag
 /// to enable/disable keeping the separator within the separated
 /// string. By default *keep* is set to *TRUE*. *TRUE* means the
 /// separator is kept, *FALSE* means the separator is discarded.
+///
+/// A string ending with the separator does not result in an empty last
+/// element, so an empty string results in an empty array.
 #[nasl_function(named(sep, keep))]
-fn split(string: NaslValue, sep: Option<NaslValue>, keep: Option<bool>) -> Vec<String> {
+fn split(string: NaslValue, sep: Option<NaslValue>, keep: Option<bool>) -> Option<Vec<String>> {
     let strb = string.to_string();
     let str = strb.as_str();
 
@@ -421,14 +600,45 @@ fn split(string: NaslValue, sep: Option<NaslValue>, keep: Option<bool>) -> Vec<S
     }
 
     let sep_aux = separator.as_str();
+    if sep_aux.is_empty() {
+        return None;
+    }
 
     if keep.unwrap_or(true) {
-        str.split_inclusive(sep_aux).map(String::from).collect()
+        Some(str.split_inclusive(sep_aux).map(String::from).collect())
     } else {
-        str.split(sep_aux).map(String::from).collect()
+        let mut parts: Vec<String> = str.split(sep_aux).map(String::from).collect();
+        if parts.last().is_some_and(String::is_empty) {
+            parts.pop();
+        }
+        Some(parts)
     }
 }
 
+/// This function joins the elements of an array into a string.
+///
+/// The first positional argument is the *array* to join.
+///
+/// The optional argument *sep*, given either positionally or named, is
+/// put between the elements. By default the elements are joined without
+/// a separator.
+#[nasl_function(maybe_named(sep))]
+fn join(array: &NaslValue, sep: Option<NaslValue>) -> Result<String, ArgumentError> {
+    let NaslValue::Array(elements) = array else {
+        return Err(ArgumentError::wrong_argument(
+            "array",
+            "an array",
+            &array.to_string(),
+        ));
+    };
+    let sep = sep.map(|x| x.to_string()).unwrap_or_default();
+    Ok(elements
+        .iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<_>>()
+        .join(&sep))
+}
+
 /// This function looks up a substring within a string and replaces them with a given string.
 /// The named argument string is the string to modify.
 /// The named argument find is a string containing the substring to replace.
@@ -470,17 +680,23 @@ fn str_replace(
 ///
 /// 1st positional argument: string to search in.
 /// 2nd positional argument: substring to search for.
+///
+/// Data is searched byte wise and the result is data again. NULL is returned if there is no
+/// match.
 fn strstr(string: NaslValue, find: NaslValue) -> NaslValue {
-    let strb = string.to_string();
-    let string = strb.as_str();
-
-    let findb = find.to_string();
-    let find = findb.as_str();
-
-    if let Some(i) = string.find(find) {
-        return NaslValue::String(string[i..].to_string());
+    if let (NaslValue::String(string), NaslValue::String(find)) = (&string, &find) {
+        return string.find(find.as_str()).map_or(NaslValue::Null, |i| {
+            NaslValue::String(string[i..].to_string())
+        });
+    }
+    let bytes = searched_bytes(&string);
+    let Some(index) = find_bytes(&bytes, &searched_bytes(&find)) else {
+        return NaslValue::Null;
+    };
+    match string {
+        NaslValue::Data(_) => NaslValue::Data(bytes[index..].to_vec()),
+        _ => NaslValue::String(bytes_to_str(&bytes[index..])),
     }
-    NaslValue::Null
 }
 /// The description builtin function
 pub struct NaslString;
@@ -501,13 +717,19 @@ function_set! {
         chomp,
         stridx,
         display,
+        hexdump,
+        dump,
         hexstr_to_data,
         data_to_hexstr,
+        base32_encode,
+        base32_decode,
         ord,
+        chr,
         (match_, "match"),
         insstr,
         int,
         split,
+        join,
         str_replace,
         strstr
     )
@@ -17,6 +17,9 @@ mod tests {
         check_code_result("hexstr(6);", Null);
         check_code_result("hexstr();", Null);
         check_code_result("hexstr(raw_string(10, 208, 102, 165, 210, 159, 63, 42, 42, 28, 124, 23, 221, 8, 42, 121));", "0ad066a5d29f3f2a2a1c7c17dd082a79");
+
+        // A string holds bytes, 0xff must not be UTF-8 encoded.
+        check_code_result("hexstr(string(raw_string(0xff, 0x41)));", "ff41");
     }
 
     #[test]
@@ -27,19 +30,44 @@ mod tests {
             "raw_string(0x7B, 1, 'Hallo');",
             vec![123u8, 1, 72, 97, 108, 108, 111],
         );
+        check_code_result(
+            r#"raw_string(0x41, "BC", 0, hexstr_to_data("ff00"), 255);"#,
+            vec![0x41u8, 0x42, 0x43, 0, 0xff, 0, 0xff],
+        );
+    }
+
+    #[test]
+    fn raw_string_out_of_range() {
+        check_err_matches!("raw_string(0x41, 256);", ArgumentError::WrongArgument(_));
+        check_err_matches!("raw_string(-1);", ArgumentError::WrongArgument(_));
     }
 
     #[test]
     fn tolower() {
         check_code_result("tolower(0x7B);", Null);
-        check_code_result("tolower('HALLO');", "hallo");
+        check_code_result("tolower('HALLO');", "hallo".as_bytes());
+        check_code_result(r#"tolower("HALLO");"#, "hallo");
     }
 
     #[test]
     fn toupper() {
         check_code_result("toupper(0x7B);", Null);
-        check_code_result("toupper('hallo');", "HALLO");
+        check_code_result("toupper('hallo');", "HALLO".as_bytes());
+        check_code_result(r#"toupper("hallo");"#, "HALLO");
         check_code_result("toupper();", Null);
+        check_code_result(r#"toupper("straße");"#, "STRAßE");
+    }
+
+    #[test]
+    fn case_conversion_of_data() {
+        check_code_result(
+            r#"tolower(raw_string(0x41, 0xff, 0x5a, 0x20, 0xc4, 0x61));"#,
+            vec![0x61u8, 0xff, 0x7a, 0x20, 0xc4, 0x61],
+        );
+        check_code_result(
+            r#"toupper(raw_string(0x61, 0xff, 0x7a, 0x5b, 0xe4));"#,
+            vec![0x41u8, 0xff, 0x5a, 0x5b, 0xe4],
+        );
     }
 
     #[test]
@@ -60,9 +88,28 @@ mod tests {
 
     #[test]
     fn substr() {
-        check_code_result("substr('hello', 1);", "ello");
-        check_code_result("substr('hello', 0, 4);", "hell");
+        check_code_result("substr('hello', 1);", Data(b"ello".to_vec()));
+        check_code_result("substr('hello', 0, 4);", Data(b"hell".to_vec()));
         check_code_result("substr('hello', 6);", Null);
+        check_code_result(r#"substr("hello", 1, 3);"#, "el");
+    }
+
+    #[test]
+    fn substr_negative_indices() {
+        check_code_result(r#"substr("hello", -3);"#, "llo");
+        check_code_result(r#"substr("hello", 0, -1);"#, "hell");
+        check_code_result(r#"substr("hello", -4, -2);"#, "el");
+        check_code_result("substr('hello', -2);", Data(b"lo".to_vec()));
+    }
+
+    #[test]
+    fn substr_out_of_range() {
+        check_code_result(r#"substr("hello", 2, 100);"#, "llo");
+        check_code_result(r#"substr("hello", -100);"#, "hello");
+        check_code_result(r#"substr("hello", -100, 2);"#, "he");
+        check_code_result(r#"substr("hello", 3, 1);"#, "");
+        check_code_result(r#"substr("hello", 5);"#, "");
+        check_err_matches!("substr(5, 1);", WrongArgument { .. });
     }
 
     #[test]
@@ -93,6 +140,15 @@ mod tests {
         check_code_result(r#"stridx("blahabc", "abc", 4);"#, 0);
         check_code_result(r#"stridx("blahabc", "abc", 3);"#, 1);
         check_code_result(r#"stridx("blahbc", "abc", 2);"#, -1);
+        check_code_result(r#"stridx("abc", "c", 10);"#, -1);
+        check_code_result(
+            r#"stridx(raw_string(0x00, 0xff, 0x0d, 0x0a, 0xff, 0x00), raw_string(0xff, 0x00));"#,
+            4,
+        );
+        check_code_result(
+            r#"stridx(raw_string(0xff, 0x01, 0xff, 0x02), raw_string(0xff), 1);"#,
+            1,
+        );
     }
 
     #[test]
@@ -101,6 +157,41 @@ mod tests {
         check_code_result(r#"display("abc");"#, Null);
     }
 
+    fn last_result(code: &str) -> std::string::String {
+        match TestBuilder::from_code(code).results().pop() {
+            Some(Ok(String(s))) => s,
+            x => panic!("Expected a string, got {x:?}"),
+        }
+    }
+
+    #[test]
+    fn hexdump() {
+        insta::assert_snapshot!(last_result(
+            "hexdump(raw_string(0x47, 0x45, 0x54, 0x20, 0x2f, 0x20, 0x48, 0x54, 0x54, 0x50, 0x2f, 0x31, 0x2e, 0x31, 0x0d, 0x0a, 0x00, 0x01, 0x7f, 0xff));"
+        ));
+        check_code_result("hexdump('');", "");
+        check_code_result("hexdump(1);", Null);
+    }
+
+    #[test]
+    fn dump() {
+        insta::assert_snapshot!(last_result(
+            r#"
+            inner[0] = 'b\n';
+            inner[1] = TRUE;
+            inner[2] = NULL;
+            dict["k"] = "v";
+            dict["a"] = 2;
+            outer[0] = 1;
+            outer[1] = "a";
+            outer[2] = inner;
+            outer[3] = dict;
+            dump(outer);
+            "#
+        ));
+        check_code_result("dump(5);", "int 5");
+    }
+
     #[test]
     fn hexstr_to_data() {
         let mut t = TestBuilder::default();
@@ -121,11 +212,24 @@ mod tests {
         check_code_result(r#"ord("\n");"#, 92);
         check_code_result(r#"ord('\n');"#, 10);
         check_code_result(r#"ord("c");"#, 99);
-        check_code_result(r#"ord("");"#, Null);
+        check_code_result(r#"ord("");"#, 0);
         check_code_result("ord(1);", 49);
+        check_code_result("ord(raw_string(0xff, 0x41));", 255);
         check_err_matches!("ord();", MissingPositionals { .. });
     }
 
+    #[test]
+    fn chr() {
+        check_code_result("chr(65);", vec![65u8]);
+        check_code_result("chr(0);", vec![0u8]);
+        check_code_result("chr(255);", vec![255u8]);
+        check_code_result("ord(chr(200));", 200);
+        check_code_result(r#"chr(ord("z"));"#, vec![b'z']);
+        check_err_matches!("chr(256);", WrongArgument(_));
+        check_err_matches!("chr(-1);", WrongArgument(_));
+        check_err_matches!("chr();", MissingPositionals { .. });
+    }
+
     #[test]
     fn match_() {
         check_code_result(r#"match(string: "abcd", pattern: "*cd");"#, true);
@@ -166,7 +270,44 @@ mod tests {
 
     #[test]
     fn insstr_data_new_line() {
-        check_code_result(r#"insstr('foo\nbar', "123456", 4 ,5);"#, "foo\n123456r");
+        check_code_result(
+            r#"insstr('foo\nbar', "123456", 4 ,5);"#,
+            Data(b"foo\n123456r".to_vec()),
+        );
+    }
+
+    #[test]
+    fn insstr_positions() {
+        check_code_result(r#"insstr("abcdef", "XY", 0, 0);"#, "XYbcdef");
+        check_code_result(r#"insstr("abcdef", "XY", 2, 3);"#, "abXYef");
+        check_code_result(r#"insstr("abcdef", "XY", 5);"#, "abcdeXY");
+        // the insertion is longer than the replaced region
+        check_code_result(r#"insstr("abcdef", "123456", 1, 2);"#, "a123456def");
+        // the insertion is shorter than the replaced region
+        check_code_result(r#"insstr("abcdef", "-", 1, 4);"#, "a-f");
+    }
+
+    #[test]
+    fn insstr_out_of_range() {
+        check_code_result(r#"insstr("abc", "XY", 10);"#, "abcXY");
+        check_code_result(r#"insstr("abc", "XY", 10, 20);"#, "abcXY");
+        check_code_result(r#"insstr("abc", "XY", 1, 20);"#, "aXY");
+    }
+
+    #[test]
+    fn insstr_non_ascii_string() {
+        // Every character of a string is a single byte, e.g. \xff of a script read as ISO-8859-1.
+        check_code_result("insstr(\"a\u{ff}b\", \"X\", 1, 1);", "aXb");
+        check_code_result("insstr(\"abc\", \"\u{ff}\", 1, 1);", "a\u{ff}c");
+        check_code_result("insstr(\"\u{ff}\u{fe}\", \"X\", 1);", "\u{ff}X");
+    }
+
+    #[test]
+    fn insstr_binary_data() {
+        check_code_result(
+            "insstr(raw_string(0x00, 0xff, 0x01), raw_string(0xfe, 0xfd), 1, 1);",
+            Data(vec![0x00, 0xfe, 0xfd, 0x01]),
+        );
     }
 
     #[test]
@@ -213,6 +354,46 @@ mod tests {
         check_err_matches!(r#"split();"#, MissingPositionals { .. });
     }
 
+    #[test]
+    fn split_multi_char_separator() {
+        check_code_result(
+            r#"split("a::b::c", sep: "::");"#,
+            vec!["a::".to_string(), "b::".to_string(), "c".to_string()],
+        );
+        check_code_result(
+            r#"split("a::b::c", sep: "::", keep: FALSE);"#,
+            vec!["a".to_string(), "b".to_string(), "c".to_string()],
+        );
+    }
+
+    #[test]
+    fn split_trailing_separator_and_empty_input() {
+        check_code_result(
+            r#"split("a;b;", sep: ";");"#,
+            vec!["a;".to_string(), "b;".to_string()],
+        );
+        check_code_result(
+            r#"split("a;;b;", sep: ";", keep: FALSE);"#,
+            vec!["a".to_string(), "".to_string(), "b".to_string()],
+        );
+        check_code_result(r#"split("", sep: ";");"#, Array(vec![].into()));
+        check_code_result(r#"split("", sep: ";", keep: FALSE);"#, Array(vec![].into()));
+        check_code_result(r#"split("abc", sep: "");"#, Null);
+    }
+
+    #[test]
+    fn join() {
+        check_code_result(r#"join(make_list("a", "b", "c"), ", ");"#, "a, b, c");
+        check_code_result(r#"join(make_list("a", 1), sep: ";");"#, "a;1");
+        check_code_result(r#"join(make_list("a", "b"));"#, "ab");
+        check_code_result(r#"join(make_list());"#, "");
+        check_code_result(
+            r#"join(split("a;b;c", sep: ";", keep: FALSE), sep: ";");"#,
+            "a;b;c",
+        );
+        check_err_matches!(r#"join("abc");"#, WrongArgument { .. });
+    }
+
     #[test]
     fn replace() {
         check_code_result(
@@ -226,6 +407,14 @@ mod tests {
             r#"str_replace(string: "abcbd", find: "b", count: 1);"#,
             "acbd",
         );
+        check_code_result(
+            r#"str_replace(string: "a-b-c-d", find: "-", replace: "+", count: 2);"#,
+            "a+b+c-d",
+        );
+        check_code_result(
+            r#"str_replace(string: "a-b-c", find: "-", replace: "+", count: 0);"#,
+            "a+b+c",
+        );
         check_code_result(r#"str_replace(string: "ab\nc", find: "\n");"#, "abc");
         check_code_result(r#"str_replace(string: 'ab\nc', find: '\n');"#, "abc");
         check_code_result(r#"str_replace(string: 'ab\nc', find: "\n");"#, "ab\nc");
@@ -235,8 +424,58 @@ mod tests {
     fn strstr() {
         check_code_result(r#"strstr("abc", "b");"#, "bc");
         check_code_result(r#"strstr("abcbd", "b");"#, "bcbd");
-        check_code_result(r#"strstr('a\rbcbd', '\rb');"#, "\rbcbd");
+        check_code_result(r#"strstr('a\rbcbd', '\rb');"#, "\rbcbd".as_bytes());
+        check_code_result(r#"strstr("abc", "d");"#, Null);
+        check_code_result(
+            r#"strstr(raw_string(0x01, 0x00, 0xff, 0x80, 0x02), raw_string(0xff, 0x80));"#,
+            vec![0xffu8, 0x80, 0x02],
+        );
         check_err_matches!(r#"strstr();"#, MissingPositionals { .. });
         check_err_matches!(r#"strstr("a");"#, MissingPositionals { .. });
     }
+
+    #[test]
+    fn base32_encode() {
+        check_code_result(r#"base32_encode("");"#, "");
+        check_code_result(r#"base32_encode("f");"#, "MY======");
+        check_code_result(r#"base32_encode("fo");"#, "MZXQ====");
+        check_code_result(r#"base32_encode("foo");"#, "MZXW6===");
+        check_code_result(r#"base32_encode("foob");"#, "MZXW6YQ=");
+        check_code_result(r#"base32_encode("fooba");"#, "MZXW6YTB");
+        check_code_result(r#"base32_encode("foobar");"#, "MZXW6YTBOI======");
+        check_code_result(r#"base32_encode(data: "foobar");"#, "MZXW6YTBOI======");
+        check_code_result(
+            r#"base32_encode(data: "foobar", padding: FALSE);"#,
+            "MZXW6YTBOI",
+        );
+    }
+
+    #[test]
+    fn base32_decode() {
+        check_code_result(r#"base32_decode("");"#, Data(vec![]));
+        check_code_result(r#"base32_decode("MY======");"#, Data(b"f".to_vec()));
+        check_code_result(r#"base32_decode("MZXQ====");"#, Data(b"fo".to_vec()));
+        check_code_result(r#"base32_decode("MZXW6===");"#, Data(b"foo".to_vec()));
+        check_code_result(r#"base32_decode("MZXW6YQ=");"#, Data(b"foob".to_vec()));
+        check_code_result(r#"base32_decode("MZXW6YTB");"#, Data(b"fooba".to_vec()));
+        check_code_result(
+            r#"base32_decode(str: "MZXW6YTBOI======");"#,
+            Data(b"foobar".to_vec()),
+        );
+        check_code_result(r#"base32_decode("mzxw6ytboi");"#, Data(b"foobar".to_vec()));
+        check_err_matches!(r#"base32_decode("MZXW1===");"#, WrongArgument { .. });
+        check_err_matches!(r#"base32_decode("MZ=XW6==");"#, WrongArgument { .. });
+    }
+
+    #[test]
+    fn base32_round_trip() {
+        check_code_result(
+            r#"base32_decode(base32_encode(raw_string(0x00, 0xff, 0x10, 0x80, 0x7f, 0x01, 0xfe)));"#,
+            Data(vec![0x00, 0xff, 0x10, 0x80, 0x7f, 0x01, 0xfe]),
+        );
+        check_code_result(
+            r#"base32_decode(base32_encode(raw_string(0x00, 0xff, 0x10), padding: FALSE));"#,
+            Data(vec![0x00, 0xff, 0x10]),
+        );
+    }
 }
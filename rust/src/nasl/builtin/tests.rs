@@ -7,7 +7,11 @@
 //! but testing proc_macros comes with a lot of difficulties and the tests
 //! are very easy to do here.
 
-use crate::nasl::{test_prelude::*, utils::Executor};
+use crate::nasl::{
+    test_prelude::*,
+    utils::{function::Bytes, Executor},
+    NamedArgSignature,
+};
 
 #[nasl_function]
 fn foo1(_context: &Context, x: usize) -> usize {
@@ -104,3 +108,158 @@ fn functions_added_properly() {
     t.ok("sync_stateless();", 5);
     t.ok("async_stateless();", 6);
 }
+
+#[test]
+fn function_names() {
+    let executor = Executor::single(Bar);
+    assert_eq!(
+        executor.function_names(),
+        [
+            "async_stateful_mut",
+            "async_stateful_ref",
+            "async_stateless",
+            "sync_stateful_mut",
+            "sync_stateful_ref",
+            "sync_stateless",
+        ]
+    );
+}
+
+#[test]
+fn available_functions() {
+    let names = crate::nasl::available_functions();
+    for name in ["aes128_gcm_encrypt", "HMAC_SHA256", "ssh_connect", "strlen"] {
+        assert!(names.contains(&name), "{name} is missing");
+        assert!(crate::nasl::has_function(name));
+    }
+    assert!(!crate::nasl::has_function("aes_gcm_encrypt"));
+    assert!(names.is_sorted());
+}
+
+#[derive(NaslArgs)]
+struct Args<'a> {
+    data: Bytes<'a>,
+    count: usize,
+    #[nasl(optional)]
+    sep: Option<&'a str>,
+    r#type: Option<String>,
+}
+
+#[nasl_function(args(Args))]
+fn repeat_args(register: &Register) -> Result<String, FnError> {
+    let args = Args::from_register(register)?;
+    let data = String::from_utf8_lossy(&args.data.0);
+    let mut out = vec![data; args.count].join(args.sep.unwrap_or(""));
+    if let Some(ty) = args.r#type {
+        out.push_str(&ty);
+    }
+    Ok(out)
+}
+
+struct ArgsSet;
+
+function_set! {
+    ArgsSet,
+    (repeat_args,)
+}
+
+#[test]
+fn derived_args_required_and_optional() {
+    let mut t = TestBuilder::default().with_executor(Executor::single(ArgsSet));
+    t.ok(r#"repeat_args(data: "ab", count: 2);"#, "abab");
+    t.ok(r#"repeat_args(data: 'ab', count: 2, sep: "-");"#, "ab-ab");
+    t.ok(r#"repeat_args(data: "a", count: 1, type: "!");"#, "a!");
+    check_err_matches!(
+        t,
+        r#"repeat_args(count: 2);"#,
+        ArgumentError::MissingNamed(_)
+    );
+}
+
+#[test]
+fn derived_args_wrong_type() {
+    let mut t = TestBuilder::default().with_executor(Executor::single(ArgsSet));
+    check_err_matches!(
+        t,
+        r#"repeat_args(data: 5, count: 2);"#,
+        ArgumentError::WrongArgument(_)
+    );
+    check_err_matches!(
+        t,
+        r#"repeat_args(data: "a", count: "two");"#,
+        ArgumentError::WrongArgument(_)
+    );
+    check_err_matches!(
+        t,
+        r#"repeat_args(data: "a", count: 1, sep: 1);"#,
+        ArgumentError::WrongArgument(_)
+    );
+}
+
+#[test]
+fn signatures() {
+    let executor = Executor::single(Foo);
+    let signature = executor.function_signature("add_positionals").unwrap();
+    assert_eq!(signature.named, []);
+    assert_eq!(signature.min_positional, 2);
+    assert_eq!(signature.max_positional, None);
+    assert_eq!(signature.returns, "usize");
+    // Taking the register without describing the arguments.
+    assert_eq!(executor.function_signature("foo2"), None);
+    assert_eq!(executor.function_signature("unknown"), None);
+
+    let executor = Executor::single(ArgsSet);
+    let signature = executor.function_signature("repeat_args").unwrap();
+    assert_eq!(
+        signature.named,
+        [
+            NamedArgSignature {
+                name: "data",
+                ty: "Bytes",
+                required: true,
+            },
+            NamedArgSignature {
+                name: "count",
+                ty: "usize",
+                required: true,
+            },
+            NamedArgSignature {
+                name: "sep",
+                ty: "&str",
+                required: false,
+            },
+            NamedArgSignature {
+                name: "type",
+                ty: "String",
+                required: false,
+            },
+        ]
+    );
+    assert_eq!(signature.max_positional, Some(0));
+    assert_eq!(signature.returns, "Result<String, FnError>");
+}
+
+#[test]
+fn std_function_signatures() {
+    let signature = crate::nasl::function_signature("aes128_cbc_encrypt").unwrap();
+    for name in ["key", "data", "iv"] {
+        assert!(signature.named(name).unwrap().required, "{name}");
+    }
+    assert!(!signature.named("len").unwrap().required);
+
+    let signature = crate::nasl::function_signature("cidr_contains").unwrap();
+    assert_eq!(signature.named("ip").unwrap().ty, "&str");
+    assert_eq!(signature.min_positional, 0);
+    assert_eq!(signature.max_positional, Some(2));
+    assert_eq!(crate::nasl::function_signature("unknown"), None);
+
+    let signature = crate::nasl::function_signature("aes128_gcm_encrypt_auth").unwrap();
+    assert!(signature.named("aad").unwrap().required);
+    assert_eq!(signature.named("len").unwrap().ty, "i64");
+    let signature = crate::nasl::function_signature("aes128_gcm_encrypt").unwrap();
+    assert!(signature.named("aad").is_none());
+    let signature = crate::nasl::function_signature("aes256_ocb_decrypt").unwrap();
+    assert!(signature.named("key").unwrap().required);
+    let signature = crate::nasl::function_signature("aes128_siv_encrypt").unwrap();
+    assert_eq!(signature.named("aad").unwrap().ty, "Vec<Bytes>");
+}
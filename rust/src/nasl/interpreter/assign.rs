@@ -2,7 +2,8 @@
 //
 // SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
 
-use std::collections::HashMap;
+use std::collections::BTreeMap;
+use std::sync::Arc;
 
 use crate::nasl::syntax::{AssignOrder, Statement, TokenCategory};
 
@@ -14,7 +15,7 @@ use crate::nasl::utils::ContextType;
 fn prepare_array(idx: &NaslValue, left: NaslValue) -> (usize, Vec<NaslValue>) {
     let idx = i64::from(idx) as usize;
     let mut arr: Vec<NaslValue> = match left {
-        NaslValue::Array(x) => x,
+        NaslValue::Array(x) => Arc::unwrap_or_clone(x),
         _ => {
             vec![left.clone()]
         }
@@ -26,16 +27,16 @@ fn prepare_array(idx: &NaslValue, left: NaslValue) -> (usize, Vec<NaslValue>) {
     (idx, arr)
 }
 
-fn prepare_dict(left: NaslValue) -> HashMap<String, NaslValue> {
+fn prepare_dict(left: NaslValue) -> BTreeMap<String, NaslValue> {
     match left {
-        NaslValue::Array(x) => x
+        NaslValue::Array(x) => Arc::unwrap_or_clone(x)
             .into_iter()
             .enumerate()
             .map(|(i, v)| (i.to_string(), v))
             .collect(),
         NaslValue::Dict(x) => x,
-        NaslValue::Null => HashMap::new(),
-        x => HashMap::from([("0".to_string(), x)]),
+        NaslValue::Null => BTreeMap::new(),
+        x => BTreeMap::from([("0".to_string(), x)]),
     }
 }
 
@@ -102,13 +103,13 @@ impl Interpreter<'_> {
                 let orig = arr[idx].clone();
                 let result = result(&orig, right);
                 arr[idx] = result;
-                self.save(ridx, key, NaslValue::Array(arr));
+                self.save(ridx, key, NaslValue::from(arr));
                 orig
             }
             AssignOrder::AssignReturn => {
                 let result = result(&arr[idx], right);
                 arr[idx] = result.clone();
-                self.save(ridx, key, NaslValue::Array(arr));
+                self.save(ridx, key, NaslValue::from(arr));
                 result
             }
         }
@@ -142,21 +143,26 @@ impl Interpreter<'_> {
                     AssignOrder::ReturnAssign => left,
                 }
             }
-            Some(idx) => match idx {
-                NaslValue::String(idx) => {
-                    self.handle_dict(ridx, key, idx, left, right, order, result)
-                }
-                NaslValue::Data(idx) => {
-                    let idx = idx.into_iter().map(|x| x as char).collect();
-                    self.handle_dict(ridx, key, idx, left, right, order, result)
-                }
-                _ => match left {
-                    NaslValue::Dict(_) => {
-                        self.handle_dict(ridx, key, idx.to_string(), left, right, order, result)
+            Some(idx) => {
+                // Drop the value of the register, so that an array which is not shared with
+                // another variable is modified in place instead of being copied.
+                self.save(ridx, key, NaslValue::Null);
+                match idx {
+                    NaslValue::String(idx) => {
+                        self.handle_dict(ridx, key, idx, left, right, order, result)
+                    }
+                    NaslValue::Data(idx) => {
+                        let idx = idx.into_iter().map(|x| x as char).collect();
+                        self.handle_dict(ridx, key, idx, left, right, order, result)
                     }
-                    _ => self.handle_array(ridx, key, &idx, left, right, order, result),
-                },
-            },
+                    _ => match left {
+                        NaslValue::Dict(_) => {
+                            self.handle_dict(ridx, key, idx.to_string(), left, right, order, result)
+                        }
+                        _ => self.handle_array(ridx, key, &idx, left, right, order, result),
+                    },
+                }
+            }
         };
         Ok(result)
     }
@@ -241,7 +247,7 @@ impl Interpreter<'_> {
 #[cfg(test)]
 mod tests {
     use crate::nasl::test_prelude::*;
-    use std::collections::HashMap;
+    use std::collections::BTreeMap;
 
     #[test]
     fn variables() {
@@ -267,7 +273,7 @@ mod tests {
         t.ok("a[2] = 12;", 12);
         t.ok(
             "a;",
-            NaslValue::Array(vec![NaslValue::Null, NaslValue::Null, 12.into()]),
+            NaslValue::from(vec![NaslValue::Null, NaslValue::Null, 12.into()]),
         );
     }
 
@@ -279,7 +285,32 @@ mod tests {
         t.ok("a[2] = 12;", 12);
         t.ok(
             "a;",
-            NaslValue::Array(vec![12.into(), NaslValue::Null, 12.into()]),
+            NaslValue::from(vec![12.into(), NaslValue::Null, 12.into()]),
+        );
+    }
+
+    #[test]
+    fn array_copies_are_independent() {
+        let mut t = TestBuilder::default();
+        t.run("a = make_list(1, 2, 3);");
+        t.run("b = a;");
+        t.ok("b[0] = 9;", 9);
+        t.ok("a[0];", 1);
+        t.ok("b[0];", 9);
+        t.ok("a[1] += 5;", 7);
+        t.ok("b[1];", 2);
+        t.run("function change(x) { x[2] = 0; return x; }");
+        t.run("c = change(x: a);");
+        t.ok("a[2];", 3);
+        t.ok("c[2];", 0);
+        t.ok("c[1];", 7);
+        t.ok(
+            "a;",
+            NaslValue::from(vec![
+                NaslValue::Number(1),
+                NaslValue::Number(7),
+                NaslValue::Number(3),
+            ]),
         );
     }
 
@@ -289,7 +320,7 @@ mod tests {
         t.ok("a['hi'] = 12;", 12);
         t.ok(
             "a;",
-            NaslValue::Dict(HashMap::from([("hi".to_string(), 12.into())])),
+            NaslValue::Dict(BTreeMap::from([("hi".to_string(), 12.into())])),
         );
         t.ok("a['hi'];", 12);
     }
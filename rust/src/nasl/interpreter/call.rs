@@ -7,12 +7,12 @@ use crate::nasl::utils::lookup_keys::FC_ANON_ARGS;
 
 use crate::nasl::interpreter::{
     error::{FunctionCallError, InterpretError},
-    interpreter::{InterpretResult, RunSpecific},
+    interpreter::{InterpretResult, RunSpecific, MAX_FORKS},
     Interpreter,
 };
 
 use crate::nasl::syntax::NaslValue;
-use crate::nasl::utils::ContextType;
+use crate::nasl::utils::{ContextType, UnknownFunctions};
 use std::collections::HashMap;
 
 use super::InterpretErrorKind;
@@ -44,11 +44,19 @@ impl Interpreter<'_> {
         }
         named.insert(
             FC_ANON_ARGS.to_owned(),
-            ContextType::Value(NaslValue::Array(position)),
+            ContextType::Value(NaslValue::from(position)),
         );
         self.register_mut().create_root_child(named);
         let result = match self.ctxconfigs.nasl_fn_execute(name, self.register()).await {
             Some(Ok(NaslValue::Fork(x))) if self.index == 0 && !x.is_empty() => {
+                let runs = self.run_specific.len().saturating_mul(x.len());
+                if runs > MAX_FORKS {
+                    // The other runs would call the function again and fork on their own, so
+                    // only the root run continues.
+                    self.register_mut().drop_last();
+                    self.run_specific.truncate(1);
+                    return Err(InterpretError::too_many_forks(runs));
+                }
                 let mut additional = Vec::with_capacity(x.len() - 1);
                 let root_pos = self.run_specific[0].position.clone();
 
@@ -88,6 +96,12 @@ impl Interpreter<'_> {
             Some(Ok(NaslValue::Fork(_))) => {
                 unreachable!("NaslValue::Fork must only occur on root instance, all other cases should return a value within run_specific")
             }
+            // The function defines the value of the failed call, so the script goes on.
+            Some(Err(e)) if e.recovered_value().is_some() => {
+                let value = e.recovered_value().cloned().unwrap_or_default();
+                self.ctxconfigs.warn(e);
+                Ok(value)
+            }
             Some(r) => r.map_err(|e| {
                 InterpretError::new(
                     InterpretErrorKind::FunctionCallError(FunctionCallError::new(name, e)),
@@ -95,11 +109,15 @@ impl Interpreter<'_> {
                 )
             }),
             None => {
-                let found = self
-                    .register()
-                    .named(name)
-                    .ok_or_else(|| InterpretError::not_found(name))?
-                    .clone();
+                let found = match self.register().named(name) {
+                    Some(found) => found.clone(),
+                    None if self.ctxconfigs.unknown_functions() == UnknownFunctions::NullStub => {
+                        self.ctxconfigs.warn_unknown_function(name);
+                        self.register_mut().drop_last();
+                        return Ok(NaslValue::Null);
+                    }
+                    None => return Err(InterpretError::not_found(name)),
+                };
                 match found {
                     ContextType::Function(params, stmt) => {
                         // prepare default values
@@ -126,7 +144,11 @@ impl Interpreter<'_> {
 
 #[cfg(test)]
 mod tests {
+    use futures::StreamExt;
+
+    use crate::nasl::interpreter::{CodeInterpreter, InterpretError, InterpretErrorKind};
     use crate::nasl::test_prelude::*;
+    use crate::nasl::utils::UnknownFunctions;
 
     #[test]
     fn default_null_on_user_defined_functions() {
@@ -141,6 +163,36 @@ mod tests {
         t.ok("test();", 0);
     }
 
+    #[tokio::test]
+    async fn unknown_functions_are_errors_by_default() {
+        let t = TestBuilder::default();
+        let context = t.context();
+        let results: Vec<_> = CodeInterpreter::new("foo();", Register::default(), &context)
+            .stream()
+            .collect()
+            .await;
+        assert!(matches!(
+            &results[..],
+            [Err(InterpretError {
+                kind: InterpretErrorKind::NotFound(name),
+                ..
+            })] if name == "foo"
+        ));
+        t.async_verify().await;
+    }
+
+    #[tokio::test]
+    async fn unknown_functions_as_null_stubs() {
+        let mut t = TestBuilder::default().with_unknown_functions(UnknownFunctions::NullStub);
+        t.ok("foo(1, a: 2);", NaslValue::Null);
+        t.run("a = bar();");
+        t.run("foo();");
+        t.ok("a;", NaslValue::Null);
+        t.run("function baz() { return 1; }");
+        t.ok("baz();", 1);
+        t.async_verify().await;
+    }
+
     #[test]
     #[tracing_test::traced_test]
     fn multiple_forks() {
@@ -217,4 +269,33 @@ get_kb_item("port") + ":" + get_kb_item("host");
             vec!["1:a".into(), "2:a".into(), "1:b".into(), "2:b".into(),]
         );
     }
+
+    #[tokio::test]
+    async fn too_many_forks() {
+        let code = r#"
+for (i = 0; i < 33; i++) {
+    set_kb_item(name: "port", value: i);
+    set_kb_item(name: "host", value: i);
+}
+a = get_kb_item("port");
+b = get_kb_item("host");
+"#;
+        let t = TestBuilder::default();
+        let context = t.context();
+        let results: Vec<_> = CodeInterpreter::new(code, Register::default(), &context)
+            .stream()
+            .collect()
+            .await;
+        // 33 runs are fine, 33 * 33 are not
+        assert_eq!(results.len(), 1 + 33 + 1);
+        assert!(results[..34].iter().all(|x| x.is_ok()));
+        assert!(matches!(
+            &results[34],
+            Err(InterpretError {
+                kind: InterpretErrorKind::TooManyForks(1089),
+                ..
+            })
+        ));
+        t.async_verify().await;
+    }
 }
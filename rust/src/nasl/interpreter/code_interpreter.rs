@@ -10,8 +10,31 @@ use futures::{stream, Stream};
 use crate::nasl::syntax::{Lexer, Statement, Tokenizer};
 
 use crate::nasl::interpreter::interpreter::{InterpretResult, Interpreter};
+use crate::nasl::interpreter::{InterpretError, InterpretErrorKind};
 use crate::nasl::prelude::*;
 
+/// The result of a script which ran to its end or called `exit`.
+#[derive(Debug)]
+pub struct ScriptOutcome {
+    /// `NaslValue::Exit` with the code given to `exit` or, when the script ran to its end, the
+    /// value of the last statement.
+    pub exit_value: NaslValue,
+    /// Errors of builtin functions which did not abort the script, in the order they occurred.
+    pub warnings: Vec<FnError>,
+}
+
+/// The error which aborted a script.
+#[derive(Debug, thiserror::Error)]
+#[error("{error}")]
+pub struct ScriptError {
+    /// The error which aborted the script.
+    #[source]
+    pub error: InterpretError,
+    /// Errors of builtin functions which did not abort the script before, see
+    /// [`ScriptOutcome::warnings`].
+    pub warnings: Vec<FnError>,
+}
+
 /// Uses given code to return results based on that.
 pub struct CodeInterpreter<'a, 'b> {
     lexer: Lexer<'b>,
@@ -71,6 +94,44 @@ impl<'a, 'b> CodeInterpreter<'a, 'b> {
         }))
     }
 
+    /// Runs the script until it ends or calls `exit`.
+    ///
+    /// Errors of builtin functions which define a return value do not abort the script. Those
+    /// marked as [`Recoverable`] are replaced by their value within the statement, for all others
+    /// the statement evaluates to that value. Both are collected into
+    /// [`ScriptOutcome::warnings`] together with the warnings reported by builtins via
+    /// [`Context::warn`]. Every other error aborts the script and is returned as [`ScriptError`]
+    /// along with the warnings collected until then.
+    pub async fn run(mut self) -> Result<ScriptOutcome, ScriptError> {
+        let mut exit_value = NaslValue::Null;
+        let mut warnings = vec![];
+        while let Some(result) = self.next_().await {
+            warnings.extend(self.interpreter.ctxconfigs.take_warnings());
+            let value = match result {
+                Ok(value) => value,
+                Err(e) => match e.return_value().cloned() {
+                    Some(value) => {
+                        tracing::warn!("{e}");
+                        if let InterpretErrorKind::FunctionCallError(fe) = e.kind {
+                            warnings.push(fe.kind);
+                        }
+                        value
+                    }
+                    None => return Err(ScriptError { error: e, warnings }),
+                },
+            };
+            if let NaslValue::Exit(rc) = value {
+                exit_value = NaslValue::Exit(rc);
+                break;
+            }
+            exit_value = value;
+        }
+        Ok(ScriptOutcome {
+            exit_value,
+            warnings,
+        })
+    }
+
     /// Blocks on the results of the stream.
     #[cfg(test)]
     pub fn iter_blocking(self) -> impl Iterator<Item = InterpretResult> + 'b
@@ -90,7 +151,13 @@ impl<'a, 'b> CodeInterpreter<'a, 'b> {
 
 #[cfg(test)]
 mod tests {
+    use super::{ScriptError, ScriptOutcome};
+    use crate::nasl::builtin::KBError;
+    use crate::nasl::interpreter::{CodeInterpreter, InterpretErrorKind};
     use crate::nasl::test_prelude::*;
+    use crate::nasl::utils::UnknownFunctions;
+    use crate::nasl::{nasl_std_functions, NoOpLoader};
+    use crate::storage::{ContextKey, DefaultDispatcher, StorageError};
 
     #[test]
     fn code_interpreter() {
@@ -98,4 +165,192 @@ mod tests {
         check_code_result(r#"set_kb_item(name: "test", value: 2);"#, NaslValue::Null);
         check_code_result(r#"display(get_kb_item("test"));"#, NaslValue::Null);
     }
+
+    fn run(code: &str) -> Result<ScriptOutcome, ScriptError> {
+        run_with(ContextFactory::default(), code)
+    }
+
+    fn run_with(
+        context_factory: ContextFactory<NoOpLoader, DefaultDispatcher>,
+        code: &str,
+    ) -> Result<ScriptOutcome, ScriptError> {
+        let context = context_factory.build(ContextKey::default());
+        let interpreter = CodeInterpreter::new(code, Register::root_initial(&[]), &context);
+        futures::executor::block_on(interpreter.run())
+    }
+
+    #[nasl_function]
+    fn fragile(x: i64) -> Result<i64, FnError> {
+        Err(
+            ArgumentError::WrongArgument(format!("fragile failed, {x} is returned anyway."))
+                .with(ReturnValue(x))
+                .with(Recoverable),
+        )
+    }
+
+    struct Fragile;
+
+    function_set! {
+        Fragile,
+        (fragile)
+    }
+
+    fn with_fragile() -> ContextFactory<NoOpLoader, DefaultDispatcher> {
+        let mut functions = nasl_std_functions();
+        functions.add_set(Fragile);
+        ContextFactory::default().functions(functions)
+    }
+
+    #[test]
+    fn run_collects_warnings() {
+        let outcome = run_with(with_fragile(), "a = fragile(1); a = a + 1; exit(a);").unwrap();
+        assert_eq!(outcome.exit_value, NaslValue::Exit(2));
+        assert_eq!(outcome.warnings.len(), 1);
+        assert!(matches!(
+            outcome.warnings[0].kind,
+            FnErrorKind::Argument(ArgumentError::WrongArgument(_))
+        ));
+    }
+
+    #[nasl_function]
+    fn brittle(x: i64) -> Result<i64, BuiltinError> {
+        Err(BuiltinError::KB(KBError::ItemNotFound(format!(
+            "brittle/{x}"
+        ))))
+    }
+
+    struct Brittle;
+
+    function_set! {
+        Brittle,
+        (brittle)
+    }
+
+    #[test]
+    fn run_continues_after_errors_with_a_return_value() {
+        // Builtin errors return `NULL` but are not marked as recoverable, the statement
+        // evaluates to that value and the script continues.
+        let mut functions = nasl_std_functions();
+        functions.add_set(Brittle);
+        let factory = ContextFactory::default().functions(functions);
+        let outcome = run_with(factory, "a = 1; brittle(a); a = a + 1; exit(a);").unwrap();
+        assert_eq!(outcome.exit_value, NaslValue::Exit(2));
+        assert_eq!(outcome.warnings.len(), 1);
+        assert!(matches!(
+            outcome.warnings[0].kind,
+            FnErrorKind::Builtin(BuiltinError::KB(_))
+        ));
+        let outcome = run("a = 1; http2_close_handle(handle: 42); exit(a);").unwrap();
+        assert_eq!(outcome.exit_value, NaslValue::Exit(1));
+        assert_eq!(outcome.warnings.len(), 1);
+    }
+
+    #[nasl_function]
+    fn deprecated(context: &Context, x: i64) -> i64 {
+        context.warn(ArgumentError::WrongArgument(format!(
+            "deprecated is deprecated, {x} is returned anyway."
+        )));
+        x
+    }
+
+    struct Deprecated;
+
+    function_set! {
+        Deprecated,
+        (deprecated)
+    }
+
+    #[test]
+    fn run_collects_warnings_of_builtins() {
+        let mut functions = nasl_std_functions();
+        functions.add_set(Deprecated);
+        functions.add_set(Fragile);
+        let factory = ContextFactory::default().functions(functions);
+        let outcome = run_with(
+            factory,
+            "a = deprecated(1); b = deprecated(a + 1); c = fragile(3); exit(a + b + c);",
+        )
+        .unwrap();
+        assert_eq!(outcome.exit_value, NaslValue::Exit(6));
+        let warnings: Vec<_> = outcome.warnings.iter().map(|w| w.to_string()).collect();
+        assert_eq!(warnings.len(), 3);
+        assert!(warnings[0].contains("1 is returned"));
+        assert!(warnings[1].contains("2 is returned"));
+        assert!(warnings[2].contains("3 is returned"));
+    }
+
+    #[test]
+    fn run_warns_once_per_unknown_function() {
+        let outcome = run_with(
+            ContextFactory::default().unknown_functions(UnknownFunctions::NullStub),
+            "a = foo(); foo(); bar(); exit(1);",
+        )
+        .unwrap();
+        assert_eq!(outcome.exit_value, NaslValue::Exit(1));
+        let names: Vec<_> = outcome
+            .warnings
+            .iter()
+            .map(|w| match &w.kind {
+                FnErrorKind::Internal(InternalError::UnknownFunction(name)) => name.as_str(),
+                kind => panic!("unexpected warning {kind:?}"),
+            })
+            .collect();
+        assert_eq!(names, vec!["foo", "bar"]);
+    }
+
+    #[test]
+    fn run_without_exit() {
+        let outcome = run("a = 40; a + 2;").unwrap();
+        assert_eq!(outcome.exit_value, NaslValue::Number(42));
+        assert!(outcome.warnings.is_empty());
+        let outcome = run("exit(0); a = 1;").unwrap();
+        assert_eq!(outcome.exit_value, NaslValue::Exit(0));
+    }
+
+    #[nasl_function]
+    fn flaky_storage() -> Result<(), StorageError> {
+        Err(StorageError::Retry("storage is busy".to_string()))
+    }
+
+    struct FlakyStorage;
+
+    function_set! {
+        FlakyStorage,
+        (flaky_storage)
+    }
+
+    #[test]
+    fn retryable_storage_errors_stay_retryable() {
+        let mut functions = nasl_std_functions();
+        functions.add_set(FlakyStorage);
+        let factory = ContextFactory::default().functions(functions);
+        let err = run_with(factory, "a = 1; flaky_storage(); exit(1);")
+            .unwrap_err()
+            .error;
+        assert!(matches!(err.kind, InterpretErrorKind::FunctionCallError(_)));
+        assert!(err.retryable());
+        assert_eq!(err.return_value(), None);
+        let err = run("a = 1; jwt_decode(); exit(1);").unwrap_err().error;
+        assert!(!err.retryable());
+    }
+
+    #[test]
+    fn run_aborts_on_errors() {
+        let err = run("a = 1; jwt_decode(); exit(1);").unwrap_err().error;
+        assert!(matches!(err.kind, InterpretErrorKind::FunctionCallError(_)));
+    }
+
+    #[test]
+    fn run_keeps_the_warnings_of_aborted_scripts() {
+        let mut functions = nasl_std_functions();
+        functions.add_set(Deprecated);
+        let factory = ContextFactory::default().functions(functions);
+        let err = run_with(factory, "a = deprecated(1); jwt_decode(); exit(a);").unwrap_err();
+        assert!(matches!(
+            err.error.kind,
+            InterpretErrorKind::FunctionCallError(_)
+        ));
+        assert_eq!(err.warnings.len(), 1);
+        assert!(err.warnings[0].to_string().contains("1 is returned"));
+    }
 }
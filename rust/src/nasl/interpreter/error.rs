@@ -3,10 +3,13 @@
 // SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
 
 use crate::nasl::syntax::LoadError;
+use crate::nasl::syntax::NaslValue;
 use crate::nasl::syntax::{Statement, SyntaxError, TokenCategory};
-use crate::nasl::utils::error::FnError;
+use crate::nasl::utils::error::{FnError, ReturnBehavior};
 use thiserror::Error;
 
+use super::interpreter::MAX_FORKS;
+
 #[derive(Debug, Error)]
 /// An error that occurred while calling a function
 #[error("Error while calling function '{function}': {kind}")]
@@ -61,6 +64,17 @@ impl InterpretError {
             _ => false,
         }
     }
+
+    /// Returns the value a failed function call returns instead of aborting the script.
+    pub fn return_value(&self) -> Option<&NaslValue> {
+        match &self.kind {
+            InterpretErrorKind::FunctionCallError(e) => match e.kind.return_behavior() {
+                ReturnBehavior::ReturnValue(value) => Some(value),
+                ReturnBehavior::ExitScript => None,
+            },
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Error)]
@@ -101,6 +115,9 @@ pub enum InterpretErrorKind {
     /// An error occurred while calling a built-in function.
     #[error("{0}")]
     FunctionCallError(FunctionCallError),
+    /// A forking value would create more runs than allowed.
+    #[error("Forking would create {0} runs, but at most {MAX_FORKS} are allowed.")]
+    TooManyForks(usize),
 }
 
 impl InterpretError {
@@ -194,6 +211,11 @@ impl InterpretError {
     pub fn unparse_regex(rx: &str) -> Self {
         Self::new(InterpretErrorKind::InvalidRegex(rx.to_owned()), None)
     }
+
+    /// When forking would exceed [`MAX_FORKS`]
+    pub fn too_many_forks(runs: usize) -> Self {
+        Self::new(InterpretErrorKind::TooManyForks(runs), None)
+    }
 }
 
 impl From<TokenCategory> for InterpretError {
@@ -0,0 +1,29 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+use crate::nasl::{ContextFactory, NaslValue, RegisterBuilder};
+use crate::storage::ContextKey;
+
+use super::{CodeInterpreter, InterpretError};
+
+/// Evaluates NASL source code with the standard library and returns the value of the last
+/// statement.
+///
+/// This is meant for embedders that only need to run a snippet, e.g. an online editor checking
+/// the syntax of a script. It uses an in memory storage and no loader, therefore `include` is not
+/// available. The first error aborts the evaluation.
+///
+/// Builtins rely on the tokio runtime, so synchronous callers have to drive the returned future
+/// with a tokio runtime instead of a plain executor.
+pub async fn eval(source: &str) -> Result<NaslValue, InterpretError> {
+    let context_factory = ContextFactory::default();
+    let context = context_factory.build(ContextKey::default());
+    let register = RegisterBuilder::build();
+    let mut interpreter = CodeInterpreter::new(source, register, &context);
+    let mut last = NaslValue::Null;
+    while let Some(result) = interpreter.next_statement().await {
+        last = result?;
+    }
+    Ok(last)
+}
@@ -4,12 +4,15 @@
 
 #[cfg(test)]
 mod tests {
-    use std::{collections::HashMap, string::String};
+    use std::{
+        collections::{BTreeMap, HashMap},
+        string::String,
+    };
 
     use crate::nasl::interpreter::CodeInterpreter;
     use crate::nasl::{syntax::LoadError, Loader};
 
-    use crate::nasl::{nasl_std_functions, prelude::*};
+    use crate::nasl::prelude::*;
     use crate::storage::DefaultDispatcher;
 
     struct FakeInclude {
@@ -46,11 +49,7 @@ mod tests {
         test();
         "#;
         let register = Register::default();
-        let context = ContextFactory {
-            loader,
-            functions: nasl_std_functions(),
-            storage: DefaultDispatcher::default(),
-        };
+        let context = ContextFactory::new(loader, DefaultDispatcher::default());
         let ctx = context.build(Default::default());
         let mut interpreter = CodeInterpreter::new(code, register, &ctx);
         assert_eq!(
@@ -63,7 +62,7 @@ mod tests {
         );
         assert_eq!(
             interpreter.next_statement().await.unwrap().unwrap(),
-            NaslValue::Dict(HashMap::from([(
+            NaslValue::Dict(BTreeMap::from([(
                 "hello".to_owned(),
                 NaslValue::Data("world".as_bytes().into())
             )]))
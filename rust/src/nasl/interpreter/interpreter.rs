@@ -72,6 +72,12 @@ impl Position {
     }
 }
 
+/// The maximum number of runs forking values may create.
+///
+/// Each forking value multiplies the number of runs by its amount of values, so a few of them
+/// within one script quickly grow out of hand.
+pub const MAX_FORKS: usize = 1024;
+
 /// Contains data that is specific for a single run
 ///
 /// Some methods start multiple runs (e.g. get_kb_item) and need to have their own specific data to
@@ -394,7 +400,7 @@ impl<'a> Interpreter<'a> {
             let val = Box::pin(self.resolve(stmt)).await?;
             result.push(val);
         }
-        Ok(NaslValue::Array(result))
+        Ok(NaslValue::from(result))
     }
 
     async fn resolve_if(
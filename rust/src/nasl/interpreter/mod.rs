@@ -10,6 +10,7 @@ mod assign;
 mod call;
 mod code_interpreter;
 mod declare;
+mod eval;
 mod include;
 #[allow(clippy::module_inception)]
 mod interpreter;
@@ -23,4 +24,6 @@ pub use code_interpreter::*;
 pub use error::FunctionCallError;
 pub use error::InterpretError;
 pub use error::InterpretErrorKind;
+pub use eval::eval;
 pub use interpreter::Interpreter;
+pub use interpreter::MAX_FORKS;
@@ -0,0 +1,114 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+use std::sync::{Arc, Mutex};
+
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::Subscriber;
+use tracing_subscriber::layer::{Context as LayerContext, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+use crate::nasl::test_prelude::*;
+
+#[derive(Debug, Default, Clone)]
+struct CapturedSpan {
+    name: String,
+    fields: Vec<(String, String)>,
+}
+
+impl Visit for CapturedSpan {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.fields
+            .push((field.name().to_owned(), format!("{value:?}")));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.fields
+            .push((field.name().to_owned(), value.to_owned()));
+    }
+}
+
+impl CapturedSpan {
+    fn field(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Stores every `builtin` span when it is closed.
+#[derive(Default, Clone)]
+struct SpanCapture {
+    open: Arc<Mutex<Vec<(Id, CapturedSpan)>>>,
+    closed: Arc<Mutex<Vec<CapturedSpan>>>,
+}
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for SpanCapture {
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, _: LayerContext<'_, S>) {
+        if attrs.metadata().name() != "builtin" {
+            return;
+        }
+        let mut span = CapturedSpan {
+            name: attrs.metadata().name().to_owned(),
+            ..Default::default()
+        };
+        attrs.record(&mut span);
+        self.open.lock().unwrap().push((id.clone(), span));
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, _: LayerContext<'_, S>) {
+        let mut open = self.open.lock().unwrap();
+        if let Some((_, span)) = open.iter_mut().find(|(x, _)| x == id) {
+            values.record(span);
+        }
+    }
+
+    fn on_close(&self, id: Id, _: LayerContext<'_, S>) {
+        let mut open = self.open.lock().unwrap();
+        if let Some(idx) = open.iter().position(|(x, _)| x == &id) {
+            let (_, span) = open.remove(idx);
+            self.closed.lock().unwrap().push(span);
+        }
+    }
+}
+
+#[test]
+fn builtin_calls_are_traced() {
+    let capture = SpanCapture::default();
+    let subscriber = tracing_subscriber::registry().with(capture.clone());
+    tracing::subscriber::with_default(subscriber, || {
+        let mut t = TestBuilder::default();
+        t.ok(r#"strlen("abc");"#, 3);
+        t.ok(r#"hexstr(raw_string(0x01));"#, "01");
+    });
+    let spans = capture.closed.lock().unwrap().clone();
+    let strlen = spans
+        .iter()
+        .find(|x| x.field("name") == Some("strlen"))
+        .expect("strlen should be traced");
+    assert_eq!(strlen.name, "builtin");
+    assert_eq!(strlen.field("positional"), Some("1"));
+    assert_eq!(strlen.field("named"), Some("0"));
+    assert!(strlen.field("elapsed_us").is_some());
+    assert!(spans.iter().any(|x| x.field("name") == Some("raw_string")));
+    assert!(spans.iter().any(|x| x.field("name") == Some("hexstr")));
+}
+
+#[test]
+#[tracing_test::traced_test]
+fn builtin_errors_emit_event() {
+    let mut t = TestBuilder::default();
+    t.check(
+        "substr();",
+        |r| r.is_err(),
+        Some("substr without arguments"),
+    );
+    // the lines are only run when the builder is dropped
+    drop(t);
+    assert!(logs_contain("builtin returned an error"));
+    assert!(logs_contain("name=\"substr\""));
+}
@@ -0,0 +1,31 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+//! Tests the eval entry point
+
+use crate::nasl::interpreter::{eval, InterpretErrorKind};
+use crate::nasl::NaslValue;
+
+#[tokio::test]
+async fn returns_last_value() {
+    assert_eq!(
+        eval("a = 1; b = a + 41; b;").await.unwrap(),
+        NaslValue::Number(42)
+    );
+    assert_eq!(eval("").await.unwrap(), NaslValue::Null);
+}
+
+#[tokio::test]
+async fn uses_builtins() {
+    assert_eq!(
+        eval(r#"toupper("nasl");"#).await.unwrap(),
+        NaslValue::String("NASL".to_string())
+    );
+}
+
+#[tokio::test]
+async fn returns_syntax_error() {
+    let err = eval("if (1 { a = 1; }").await.unwrap_err();
+    assert!(matches!(err.kind, InterpretErrorKind::SyntaxError(_)));
+}
@@ -2,6 +2,8 @@
 //
 // SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
 
+mod builtin_spans;
 mod description;
+mod eval;
 mod local_var;
 mod retry;
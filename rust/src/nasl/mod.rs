@@ -23,11 +23,13 @@ pub mod prelude {
     pub use super::syntax::Loader;
     pub use super::syntax::NaslValue;
     pub use super::utils::error::FnErrorKind;
+    pub use super::utils::error::Recoverable;
     pub use super::utils::error::Retryable;
     pub use super::utils::error::ReturnValue;
     pub use super::utils::error::WithErrorInfo;
     pub use super::utils::function::CheckedPositionals;
     pub use super::utils::function::FromNaslValue;
+    pub use super::utils::function::NaslArgs;
     pub use super::utils::function::Positionals;
     pub use super::utils::function::ToNaslResult;
     pub use super::utils::ArgumentError;
@@ -39,11 +41,19 @@ pub mod prelude {
     pub use super::utils::Register;
     pub use crate::function_set;
     pub use nasl_function_proc_macro::nasl_function;
+    pub use nasl_function_proc_macro::NaslArgs;
 }
 
 pub use prelude::*;
 
-pub use builtin::nasl_std_functions;
+#[cfg(feature = "test-support")]
+pub use builtin::ssh_test_utils;
+pub use builtin::{available_functions, function_signature, has_function, nasl_std_functions};
+pub use builtin::{BuiltinRegistry, RegistryError};
+pub use builtin::{Passwords, DEFAULT_COMMON_PASSWORDS};
+
+pub use utils::function::{FnSignature, NamedArgSignature};
+pub use utils::NaslFunction;
 
 pub use syntax::NoOpLoader;
 
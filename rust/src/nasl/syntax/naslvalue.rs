@@ -2,8 +2,14 @@
 //
 // SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
 
-use std::{cmp::Ordering, collections::HashMap, fmt::Display};
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap},
+    fmt::Display,
+    sync::Arc,
+};
 
+use crate::nasl::utils::function::str_to_bytes;
 use crate::storage::types::Primitive;
 
 use super::{IdentifierType, Token, TokenCategory, ACT};
@@ -18,9 +24,15 @@ pub enum NaslValue {
     /// Number value
     Number(i64),
     /// Array value
-    Array(Vec<NaslValue>),
+    ///
+    /// The elements are shared, so cloning an array is cheap. Modifying an array takes the
+    /// elements out via [`Arc::unwrap_or_clone`], which clones them if they are still shared, so a
+    /// script never observes a change of another binding.
+    Array(Arc<Vec<NaslValue>>),
     /// Array value
-    Dict(HashMap<String, NaslValue>),
+    ///
+    /// The keys are kept sorted so that iterating, printing and storing a dict is deterministic.
+    Dict(BTreeMap<String, NaslValue>),
     /// Boolean value
     Boolean(bool),
     /// Attack category keyword
@@ -62,7 +74,12 @@ impl NaslValue {
             Self::String(s) => String(s),
             Self::Data(x) => Data(x),
             Self::Number(x) => Number(x),
-            Self::Array(x) => Array(x.into_iter().map(|x| x.as_primitive()).collect()),
+            Self::Array(x) => Array(
+                Arc::unwrap_or_clone(x)
+                    .into_iter()
+                    .map(|x| x.as_primitive())
+                    .collect(),
+            ),
             Self::Dict(x) => Dict(x.into_iter().map(|(k, v)| (k, v.as_primitive())).collect()),
             Self::Boolean(x) => Boolean(x),
             _ => Null,
@@ -70,17 +87,123 @@ impl NaslValue {
     }
 }
 
+/// The type of a value as seen by a script, see [`NaslValue::value_type`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum NaslValueType {
+    /// `NULL` or an undefined variable
+    Undef,
+    /// Numbers and booleans
+    Int,
+    /// String value
+    String,
+    /// Data value
+    Data,
+    /// Lists as well as dicts
+    Array,
+    /// Internal values that are not visible to a script
+    Unknown,
+}
+
+impl NaslValueType {
+    /// The name of the type as returned by `typeof`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            NaslValueType::Undef => "undef",
+            NaslValueType::Int => "int",
+            NaslValueType::String => "string",
+            NaslValueType::Data => "data",
+            NaslValueType::Array => "array",
+            NaslValueType::Unknown => "unknown",
+        }
+    }
+}
+
+impl Display for NaslValueType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl NaslValue {
+    /// Returns the type of the value.
+    pub fn value_type(&self) -> NaslValueType {
+        match self {
+            NaslValue::Null => NaslValueType::Undef,
+            NaslValue::String(_) => NaslValueType::String,
+            NaslValue::Data(_) => NaslValueType::Data,
+            NaslValue::Number(_) | NaslValue::Boolean(_) => NaslValueType::Int,
+            NaslValue::Array(_) | NaslValue::Dict(_) => NaslValueType::Array,
+            _ => NaslValueType::Unknown,
+        }
+    }
+}
+
 impl PartialOrd for NaslValue {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
 }
 
+impl NaslValue {
+    /// The position of the kind of value in the ordering of `NaslValue`.
+    fn order_rank(&self) -> u8 {
+        match self {
+            NaslValue::Null => 0,
+            NaslValue::Boolean(_) | NaslValue::Number(_) => 1,
+            NaslValue::String(_) | NaslValue::Data(_) => 2,
+            NaslValue::Array(_) => 3,
+            NaslValue::Dict(_) => 4,
+            NaslValue::AttackCategory(_) => 5,
+            NaslValue::Fork(_) => 6,
+            NaslValue::Return(_) => 7,
+            NaslValue::Continue => 8,
+            NaslValue::Break => 9,
+            NaslValue::Exit(_) => 10,
+        }
+    }
+
+    /// Breaks ties between values of different variants that compare equal otherwise.
+    fn variant_rank(&self) -> u8 {
+        match self {
+            NaslValue::Boolean(_) | NaslValue::String(_) => 0,
+            _ => 1,
+        }
+    }
+}
+
+/// A total order over all values, as used by `sort`.
+///
+/// NULL comes first, followed by numbers and booleans ordered numerically, strings and data
+/// ordered by their bytes, arrays ordered element wise and dicts ordered by their sorted entries.
+/// The values only used within the interpreter follow in the order attack categories, forks,
+/// return values, continue, break and exit values.
+/// Values of different variants that would be equal, such as `TRUE` and `1`, are ordered by
+/// their variant so that the order stays consistent with equality.
 impl Ord for NaslValue {
     fn cmp(&self, other: &Self) -> Ordering {
-        let a: Vec<u8> = self.into();
-        let b: Vec<u8> = other.into();
-        a.cmp(&b)
+        let ordering =
+            self.order_rank()
+                .cmp(&other.order_rank())
+                .then_with(|| match (self, other) {
+                    (NaslValue::Null, NaslValue::Null) => Ordering::Equal,
+                    (
+                        NaslValue::Boolean(_) | NaslValue::Number(_),
+                        NaslValue::Boolean(_) | NaslValue::Number(_),
+                    ) => i64::from(self).cmp(&i64::from(other)),
+                    (
+                        NaslValue::String(_) | NaslValue::Data(_),
+                        NaslValue::String(_) | NaslValue::Data(_),
+                    ) => Vec::<u8>::from(self).cmp(&Vec::<u8>::from(other)),
+                    (NaslValue::Array(a), NaslValue::Array(b)) => a.cmp(b),
+                    (NaslValue::Dict(a), NaslValue::Dict(b)) => a.cmp(b),
+                    (NaslValue::AttackCategory(a), NaslValue::AttackCategory(b)) => a.cmp(b),
+                    (NaslValue::Fork(a), NaslValue::Fork(b)) => a.cmp(b),
+                    (NaslValue::Return(a), NaslValue::Return(b)) => a.cmp(b),
+                    (NaslValue::Exit(a), NaslValue::Exit(b)) => a.cmp(b),
+                    // Same rank without content, i.e. Continue or Break on both sides.
+                    _ => Ordering::Equal,
+                });
+        ordering.then_with(|| self.variant_rank().cmp(&other.variant_rank()))
     }
 }
 
@@ -178,8 +301,20 @@ impl From<usize> for NaslValue {
     }
 }
 
+impl From<Vec<NaslValue>> for NaslValue {
+    fn from(x: Vec<NaslValue>) -> Self {
+        Self::Array(Arc::new(x))
+    }
+}
+
 impl From<HashMap<String, NaslValue>> for NaslValue {
     fn from(x: HashMap<String, NaslValue>) -> Self {
+        NaslValue::Dict(x.into_iter().collect())
+    }
+}
+
+impl From<BTreeMap<String, NaslValue>> for NaslValue {
+    fn from(x: BTreeMap<String, NaslValue>) -> Self {
         NaslValue::Dict(x)
     }
 }
@@ -250,7 +385,7 @@ impl From<&NaslValue> for i64 {
 impl From<&NaslValue> for Vec<u8> {
     fn from(value: &NaslValue) -> Vec<u8> {
         match value {
-            NaslValue::String(x) => x.as_bytes().to_vec(),
+            NaslValue::String(x) => str_to_bytes(x).into_owned(),
             &NaslValue::Number(x) => x.to_ne_bytes().to_vec(),
             NaslValue::Data(x) => x.to_vec(),
             _ => Vec::new(),
@@ -289,7 +424,7 @@ impl TryFrom<&Token> for NaslValue {
 impl From<NaslValue> for Vec<NaslValue> {
     fn from(value: NaslValue) -> Self {
         match value {
-            NaslValue::Array(ret) => ret,
+            NaslValue::Array(ret) => Arc::unwrap_or_clone(ret),
             NaslValue::Dict(ret) => ret.values().cloned().collect(),
             NaslValue::Boolean(_) | NaslValue::Number(_) => vec![value],
             NaslValue::Data(ret) => ret.into_iter().map(|x| NaslValue::Data(vec![x])).collect(),
@@ -309,7 +444,7 @@ impl From<Primitive> for NaslValue {
             String(x) => Self::String(x),
             Data(x) => Self::Data(x),
             Number(x) => Self::Number(x),
-            Array(x) => Self::Array(x.into_iter().map(Self::from).collect()),
+            Array(x) => Self::from(x.into_iter().map(Self::from).collect::<Vec<_>>()),
             Dict(x) => Self::Dict(x.into_iter().map(|(k, v)| (k, Self::from(v))).collect()),
             Boolean(x) => Self::Boolean(x),
             Null => Self::Null,
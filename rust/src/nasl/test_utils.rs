@@ -7,6 +7,8 @@
 use std::{
     fmt::{self, Display, Formatter},
     panic::Location,
+    sync::Arc,
+    time::Instant,
 };
 
 use crate::storage::ContextKey;
@@ -22,7 +24,7 @@ use futures::{Stream, StreamExt};
 use super::{
     builtin::ContextFactory,
     interpreter::{CodeInterpreter, InterpretErrorKind},
-    utils::Executor,
+    utils::{Clock, Executor, NetworkMode, UnknownFunctions},
 };
 
 // The following exists to trick the trait solver into
@@ -369,6 +371,31 @@ where
         self
     }
 
+    /// Return a new `TestBuilder` whose execution ends at the given deadline.
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.context.deadline = Some(deadline);
+        self
+    }
+
+    /// Return a new `TestBuilder` whose time related builtins use the given clock.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.context.clock = clock;
+        self
+    }
+
+    /// Return a new `TestBuilder` whose network builtins use the given mode, e.g. to replay
+    /// recorded interactions.
+    pub fn with_network_mode(mut self, network_mode: NetworkMode) -> Self {
+        self.context.network_mode = Arc::new(network_mode);
+        self
+    }
+
+    /// Return a new `TestBuilder` which handles calls of unknown functions as given.
+    pub fn with_unknown_functions(mut self, unknown_functions: UnknownFunctions) -> Self {
+        self.context.unknown_functions = unknown_functions;
+        self
+    }
+
     /// Set the variable with name `arg` to the given `value`
     pub fn set_variable(&mut self, arg: &str, value: NaslValue) {
         self.variables.push((arg.to_string(), value));
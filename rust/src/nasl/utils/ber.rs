@@ -0,0 +1,74 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+//! Minimal BER encoding and decoding of single elements, shared by the builtins which speak
+//! ASN.1 based protocols. Only the definite length form is supported, which also makes the
+//! encoding valid DER.
+
+pub const INTEGER: u8 = 0x02;
+pub const OCTET_STRING: u8 = 0x04;
+pub const SEQUENCE: u8 = 0x30;
+
+/// Encodes a single element.
+pub fn encode(tag: u8, content: &[u8]) -> Vec<u8> {
+    let mut result = vec![tag];
+    if content.len() < 0x80 {
+        result.push(content.len() as u8);
+    } else {
+        let len = content.len().to_be_bytes();
+        let skip = len.iter().take_while(|x| **x == 0).count();
+        result.push(0x80 | (len.len() - skip) as u8);
+        result.extend(&len[skip..]);
+    }
+    result.extend(content);
+    result
+}
+
+/// Returns the minimal two's complement representation of `value` as INTEGER.
+pub fn encode_integer(value: i64) -> Vec<u8> {
+    let bytes = value.to_be_bytes();
+    let skip = bytes
+        .windows(2)
+        .take_while(|x| (x[0] == 0 && x[1] & 0x80 == 0) || (x[0] == 0xff && x[1] & 0x80 != 0))
+        .count();
+    encode(INTEGER, &bytes[skip..])
+}
+
+/// Splits the first element off `data` and returns its tag, its content and the remaining data.
+pub fn decode(data: &[u8]) -> Option<(u8, &[u8], &[u8])> {
+    let tag = *data.first()?;
+    let first = *data.get(1)?;
+    let (len, header) = if first & 0x80 == 0 {
+        (first as usize, 2)
+    } else {
+        let count = (first & 0x7f) as usize;
+        if count == 0 || count > std::mem::size_of::<usize>() {
+            return None;
+        }
+        let len = data
+            .get(2..2 + count)?
+            .iter()
+            .fold(0usize, |acc, x| (acc << 8) | *x as usize);
+        (len, 2 + count)
+    };
+    let end = header.checked_add(len)?;
+    Some((tag, data.get(header..end)?, &data[end..]))
+}
+
+/// Like [`decode`], but only accepts an element with the given tag.
+pub fn expect(data: &[u8], tag: u8) -> Option<(&[u8], &[u8])> {
+    match decode(data)? {
+        (found, content, rest) if found == tag => Some((content, rest)),
+        _ => None,
+    }
+}
+
+/// Decodes the content of an INTEGER which fits into an i64.
+pub fn decode_integer(content: &[u8]) -> Option<i64> {
+    if content.is_empty() || content.len() > 8 {
+        return None;
+    }
+    let init = if content[0] & 0x80 != 0 { -1 } else { 0 };
+    Some(content.iter().fold(init, |acc, x| (acc << 8) | *x as i64))
+}
@@ -0,0 +1,79 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+//! Defines the clock used by time related builtins
+
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use async_trait::async_trait;
+
+/// Source of the current time of a [`super::Context`].
+#[async_trait]
+pub trait Clock: Send + Sync {
+    /// Returns the current time.
+    fn now(&self) -> SystemTime;
+
+    /// Lets `duration` pass.
+    async fn sleep(&self, duration: Duration);
+}
+
+/// The clock of the operating system, used by default.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+#[async_trait]
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+
+    /// Waits without blocking the runtime, so other tasks keep progressing. Without a tokio
+    /// runtime the thread is blocked instead.
+    async fn sleep(&self, duration: Duration) {
+        if tokio::runtime::Handle::try_current().is_ok() {
+            tokio::time::sleep(duration).await;
+        } else {
+            thread::sleep(duration);
+        }
+    }
+}
+
+/// A clock which only changes when told to, so that time dependent results are deterministic.
+#[derive(Debug)]
+pub struct MockClock {
+    now: Mutex<SystemTime>,
+}
+
+impl MockClock {
+    /// Creates a clock standing still at `now`.
+    pub fn new(now: SystemTime) -> Self {
+        Self {
+            now: Mutex::new(now),
+        }
+    }
+
+    /// Sets the current time.
+    pub fn set(&self, now: SystemTime) {
+        *self.now.lock().unwrap() = now;
+    }
+
+    /// Moves the current time forward by `duration`.
+    pub fn advance(&self, duration: Duration) {
+        *self.now.lock().unwrap() += duration;
+    }
+}
+
+#[async_trait]
+impl Clock for MockClock {
+    fn now(&self) -> SystemTime {
+        *self.now.lock().unwrap()
+    }
+
+    /// Advances the clock instead of waiting.
+    async fn sleep(&self, duration: Duration) {
+        self.advance(duration);
+    }
+}
@@ -4,6 +4,8 @@
 
 //! Defines the context used within the interpreter and utilized by the builtin functions
 
+use std::time::Instant;
+
 use itertools::Itertools;
 
 use crate::nasl::builtin::KBError;
@@ -11,10 +13,12 @@ use crate::nasl::syntax::{Loader, NaslValue, Statement};
 use crate::nasl::{FromNaslValue, WithErrorInfo};
 use crate::storage::{ContextKey, Dispatcher, Field, Retrieve, Retriever};
 
+use super::clock::{Clock, SystemClock};
 use super::error::ReturnBehavior;
 use super::hosts::resolve;
-use super::FnError;
+use super::recording::NetworkMode;
 use super::{executor::Executor, lookup_keys::FC_ANON_ARGS};
+use super::{FnError, InternalError};
 
 /// Contexts are responsible to locate, add and delete everything that is declared within a NASL plugin
 ///
@@ -295,7 +299,7 @@ impl Default for Register {
         Self::new()
     }
 }
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::net::IpAddr;
 use std::str::FromStr;
 use std::sync::Mutex;
@@ -389,6 +393,20 @@ impl Default for Target {
         }
     }
 }
+/// Defines how the interpreter handles calls of functions which are neither builtins nor defined
+/// by the script.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UnknownFunctions {
+    /// Abort the script with an error.
+    #[default]
+    Error,
+    /// Return NULL and report a warning for the first call of each unknown function.
+    NullStub,
+}
+
+/// Default for [`Context::blocking_threshold`].
+pub const DEFAULT_BLOCKING_THRESHOLD: usize = 1024 * 1024;
+
 /// Configurations
 ///
 /// This struct includes all objects that a nasl function requires.
@@ -406,6 +424,20 @@ pub struct Context<'a> {
     loader: &'a dyn Loader,
     /// Default function executor.
     executor: &'a Executor,
+    /// Point in time at which the execution of the script should be aborted.
+    deadline: Option<Instant>,
+    /// Source of the current time for time related builtins.
+    clock: &'a dyn Clock,
+    /// Whether network builtins talk to the target or replay recorded interactions.
+    network_mode: &'a NetworkMode,
+    /// Number of input bytes from which hashes and HMACs are calculated on a blocking thread.
+    blocking_threshold: usize,
+    /// Problems reported by builtins which do not abort the script.
+    warnings: Mutex<Vec<FnError>>,
+    /// How calls of unknown functions are handled.
+    unknown_functions: UnknownFunctions,
+    /// The unknown functions which were reported as warning already.
+    reported_unknown_functions: Mutex<HashSet<String>>,
 }
 
 impl<'a> Context<'a> {
@@ -425,6 +457,13 @@ impl<'a> Context<'a> {
             retriever,
             loader,
             executor,
+            deadline: None,
+            clock: &SystemClock,
+            network_mode: &NetworkMode::Live,
+            blocking_threshold: DEFAULT_BLOCKING_THRESHOLD,
+            warnings: Mutex::default(),
+            unknown_functions: UnknownFunctions::default(),
+            reported_unknown_functions: Mutex::default(),
         }
     }
 
@@ -477,6 +516,86 @@ impl<'a> Context<'a> {
         self.target.add_hostname(hostname, source);
     }
 
+    /// Get the point in time at which the execution should be aborted, if any.
+    ///
+    /// Builtins which wait, like `sleep`, must not wait beyond it.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    pub fn set_deadline(&mut self, deadline: Option<Instant>) {
+        self.deadline = deadline;
+    }
+
+    /// Get the clock time related builtins have to use instead of the system time.
+    pub fn clock(&self) -> &dyn Clock {
+        self.clock
+    }
+
+    pub fn set_clock(&mut self, clock: &'a dyn Clock) {
+        self.clock = clock;
+    }
+
+    /// Get the mode network builtins have to pass their interactions with the target through, see
+    /// [`NetworkMode::interact`].
+    pub fn network_mode(&self) -> &NetworkMode {
+        self.network_mode
+    }
+
+    pub fn set_network_mode(&mut self, network_mode: &'a NetworkMode) {
+        self.network_mode = network_mode;
+    }
+
+    /// Get the number of input bytes from which hashes and HMACs are calculated on a blocking
+    /// thread, so they do not stall other tasks of the runtime like the network builtins.
+    pub fn blocking_threshold(&self) -> usize {
+        self.blocking_threshold
+    }
+
+    pub fn set_blocking_threshold(&mut self, bytes: usize) {
+        self.blocking_threshold = bytes;
+    }
+
+    /// Reports a problem which does not abort the script, e.g. the use of a deprecated argument.
+    ///
+    /// The warnings are collected into the `ScriptOutcome` of
+    /// [`crate::nasl::interpreter::CodeInterpreter::run`].
+    pub fn warn(&self, warning: impl Into<FnError>) {
+        let warning = warning.into();
+        tracing::warn!(key = %self.key, "{warning}");
+        self.warnings
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(warning);
+    }
+
+    /// Removes and returns the warnings reported so far.
+    pub fn take_warnings(&self) -> Vec<FnError> {
+        std::mem::take(&mut *self.warnings.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    /// Get how the interpreter handles calls of unknown functions.
+    pub fn unknown_functions(&self) -> UnknownFunctions {
+        self.unknown_functions
+    }
+
+    pub fn set_unknown_functions(&mut self, unknown_functions: UnknownFunctions) {
+        self.unknown_functions = unknown_functions;
+    }
+
+    /// Reports a warning for the call of the unknown function `name`, unless it was reported
+    /// before.
+    pub fn warn_unknown_function(&self, name: &str) {
+        let first_call = self
+            .reported_unknown_functions
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(name.to_owned());
+        if first_call {
+            self.warn(InternalError::UnknownFunction(name.to_owned()));
+        }
+    }
+
     /// Get the storage
     pub fn dispatcher(&self) -> &dyn Dispatcher {
         self.dispatcher
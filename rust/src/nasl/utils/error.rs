@@ -9,6 +9,8 @@ use crate::nasl::prelude::NaslValue;
 
 use crate::storage::StorageError;
 
+use super::recording::RecordingError;
+
 #[derive(Debug, Error)]
 #[error("{kind}")]
 pub struct FnError {
@@ -16,6 +18,7 @@ pub struct FnError {
     pub kind: FnErrorKind,
     return_behavior: ReturnBehavior,
     retryable: bool,
+    recoverable: bool,
 }
 
 #[derive(Debug)]
@@ -33,11 +36,27 @@ impl FnError {
         self.retryable
     }
 
+    /// Returns the value the failed call evaluates to if the error was marked as [`Recoverable`].
+    /// The script then goes on instead of being aborted.
+    pub fn recovered_value(&self) -> Option<&NaslValue> {
+        match &self.return_behavior {
+            ReturnBehavior::ReturnValue(value) if self.recoverable => Some(value),
+            _ => None,
+        }
+    }
+
     fn from_kind(kind: FnErrorKind) -> FnError {
+        // Internal errors know whether they are retryable, so the flag
+        // must not depend on the way they were converted.
+        let retryable = match &kind {
+            FnErrorKind::Internal(e) => e.retryable(),
+            _ => false,
+        };
         Self {
             kind,
             return_behavior: ReturnBehavior::ExitScript,
-            retryable: false,
+            retryable,
+            recoverable: false,
         }
     }
 }
@@ -60,18 +79,14 @@ impl From<BuiltinError> for FnError {
             kind: FnErrorKind::Builtin(kind),
             retryable: false,
             return_behavior: ReturnBehavior::ReturnValue(NaslValue::Null),
+            recoverable: false,
         }
     }
 }
 
 impl From<InternalError> for FnError {
     fn from(kind: InternalError) -> Self {
-        let retryable = kind.retryable();
-        Self {
-            kind: FnErrorKind::Internal(kind),
-            retryable,
-            return_behavior: ReturnBehavior::ExitScript,
-        }
+        FnError::from_kind(FnErrorKind::Internal(kind))
     }
 }
 
@@ -97,12 +112,21 @@ pub enum ArgumentError {
     UnexpectedArgument(String),
     #[error("Wrong arguments given: {0}")]
     WrongArgument(String),
+    /// Several problems with the arguments of a single call.
+    #[error("{}", .0.iter().map(|e| e.to_string()).collect::<Vec<_>>().join(" "))]
+    Multiple(Vec<ArgumentError>),
 }
 
 #[derive(Debug, Clone, PartialEq, Error)]
 pub enum InternalError {
     #[error("{0}")]
     Storage(#[from] StorageError),
+    #[error("{0}")]
+    Recording(#[from] RecordingError),
+    #[error("The function {0} is not defined, NULL is returned instead.")]
+    UnknownFunction(String),
+    #[error("No source of random data is available.")]
+    NoRandomness,
 }
 
 impl InternalError {
@@ -113,6 +137,9 @@ impl InternalError {
         match self {
             InternalError::Storage(StorageError::Retry(_)) => true,
             InternalError::Storage(_) => false,
+            InternalError::Recording(_) => false,
+            InternalError::UnknownFunction(_) => false,
+            InternalError::NoRandomness => false,
         }
     }
 }
@@ -173,6 +200,20 @@ impl<E: Into<FnError>> WithErrorInfo<Retryable> for E {
     }
 }
 
+/// Marks an error as recoverable: the interpreter reports it as a warning and the call evaluates
+/// to the value given via [`ReturnValue`] instead of aborting the script.
+pub struct Recoverable;
+
+impl<E: Into<FnError>> WithErrorInfo<Recoverable> for E {
+    type Error = FnError;
+
+    fn with(self, _: Recoverable) -> Self::Error {
+        let mut e = self.into();
+        e.recoverable = true;
+        e
+    }
+}
+
 pub struct ReturnValue<T>(pub T);
 
 impl<T: Into<NaslValue>, E: Into<FnError>> WithErrorInfo<ReturnValue<T>> for E {
@@ -19,13 +19,17 @@
 mod nasl_function;
 
 use std::collections::HashMap;
+use std::time::Instant;
 
 use async_trait::async_trait;
 pub use nasl_function::NaslFunction;
 use nasl_function::{AsyncDoubleArgFn, AsyncTripleArgFn};
 use tokio::sync::RwLock;
+use tracing::Instrument;
 
 use crate::nasl::prelude::*;
+use crate::nasl::utils::function::FnSignature;
+use crate::nasl::utils::lookup_keys::FC_ANON_ARGS;
 
 #[derive(Default)]
 /// The executor. This is the main outward facing type of this module
@@ -56,6 +60,15 @@ impl Executor {
         self
     }
 
+    /// Adds a set that is consulted before all previously added sets.
+    pub fn prepend_set<S: IntoFunctionSet + 'static>(&mut self, s: S) -> &mut Self
+    where
+        <S as IntoFunctionSet>::State: Send + Sync,
+    {
+        self.sets.insert(0, Box::new(S::into_function_set(s)));
+        self
+    }
+
     pub async fn exec(
         &self,
         k: &str,
@@ -64,7 +77,7 @@ impl Executor {
     ) -> Option<NaslResult> {
         for set in self.sets.iter() {
             if set.contains(k) {
-                return Some(set.exec(k, register, context).await);
+                return Some(Self::exec_traced(set.as_ref(), k, context, register).await);
             }
         }
         None
@@ -73,11 +86,69 @@ impl Executor {
     pub fn contains(&self, k: &str) -> bool {
         self.sets.iter().any(|set| set.contains(k))
     }
+
+    /// Returns the signature of the function that is called for `k`, if it is known.
+    pub fn function_signature(&self, k: &str) -> Option<&FnSignature> {
+        self.sets
+            .iter()
+            .find(|set| set.contains(k))
+            .and_then(|set| set.signature(k))
+    }
+
+    /// Returns the sorted names of all functions of all sets. A name defined by several sets is
+    /// only listed once.
+    pub fn function_names(&self) -> Vec<&str> {
+        let mut names: Vec<_> = self
+            .sets
+            .iter()
+            .flat_map(|set| set.function_names())
+            .collect();
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+
+    /// Calls the builtin within a `builtin` span that records the name, the number of arguments
+    /// and the elapsed time. Errors are emitted as event within that span.
+    ///
+    /// When the span is disabled, e.g. because no subscriber is interested in it, neither the
+    /// arguments are counted nor the time is measured.
+    async fn exec_traced(
+        set: &(dyn FunctionSet + Send + Sync),
+        k: &str,
+        context: &Context<'_>,
+        register: &Register,
+    ) -> NaslResult {
+        let span = tracing::debug_span!(
+            "builtin",
+            name = k,
+            positional = register.positional().len(),
+            named = register
+                .iter_named_args()
+                .map(|x| x.filter(|x| *x != FC_ANON_ARGS).count())
+                .unwrap_or_default(),
+            elapsed_us = tracing::field::Empty,
+        );
+        if span.is_disabled() {
+            return set.exec(k, register, context).await;
+        }
+        let start = Instant::now();
+        let result = set
+            .exec(k, register, context)
+            .instrument(span.clone())
+            .await;
+        span.record("elapsed_us", start.elapsed().as_micros() as u64);
+        if let Err(e) = &result {
+            tracing::debug!(parent: &span, error = %e, "builtin returned an error");
+        }
+        result
+    }
 }
 
 pub struct StoredFunctionSet<State> {
     state: RwLock<State>,
     fns: HashMap<String, NaslFunction<State>>,
+    signatures: HashMap<String, FnSignature>,
 }
 
 impl<State> StoredFunctionSet<State> {
@@ -85,6 +156,7 @@ impl<State> StoredFunctionSet<State> {
         Self {
             state: RwLock::new(state),
             fns: HashMap::new(),
+            signatures: HashMap::new(),
         }
     }
 
@@ -144,16 +216,37 @@ impl<State> StoredFunctionSet<State> {
             .insert(k.to_string(), NaslFunction::SyncStateless(v));
     }
 
+    pub fn contains_key(&self, k: &str) -> bool {
+        self.fns.contains_key(k)
+    }
+
+    /// Returns the names of the functions in this set in no particular order.
+    pub fn function_names(&self) -> impl Iterator<Item = &str> {
+        self.fns.keys().map(String::as_str)
+    }
+
     pub fn add_nasl_function(&mut self, k: &str, f: NaslFunction<State>) {
         self.fns.insert(k.to_string(), f);
     }
 
+    /// Sets the signature of the function `k`, which is usually done by the code generated by
+    /// `#[nasl_function]`.
+    pub fn add_signature(&mut self, k: &str, signature: FnSignature) {
+        self.signatures.insert(k.to_string(), signature);
+    }
+
+    /// Returns the signature of the function `k`, if it is known.
+    pub fn signature(&self, k: &str) -> Option<&FnSignature> {
+        self.signatures.get(k)
+    }
+
     /// Add a set of functions to this set.  This is useful in order
     /// to combine multiple smaller sets into one large set which can
     /// then be exported.
     /// This only works for sets with stateless functions.
     pub fn add_set<State2>(&mut self, other: impl IntoFunctionSet<State = State2>) {
         let set = other.into_function_set();
+        self.signatures.extend(set.signatures);
         self.fns.extend(set.fns.into_iter().map(|(name, f)| {
             let f: NaslFunction<State> = match f {
                 // The following is marked as `unimplemented()` because
@@ -191,6 +284,12 @@ pub trait FunctionSet {
     ) -> NaslResult;
 
     fn contains(&self, k: &str) -> bool;
+
+    /// Returns the names of all functions of the set.
+    fn function_names(&self) -> Vec<&str>;
+
+    /// Returns the signature of the function `k`, if it is known.
+    fn signature(&self, k: &str) -> Option<&FnSignature>;
 }
 
 #[async_trait]
@@ -227,6 +326,14 @@ impl<State: Sync + Send> FunctionSet for StoredFunctionSet<State> {
     fn contains(&self, k: &str) -> bool {
         self.fns.contains_key(k)
     }
+
+    fn function_names(&self) -> Vec<&str> {
+        StoredFunctionSet::function_names(self).collect()
+    }
+
+    fn signature(&self, k: &str) -> Option<&FnSignature> {
+        StoredFunctionSet::signature(self, k)
+    }
 }
 
 /// Anything that can be converted into a `StoredFunctionSet`.
@@ -255,6 +362,14 @@ macro_rules! internal_call_expr {
     };
 }
 
+impl<State> IntoFunctionSet for StoredFunctionSet<State> {
+    type State = State;
+
+    fn into_function_set(self) -> StoredFunctionSet<Self::State> {
+        self
+    }
+}
+
 /// Convenience macro to define a set of functions.
 /// Example:
 /// ```rust ignore
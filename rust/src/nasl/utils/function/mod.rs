@@ -7,15 +7,22 @@
 
 mod from_nasl_value;
 mod maybe;
+mod nasl_args;
 mod positionals;
+mod signature;
 mod to_nasl_result;
 mod types;
 pub mod utils;
 
 pub use from_nasl_value::FromNaslValue;
 pub use maybe::Maybe;
+pub use nasl_args::NaslArgs;
 pub use positionals::CheckedPositionals;
 pub use positionals::Positionals;
+pub use signature::FnSignature;
+pub use signature::NamedArgSignature;
 pub use to_nasl_result::ToNaslResult;
 pub use types::bytes_to_str;
+pub use types::str_to_bytes;
+pub use types::Bytes;
 pub use types::StringOrData;
@@ -0,0 +1,29 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+use crate::nasl::prelude::*;
+
+/// A set of named arguments that can be parsed from the `Register` at once.
+///
+/// This is usually implemented via `#[derive(NaslArgs)]`. Each field is
+/// read from the named argument of the same name using its
+/// `FromNaslValue` implementation. Fields of type `Option<T>` are
+/// optional, missing required fields result in
+/// `ArgumentError::MissingNamed`.
+/// ```rust ignore
+/// #[derive(NaslArgs)]
+/// struct AesArgs<'a> {
+///     key: Bytes<'a>,
+///     data: Bytes<'a>,
+///     #[nasl(optional)]
+///     len: Option<usize>,
+/// }
+/// let args = AesArgs::from_register(register)?;
+/// ```
+pub trait NaslArgs<'a>: Sized {
+    fn from_register(register: &'a Register) -> Result<Self, FnError>;
+
+    /// Describes the named arguments read by [`NaslArgs::from_register`].
+    fn named_args() -> Vec<super::NamedArgSignature>;
+}
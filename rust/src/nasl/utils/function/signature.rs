@@ -0,0 +1,37 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+/// A named argument of a builtin function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamedArgSignature {
+    pub name: &'static str,
+    /// The Rust type the argument is converted into, e.g. `&str` or `Bytes`.
+    pub ty: &'static str,
+    pub required: bool,
+}
+
+/// The arguments and the return type of a builtin function.
+///
+/// This is generated by `#[nasl_function]` from the same definitions that are used to check the
+/// arguments of a call, so it cannot get out of sync with the implementation. Functions taking
+/// the `Register` directly only have a signature when their arguments are described by a
+/// `NaslArgs` struct, see the `args` attribute of `#[nasl_function]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FnSignature {
+    /// The named arguments, including those which may also be given positionally.
+    pub named: Vec<NamedArgSignature>,
+    /// The number of positional arguments that must be given.
+    pub min_positional: usize,
+    /// The number of positional arguments that may be given, `None` if there is no limit.
+    pub max_positional: Option<usize>,
+    /// The Rust type returned by the implementation.
+    pub returns: &'static str,
+}
+
+impl FnSignature {
+    /// Returns the named argument called `name`.
+    pub fn named(&self, name: &str) -> Option<&NamedArgSignature> {
+        self.named.iter().find(|arg| arg.name == name)
+    }
+}
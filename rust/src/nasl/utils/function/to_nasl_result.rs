@@ -2,7 +2,7 @@
 //
 // SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 
 use crate::nasl::syntax::NaslValue;
@@ -69,7 +69,7 @@ impl ToNaslResult for Vec<u8> {
 
 impl ToNaslResult for Vec<&str> {
     fn to_nasl_result(self) -> NaslResult {
-        Ok(NaslValue::Array(
+        Ok(NaslValue::from(
             self.into_iter()
                 .map(|s| s.to_nasl_result())
                 .collect::<Result<Vec<_>, FnError>>()?,
@@ -79,7 +79,7 @@ impl ToNaslResult for Vec<&str> {
 
 impl ToNaslResult for Vec<String> {
     fn to_nasl_result(self) -> NaslResult {
-        Ok(NaslValue::Array(
+        Ok(NaslValue::from(
             self.into_iter()
                 .map(|s| s.to_nasl_result())
                 .collect::<Result<Vec<_>, FnError>>()?,
@@ -89,7 +89,7 @@ impl ToNaslResult for Vec<String> {
 
 impl ToNaslResult for Vec<NaslValue> {
     fn to_nasl_result(self) -> NaslResult {
-        Ok(NaslValue::Array(self))
+        Ok(NaslValue::from(self))
     }
 }
 
@@ -98,7 +98,7 @@ impl<T: ToNaslResult> ToNaslResult for HashMap<String, T> {
         Ok(NaslValue::Dict(
             self.into_iter()
                 .map(|(key, s)| s.to_nasl_result().map(|res| (key, res)))
-                .collect::<Result<HashMap<_, _>, FnError>>()?,
+                .collect::<Result<BTreeMap<_, _>, FnError>>()?,
         ))
     }
 }
@@ -133,7 +133,7 @@ macro_rules! impl_to_nasl_result_for_numeric_type {
             fn to_nasl_result(self) -> NaslResult {
                 let collected: Result<Vec<_>, FnError> =
                     self.into_iter().map(|x| x.to_nasl_result()).collect();
-                Ok(NaslValue::Array(collected?))
+                Ok(NaslValue::from(collected?))
             }
         }
     };
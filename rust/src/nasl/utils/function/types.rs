@@ -2,6 +2,8 @@
 //
 // SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
 
+use std::borrow::Cow;
+
 use crate::nasl::prelude::*;
 
 /// `Some(string)` if constructed from either a `NaslValue::String`
@@ -20,6 +22,48 @@ impl<'a> FromNaslValue<'a> for StringOrData {
     }
 }
 
+/// The bytes of either a `NaslValue::Data` or a `NaslValue::String`, the
+/// latter converted by [`str_to_bytes`].
+pub struct Bytes<'a>(pub Cow<'a, [u8]>);
+
+impl<'a> FromNaslValue<'a> for Bytes<'a> {
+    fn from_nasl_value(value: &'a NaslValue) -> Result<Self, FnError> {
+        match value {
+            NaslValue::String(string) => Ok(Self(str_to_bytes(string))),
+            NaslValue::Data(buffer) => Ok(Self(Cow::Borrowed(buffer))),
+            _ => Err(
+                ArgumentError::WrongArgument("Expected string or byte buffer.".to_string()).into(),
+            ),
+        }
+    }
+}
+
+/// Converts bytes into a string by mapping each byte to the character of the
+/// same code point, as scripts are loaded in ISO-8859-1.
+///
+/// This is the inverse of [`str_to_bytes`].
 pub fn bytes_to_str(bytes: &[u8]) -> String {
     bytes.iter().map(|x| *x as char).collect::<String>()
 }
+
+/// Converts a string into the bytes it represents to NASL.
+///
+/// NASL strings are byte strings. Scripts as well as data converted by
+/// [`bytes_to_str`] map each byte to the character of the same code point, so
+/// every character up to U+00FF is converted back into that single byte
+/// instead of its UTF-8 encoding. A string containing `\u{ff}` therefore
+/// results in the byte 0xFF. Characters above U+00FF cannot stem from a byte
+/// and are UTF-8 encoded.
+pub fn str_to_bytes(s: &str) -> Cow<'_, [u8]> {
+    if s.is_ascii() {
+        return Cow::Borrowed(s.as_bytes());
+    }
+    let mut bytes = Vec::with_capacity(s.len());
+    for c in s.chars() {
+        match u8::try_from(c) {
+            Ok(b) => bytes.push(b),
+            Err(_) => bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes()),
+        }
+    }
+    Cow::Owned(bytes)
+}
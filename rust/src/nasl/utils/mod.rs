@@ -3,19 +3,25 @@
 // SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
 
 #![doc = include_str!("README.md")]
+pub mod ber;
+pub mod clock;
 pub mod context;
 pub mod error;
 mod executor;
 pub mod function;
 pub mod hosts;
 pub mod lookup_keys;
+pub mod random;
+pub mod recording;
 
 use std::collections::HashMap;
 
-pub use context::{Context, ContextType, Register};
+pub use clock::{Clock, MockClock, SystemClock};
+pub use context::{Context, ContextType, Register, UnknownFunctions};
 pub use error::ArgumentError;
 pub use error::FnError;
 pub use error::InternalError;
+pub use recording::{NetworkMode, Recorder, Replayer};
 
 pub use executor::{Executor, IntoFunctionSet, NaslFunction, StoredFunctionSet};
 
@@ -25,7 +31,9 @@ pub type NaslResult = Result<crate::nasl::syntax::NaslValue, FnError>;
 /// Resolves positional arguments from the register.
 pub fn resolve_positional_arguments(register: &Register) -> Vec<crate::nasl::syntax::NaslValue> {
     match register.named(lookup_keys::FC_ANON_ARGS).cloned() {
-        Some(ContextType::Value(crate::nasl::syntax::NaslValue::Array(arr))) => arr,
+        Some(ContextType::Value(crate::nasl::syntax::NaslValue::Array(arr))) => {
+            std::sync::Arc::unwrap_or_clone(arr)
+        }
         Some(unexpected) => {
             tracing::warn!(
                 "expected array but got: {:?}. Maybe {} was overridden. Ignoring.",
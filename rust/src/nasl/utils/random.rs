@@ -0,0 +1,59 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+//! Defines the source of random data used by builtins
+
+use super::InternalError;
+
+/// Random number generator handed out by [`rng`].
+#[cfg(feature = "nasl-os-rng")]
+pub type Rng = rand::rngs::ThreadRng;
+
+/// Random number generator handed out by [`rng`].
+///
+/// Without the `nasl-os-rng` feature there is no source of random data, so this type cannot be
+/// constructed.
+#[cfg(not(feature = "nasl-os-rng"))]
+#[derive(Debug)]
+pub enum Rng {}
+
+#[cfg(not(feature = "nasl-os-rng"))]
+impl rand::RngCore for Rng {
+    fn next_u32(&mut self) -> u32 {
+        match *self {}
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        match *self {}
+    }
+
+    fn fill_bytes(&mut self, _: &mut [u8]) {
+        match *self {}
+    }
+
+    fn try_fill_bytes(&mut self, _: &mut [u8]) -> Result<(), rand::Error> {
+        match *self {}
+    }
+}
+
+#[cfg(not(feature = "nasl-os-rng"))]
+impl rand::CryptoRng for Rng {}
+
+/// Returns a cryptographically secure random number generator seeded by the operating system.
+///
+/// Fails with [`InternalError::NoRandomness`] if the crate is built without the `nasl-os-rng`
+/// feature, e.g. for `wasm32-unknown-unknown`.
+pub fn rng() -> Result<Rng, InternalError> {
+    #[cfg(feature = "nasl-os-rng")]
+    return Ok(rand::thread_rng());
+    #[cfg(not(feature = "nasl-os-rng"))]
+    return Err(InternalError::NoRandomness);
+}
+
+/// Returns `N` random bytes, see [`rng`].
+pub fn random_bytes<const N: usize>() -> Result<[u8; N], InternalError> {
+    let mut bytes = [0; N];
+    rand::RngCore::fill_bytes(&mut rng()?, &mut bytes);
+    Ok(bytes)
+}
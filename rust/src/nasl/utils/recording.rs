@@ -0,0 +1,377 @@
+// SPDX-FileCopyrightText: 2025 Greenbone AG
+//
+// SPDX-License-Identifier: GPL-2.0-or-later WITH x11vnc-openssl-exception
+
+//! Records the interactions of network builtins with the target and replays them, so that
+//! scripts can be tested without the services they talk to.
+//!
+//! An interaction is keyed by a description of the request, e.g. the name of the builtin and the
+//! arguments which are sent to the target. Failed requests are recorded with their error, so that
+//! a replay fails the same way.
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fs::File;
+use std::future::Future;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+use std::sync::Mutex;
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::json;
+use thiserror::Error;
+
+use super::error::{FnError, InternalError, Recoverable, ReturnBehavior, WithErrorInfo};
+use crate::nasl::syntax::NaslValue;
+
+/// A request of a network builtin together with its outcome.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Interaction {
+    /// Describes the request.
+    pub request: String,
+    /// The response or the error the request failed with.
+    pub response: Result<serde_json::Value, RecordedError>,
+}
+
+/// The error a recorded request failed with.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedError {
+    /// The message of the error.
+    pub message: String,
+    /// The value the builtin returned instead, or None if the error ended the script.
+    pub return_value: Option<serde_json::Value>,
+    /// Whether the script continued with the return value.
+    #[serde(default)]
+    pub recoverable: bool,
+}
+
+impl RecordedError {
+    fn of(error: &FnError) -> Self {
+        let return_value = match error.return_behavior() {
+            ReturnBehavior::ExitScript => None,
+            ReturnBehavior::ReturnValue(value) => Some(value_to_json(value)),
+        };
+        Self {
+            message: error.to_string(),
+            return_value,
+            recoverable: error.recovered_value().is_some(),
+        }
+    }
+
+    fn into_error(self, request: String) -> FnError {
+        let behavior = match self.return_value {
+            None => ReturnBehavior::ExitScript,
+            Some(value) => ReturnBehavior::ReturnValue(json_to_value(value)),
+        };
+        let error = RecordingError::Failed(request, self.message).with(behavior);
+        match self.recoverable {
+            true => error.with(Recoverable),
+            false => error,
+        }
+    }
+}
+
+/// Converts the value a failed builtin returns to JSON. Data is written as object with the key
+/// `data`, dicts as object with the key `dict`, so that both can be told apart from strings and
+/// arrays. Values which a builtin cannot return are written as null.
+fn value_to_json(value: &NaslValue) -> serde_json::Value {
+    match value {
+        NaslValue::Boolean(x) => json!(x),
+        NaslValue::Number(x) => json!(x),
+        NaslValue::String(x) => json!(x),
+        NaslValue::Data(x) => json!({ "data": x }),
+        NaslValue::Array(x) => x.iter().map(value_to_json).collect(),
+        NaslValue::Dict(x) => {
+            let dict: serde_json::Map<_, _> = x
+                .iter()
+                .map(|(k, v)| (k.clone(), value_to_json(v)))
+                .collect();
+            json!({ "dict": dict })
+        }
+        _ => serde_json::Value::Null,
+    }
+}
+
+/// The inverse of [`value_to_json`].
+fn json_to_value(value: serde_json::Value) -> NaslValue {
+    match value {
+        serde_json::Value::Bool(x) => NaslValue::Boolean(x),
+        serde_json::Value::Number(x) => x.as_i64().map(NaslValue::Number).unwrap_or_default(),
+        serde_json::Value::String(x) => NaslValue::String(x),
+        serde_json::Value::Array(x) => {
+            NaslValue::from(x.into_iter().map(json_to_value).collect::<Vec<_>>())
+        }
+        serde_json::Value::Object(mut x) => {
+            if let Some(data) = x.remove("data") {
+                NaslValue::Data(serde_json::from_value(data).unwrap_or_default())
+            } else if let Some(serde_json::Value::Object(dict)) = x.remove("dict") {
+                NaslValue::Dict(
+                    dict.into_iter()
+                        .map(|(k, v)| (k, json_to_value(v)))
+                        .collect::<BTreeMap<_, _>>(),
+                )
+            } else {
+                NaslValue::Null
+            }
+        }
+        serde_json::Value::Null => NaslValue::Null,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum RecordingError {
+    #[error("No response recorded for the request '{0}'.")]
+    NoResponse(String),
+    #[error("All responses recorded for the request '{0}' were already replayed.")]
+    Exhausted(String),
+    #[error("The recorded response to the request '{0}' is invalid: {1}")]
+    InvalidResponse(String, String),
+    #[error("Unable to record the response to the request '{0}': {1}")]
+    Record(String, String),
+    /// The request failed when it was recorded.
+    #[error("{1}")]
+    Failed(String, String),
+}
+
+/// Defines whether network builtins talk to the target.
+#[derive(Debug, Default)]
+pub enum NetworkMode {
+    /// Talk to the target.
+    #[default]
+    Live,
+    /// Talk to the target and record every interaction.
+    Record(Recorder),
+    /// Do not talk to the target but answer with the recorded responses.
+    Replay(Replayer),
+}
+
+impl NetworkMode {
+    /// Executes `live` unless the response to `request` is replayed.
+    ///
+    /// When replaying, an error is returned if there is no recorded response to `request` left.
+    pub async fn interact<T, E>(
+        &self,
+        request: impl Into<String>,
+        live: impl Future<Output = Result<T, E>>,
+    ) -> Result<T, FnError>
+    where
+        T: Serialize + DeserializeOwned,
+        E: Into<FnError>,
+    {
+        let request = request.into();
+        match self {
+            NetworkMode::Replay(replayer) => replayer.replay(request),
+            _ => self.record(request, live.await.map_err(Into::into)),
+        }
+    }
+
+    /// Like [`NetworkMode::interact`] for builtins which talk to the target synchronously.
+    pub fn interact_blocking<T, E>(
+        &self,
+        request: impl Into<String>,
+        live: impl FnOnce() -> Result<T, E>,
+    ) -> Result<T, FnError>
+    where
+        T: Serialize + DeserializeOwned,
+        E: Into<FnError>,
+    {
+        let request = request.into();
+        match self {
+            NetworkMode::Replay(replayer) => replayer.replay(request),
+            _ => self.record(request, live().map_err(Into::into)),
+        }
+    }
+
+    fn record<T: Serialize>(
+        &self,
+        request: String,
+        outcome: Result<T, FnError>,
+    ) -> Result<T, FnError> {
+        if let NetworkMode::Record(recorder) = self {
+            recorder.record(request, &outcome)?;
+        }
+        outcome
+    }
+}
+
+/// Writes interactions into a file, one JSON object per line.
+#[derive(Debug)]
+pub struct Recorder {
+    file: Mutex<File>,
+}
+
+impl Recorder {
+    /// Creates the file at `path`, overwriting an existing one.
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Self {
+            file: Mutex::new(File::create(path)?),
+        })
+    }
+
+    fn record<T: Serialize>(
+        &self,
+        request: String,
+        outcome: &Result<T, FnError>,
+    ) -> Result<(), RecordingError> {
+        let write = || -> Result<(), String> {
+            let response = match outcome {
+                Ok(response) => Ok(serde_json::to_value(response).map_err(|e| e.to_string())?),
+                Err(e) => Err(RecordedError::of(e)),
+            };
+            let interaction = Interaction {
+                request: request.clone(),
+                response,
+            };
+            let line = serde_json::to_string(&interaction).map_err(|e| e.to_string())?;
+            let mut file = self.file.lock().unwrap_or_else(|e| e.into_inner());
+            writeln!(file, "{line}").map_err(|e| e.to_string())
+        };
+        write().map_err(|e| RecordingError::Record(request.clone(), e))
+    }
+}
+
+/// Answers requests with the outcomes of recorded interactions.
+///
+/// The outcomes of the same request are returned in the order they were recorded, each one once.
+#[derive(Debug, Default)]
+pub struct Replayer {
+    responses: Mutex<HashMap<String, VecDeque<Result<serde_json::Value, RecordedError>>>>,
+}
+
+impl Replayer {
+    /// Creates a replayer answering with the given interactions.
+    pub fn new(interactions: impl IntoIterator<Item = Interaction>) -> Self {
+        let mut responses: HashMap<_, VecDeque<_>> = HashMap::new();
+        for interaction in interactions {
+            responses
+                .entry(interaction.request)
+                .or_default()
+                .push_back(interaction.response);
+        }
+        Self {
+            responses: Mutex::new(responses),
+        }
+    }
+
+    /// Loads the interactions written by a [`Recorder`].
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let reader = BufReader::new(File::open(path)?);
+        let mut interactions = vec![];
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            interactions.push(serde_json::from_str(&line)?);
+        }
+        Ok(Self::new(interactions))
+    }
+
+    fn replay<T: DeserializeOwned>(&self, request: String) -> Result<T, FnError> {
+        let mut responses = self.responses.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(recorded) = responses.get_mut(&request) else {
+            return Err(RecordingError::NoResponse(request).into());
+        };
+        let Some(response) = recorded.pop_front() else {
+            return Err(RecordingError::Exhausted(request).into());
+        };
+        match response {
+            Ok(response) => serde_json::from_value(response)
+                .map_err(|e| RecordingError::InvalidResponse(request, e.to_string()).into()),
+            Err(e) => Err(e.into_error(request)),
+        }
+    }
+}
+
+impl From<RecordingError> for FnError {
+    fn from(value: RecordingError) -> Self {
+        InternalError::Recording(value).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::nasl::utils::error::{FnErrorKind, ReturnValue};
+
+    fn interaction(request: &str, response: serde_json::Value) -> Interaction {
+        Interaction {
+            request: request.to_owned(),
+            response: Ok(response),
+        }
+    }
+
+    fn recording_error(result: Result<impl std::fmt::Debug, FnError>) -> RecordingError {
+        match result.unwrap_err().kind {
+            FnErrorKind::Internal(InternalError::Recording(e)) => e,
+            kind => panic!("unexpected error {kind:?}"),
+        }
+    }
+
+    #[test]
+    fn replay_in_recorded_order() {
+        let replayer = Replayer::new([
+            interaction("a", json!(1)),
+            interaction("b", json!("x")),
+            interaction("a", json!(2)),
+        ]);
+        assert_eq!(replayer.replay::<i64>("a".into()).unwrap(), 1);
+        assert_eq!(replayer.replay::<i64>("a".into()).unwrap(), 2);
+        assert_eq!(
+            recording_error(replayer.replay::<i64>("a".into())),
+            RecordingError::Exhausted("a".into())
+        );
+        assert_eq!(
+            recording_error(replayer.replay::<i64>("c".into())),
+            RecordingError::NoResponse("c".into())
+        );
+        assert!(matches!(
+            recording_error(replayer.replay::<i64>("b".into())),
+            RecordingError::InvalidResponse(..)
+        ));
+    }
+
+    #[test]
+    fn replay_recorded_errors() {
+        let path = std::env::temp_dir().join(format!("recording_{}.jsonl", std::process::id()));
+        let mode = NetworkMode::Record(Recorder::create(&path).unwrap());
+        let fatal = mode.interact_blocking("fatal", || {
+            Err::<i64, _>(InternalError::UnknownFunction("foo".into()))
+        });
+        let returned = mode.interact_blocking("returned", || {
+            Err::<i64, _>(
+                InternalError::UnknownFunction("bar".into())
+                    .with(ReturnValue(NaslValue::Data(vec![1, 2]))),
+            )
+        });
+        let recovered = mode.interact_blocking("recovered", || {
+            Err::<i64, _>(
+                InternalError::UnknownFunction("baz".into())
+                    .with(ReturnValue(NaslValue::Number(0)))
+                    .with(Recoverable),
+            )
+        });
+        assert!(fatal.is_err() && returned.is_err() && recovered.is_err());
+
+        let replayer = Replayer::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let fatal = replayer.replay::<i64>("fatal".into()).unwrap_err();
+        assert_eq!(
+            fatal.to_string(),
+            InternalError::UnknownFunction("foo".into()).to_string()
+        );
+        assert!(matches!(
+            fatal.return_behavior(),
+            ReturnBehavior::ExitScript
+        ));
+        let returned = replayer.replay::<i64>("returned".into()).unwrap_err();
+        assert!(matches!(
+            returned.return_behavior(),
+            ReturnBehavior::ReturnValue(NaslValue::Data(x)) if x == &[1, 2]
+        ));
+        assert_eq!(returned.recovered_value(), None);
+        let recovered = replayer.replay::<i64>("recovered".into()).unwrap_err();
+        assert_eq!(recovered.recovered_value(), Some(&NaslValue::Number(0)));
+    }
+}
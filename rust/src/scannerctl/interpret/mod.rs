@@ -7,10 +7,8 @@ use std::{
     path::PathBuf,
 };
 
-use futures::StreamExt;
-use scannerlib::nasl::{interpreter::CodeInterpreter, utils::error::ReturnBehavior};
+use scannerlib::nasl::interpreter::CodeInterpreter;
 use scannerlib::nasl::{
-    interpreter::InterpretErrorKind,
     prelude::*,
     syntax::{load_non_utf8_path, LoadError},
     Loader, NoOpLoader,
@@ -134,32 +132,14 @@ where
             .build(ContextKey::Scan(self.scan_id.clone(), target));
         let register = RegisterBuilder::build();
         let code = self.load(script)?;
-        let mut results = CodeInterpreter::new(&code, register, &context).stream();
-        while let Some(result) = results.next().await {
-            let r = match result {
-                Ok(x) => x,
-                Err(e) => {
-                    if let InterpretErrorKind::FunctionCallError(ref fe) = e.kind {
-                        match fe.kind.return_behavior() {
-                            ReturnBehavior::ExitScript => return Err(e.into()),
-                            ReturnBehavior::ReturnValue(val) => {
-                                tracing::warn!("{}", e.to_string());
-                                val.clone()
-                            }
-                        }
-                    } else {
-                        return Err(e.into());
-                    }
-                }
-            };
-            match r {
-                NaslValue::Exit(rc) => std::process::exit(rc as i32),
-                _ => {
-                    tracing::debug!("=> {r:?}", r = r);
-                }
-            }
+        // The warnings were logged when they occurred.
+        let outcome = CodeInterpreter::new(&code, register, &context)
+            .run()
+            .await
+            .map_err(|e| e.error)?;
+        if let NaslValue::Exit(rc) = outcome.exit_value {
+            std::process::exit(rc as i32);
         }
-
         Ok(())
     }
 }
@@ -81,7 +81,9 @@ impl From<usize> for Primitive {
 
 impl From<HashMap<String, Primitive>> for Primitive {
     fn from(x: HashMap<String, Primitive>) -> Self {
-        Primitive::Dict(x.into_iter().collect())
+        let mut entries: Vec<_> = x.into_iter().collect();
+        entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+        Primitive::Dict(entries)
     }
 }
 